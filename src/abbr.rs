@@ -0,0 +1,92 @@
+//! # Abbreviations Module
+//!
+//! Abreviações no estilo do fish (`abbr gco 'git checkout'`).
+//!
+//! Diferente de um alias, a abreviação é trocada **na própria linha** quando o
+//! usuário aperta espaço ou Enter logo após digitá-la na posição de comando,
+//! então o comando real fica visível antes de executar e vai assim para o histórico.
+//!
+//! As abreviações ficam em `~/.clios_abbr.json`.
+
+use crate::parser::split_command_positions;
+use rustyline::{Cmd, ConditionalEventHandler, Event, EventContext, InputMode, RepeatCount};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+/// Mapa de abreviações compartilhado entre a shell e o editor de linha.
+pub type AbbrMap = Arc<RwLock<BTreeMap<String, String>>>;
+
+/// Caminho do arquivo de abreviações.
+pub fn abbr_path() -> PathBuf {
+    env::var("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join(".clios_abbr.json")
+}
+
+/// Carrega as abreviações salvas (arquivo ausente ou inválido = vazio).
+pub fn load_abbreviations() -> BTreeMap<String, String> {
+    fs::read_to_string(abbr_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Grava as abreviações em disco.
+pub fn save_abbreviations(abbrs: &BTreeMap<String, String>) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(abbrs).map_err(|e| e.to_string())?;
+    fs::write(abbr_path(), json).map_err(|e| e.to_string())
+}
+
+/// Procura uma abreviação terminando no cursor, em posição de comando.
+///
+/// `before_cursor` é o texto da linha até o cursor. Retorna o índice (em bytes)
+/// onde a abreviação começa e a expansão.
+pub fn abbr_at_cursor(before_cursor: &str, abbrs: &BTreeMap<String, String>) -> Option<(usize, String)> {
+    let segment = split_command_positions(before_cursor).pop()?;
+    let word = segment.text.trim_start();
+    if word.is_empty() || word.contains(char::is_whitespace) {
+        return None;
+    }
+    abbrs
+        .get(word)
+        .map(|expansion| (before_cursor.len() - word.len(), expansion.clone()))
+}
+
+/// Expande a abreviação da última posição de comando de uma linha já aceita.
+///
+/// Cobre o caso de Enter logo após a abreviação (ex: `gst` + Enter).
+pub fn expand_line(line: &str, abbrs: &BTreeMap<String, String>) -> String {
+    let trimmed = line.trim_end();
+    match abbr_at_cursor(trimmed, abbrs) {
+        Some((start, expansion)) => format!("{}{}", &trimmed[..start], expansion),
+        None => line.to_string(),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// LINE EDITOR HANDLER
+// -----------------------------------------------------------------------------
+
+/// Handler do `rustyline` ligado à barra de espaço.
+///
+/// Quando há uma abreviação antes do cursor, dispara a completação: o
+/// `Completer` do `CliosHelper` oferece a expansão como candidato único, o que
+/// troca a palavra e deixa o cursor no fim do texto inserido.
+pub struct AbbrExpander {
+    pub abbrs: AbbrMap,
+}
+
+impl ConditionalEventHandler for AbbrExpander {
+    fn handle(&self, _evt: &Event, _n: RepeatCount, _positive: bool, ctx: &EventContext) -> Option<Cmd> {
+        // No modo normal do vi o espaço só move o cursor
+        if ctx.input_mode() == InputMode::Command {
+            return None;
+        }
+        let abbrs = self.abbrs.read().ok()?;
+        abbr_at_cursor(&ctx.line()[..ctx.pos()], &abbrs).map(|_| Cmd::Complete)
+    }
+}
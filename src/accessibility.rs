@@ -0,0 +1,60 @@
+//! # Accessibility Module
+//!
+//! Modo para leitores de tela (`screen_reader = true` em `[accessibility]`):
+//! a saída da shell fica em texto corrido, sem o que um leitor soletra ou lê
+//! como ruído.
+//!
+//! - Prompt em texto simples, sem os glifos do Powerline nem ícones
+//!   (`clios ~/api, git main, código 1 >`)
+//! - `help` sem a moldura de caracteres de caixa
+//! - Sem linhas que se reescrevem (progresso do `fetch`/`extract`, o
+//!   "consultando..." do `??`): só mensagens completas, uma por linha
+//! - Listas com `-` no lugar de setas e marcadores decorativos
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static SCREEN_READER: AtomicBool = AtomicBool::new(false);
+
+/// Liga ou desliga o modo (opção `screen_reader`).
+pub fn set_screen_reader(enabled: bool) {
+    SCREEN_READER.store(enabled, Ordering::Relaxed);
+}
+
+/// Se o modo para leitores de tela está ligado.
+pub fn screen_reader() -> bool {
+    SCREEN_READER.load(Ordering::Relaxed)
+}
+
+/// Caracteres de moldura (caixas do `help`).
+fn is_box_char(c: char) -> bool {
+    ('\u{2500}'..='\u{257F}').contains(&c)
+}
+
+/// O texto sem sequências ANSI e sem moldura, sem espaços no fim.
+pub fn plain_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // CSI: `ESC [` ... até a letra final
+            if chars.peek() == Some(&'[') {
+                chars.next();
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        if !is_box_char(c) {
+            out.push(c);
+        }
+    }
+    out.trim_end().to_string()
+}
+
+/// Marcador de item de lista: a seta colorida ou, no modo, um `-`.
+pub fn bullet() -> &'static str {
+    if screen_reader() { "-" } else { "➜" }
+}
@@ -0,0 +1,584 @@
+//! # Archive Module
+//!
+//! Builtins `extract` e `compress`, feitos com crates em Rust puro: funcionam
+//! em containers e sistemas mínimos sem `tar`, `unzip`, `xz` ou `zstd`.
+//!
+//! - `extract pacote.tar.gz [-C dir]` - detecta o formato pelo conteúdo
+//!   (assinatura do arquivo) e, para `.tar.*`, pelo nome
+//! - `compress src/ docs/ saida.tar.zst` - o formato vem da extensão da saída
+//!
+//! Formatos: `.tar`, `.tar.gz`/`.tgz`, `.tar.xz`/`.txz`, `.tar.zst`/`.tzst`,
+//! `.zip` e os arquivos únicos `.gz`, `.xz` e `.zst`. Os dados passam em fluxo
+//! (sem carregar o arquivo na memória) e, no terminal, uma linha mostra o
+//! progresso. Ctrl+C interrompe.
+
+use crate::accessibility::screen_reader;
+use crate::registry::usage;
+use crate::signals::{is_interrupted, take_interrupt, EXIT_INTERRUPTED};
+use crate::t;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, IsTerminal, Read, Seek, Write};
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Intervalo mínimo entre atualizações da linha de progresso.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Compressão de um fluxo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Codec {
+    None,
+    Gzip,
+    Xz,
+    Zstd,
+}
+
+/// Formato de um arquivo compactado.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// Tar, possivelmente compactado
+    Tar(Codec),
+    Zip,
+    /// Um único arquivo compactado (`log.gz`)
+    Single(Codec),
+}
+
+/// Formato pela extensão (`.tar.gz`, `.tgz`, `.zip`, `.xz`...).
+pub fn format_from_name(name: &str) -> Option<Format> {
+    let name = name.to_ascii_lowercase();
+    const TABLE: &[(&str, Format)] = &[
+        (".tar.gz", Format::Tar(Codec::Gzip)),
+        (".tgz", Format::Tar(Codec::Gzip)),
+        (".tar.xz", Format::Tar(Codec::Xz)),
+        (".txz", Format::Tar(Codec::Xz)),
+        (".tar.zst", Format::Tar(Codec::Zstd)),
+        (".tzst", Format::Tar(Codec::Zstd)),
+        (".tar", Format::Tar(Codec::None)),
+        (".zip", Format::Zip),
+        (".gz", Format::Single(Codec::Gzip)),
+        (".xz", Format::Single(Codec::Xz)),
+        (".zst", Format::Single(Codec::Zstd)),
+    ];
+    TABLE.iter().find(|(ext, _)| name.ends_with(ext)).map(|(_, format)| *format)
+}
+
+/// Formato pelos primeiros bytes do arquivo; o nome decide só se um fluxo
+/// compactado contém um tar (`.tar.gz`) ou um arquivo único (`.gz`).
+pub fn detect_format(header: &[u8], name: &str) -> Option<Format> {
+    let codec = if header.starts_with(&[0x1f, 0x8b]) {
+        Codec::Gzip
+    } else if header.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+        Codec::Xz
+    } else if header.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+        Codec::Zstd
+    } else if header.starts_with(b"PK\x03\x04") || header.starts_with(b"PK\x05\x06") {
+        return Some(Format::Zip);
+    } else if header.get(257..262) == Some(b"ustar") {
+        return Some(Format::Tar(Codec::None));
+    } else {
+        return format_from_name(name).filter(|f| matches!(f, Format::Tar(Codec::None)));
+    };
+    match format_from_name(name) {
+        Some(Format::Tar(_)) => Some(Format::Tar(codec)),
+        _ => Some(Format::Single(codec)),
+    }
+}
+
+/// Tamanho legível (`512 B`, `1.5 KB`, `3.2 GB`).
+pub fn human_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 { format!("{} B", bytes) } else { format!("{:.1} {}", size, UNITS[unit]) }
+}
+
+// -----------------------------------------------------------------------------
+// PROGRESS
+// -----------------------------------------------------------------------------
+
+/// Linha de progresso no STDERR (só quando ele é um terminal). Também usada
+/// pelos downloads do `fetch`.
+pub(crate) struct Progress {
+    label: &'static str,
+    total: u64,
+    done: u64,
+    last: Option<Instant>,
+    enabled: bool,
+}
+
+impl Progress {
+    /// `total` 0 quando o tamanho não é conhecido: mostra só o que já passou.
+    pub(crate) fn new(label: &'static str, total: u64) -> Self {
+        // Leitores de tela leriam cada atualização da linha
+        Progress { label, total, done: 0, last: None, enabled: io::stderr().is_terminal() && !screen_reader() }
+    }
+
+    /// Sem linha: só o Ctrl+C do [`ProgressReader`].
+    pub(crate) fn hidden() -> Self {
+        Progress { label: "", total: 0, done: 0, last: None, enabled: false }
+    }
+
+    fn advance(&mut self, n: u64) {
+        self.done += n;
+        if !self.enabled || self.last.is_some_and(|at| at.elapsed() < PROGRESS_INTERVAL) {
+            return;
+        }
+        self.last = Some(Instant::now());
+        if self.total == 0 {
+            eprint!("\r\x1b[K{} {}", self.label, human_size(self.done));
+            return;
+        }
+        let percent = (self.done * 100).checked_div(self.total).unwrap_or(100).min(100);
+        eprint!("\r\x1b[K{} {:>3}%  {} / {}", self.label, percent, human_size(self.done), human_size(self.total));
+    }
+
+    pub(crate) fn finish(&self) {
+        if self.enabled && self.last.is_some() {
+            eprint!("\r\x1b[K");
+        }
+    }
+}
+
+/// Leitor que avança o progresso e para no Ctrl+C.
+pub(crate) struct ProgressReader<'a, R> {
+    pub(crate) inner: R,
+    pub(crate) progress: &'a mut Progress,
+}
+
+impl<R: Read> Read for ProgressReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // `Interrupted` faria o `io::copy` tentar de novo
+        if is_interrupted() {
+            return Err(io::Error::other(t!("archive.interrupted")));
+        }
+        let n = self.inner.read(buf)?;
+        self.progress.advance(n as u64);
+        Ok(n)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// EXTRACT
+// -----------------------------------------------------------------------------
+
+fn decoder<'a, R: Read + 'a>(codec: Codec, reader: R) -> io::Result<Box<dyn Read + 'a>> {
+    Ok(match codec {
+        Codec::None => Box::new(reader),
+        Codec::Gzip => Box::new(MultiGzDecoder::new(reader)),
+        Codec::Xz => Box::new(lzma_rust2::XzReader::new(reader, true)),
+        Codec::Zstd => Box::new(ruzstd::decoding::StreamingDecoder::new(reader).map_err(io::Error::other)?),
+    })
+}
+
+/// Extrai `archive` em `dest`; devolve quantas entradas foram extraídas.
+pub fn extract(archive: &Path, dest: &Path) -> io::Result<usize> {
+    let mut file = File::open(archive)?;
+    let total = file.metadata()?.len();
+    let mut header = [0u8; 512];
+    let read = file.read(&mut header)?;
+    file.rewind()?;
+    let name = archive.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let format = detect_format(&header[..read], &name)
+        .ok_or_else(|| io::Error::other(t!("archive.unknown_format")))?;
+
+    fs::create_dir_all(dest)?;
+    let mut progress = Progress::new("extract", total);
+    let result = match format {
+        Format::Tar(codec) => {
+            let reader = ProgressReader { inner: BufReader::new(file), progress: &mut progress };
+            let mut tar = tar::Archive::new(decoder(codec, reader)?);
+            tar.set_preserve_permissions(true);
+            // `unpack_in` recusa caminhos que saem de `dest` (`../`, absolutos)
+            let mut count = 0;
+            for entry in tar.entries()? {
+                if entry?.unpack_in(dest)? {
+                    count += 1;
+                }
+            }
+            Ok(count)
+        }
+        Format::Single(codec) => {
+            let stem = Path::new(&name).file_stem().map(PathBuf::from).unwrap_or_else(|| PathBuf::from("saida"));
+            let target = dest.join(stem);
+            if target.exists() {
+                return Err(io::Error::other(t!("archive.exists", target.display())));
+            }
+            let reader = ProgressReader { inner: BufReader::new(file), progress: &mut progress };
+            let mut out = BufWriter::new(File::create(&target)?);
+            io::copy(&mut decoder(codec, reader)?, &mut out)?;
+            out.flush()?;
+            Ok(1)
+        }
+        Format::Zip => extract_zip(file, dest, &mut progress),
+    };
+    progress.finish();
+    result
+}
+
+fn extract_zip(file: File, dest: &Path, progress: &mut Progress) -> io::Result<usize> {
+    let mut zip = zip::ZipArchive::new(BufReader::new(file)).map_err(io::Error::other)?;
+    let mut links = Vec::new();
+    let mut count = 0;
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).map_err(io::Error::other)?;
+        // Caminhos que saem de `dest` ficam de fora
+        let Some(relative) = entry.enclosed_name() else {
+            eprintln!("{}", t!("archive.unsafe_path", entry.name()));
+            continue;
+        };
+        let target = dest.join(relative);
+        let compressed = entry.compressed_size();
+        if entry.is_symlink() {
+            let mut link = String::new();
+            entry.read_to_string(&mut link)?;
+            links.push((target, link));
+        } else if entry.is_dir() {
+            fs::create_dir_all(&target)?;
+        } else {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut out = File::create(&target)?;
+            let mut reader = ProgressReader { inner: &mut entry, progress: &mut Progress::hidden() };
+            io::copy(&mut reader, &mut out)?;
+            if let Some(mode) = entry.unix_mode() {
+                fs::set_permissions(&target, fs::Permissions::from_mode(mode & 0o7777))?;
+            }
+        }
+        progress.advance(compressed);
+        count += 1;
+    }
+    // Links por último: um link para fora de `dest` não pode desviar a
+    // escrita das outras entradas
+    for (target, link) in links {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        std::os::unix::fs::symlink(link, &target)?;
+    }
+    Ok(count)
+}
+
+// -----------------------------------------------------------------------------
+// COMPRESS
+// -----------------------------------------------------------------------------
+
+/// Item a guardar no arquivo compactado.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntryKind {
+    Dir,
+    File(u64),
+    Symlink(PathBuf),
+}
+
+/// Caminho no disco e nome dentro do arquivo compactado.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub path: PathBuf,
+    pub name: String,
+    pub kind: EntryKind,
+}
+
+/// Nome de `src` dentro do arquivo: o último componente (`dist/` vira
+/// `dist`; `.` vira o nome do diretório atual).
+fn archive_name(src: &Path) -> io::Result<String> {
+    let name = match src.file_name() {
+        Some(name) => name.to_os_string(),
+        None => fs::canonicalize(src)?.file_name().map(|n| n.to_os_string()).unwrap_or_else(|| "raiz".into()),
+    };
+    Ok(name.to_string_lossy().into_owned())
+}
+
+/// Percorre `sources` (diretórios recursivamente, sem seguir symlinks).
+pub fn collect_entries(sources: &[PathBuf]) -> io::Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    for src in sources {
+        walk(src, archive_name(src)?, &mut entries)?;
+    }
+    Ok(entries)
+}
+
+fn walk(path: &Path, name: String, entries: &mut Vec<Entry>) -> io::Result<()> {
+    let meta = fs::symlink_metadata(path)?;
+    if meta.file_type().is_symlink() {
+        entries.push(Entry { path: path.to_path_buf(), name, kind: EntryKind::Symlink(fs::read_link(path)?) });
+    } else if meta.is_dir() {
+        entries.push(Entry { path: path.to_path_buf(), name: name.clone(), kind: EntryKind::Dir });
+        let mut children: Vec<_> = fs::read_dir(path)?.collect::<Result<_, _>>()?;
+        children.sort_by_key(|c| c.file_name());
+        for child in children {
+            let child_name = format!("{}/{}", name, child.file_name().to_string_lossy());
+            walk(&child.path(), child_name, entries)?;
+        }
+    } else {
+        entries.push(Entry { path: path.to_path_buf(), name, kind: EntryKind::File(meta.len()) });
+    }
+    Ok(())
+}
+
+/// Compressor de um fluxo; `finish` fecha o formato.
+enum Encoder<W: Write + Send + 'static> {
+    Plain(W),
+    Gzip(GzEncoder<W>),
+    Xz(Box<lzma_rust2::XzWriter<W>>),
+    /// O `ruzstd` lê de um `Read`: o tar vai por um pipe para uma thread
+    Zstd(io::PipeWriter, JoinHandle<io::Result<()>>),
+}
+
+/// Saída que guarda o primeiro erro em vez de devolvê-lo (o compressor do
+/// `ruzstd` entra em pânico em erros de escrita).
+struct LatchWriter<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> Write for LatchWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.error.is_none()
+            && let Err(e) = self.inner.write_all(buf)
+        {
+            self.error = Some(e);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: Write + Send + 'static> Encoder<W> {
+    fn new(codec: Codec, out: W) -> io::Result<Self> {
+        Ok(match codec {
+            Codec::None => Encoder::Plain(out),
+            Codec::Gzip => Encoder::Gzip(GzEncoder::new(out, flate2::Compression::default())),
+            Codec::Xz => Encoder::Xz(Box::new(lzma_rust2::XzWriter::new(out, lzma_rust2::XzOptions::with_preset(6))?)),
+            Codec::Zstd => {
+                let (reader, writer) = io::pipe()?;
+                let handle = std::thread::spawn(move || {
+                    let mut latch = LatchWriter { inner: out, error: None };
+                    ruzstd::encoding::compress(reader, &mut latch, ruzstd::encoding::CompressionLevel::Fastest);
+                    match latch.error {
+                        Some(e) => Err(e),
+                        None => latch.inner.flush(),
+                    }
+                });
+                Encoder::Zstd(writer, handle)
+            }
+        })
+    }
+
+    fn finish(self) -> io::Result<()> {
+        match self {
+            Encoder::Plain(mut out) => out.flush(),
+            Encoder::Gzip(gz) => gz.finish()?.flush(),
+            Encoder::Xz(xz) => xz.finish()?.flush(),
+            Encoder::Zstd(writer, handle) => {
+                drop(writer);
+                handle.join().unwrap_or_else(|_| Err(io::Error::other("zstd")))
+            }
+        }
+    }
+}
+
+impl<W: Write + Send + 'static> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Encoder::Plain(out) => out.write(buf),
+            Encoder::Gzip(gz) => gz.write(buf),
+            Encoder::Xz(xz) => xz.write(buf),
+            Encoder::Zstd(writer, _) => writer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Encoder::Plain(out) => out.flush(),
+            Encoder::Gzip(gz) => gz.flush(),
+            Encoder::Xz(xz) => xz.flush(),
+            Encoder::Zstd(writer, _) => writer.flush(),
+        }
+    }
+}
+
+/// Compacta `sources` em `output` no formato `format`; devolve quantas
+/// entradas foram gravadas.
+pub fn compress(sources: &[PathBuf], output: &Path, format: Format) -> io::Result<usize> {
+    let mut entries = collect_entries(sources)?;
+    if let Format::Single(_) = format
+        && !matches!(entries.as_slice(), [Entry { kind: EntryKind::File(_), .. }])
+    {
+        return Err(io::Error::other(t!("archive.single_file")));
+    }
+    if entries.iter().any(|e| e.path == output) {
+        return Err(io::Error::other(t!("archive.exists", output.display())));
+    }
+
+    let file = File::create(output)?;
+    // A própria saída pode estar dentro de um dos diretórios (`compress . x.zip`)
+    let out_meta = file.metadata()?;
+    entries.retain(|e| {
+        fs::symlink_metadata(&e.path).map_or(true, |m| (m.dev(), m.ino()) != (out_meta.dev(), out_meta.ino()))
+    });
+    let total = entries
+        .iter()
+        .map(|e| if let EntryKind::File(size) = e.kind { size } else { 0 })
+        .sum();
+    let mut progress = Progress::new("compress", total);
+
+    let result = match format {
+        Format::Tar(codec) => write_tar(&entries, Encoder::new(codec, BufWriter::new(file))?, &mut progress),
+        Format::Single(codec) => {
+            let mut encoder = Encoder::new(codec, BufWriter::new(file))?;
+            let mut reader = ProgressReader { inner: File::open(&entries[0].path)?, progress: &mut progress };
+            io::copy(&mut reader, &mut encoder)?;
+            encoder.finish().map(|_| 1)
+        }
+        Format::Zip => write_zip(&entries, BufWriter::new(file), &mut progress),
+    };
+    progress.finish();
+    result.map(|_| entries.len())
+}
+
+fn write_tar<W: Write + Send + 'static>(entries: &[Entry], encoder: Encoder<W>, progress: &mut Progress) -> io::Result<usize> {
+    let mut builder = tar::Builder::new(encoder);
+    for entry in entries {
+        match &entry.kind {
+            EntryKind::Dir => builder.append_dir(&entry.name, &entry.path)?,
+            EntryKind::File(size) => {
+                let file = File::open(&entry.path)?;
+                let mut header = tar::Header::new_gnu();
+                header.set_metadata_in_mode(&file.metadata()?, tar::HeaderMode::Complete);
+                header.set_size(*size);
+                let reader = ProgressReader { inner: file.take(*size), progress: &mut *progress };
+                builder.append_data(&mut header, &entry.name, reader)?;
+            }
+            EntryKind::Symlink(target) => {
+                let mut header = tar::Header::new_gnu();
+                header.set_metadata_in_mode(&fs::symlink_metadata(&entry.path)?, tar::HeaderMode::Complete);
+                header.set_entry_type(tar::EntryType::Symlink);
+                header.set_size(0);
+                builder.append_link(&mut header, &entry.name, target)?;
+            }
+        }
+    }
+    builder.into_inner()?.finish()?;
+    Ok(entries.len())
+}
+
+fn write_zip<W: Write + Seek>(entries: &[Entry], out: W, progress: &mut Progress) -> io::Result<usize> {
+    use zip::write::SimpleFileOptions;
+    let mut zip = zip::ZipWriter::new(out);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for entry in entries {
+        let mode = fs::symlink_metadata(&entry.path)?.permissions().mode();
+        let options = options.unix_permissions(mode & 0o7777);
+        match &entry.kind {
+            EntryKind::Dir => zip.add_directory(entry.name.as_str(), options).map_err(io::Error::other)?,
+            EntryKind::File(size) => {
+                zip.start_file(entry.name.as_str(), options.large_file(*size >= u32::MAX as u64))
+                    .map_err(io::Error::other)?;
+                let mut reader = ProgressReader { inner: File::open(&entry.path)?, progress: &mut *progress };
+                io::copy(&mut reader, &mut zip)?;
+            }
+            EntryKind::Symlink(target) => zip
+                .add_symlink(entry.name.as_str(), target.to_string_lossy(), options)
+                .map_err(io::Error::other)?,
+        }
+    }
+    zip.finish().map_err(io::Error::other)?.flush()?;
+    Ok(entries.len())
+}
+
+// -----------------------------------------------------------------------------
+// BUILTINS
+// -----------------------------------------------------------------------------
+
+/// Erro de `extract`/`compress`: 130 se foi o Ctrl+C.
+fn report_error(command: &str, path: &Path, e: io::Error) -> i32 {
+    if take_interrupt() {
+        eprintln!();
+        return EXIT_INTERRUPTED;
+    }
+    eprintln!("{}: {}: {}", command, path.display(), e);
+    1
+}
+
+/// Builtin `extract <arquivo...> [-C dir]`.
+pub fn handle_extract(tokens: &[String]) -> i32 {
+    let mut dest = PathBuf::from(".");
+    let mut archives = Vec::new();
+    let mut args = tokens[1..].iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-C" | "--dir" => match args.next() {
+                Some(dir) => dest = PathBuf::from(dir),
+                None => {
+                    eprintln!("{}", usage("extract"));
+                    return 2;
+                }
+            },
+            flag if flag.starts_with('-') => {
+                eprintln!("{}", usage("extract"));
+                return 2;
+            }
+            _ => archives.push(PathBuf::from(arg)),
+        }
+    }
+    if archives.is_empty() {
+        eprintln!("{}", usage("extract"));
+        return 2;
+    }
+
+    for archive in &archives {
+        match extract(archive, &dest) {
+            Ok(count) => println!("{}", t!("archive.extracted", archive.display(), count, dest.display())),
+            Err(e) => return report_error("extract", archive, e),
+        }
+    }
+    0
+}
+
+/// Builtin `compress [-f] <origem...> <saída>`.
+pub fn handle_compress(tokens: &[String]) -> i32 {
+    let force = tokens.get(1).is_some_and(|a| a == "-f" || a == "--force");
+    let args = &tokens[if force { 2 } else { 1 }..];
+    let Some((output, sources)) = args.split_last().filter(|(_, sources)| !sources.is_empty()) else {
+        eprintln!("{}", usage("compress"));
+        return 2;
+    };
+    if args.iter().any(|a| a.starts_with('-')) {
+        eprintln!("{}", usage("compress"));
+        return 2;
+    }
+    let output = PathBuf::from(output);
+    let Some(format) = format_from_name(&output.to_string_lossy()) else {
+        eprintln!("{}", t!("archive.unknown_output", output.display()));
+        return 2;
+    };
+    if output.exists() && !force {
+        eprintln!("{}", t!("archive.exists", output.display()));
+        return 1;
+    }
+
+    let sources: Vec<PathBuf> = sources.iter().map(PathBuf::from).collect();
+    match compress(&sources, &output, format) {
+        Ok(count) => {
+            let size = fs::metadata(&output).map(|m| m.len()).unwrap_or(0);
+            println!("{}", t!("archive.created", output.display(), count, human_size(size)));
+            0
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&output);
+            report_error("compress", &output, e)
+        }
+    }
+}
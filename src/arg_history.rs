@@ -0,0 +1,82 @@
+//! # Arg History Module
+//!
+//! Autocomplete de argumentos pelo histórico: ao completar um argumento, os
+//! valores já usados com o mesmo comando (e as mesmas palavras antes dele)
+//! aparecem primeiro, do mais recente ao mais antigo.
+//!
+//! ```text
+//! $ ssh <TAB>               # hosts de `ssh prod`, `ssh deploy@10.0.0.5`...
+//! $ git checkout <TAB>      # branches usadas antes com `git checkout`
+//! $ cd <TAB>                # diretórios visitados
+//! ```
+//!
+//! Caminhos (`/x`, `./x`, `~/x`) que não existem mais ficam de fora. Liga e
+//! desliga com `argument_history` em `[completion]` (padrão: ligado).
+
+use crate::expansion::expand_tilde_token;
+use crate::parser::split_command_positions;
+use std::borrow::Cow;
+use std::path::Path;
+
+/// Sugestões do histórico no máximo.
+pub const MAX_ARGUMENTS: usize = 10;
+
+/// Comandos do histórico olhados, do mais novo para trás.
+pub const ARGUMENT_SCAN: usize = 1000;
+
+/// As palavras de um trecho de comando (com aspas, se estiverem fechadas).
+fn words(text: &str) -> Vec<String> {
+    shlex::split(text).unwrap_or_else(|| text.split_whitespace().map(String::from).collect())
+}
+
+/// A palavra é um caminho (`/x`, `./x`, `~/x`) que não existe mais? Nomes
+/// com barra no meio ficam (`feature/login` é uma branch).
+fn missing_path(word: &str, home: &str) -> bool {
+    let looks_like_path = ["/", "./", "../", "~"].iter().any(|p| word.starts_with(p));
+    looks_like_path && !Path::new(expand_tilde_token(Cow::Borrowed(word), home).as_ref()).exists()
+}
+
+/// Os argumentos usados no histórico (do mais novo ao mais antigo) depois de
+/// exatamente as palavras `before` (`["git", "checkout"]`), que começam com
+/// `prefix`. Flags só aparecem quando o `prefix` começa com `-`.
+pub fn argument_suggestions<'a>(
+    history: impl IntoIterator<Item = &'a str>,
+    before: &[String],
+    prefix: &str,
+    home: &str,
+) -> Vec<String> {
+    let mut found: Vec<String> = Vec::new();
+    if before.is_empty() {
+        return found;
+    }
+    for line in history.into_iter().take(ARGUMENT_SCAN) {
+        for segment in split_command_positions(line) {
+            let words = words(&segment.text);
+            let Some(word) = words.get(before.len()) else {
+                continue;
+            };
+            if words[..before.len()] != *before
+                || !word.starts_with(prefix)
+                || word == prefix
+                || (word.starts_with('-') && !prefix.starts_with('-'))
+                || found.contains(word)
+                || missing_path(word, home)
+            {
+                continue;
+            }
+            found.push(word.clone());
+            if found.len() == MAX_ARGUMENTS {
+                return found;
+            }
+        }
+    }
+    found
+}
+
+/// As palavras já digitadas no comando sendo completado (`git checkout `).
+pub fn words_before(line_before_word: &str) -> Vec<String> {
+    split_command_positions(line_before_word)
+        .pop()
+        .map(|segment| words(&segment.text))
+        .unwrap_or_default()
+}
@@ -0,0 +1,107 @@
+//! # Arrays Module
+//!
+//! Variáveis com uma lista de valores. Ficam só na shell (não vão para o
+//! ambiente dos programas, como no bash):
+//!
+//! ```bash
+//! files=(a.txt b.txt *.md)     # globs e variáveis já expandidos
+//! files+=(c.txt)               # adiciona no fim
+//! echo ${files[1]}             # b.txt (índices a partir de 0; -1 é o último)
+//! echo ${files[@]}             # todos, um argumento cada
+//! echo ${files[*]}             # todos num argumento só
+//! echo ${#files[@]}            # quantidade de elementos
+//! unset files
+//! ```
+//!
+//! `$files` sozinho é o primeiro elemento, também como no bash.
+
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+/// Arrays da shell, por nome.
+static ARRAYS: RwLock<BTreeMap<String, Vec<String>>> = RwLock::new(BTreeMap::new());
+
+/// Uma linha `nome=(...)` ou `nome+=(...)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArrayAssignment {
+    pub name: String,
+    /// `+=`: adiciona ao array em vez de substituir
+    pub append: bool,
+    /// O que está entre os parênteses, ainda sem separar nem expandir
+    pub items: String,
+}
+
+/// Nome válido de variável: letras, números e `_`, sem começar com número.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Reconhece a atribuição de um array (`files=(a b)`, `files+=(c)`).
+pub fn parse_array_assignment(line: &str) -> Option<ArrayAssignment> {
+    let (target, value) = line.trim().split_once('=')?;
+    let items = value.strip_prefix('(')?.strip_suffix(')')?;
+    let (name, append) = match target.strip_suffix('+') {
+        Some(name) => (name, true),
+        None => (target, false),
+    };
+    is_valid_name(name).then(|| ArrayAssignment {
+        name: name.to_string(),
+        append,
+        items: items.to_string(),
+    })
+}
+
+/// A palavra começa uma atribuição de array (`files=(`), mesmo incompleta?
+/// Usado no destaque da linha enquanto ela é digitada.
+pub fn starts_array_assignment(word: &str) -> bool {
+    word.split_once("=(")
+        .is_some_and(|(target, _)| is_valid_name(target.strip_suffix('+').unwrap_or(target)))
+}
+
+/// Define (ou, com `append`, estende) o array `name`.
+pub fn assign_array(name: &str, items: Vec<String>, append: bool) {
+    let mut arrays = ARRAYS.write().unwrap_or_else(|e| e.into_inner());
+    match arrays.get_mut(name) {
+        Some(existing) if append => existing.extend(items),
+        _ => {
+            arrays.insert(name.to_string(), items);
+        }
+    }
+}
+
+/// Remove o array `name` (`unset`); devolve se ele existia.
+pub fn remove_array(name: &str) -> bool {
+    ARRAYS.write().unwrap_or_else(|e| e.into_inner()).remove(name).is_some()
+}
+
+/// Os elementos do array `name`.
+pub fn array_items(name: &str) -> Option<Vec<String>> {
+    ARRAYS.read().unwrap_or_else(|e| e.into_inner()).get(name).cloned()
+}
+
+/// `${name[index]}`: um elemento (índice negativo conta do fim) ou, com `@`
+/// e `*`, todos separados por espaço.
+pub fn array_element(name: &str, index: &str) -> Option<String> {
+    let arrays = ARRAYS.read().unwrap_or_else(|e| e.into_inner());
+    let items = arrays.get(name)?;
+    if index == "@" || index == "*" {
+        return Some(items.join(" "));
+    }
+    let index: i64 = index.trim().parse().ok()?;
+    let position = if index < 0 { items.len() as i64 + index } else { index };
+    usize::try_from(position).ok().and_then(|i| items.get(i)).cloned()
+}
+
+/// `${#name[@]}`: quantidade de elementos (0 para um array que não existe).
+pub fn array_len(name: &str) -> usize {
+    ARRAYS.read().unwrap_or_else(|e| e.into_inner()).get(name).map_or(0, Vec::len)
+}
+
+/// Um token que é exatamente `${name[@]}` vira um argumento por elemento
+/// (nenhum, se o array não existe). Outros tokens devolvem `None`.
+pub fn array_words(token: &str) -> Option<Vec<String>> {
+    let name = token.strip_prefix("${")?.strip_suffix("[@]}")?;
+    is_valid_name(name).then(|| array_items(name).unwrap_or_default())
+}
@@ -0,0 +1,83 @@
+//! # Auto-Pair Module
+//!
+//! Fechamento automático de aspas e parênteses/colchetes/chaves durante a edição
+//! (opção `auto_pair` da seção `[behavior]`).
+//!
+//! ## Comportamento
+//! - Os fechamentos que faltam aparecem como *hint* logo após o cursor (seta
+//!   para a direita aceita) e são acrescentados ao apertar Enter.
+//! - Digitar um fechamento que já está logo após o cursor apenas pula sobre ele.
+//! - Apagar a abertura remove junto o fechamento pendente, já que ele só existe
+//!   como hint até a linha ser aceita.
+
+use rustyline::{Cmd, ConditionalEventHandler, Event, EventContext, KeyCode, KeyEvent, Movement, RepeatCount};
+
+/// Caracteres de fechamento tratados pelo [`SkipCloser`].
+pub const CLOSERS: &[char] = &[')', ']', '}', '\'', '"'];
+
+/// Retorna o fechamento correspondente a uma abertura.
+fn closer_for(c: char) -> Option<char> {
+    match c {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        _ => None,
+    }
+}
+
+/// Calcula os fechamentos que faltam para balancear a linha, na ordem certa.
+///
+/// Dentro de aspas simples nada é interpretado; dentro de aspas duplas só a
+/// própria aspa fecha. Fechamentos sem abertura correspondente são ignorados.
+pub fn missing_closers(line: &str) -> String {
+    let mut stack: Vec<char> = Vec::new();
+    let mut escaped = false;
+
+    for c in line.chars() {
+        let quote = stack.last().copied().filter(|q| *q == '\'' || *q == '"');
+
+        if escaped {
+            escaped = false;
+            continue;
+        }
+
+        match quote {
+            Some(q) if c == q => {
+                stack.pop();
+            }
+            Some('"') if c == '\\' => escaped = true,
+            Some(_) => {}
+            None => match c {
+                '\\' => escaped = true,
+                '\'' | '"' => stack.push(c),
+                _ => {
+                    if let Some(close) = closer_for(c) {
+                        stack.push(close);
+                    } else if stack.last() == Some(&c) {
+                        stack.pop();
+                    }
+                }
+            },
+        }
+    }
+
+    stack.iter().rev().collect()
+}
+
+// -----------------------------------------------------------------------------
+// LINE EDITOR HANDLER
+// -----------------------------------------------------------------------------
+
+/// Handler ligado aos fechamentos: se o caractere sob o cursor é o mesmo que
+/// foi digitado, move o cursor em vez de duplicá-lo.
+pub struct SkipCloser;
+
+impl ConditionalEventHandler for SkipCloser {
+    fn handle(&self, evt: &Event, _n: RepeatCount, _positive: bool, ctx: &EventContext) -> Option<Cmd> {
+        let Some(KeyEvent(KeyCode::Char(typed), _)) = evt.get(0) else {
+            return None;
+        };
+        let next = ctx.line()[ctx.pos()..].chars().next()?;
+        (next == *typed && CLOSERS.contains(typed)).then_some(Cmd::Move(Movement::ForwardChar(1)))
+    }
+}
@@ -0,0 +1,164 @@
+//! # Batch Module
+//!
+//! Modo em lote, para scripts de provisionamento e passos de CI: uma lista de
+//! comandos, um por linha, lida de um arquivo ou da entrada padrão (`-`).
+//!
+//! - `--fail-fast` - para no primeiro comando que falhar
+//! - `--trace` - mostra cada comando (`+ cmd`) na saída de erro antes de rodar
+//!
+//! Linhas em branco e comentários (`#`) são ignorados. No fim, se algo falhou,
+//! um resumo com a linha, o código de saída e o comando de cada falha vai para
+//! a saída de erro, e a shell sai com o código da última falha.
+//!
+//! ```bash
+//! clios-shell --command-file provision.txt --fail-fast --trace
+//! cat passos.txt | clios-shell --command-file -
+//! ```
+
+use crate::crash::{report_crash, run_guarded};
+use crate::rhai_integration::flush_queued_output;
+use crate::shell::CliosShell;
+use crate::signals::EXIT_INTERRUPTED;
+use crate::statements::StatementBuffer;
+use crate::t;
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+
+/// Código de um comando que derrubou a shell (ver módulo crash).
+const EXIT_CRASHED: i32 = 70;
+
+/// Opções do modo em lote.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BatchOptions {
+    /// Para no primeiro comando que falhar.
+    pub fail_fast: bool,
+    /// Mostra cada comando antes de rodar.
+    pub trace: bool,
+}
+
+/// Um comando da lista, com a linha de onde veio.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchCommand {
+    pub line: usize,
+    pub text: String,
+}
+
+/// Um comando que terminou com código diferente de zero.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchFailure {
+    pub line: usize,
+    pub command: String,
+    pub code: i32,
+}
+
+/// Resultado de uma execução em lote.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchReport {
+    /// Comandos executados.
+    pub ran: usize,
+    /// Comandos que não chegaram a rodar (`--fail-fast`, `exit`, Ctrl+C).
+    pub skipped: usize,
+    pub failures: Vec<BatchFailure>,
+}
+
+impl BatchReport {
+    /// Código de saída do lote: o da última falha, ou 0.
+    pub fn exit_code(&self) -> i32 {
+        self.failures.last().map(|f| f.code).unwrap_or(0)
+    }
+}
+
+/// Os comandos de um conteúdo, sem linhas em branco e comentários. Um `case`
+/// ou `select` de várias linhas vira um comando só, com a linha em que começa.
+pub fn parse_commands(content: &str) -> Vec<BatchCommand> {
+    let mut commands = Vec::new();
+    let mut statements = StatementBuffer::default();
+    let mut first_line = 0;
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if statements.is_empty() {
+            first_line = i + 1;
+        }
+        if let Some(text) = statements.push(line) {
+            commands.push(BatchCommand { line: first_line, text });
+        }
+    }
+    if let Some(text) = statements.finish() {
+        commands.push(BatchCommand { line: first_line, text });
+    }
+    commands
+}
+
+/// Os comandos do arquivo `source` (ou da entrada padrão, com `-`).
+pub fn read_commands(source: &str) -> io::Result<Vec<BatchCommand>> {
+    let content = if source == "-" {
+        let mut content = String::new();
+        io::stdin().read_to_string(&mut content)?;
+        content
+    } else {
+        fs::read_to_string(source)?
+    };
+    Ok(parse_commands(&content))
+}
+
+/// A linha do `--trace` (`+ cmd`, como o `set -x` do bash).
+pub fn trace_line(command: &str, color: bool) -> String {
+    if color {
+        format!("\x1b[2m+ {}\x1b[0m", command)
+    } else {
+        format!("+ {}", command)
+    }
+}
+
+/// O resumo do fim; vazio quando nada falhou.
+pub fn format_summary(report: &BatchReport) -> String {
+    if report.failures.is_empty() {
+        return String::new();
+    }
+    let mut out = format!("{}\n", t!("batch.failed", report.failures.len(), report.ran));
+    for failure in &report.failures {
+        // Um `case` ou `select` de várias linhas aparece pela primeira
+        let command = match failure.command.split_once('\n') {
+            Some((first, _)) => format!("{} ...", first),
+            None => failure.command.clone(),
+        };
+        out.push_str(&format!("  {}\n", t!("batch.failure", failure.line, failure.code, command)));
+    }
+    if report.skipped > 0 {
+        out.push_str(&format!("  {}\n", t!("batch.skipped", report.skipped)));
+    }
+    out
+}
+
+/// Roda os comandos em ordem na shell e devolve o resultado.
+pub fn run_batch(shell: &mut CliosShell, commands: &[BatchCommand], options: BatchOptions) -> BatchReport {
+    let color = io::stderr().is_terminal();
+    let mut report = BatchReport::default();
+    for (i, command) in commands.iter().enumerate() {
+        if options.trace {
+            eprintln!("{}", trace_line(&command.text, color));
+        }
+        if let Err(crash) = run_guarded(&command.text, || shell.process_input_line(&command.text)) {
+            report_crash(&crash);
+            shell.last_exit_code = EXIT_CRASHED;
+        }
+        report.ran += 1;
+        flush_queued_output();
+
+        let code = shell.last_exit_code;
+        if code != 0 && shell.exit_requested.is_none() {
+            report.failures.push(BatchFailure { line: command.line, command: command.text.clone(), code });
+        }
+
+        // Ctrl+C interrompe o lote inteiro, não só o comando atual
+        let stop = code == EXIT_INTERRUPTED || (options.fail_fast && code != 0);
+        if stop || shell.exit_requested.is_some() {
+            report.skipped = commands.len() - i - 1;
+            break;
+        }
+    }
+    report
+}
@@ -0,0 +1,271 @@
+//! # Bench Module
+//!
+//! Implements the hidden `bench` builtin (also reachable as `clios bench`).
+//!
+//! ## Medições
+//! - Tempo de renderização dos prompts (Powerline, Clássico e com o cache)
+//! - Vazão das expansões (variáveis, til, aliases, globs) sobre entradas sintéticas
+//! - Latência da varredura do PATH
+//!
+//! As entradas são sempre as mesmas (sintéticas e fixas), para que dois relatórios
+//! gerados em máquinas diferentes possam ser comparados em uma issue de performance.
+//!
+//! As medições rodam num processo filho (`clios bench` com `CLIOS_BENCH` no
+//! ambiente dele), então o ambiente da shell não muda, e os arquivos do teste
+//! de glob são apagados pelo pai mesmo se o filho for interrompido.
+
+use crate::config::CliosConfig;
+use crate::expansion::{
+    expand_alias_string, expand_globs, expand_tilde, expand_tokens, expand_variables_with_state,
+};
+use crate::jobs::new_job_list;
+use crate::prompt::{build_classic_prompt, build_powerline_prompt, get_powerline_segments};
+use crate::prompt_cache::{PromptCache, PromptKey};
+use crate::t;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+// -----------------------------------------------------------------------------
+// CONSTANTES
+// -----------------------------------------------------------------------------
+
+/// Quantas vezes cada prompt é renderizado
+const PROMPT_ITERATIONS: usize = 50;
+
+/// Quantidade de tokens sintéticos usados no teste de expansão
+const EXPANSION_TOKENS: usize = 10_000;
+
+/// Quantidade de arquivos criados para o teste de glob
+const GLOB_FILES: usize = 500;
+
+/// Quantas varreduras completas do PATH são feitas
+const PATH_SCANS: usize = 5;
+
+/// Variável usada pelos tokens sintéticos (`pre-$CLIOS_BENCH-suf`), definida
+/// só no processo que mede
+const BENCH_VAR: &str = "CLIOS_BENCH";
+
+// -----------------------------------------------------------------------------
+// MEASUREMENT HELPERS
+// -----------------------------------------------------------------------------
+
+/// Resultado agregado de uma série de medições.
+pub struct BenchStats {
+    pub min: Duration,
+    pub max: Duration,
+    pub avg: Duration,
+}
+
+/// Executa `f` `iterations` vezes e devolve min/máx/média.
+pub fn measure<F: FnMut()>(iterations: usize, mut f: F) -> BenchStats {
+    let iterations = iterations.max(1);
+    let mut min = Duration::MAX;
+    let mut max = Duration::ZERO;
+    let mut total = Duration::ZERO;
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        f();
+        let elapsed = start.elapsed();
+        min = min.min(elapsed);
+        max = max.max(elapsed);
+        total += elapsed;
+    }
+
+    BenchStats {
+        min,
+        max,
+        avg: total / iterations as u32,
+    }
+}
+
+/// Gera a lista fixa de tokens usada no teste de expansão.
+fn synthetic_tokens() -> Vec<String> {
+    let patterns = ["plain", "$HOME", "${USER}_file", "~/docs", "pre-$CLIOS_BENCH-suf", "$?"];
+    (0..EXPANSION_TOKENS)
+        .map(|i| patterns[i % patterns.len()].to_string())
+        .collect()
+}
+
+fn print_stats(label: &str, stats: &BenchStats) {
+    println!(
+        "  {:<22} avg {:>10.3?}  min {:>10.3?}  max {:>10.3?}",
+        label, stats.avg, stats.min, stats.max
+    );
+}
+
+fn print_throughput(label: &str, items: usize, elapsed: Duration) {
+    let per_sec = items as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+    println!("  {:<22} {:>10.3?}  ({})", label, elapsed, t!("bench.per_second", format!("{:.0}", per_sec)));
+}
+
+// -----------------------------------------------------------------------------
+// BENCH SECTIONS
+// -----------------------------------------------------------------------------
+
+fn bench_prompts(config: &CliosConfig) {
+    println!("\x1b[1;32m{}\x1b[0m", t!("bench.prompts", PROMPT_ITERATIONS));
+    let jobs = new_job_list();
+
+    let powerline = measure(PROMPT_ITERATIONS, || {
+        let _ = build_powerline_prompt(get_powerline_segments(config, &jobs));
+    });
+    print_stats("powerline", &powerline);
+
+    let classic = measure(PROMPT_ITERATIONS, || {
+        let _ = build_classic_prompt(config, 0, &jobs);
+    });
+    print_stats("classic", &classic);
+
+    // Enter numa linha vazia: chave calculada, prompt reaproveitado
+    let mut cache = PromptCache::default();
+    let cached = measure(PROMPT_ITERATIONS, || {
+        let key = PromptKey::current(config, "classic", false, 0, &jobs);
+        let _ = cache.get_or_render(key, || build_classic_prompt(config, 0, &jobs));
+    });
+    print_stats("classic (cache)", &cached);
+}
+
+fn bench_expansion() {
+    println!("\x1b[1;32m{}\x1b[0m", t!("bench.expansion", EXPANSION_TOKENS));
+
+    let tokens = synthetic_tokens();
+
+    let start = Instant::now();
+    let _ = expand_variables_with_state(tokens.clone(), 0, std::process::id());
+    print_throughput(t!("bench.variables"), EXPANSION_TOKENS, start.elapsed());
+
+    let start = Instant::now();
+    let _ = expand_tilde(tokens.clone());
+    print_throughput(t!("bench.tilde"), EXPANSION_TOKENS, start.elapsed());
+
+    // Comparação: três passagens encadeadas vs. pipeline de passagem única
+    let start = Instant::now();
+    let _ = expand_globs(expand_tilde(expand_variables_with_state(tokens.clone(), 0, 0)));
+    print_throughput(t!("bench.three_passes"), EXPANSION_TOKENS, start.elapsed());
+
+    let start = Instant::now();
+    let _ = expand_tokens(tokens.clone(), 0, 0);
+    print_throughput(t!("bench.single_pass"), EXPANSION_TOKENS, start.elapsed());
+
+    let mut aliases = HashMap::new();
+    aliases.insert("ll".to_string(), "ls -la".to_string());
+    aliases.insert("la".to_string(), "ll -A".to_string());
+    let start = Instant::now();
+    for _ in 0..EXPANSION_TOKENS {
+        let _ = expand_alias_string("la /tmp", &aliases);
+    }
+    print_throughput("aliases", EXPANSION_TOKENS, start.elapsed());
+
+    // Glob: diretório temporário com uma quantidade fixa de arquivos
+    let dir = glob_dir(std::process::id());
+    if fs::create_dir_all(&dir).is_ok() {
+        for i in 0..GLOB_FILES {
+            let _ = fs::write(dir.join(format!("file_{:04}.txt", i)), "");
+        }
+        let pattern = format!("{}/*.txt", dir.display());
+        let start = Instant::now();
+        let found = expand_globs(vec![pattern.clone()]).len();
+        print_throughput("globs", found, start.elapsed());
+
+        let start = Instant::now();
+        let found = expand_tokens(vec![pattern], 0, 0).len();
+        print_throughput(t!("bench.globs_single_pass"), found, start.elapsed());
+        let _ = fs::remove_dir_all(&dir);
+    } else {
+        println!("  {:<22} ({})", "globs", t!("bench.no_temp_dir"));
+    }
+}
+
+fn bench_path_scan() {
+    println!("\x1b[1;32m{}\x1b[0m", t!("bench.path", PATH_SCANS));
+
+    let path_var = env::var("PATH").unwrap_or_default();
+    let dirs: Vec<&str> = path_var.split(':').filter(|d| !d.is_empty()).collect();
+    let mut entries = 0;
+
+    let stats = measure(PATH_SCANS, || {
+        entries = 0;
+        for dir in &dirs {
+            if let Ok(list) = fs::read_dir(dir) {
+                entries += list.count();
+            }
+        }
+    });
+    print_stats(t!("bench.full_scan"), &stats);
+    println!("  {:<22} {}", t!("bench.size"), t!("bench.path_size", dirs.len(), entries));
+
+    let lookup = measure(PATH_SCANS, || {
+        let _ = which::which("sh");
+    });
+    print_stats("lookup 'sh'", &lookup);
+}
+
+// -----------------------------------------------------------------------------
+// ENTRY POINT
+// -----------------------------------------------------------------------------
+
+/// Diretório dos arquivos do teste de glob do processo `pid`.
+fn glob_dir(pid: u32) -> PathBuf {
+    env::temp_dir().join(format!("clios-bench-{}", pid))
+}
+
+/// Roda o bench num processo filho, com `CLIOS_BENCH` só no ambiente dele.
+/// Devolve o código de saída do filho.
+pub fn spawn_bench() -> i32 {
+    let child = env::current_exe().and_then(|exe| Command::new(exe).arg("bench").env(BENCH_VAR, "value").spawn());
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            eprintln!("bench: {}", e);
+            return 1;
+        }
+    };
+    let status = child.wait();
+    // Interrompido no meio do teste de glob, o filho não apaga os arquivos
+    let _ = fs::remove_dir_all(glob_dir(child.id()));
+    match status {
+        Ok(status) => status.code().unwrap_or(1),
+        Err(e) => {
+            eprintln!("bench: {}", e);
+            1
+        }
+    }
+}
+
+/// `clios bench`: mede no próprio processo se ele já tem `CLIOS_BENCH` (é o
+/// filho de [`spawn_bench`]); senão, roda o bench num filho.
+pub fn run_bench_command(config: &CliosConfig) -> i32 {
+    if env::var_os(BENCH_VAR).is_none() {
+        return spawn_bench();
+    }
+    run_bench(config);
+    0
+}
+
+/// Executa todas as medições e imprime o relatório.
+pub fn run_bench(config: &CliosConfig) {
+    println!("\x1b[1;36m=== Clios Bench v{} ===\x1b[0m", env!("CARGO_PKG_VERSION"));
+    println!(
+        "{}",
+        t!(
+            "bench.system",
+            env::consts::OS,
+            env::consts::ARCH,
+            env::current_dir().map(|p| p.display().to_string()).unwrap_or_default()
+        )
+    );
+    println!();
+
+    bench_prompts(config);
+    println!();
+    bench_expansion();
+    println!();
+    bench_path_scan();
+    println!();
+    println!("{}", t!("bench.attach"));
+}
@@ -3,29 +3,111 @@
 //! Contains all built-in shell commands that are executed internally
 //! rather than spawning external processes.
 //!
+//! A documentação de cada builtin (`help <cmd>`, `type`, autocomplete) fica no
+//! registro do módulo [`crate::registry`].
+//!
 //! ## Comandos Suportados
 //! - `cd` - Navegar entre diretórios
 //! - `pwd` - Exibir diretório atual
 //! - `alias` - Gerenciar aliases
 //! - `export` - Definir variáveis de ambiente
-//! - `history` - Exibir histórico de comandos
+//! - `env` - Listar o ambiente com filtro e JSON
+//! - `history` - Exibir histórico de comandos (`history stats` para estatísticas)
 //! - `source/load` - Carregar plugins Rhai
 //! - `plugins` - Listar plugins carregados
 //! - `rhai` - Executar código Rhai
-//! - `fg` - Trazer processo para foreground
+//! - `fg` - Trazer job para foreground (sem argumentos, o atual)
+//! - `fix-terminal` - Restaurar o terminal após um app quebrar
+//! - `trap` - Registrar comandos para eventos (EXIT)
+//! - `trash/restore` - Lixeira XDG para arquivos apagados
+//! - `tldr` - Exemplos de uso de um comando (tldr-pages, com cache)
+//! - `watch` - Reexecutar um comando periodicamente (tratado em `CliosShell`)
+//! - `repeat/loop` - Laços simples sobre um comando (tratado em `CliosShell`)
+//! - `run` - Tarefas do `clios.toml` do projeto (tratado em `CliosShell`)
+//! - `parallel` - Executar um comando por argumento em paralelo
+//! - `each` - Executar um comando por linha da entrada (tratado em `CliosShell`)
+//! - `range` - Sequência de números, um por linha
+//! - `clean-env` - Executar um comando com ambiente mínimo (tratado em `CliosShell`)
+//! - `out` - Saída guardada dos últimos comandos
+//! - `mkfifo/pipe` - Named pipes e pipes entre sessões
+//! - `remote` - Execução remota via ssh/scp com favoritos
+//! - `inside` - Executar comandos em um container
+//! - `gstatus/gbranches/glog` - Saída do git em tabelas ou JSON
+//! - `open` - Abrir arquivos e URLs no programa padrão
+//! - `calc` / `=` - Calculadora (tratado em `CliosShell`, antes das expansões)
+//! - `when` - Datas, epoch e durações
+//! - `path` - Ver e editar o $PATH
+//! - `extract` / `compress` - Arquivos compactados sem ferramentas externas
+//! - `hashsum` - Checksums e conferência de listas
+//! - `fetch` - Requisições HTTP com JSON colorido e downloads
+//! - `sysinfo` - Painel com sistema, CPU, memória, disco e carga
+//! - `procs` - Lista e encerra processos
+//! - `list` - Conteúdo do diretório em colunas
+//! - `usage` - Espaço em disco por entrada do diretório
+//! - `ports` / `myip` / `ping-lite` - Diagnósticos de rede
+//! - `secret` - Cofre cifrado de tokens
+//! - `??` - Sugestão de comando por programa externo ou Rhai (tratado em `CliosShell`)
+//! - `keys` - Atalhos ativos do editor de linha
+//! - `session` - Salvar/restaurar contexto (tratado em `CliosShell`)
+//! - `profile` - Perfis do `.clios.toml` (tratado em `CliosShell`)
+//! - `demo` - Modo de apresentação (tratado em `CliosShell`)
+//! - `abbr` - Abreviações expandidas na linha (tratado em `CliosShell`)
+//! - `export-compat` - Aliases e abreviações em sintaxe do bash (tratado em `CliosShell`)
+//! - `wait` / `disown` - Esperar e soltar jobs em background
+//! - `set` - Opções da shell (`pipefail`, `xtrace`, `dryrun`)
+//! - `include` - Ler outro arquivo rc (tratado em `CliosShell`)
 //! - `exit` - Sair da shell
 
-use nix::sys::signal::{self, Signal};
-use nix::sys::wait::{self, WaitPidFlag};
-use nix::unistd::{self, Pid};
 use rhai::{Engine, Scope, AST};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::env;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
-
-use crate::jobs::{JobList, list_jobs};
+use std::rc::Rc;
+
+use crate::accessibility::{bullet, plain_text, screen_reader};
+use crate::archive::{handle_compress, handle_extract};
+use crate::arrays::remove_array;
+use crate::bench::spawn_bench;
+use crate::command_hash::{self, handle_hash, hashed_path};
+use crate::compat::export_aliases_bash;
+use crate::config::CliosConfig;
+use crate::container::handle_inside;
+use crate::disk_usage::handle_usage;
+use crate::env_list::{handle_env, parse_env_args};
+use crate::fetch::handle_fetch;
+use crate::fifo::{handle_mkfifo, handle_pipe};
+use crate::git::handle_git_builtin;
+use crate::hashsum::handle_hashsum;
+use crate::history;
+use crate::jobs::{current_job, handle_disown, handle_wait, list_jobs, resolve_job_spec, resume_background, resume_foreground, show_job_output, stopped_job, update_jobs, watch_jobs, JobList};
+use crate::keys::handle_keys;
+use crate::last_output::handle_out;
+use crate::listing::handle_list;
+use crate::net::{handle_myip, handle_ping_lite, handle_ports};
+use crate::open::handle_open;
+use crate::options::handle_set;
+use crate::parallel::{parse_parallel_args, run_parallel};
+use crate::path_env::handle_path;
+use crate::plugin_registry::{format_plugin_help, PluginRegistry, SharedPluginRegistry};
+use crate::plugin_test::handle_plugin;
+use crate::procs::{handle_procs, truncate};
+use crate::quote::quote_word;
+use crate::range::handle_range;
+use crate::registry::{find_builtin, format_help_page, is_builtin, usage};
+use crate::remote::handle_remote;
+use crate::rhai_debug::{debug_script, format_trace};
+use crate::rhai_integration::{create_rhai_engine, PluginSet};
+use crate::secrets::handle_secret;
+use crate::signals::{clear_interrupt, is_interrupted};
+use crate::system_info::handle_sysinfo;
+use crate::t;
+use crate::terminal::fix_terminal;
+use crate::tldr::handle_tldr;
+use crate::trash::{handle_restore, handle_trash};
+use crate::when::handle_when;
 
 // -----------------------------------------------------------------------------
 // BUILTIN EXECUTION
@@ -35,10 +117,12 @@ use crate::jobs::{JobList, list_jobs};
 pub enum BuiltinResult {
     /// O comando foi tratado como builtin.
     Handled,
+    /// O comando foi tratado como builtin e terminou com o código informado.
+    Status(i32),
     /// O comando não era um builtin.
     NotBuiltin,
-    /// O shell deve sair.
-    Exit,
+    /// O shell deve sair com o código informado.
+    Exit(i32),
 }
 
 /// Executa comandos internos da Shell (Builtins).
@@ -50,10 +134,14 @@ pub fn handle_builtin(
     previous_dir: &mut Option<PathBuf>,
     rhai_engine: &mut Engine,
     rhai_scope: &mut Scope,
-    plugin_ast: &mut Option<AST>,
-    load_plugin_fn: impl Fn(&mut Engine, &mut Option<AST>, &str) -> Result<(), String>,
+    plugin_set: &mut PluginSet,
+    plugins: &SharedPluginRegistry,
+    load_plugin_fn: impl Fn(&mut Engine, &mut PluginSet, &SharedPluginRegistry, &str) -> Result<(), String>,
     history_file: &str,
     jobs_list: &JobList,
+    config: &CliosConfig,
+    traps: &mut HashMap<String, String>,
+    last_exit_code: i32,
 ) -> BuiltinResult {
     if tokens.is_empty() {
         return BuiltinResult::NotBuiltin;
@@ -65,21 +153,25 @@ pub fn handle_builtin(
             BuiltinResult::Handled
         }
         "history" => {
-            handle_history(history_file);
+            if tokens.get(1).map(|s| s.as_str()) == Some("stats") {
+                history::print_stats(history_file);
+            } else {
+                handle_history(history_file);
+            }
             BuiltinResult::Handled
         }
         "source" | "load" => {
             if let Some(path) = tokens.get(1) {
-                if let Err(e) = load_plugin_fn(rhai_engine, plugin_ast, path) {
+                if let Err(e) = load_plugin_fn(rhai_engine, plugin_set, plugins, path) {
                     eprintln!("{}", e);
                 }
             } else {
-                println!("Uso: source <arquivo.rhai>");
+                println!("{}", usage("source"));
             }
             BuiltinResult::Handled
         }
         "plugins" => {
-            handle_plugins(plugin_ast);
+            handle_plugins(plugin_set, &plugins.borrow());
             BuiltinResult::Handled
         }
         "pwd" => {
@@ -88,21 +180,64 @@ pub fn handle_builtin(
             }
             BuiltinResult::Handled
         }
-        "exit" => BuiltinResult::Exit,
+        "exit" => match tokens.get(1) {
+            None => BuiltinResult::Exit(last_exit_code),
+            Some(code) => match code.parse::<i32>() {
+                Ok(code) => BuiltinResult::Exit(code),
+                Err(_) => {
+                    eprintln!("{}", t!("exit.numeric_required", code));
+                    BuiltinResult::Exit(2)
+                }
+            },
+        },
+        "trap" => {
+            handle_trap(tokens, traps);
+            BuiltinResult::Handled
+        }
         "alias" => {
             handle_alias(tokens, aliases);
             BuiltinResult::Handled
         }
         "rhai" => {
-            handle_rhai_command(tokens, rhai_engine, rhai_scope, plugin_ast);
+            match tokens.get(1).and_then(|code| debug_args(code)) {
+                Some(args) => handle_rhai_debug(&args),
+                None => handle_rhai_command(tokens, rhai_engine, rhai_scope, plugin_set.public_ast()),
+            }
             BuiltinResult::Handled
         }
         "fg" => {
-            handle_fg(tokens);
+            handle_fg(tokens, jobs_list);
+            BuiltinResult::Handled
+        }
+        "bg" => {
+            handle_bg(tokens, jobs_list);
+            BuiltinResult::Handled
+        }
+        "fix-terminal" => {
+            if !fix_terminal() {
+                eprintln!("{}", t!("fix_terminal.no_state"));
+            }
+            BuiltinResult::Handled
+        }
+        "tldr" => {
+            handle_tldr(tokens);
+            BuiltinResult::Handled
+        }
+        "trash" => {
+            handle_trash(tokens);
+            BuiltinResult::Handled
+        }
+        "restore" => {
+            handle_restore(tokens);
             BuiltinResult::Handled
         }
         "jobs" => {
-            list_jobs(jobs_list);
+            match (tokens.get(1).map(|s| s.as_str()), tokens.get(2)) {
+                (Some("--output" | "-o"), Some(spec)) => show_job_output(jobs_list, spec),
+                (Some("--watch" | "-w"), None) => watch_jobs(jobs_list),
+                (Some(_), _) => eprintln!("{}", usage("jobs")),
+                (None, _) => list_jobs(jobs_list),
+            }
             BuiltinResult::Handled
         }
         "export" => {
@@ -118,16 +253,76 @@ pub fn handle_builtin(
             BuiltinResult::Handled
         }
         "type" => {
-            handle_type(tokens, aliases);
+            handle_type(tokens, aliases, &plugins.borrow());
+            BuiltinResult::Handled
+        }
+        "hash" => {
+            handle_hash(tokens);
             BuiltinResult::Handled
         }
         "help" => {
-            handle_help();
+            match tokens.get(1) {
+                // Comandos de plugin vêm antes dos builtins, como na execução
+                Some(name) if name == "keys" => {
+                    handle_keys(&tokens[1..2], config);
+                }
+                Some(name) => match (plugins.borrow().get(name), find_builtin(name)) {
+                    (Some(command), _) => print!("{}", format_plugin_help(command)),
+                    (None, Some(info)) => print!("{}", format_help_page(info)),
+                    (None, None) => eprintln!("{}", t!("help.not_found", name)),
+                },
+                None => handle_help(&plugins.borrow()),
+            }
             BuiltinResult::Handled
         }
+        "out" => BuiltinResult::Status(handle_out(tokens)),
+        "pipe" => BuiltinResult::Status(handle_pipe(tokens, None)),
+        "mkfifo" => BuiltinResult::Status(handle_mkfifo(tokens)),
+        "remote" => BuiltinResult::Status(handle_remote(tokens, jobs_list)),
+        "inside" => BuiltinResult::Status(handle_inside(tokens)),
+        "gstatus" | "gbranches" | "glog" => BuiltinResult::Status(handle_git_builtin(tokens)),
+        "open" => BuiltinResult::Status(handle_open(tokens)),
+        "when" => BuiltinResult::Status(handle_when(tokens)),
+        "path" => BuiltinResult::Status(handle_path(tokens)),
+        "extract" => BuiltinResult::Status(handle_extract(tokens)),
+        "compress" => BuiltinResult::Status(handle_compress(tokens)),
+        "hashsum" => BuiltinResult::Status(handle_hashsum(tokens)),
+        "fetch" => BuiltinResult::Status(handle_fetch(tokens)),
+        "sysinfo" => BuiltinResult::Status(handle_sysinfo(tokens)),
+        "procs" => BuiltinResult::Status(handle_procs(tokens)),
+        "usage" => BuiltinResult::Status(handle_usage(tokens)),
+        "list" => BuiltinResult::Status(handle_list(tokens)),
+        "range" => BuiltinResult::Status(handle_range(tokens)),
+        "wait" => BuiltinResult::Status(handle_wait(tokens, jobs_list)),
+        "disown" => BuiltinResult::Status(handle_disown(tokens, jobs_list)),
+        "set" => BuiltinResult::Status(handle_set(tokens)),
+        "plugin" => {
+            let loaded: Vec<String> = plugin_set.iter().map(|p| p.path.clone()).collect();
+            BuiltinResult::Status(handle_plugin(tokens, &loaded))
+        }
+        "ports" => BuiltinResult::Status(handle_ports(tokens)),
+        "myip" => BuiltinResult::Status(handle_myip(tokens)),
+        "ping-lite" => BuiltinResult::Status(handle_ping_lite(tokens)),
+        "secret" => BuiltinResult::Status(handle_secret(tokens)),
+        "keys" => BuiltinResult::Status(handle_keys(tokens, config)),
+        // Com opções que não são do builtin, é o `env` do sistema
+        "env" => match parse_env_args(&tokens[1..]) {
+            Some(opts) => BuiltinResult::Status(handle_env(&opts)),
+            None => BuiltinResult::NotBuiltin,
+        },
+        "parallel" => BuiltinResult::Status(match parse_parallel_args(&tokens[1..]) {
+            Ok(opts) => run_parallel(&opts, jobs_list),
+            Err(e) => {
+                eprintln!("parallel: {}", e);
+                eprintln!("{}", usage("parallel"));
+                2
+            }
+        }),
+        // Oculto: não aparece em `help`, `type` nem no autocomplete
+        "bench" => BuiltinResult::Status(spawn_bench()),
         "version" => {
             println!("Clios Shell v0.7.0");
-            println!("Desenvolvido em Rust");
+            println!("{}", t!("version.built_with"));
             BuiltinResult::Handled
         }
         _ => BuiltinResult::NotBuiltin,
@@ -146,7 +341,7 @@ fn handle_cd(tokens: &[String], previous_dir: &mut Option<PathBuf>) {
                 println!("{}", prev.display());
                 Some(prev.clone())
             } else {
-                println!("Erro: Nenhuma pasta anterior definida");
+                println!("{}", t!("cd.no_previous"));
                 None
             }
         } else {
@@ -183,45 +378,84 @@ fn handle_history(history_file: &str) {
             }
         }
     } else {
-        println!("Histórico vazio ou arquivo não encontrado: {}", history_path.display());
+        println!("{}", t!("history.empty", history_path.display()));
     }
 }
 
 /// Handles the `plugins` command.
-fn handle_plugins(plugin_ast: &Option<AST>) {
-    if let Some(ast) = plugin_ast {
-        println!("Comandos de Plugins Ativos:");
-        println!("----------------------------");
-
-        for func in ast.iter_functions() {
-            if !func.name.starts_with('_') {
-                println!("  ➜ {} ({} args)", func.name, func.params.len());
+fn handle_plugins(plugin_set: &PluginSet, plugins: &PluginRegistry) {
+    if plugin_set.is_empty() && plugins.is_empty() {
+        println!("{}", t!("plugins.none"));
+        return;
+    }
+    println!("{}", t!("plugins.header"));
+    println!("----------------------------");
+
+    if !plugins.is_empty() {
+        println!("{}", t!("plugins.commands"));
+        for command in plugins.commands() {
+            println!("  {} {:<16} {}", bullet(), command.name, command.summary());
+        }
+    }
+    if !plugin_set.is_empty() {
+        if !plugins.is_empty() {
+            println!("{}", t!("plugins.functions"));
+        }
+        // Nome repetido entre plugins: só aparece o do dono
+        for plugin in plugin_set.iter() {
+            for func in plugin.exports() {
+                if !func.name.starts_with('_') && plugin_set.owner(func.name).is_some_and(|o| o.path == plugin.path) {
+                    println!("  {} {} ({} args) \x1b[90m{}::\x1b[0m", bullet(), func.name, func.params.len(), plugin.namespace);
+                }
             }
         }
-        println!("----------------------------");
-    } else {
-        println!("Nenhum plugin carregado na memória.");
     }
+    println!("----------------------------");
 }
 
 /// Handles the `alias` command.
 fn handle_alias(tokens: &[String], aliases: &mut HashMap<String, String>) {
     if tokens.len() < 2 {
         for (name, val) in aliases.iter() {
-            println!("{}='{}'", name, val);
+            println!("{}={}", name, quote_word(val));
         }
+    } else if tokens[1] == "--export-bash" {
+        print!("{}", export_aliases_bash(aliases));
     } else {
         let arg = tokens[1..].join(" ");
         if let Some((name, value)) = arg.split_once('=') {
             aliases.insert(name.to_string(), value.to_string());
         } else {
-            println!("Erro: Use alias nome=valor");
+            println!("{}", t!("alias.format"));
         }
     }
 }
 
+/// Os argumentos de `rhai debug <arquivo> [linha|função...]`; outro código
+/// devolve `None` (`debug(x)` é a função do Rhai).
+fn debug_args(code: &str) -> Option<Vec<String>> {
+    let rest = code.trim().strip_prefix("debug")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(shlex::split(rest).unwrap_or_else(|| rest.split_whitespace().map(String::from).collect()))
+}
+
+/// Handles `rhai debug`: roda o arquivo no debugger, num motor à parte (os
+/// `register_command` do script não chegam à sessão).
+fn handle_rhai_debug(args: &[String]) {
+    let Some((path, breakpoints)) = args.split_first() else {
+        eprintln!("{}", usage("rhai"));
+        return;
+    };
+    let engine = create_rhai_engine(&Rc::new(RefCell::new(PluginRegistry::default())));
+    if let Err(e) = debug_script(engine, path, breakpoints) {
+        println!("{}", t!("rhai.error", e));
+    }
+}
+
 /// Handles the `rhai` command.
-fn handle_rhai_command(tokens: &[String], rhai_engine: &mut Engine, rhai_scope: &mut Scope, plugin_ast: &Option<AST>) {
+fn handle_rhai_command(tokens: &[String], rhai_engine: &mut Engine, rhai_scope: &mut Scope, plugin_ast: Option<&AST>) {
     let code = tokens.get(1).map(|s| s.as_str()).unwrap_or("").trim();
 
     if code.is_empty() {
@@ -247,33 +481,111 @@ fn handle_rhai_command(tokens: &[String], rhai_engine: &mut Engine, rhai_scope:
                     println!("=> {}", valor);
                 }
             }
-            Err(e) => println!("Erro Rhai: {}", e),
+            Err(_) if is_interrupted() => println!("{}", t!("rhai.interrupted")),
+            Err(e) => println!("{}", t!("rhai.error", format_trace(&e, None, None))),
         }
     }
 }
 
 /// Handles the `fg` command.
-fn handle_fg(tokens: &[String]) {
-    if let Some(pid_str) = tokens.get(1) {
-        if let Ok(pid_int) = pid_str.parse::<i32>() {
-            let pid = Pid::from_raw(pid_int);
-
-            let _ = signal::kill(pid, Signal::SIGCONT);
-            let _ = unistd::tcsetpgrp(std::io::stdin(), pid);
-            let _ = wait::waitpid(pid, Some(WaitPidFlag::WUNTRACED));
+///
+/// `fg` sem argumentos traz o job atual (o último parado com Ctrl+Z, ou o
+/// último em background); `fg %N` e `fg PID` escolhem um da lista.
+fn handle_fg(tokens: &[String], jobs_list: &JobList) {
+    update_jobs(jobs_list);
+    let pid = match tokens.get(1) {
+        Some(spec) if tokens.len() == 2 => match resolve_job_spec(jobs_list, spec) {
+            Some(pid) => pid,
+            None => {
+                eprintln!("{}", t!("jobs.fg_not_found", spec));
+                return;
+            }
+        },
+        Some(_) => {
+            eprintln!("{}", usage("fg"));
+            return;
+        }
+        None => match current_job(jobs_list) {
+            Some(pid) => pid,
+            None => {
+                eprintln!("{}", t!("jobs.no_current"));
+                return;
+            }
+        },
+    };
+    resume_foreground(jobs_list, pid);
+}
 
-            let shell_pgid = unistd::getpid();
-            let _ = unistd::tcsetpgrp(std::io::stdin(), shell_pgid);
+/// Handles the `bg` command.
+///
+/// `bg` sem argumentos continua o último job parado com Ctrl+Z; `bg %N` e
+/// `bg PID` (um ou mais) escolhem da lista.
+fn handle_bg(tokens: &[String], jobs_list: &JobList) {
+    update_jobs(jobs_list);
+    let pids: Vec<(String, i32)> = if tokens.len() == 1 {
+        match stopped_job(jobs_list) {
+            Some(pid) => vec![(pid.to_string(), pid)],
+            None => {
+                eprintln!("{}", t!("jobs.no_stopped"));
+                return;
+            }
         }
     } else {
-        println!("Uso: fg <PID>");
+        let mut pids = Vec::new();
+        for spec in &tokens[1..] {
+            match resolve_job_spec(jobs_list, spec) {
+                Some(pid) => pids.push((spec.clone(), pid)),
+                None => eprintln!("{}", t!("jobs.bg_not_found", spec)),
+            }
+        }
+        pids
+    };
+    for (spec, pid) in pids {
+        if !resume_background(jobs_list, pid) {
+            eprintln!("{}", t!("jobs.bg_not_stopped", spec));
+        }
+    }
+}
+
+/// Handles the `trap` command.
+///
+/// Formas aceitas:
+/// * `trap` - lista os traps registrados
+/// * `trap 'cmd' EXIT` - executa `cmd` quando a shell sair
+/// * `trap - EXIT` / `trap '' EXIT` - remove o trap
+fn handle_trap(tokens: &[String], traps: &mut HashMap<String, String>) {
+    if tokens.len() < 2 {
+        for (event, cmd) in traps.iter() {
+            println!("trap -- '{}' {}", cmd, event);
+        }
+        return;
+    }
+
+    if tokens.len() < 3 {
+        eprintln!("{}", usage("trap"));
+        return;
+    }
+
+    let action = &tokens[1];
+    for event in &tokens[2..] {
+        let event = event.to_uppercase();
+        if event != "EXIT" && event != "0" {
+            eprintln!("{}", t!("trap.unsupported", event));
+            continue;
+        }
+
+        if action == "-" || action.is_empty() {
+            traps.remove("EXIT");
+        } else {
+            traps.insert("EXIT".to_string(), action.clone());
+        }
     }
 }
 
 /// Handles the `export` command.
 fn handle_export(tokens: &[String]) {
     if tokens.len() < 2 {
-        println!("Uso: export VAR=VALOR");
+        println!("{}", usage("export"));
     } else {
         let arg = tokens[1..].join("");
         if let Some((key, value)) = arg.split_once('=') {
@@ -281,7 +593,7 @@ fn handle_export(tokens: &[String]) {
                 std::env::set_var(key, value);
             }
         } else {
-            println!("Erro: Use formato VAR=VALOR");
+            println!("{}", t!("export.format"));
         }
     }
 }
@@ -291,10 +603,16 @@ fn handle_export(tokens: &[String]) {
 // -----------------------------------------------------------------------------
 
 /// Executa o modo interativo dedicado ao Rhai (REPL).
-fn run_rhai_repl(rhai_engine: &mut Engine, rhai_scope: &mut Scope, plugin_ast: &Option<AST>) {
-    println!("Entrando no modo Rhai (Digite 'exit' para sair)");
-
-    let mut rl = rustyline::DefaultEditor::new().unwrap_or_else(|_| panic!("Falha ao iniciar REPL"));
+fn run_rhai_repl(rhai_engine: &mut Engine, rhai_scope: &mut Scope, plugin_ast: Option<&AST>) {
+    println!("{}", t!("rhai.repl_enter"));
+
+    let mut rl = match rustyline::DefaultEditor::new() {
+        Ok(rl) => rl,
+        Err(e) => {
+            eprintln!("{}", t!("tag.error", t!("rhai.repl_failed", e)));
+            return;
+        }
+    };
 
     let mut input_buffer = String::new();
     let mut open_braces = 0;
@@ -327,6 +645,8 @@ fn run_rhai_repl(rhai_engine: &mut Engine, rhai_scope: &mut Scope, plugin_ast: &
                 input_buffer.push('\n');
 
                 if open_braces == 0 {
+                    clear_interrupt();
+
                     // Combina com funções do plugin se disponível
                     let result = if let Some(ast) = plugin_ast {
                         match rhai_engine.compile(&input_buffer) {
@@ -346,7 +666,8 @@ fn run_rhai_repl(rhai_engine: &mut Engine, rhai_scope: &mut Scope, plugin_ast: &
                                 println!("=> {}", val);
                             }
                         }
-                        Err(e) => println!("Erro: {}", e),
+                        Err(_) if is_interrupted() => println!("{}", t!("rhai.interrupted")),
+                        Err(e) => println!("{}", t!("rhai.error", format_trace(&e, None, None))),
                     }
 
                     input_buffer.clear();
@@ -355,6 +676,9 @@ fn run_rhai_repl(rhai_engine: &mut Engine, rhai_scope: &mut Scope, plugin_ast: &
             Err(_) => break,
         }
     }
+
+    // Um Ctrl+C dentro do REPL não deve marcar o comando `rhai` como interrompido
+    clear_interrupt();
 }
 
 // -----------------------------------------------------------------------------
@@ -364,26 +688,27 @@ fn run_rhai_repl(rhai_engine: &mut Engine, rhai_scope: &mut Scope, plugin_ast: &
 /// Handles the `unalias` command - remove um alias.
 fn handle_unalias(tokens: &[String], aliases: &mut HashMap<String, String>) {
     if tokens.len() < 2 {
-        eprintln!("Uso: unalias <nome>");
+        eprintln!("{}", usage("unalias"));
         return;
     }
 
     let name = &tokens[1];
     if aliases.remove(name).is_some() {
-        println!("Alias '{}' removido.", name);
+        println!("{}", t!("unalias.removed", name));
     } else {
-        eprintln!("Alias '{}' não encontrado.", name);
+        eprintln!("{}", t!("unalias.not_found", name));
     }
 }
 
-/// Handles the `unset` command - remove uma variável de ambiente.
+/// Handles the `unset` command - remove uma variável de ambiente (ou um array).
 fn handle_unset(tokens: &[String]) {
     if tokens.len() < 2 {
-        eprintln!("Uso: unset <VARIAVEL>");
+        eprintln!("{}", usage("unset"));
         return;
     }
 
     for var in &tokens[1..] {
+        remove_array(var);
         unsafe {
             env::remove_var(var);
         }
@@ -391,9 +716,9 @@ fn handle_unset(tokens: &[String]) {
 }
 
 /// Handles the `type` command - mostra o tipo de um comando.
-fn handle_type(tokens: &[String], aliases: &HashMap<String, String>) {
+fn handle_type(tokens: &[String], aliases: &HashMap<String, String>, plugins: &PluginRegistry) {
     if tokens.len() < 2 {
-        eprintln!("Uso: type <comando>");
+        eprintln!("{}", usage("type"));
         return;
     }
 
@@ -401,72 +726,214 @@ fn handle_type(tokens: &[String], aliases: &HashMap<String, String>) {
 
     // Verificar se é um alias
     if let Some(val) = aliases.get(cmd) {
-        println!("{} is aliased to '{}'", cmd, val);
+        println!("{}", t!("type.alias", cmd, val));
+        return;
+    }
+
+    // Comandos de plugin rodam antes dos builtins
+    if plugins.get(cmd).is_some() {
+        println!("{}", t!("type.plugin", cmd));
         return;
     }
 
     // Verificar se é um builtin
-    let builtins = [
-        "cd", "pwd", "alias", "unalias", "export", "unset", "history",
-        "source", "load", "plugins", "rhai", "fg", "exit", "type", "help", "version"
-    ];
-    if builtins.contains(&cmd.as_str()) {
-        println!("{} is a shell builtin", cmd);
+    if is_builtin(cmd) {
+        println!("{}", t!("type.builtin", cmd));
         return;
     }
 
-    // Verificar se é um executável no PATH
-    if let Ok(path_var) = env::var("PATH") {
-        for path in path_var.split(':') {
-            let full_path = std::path::Path::new(path).join(cmd);
-            if full_path.exists() && full_path.is_file() {
-                println!("{} is {}", cmd, full_path.display());
-                return;
-            }
-        }
+    // Verificar se já está na tabela do `hash` ou é um executável no PATH
+    if let Some(path) = hashed_path(cmd) {
+        println!("{}", t!("type.hashed", cmd, path.display()));
+        return;
     }
+    if let Some(path) = command_hash::peek(cmd) {
+        println!("{}", t!("type.path", cmd, path.display()));
+        return;
+    }
+
+    eprintln!("{}", t!("type.not_found", cmd));
+}
+
+/// O resumo do `help`: cada seção com as linhas dela, como chaves do catálogo
+/// (`help.row.*` tem o uso e a descrição separados por tab).
+const HELP_SECTIONS: &[(&str, &[&str])] = &[
+    (
+        "help.section.navigation",
+        &[
+            "help.row.cd",
+            "help.row.pwd",
+        ],
+    ),
+    (
+        "help.section.aliases",
+        &[
+            "help.row.alias_list",
+            "help.row.alias_add",
+            "help.row.abbr",
+            "help.row.unalias",
+            "help.row.export_compat",
+        ],
+    ),
+    (
+        "help.section.variables",
+        &[
+            "help.row.export",
+            "help.row.unset",
+            "help.row.set",
+            "help.row.include",
+            "help.row.env",
+        ],
+    ),
+    (
+        "help.section.plugins",
+        &[
+            "help.row.source",
+            "help.row.plugins",
+            "help.row.plugin_test",
+            "help.row.rhai_code",
+            "help.row.rhai_repl",
+            "help.row.rhai_debug",
+        ],
+    ),
+    (
+        "help.section.other",
+        &[
+            "help.row.history",
+            "help.row.history_stats",
+            "help.row.trash",
+            "help.row.restore",
+            "help.row.watch",
+            "help.row.repeat",
+            "help.row.run",
+            "help.row.loop",
+            "help.row.parallel",
+            "help.row.each",
+            "help.row.range",
+            "help.row.clean_env",
+            "help.row.out",
+            "help.row.pipe",
+            "help.row.mkfifo",
+            "help.row.remote",
+            "help.row.inside",
+            "help.row.gstatus",
+            "help.row.gbranches",
+            "help.row.open",
+            "help.row.calc",
+            "help.row.when",
+            "help.row.path",
+            "help.row.extract",
+            "help.row.compress",
+            "help.row.hashsum",
+            "help.row.fetch",
+            "help.row.sysinfo",
+            "help.row.procs",
+            "help.row.list",
+            "help.row.usage",
+            "help.row.ports",
+            "help.row.myip",
+            "help.row.ping_lite",
+            "help.row.secret",
+            "help.row.suggest",
+            "help.row.keys",
+            "help.row.tldr",
+            "help.row.type",
+            "help.row.hash",
+            "help.row.fg",
+            "help.row.bg",
+            "help.row.wait",
+            "help.row.disown",
+            "help.row.jobs",
+            "help.row.fix_terminal",
+            "help.row.session",
+            "help.row.profile",
+            "help.row.demo",
+            "help.row.version",
+            "help.row.help",
+            "help.row.trap",
+            "help.row.exit",
+        ],
+    ),
+    (
+        "help.section.operators",
+        &[
+            "help.row.op_pipe",
+            "help.row.op_and",
+            "help.row.op_redirect",
+            "help.row.op_append",
+            "help.row.op_stderr",
+            "help.row.op_background",
+        ],
+    ),
+];
+
+/// Largura da moldura do `help`, sem as bordas.
+const HELP_WIDTH: usize = 62;
+
+/// Uma linha do `help` dentro da moldura, completada com espaços.
+fn help_box_line(content: &str) -> String {
+    let width = plain_text(content).chars().count();
+    format!(
+        "\x1b[1;36m║\x1b[0m{}{}\x1b[1;36m║\x1b[0m",
+        content,
+        " ".repeat(HELP_WIDTH.saturating_sub(width))
+    )
+}
 
-    eprintln!("{}: not found", cmd);
+/// Uma linha de comando do `help`: o uso numa coluna, a descrição na outra.
+fn help_row(usage: &str, description: &str) -> String {
+    help_box_line(&format!("   {:<15} {}", usage, description))
 }
 
 /// Handles the `help` command - exibe ajuda.
-fn handle_help() {
-    println!("\x1b[1;36m╔══════════════════════════════════════════════════════════════╗\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m           \x1b[1;33mClios Shell v1.0.0\x1b[0m - Comandos Internos           \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m╠══════════════════════════════════════════════════════════════╣\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m \x1b[1;32mNavegação:\x1b[0m                                                   \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   cd [dir]        Mudar diretório (cd - para anterior)       \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   pwd             Exibir diretório atual                     \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m                                                              \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m \x1b[1;32mAliases:\x1b[0m                                                     \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   alias           Listar todos os aliases                    \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   alias x='cmd'   Criar alias                                \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   unalias <nome>  Remover alias                              \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m                                                              \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m \x1b[1;32mVariáveis:\x1b[0m                                                   \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   export VAR=val  Definir variável de ambiente               \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   unset VAR       Remover variável de ambiente               \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m                                                              \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m \x1b[1;32mPlugins (Rhai):\x1b[0m                                              \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   source <file>   Carregar plugin Rhai                       \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   plugins         Listar plugins carregados                  \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   rhai <código>   Executar código Rhai inline                \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   rhai            Entrar no modo REPL Rhai                   \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m                                                              \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m \x1b[1;32mOutros:\x1b[0m                                                      \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   history         Exibir histórico de comandos               \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   type <cmd>      Mostrar tipo do comando                    \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   fg <PID>        Trazer processo para foreground            \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   version         Exibir versão da shell                     \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   help            Exibir esta ajuda                          \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   exit            Sair da shell                              \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m                                                              \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m \x1b[1;32mOperadores:\x1b[0m                                                  \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   cmd1 | cmd2     Pipeline (conectar stdout -> stdin)        \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   cmd1 && cmd2    Executar cmd2 se cmd1 sucesso              \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   cmd > file      Redirecionar stdout para arquivo           \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   cmd >> file     Append stdout ao arquivo                   \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   cmd 2> file     Redirecionar stderr para arquivo           \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m║\x1b[0m   cmd &           Executar em background                     \x1b[1;36m║\x1b[0m");
-    println!("\x1b[1;36m╚══════════════════════════════════════════════════════════════╝\x1b[0m");
+fn handle_help(plugins: &PluginRegistry) {
+    let rule = "═".repeat(HELP_WIDTH);
+    let title = format!("Clios Shell v1.0.0 - {}", t!("help.title"));
+    let margin = HELP_WIDTH.saturating_sub(title.chars().count()) / 2;
+
+    let mut lines: Vec<String> = vec![
+        format!("\x1b[1;36m╔{}╗\x1b[0m", rule),
+        help_box_line(&format!("{}\x1b[1;33m{}\x1b[0m", " ".repeat(margin), title)),
+        format!("\x1b[1;36m╠{}╣\x1b[0m", rule),
+    ];
+    for (i, (section, rows)) in HELP_SECTIONS.iter().enumerate() {
+        if i > 0 {
+            lines.push(help_box_line(""));
+        }
+        lines.push(help_box_line(&format!(" \x1b[1;32m{}\x1b[0m", t!(section))));
+        for row in *rows {
+            let (usage, description) = t!(row).split_once('\t').unwrap_or((row, ""));
+            lines.push(help_row(usage, description));
+        }
+        // Comandos registrados pelos plugins carregados (`register_command`)
+        if *section == "help.section.plugins" {
+            for command in plugins.commands() {
+                lines.push(help_box_line(&format!("   {:<16}{}", truncate(&command.name, 15), truncate(command.summary(), 43))));
+            }
+        }
+    }
+    lines.push(format!("\x1b[1;36m╚{}╝\x1b[0m", rule));
+
+    // Leitor de tela: o mesmo conteúdo, sem moldura nem cores, e uma linha
+    // em branco só entre as seções
+    if screen_reader() {
+        let mut pending_blank = false;
+        let mut printed = false;
+        for line in lines.iter().map(|line| plain_text(line)) {
+            let line = line.trim_start();
+            if line.is_empty() {
+                pending_blank = printed;
+                continue;
+            }
+            if std::mem::take(&mut pending_blank) {
+                println!();
+            }
+            println!("{}", line);
+            printed = true;
+        }
+        return;
+    }
+    for line in &lines {
+        println!("{}", line);
+    }
 }
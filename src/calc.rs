@@ -0,0 +1,528 @@
+//! # Calc Module
+//!
+//! Avaliador de expressões aritméticas e o builtin `calc` (ou `=`):
+//!
+//! ```text
+//! = 2*(3+4)/5          → 2.8
+//! = 0xff + 0b1010      → 265
+//! calc -x 255          → 0xff
+//! = 200 * 15%          → 30
+//! = 80 + 10%           → 88 (somar/subtrair porcentagem é relativo ao valor)
+//! echo $(= 2^10)       → 1024
+//! ```
+//!
+//! Inteiros ficam inteiros enquanto o resultado for exato (`7/2` dá `3.5`,
+//! `6/2` dá `3`); estouros de `i64` passam para ponto flutuante. A expressão é
+//! lida do texto cru da linha, então `*`, `(` e `%` não precisam de aspas.
+
+use crate::registry::usage;
+use crate::t;
+use std::env;
+use std::fmt;
+
+/// Casas decimais mostradas (o resto é ruído de ponto flutuante).
+pub const DECIMALS: usize = 10;
+
+/// Valor de uma expressão.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+impl Number {
+    fn as_f64(self) -> f64 {
+        match self {
+            Number::Int(n) => n as f64,
+            Number::Float(f) => f,
+        }
+    }
+
+    /// Valor inteiro, se houver (`3.0` conta; `3.5` não).
+    pub fn as_int(self) -> Option<i64> {
+        match self {
+            Number::Int(n) => Some(n),
+            Number::Float(f) if f.is_finite() && f.fract().abs() < 1e-9 && f.abs() < i64::MAX as f64 => {
+                Some(f.round() as i64)
+            }
+            Number::Float(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Number::Int(n) => write!(f, "{}", n),
+            Number::Float(x) if !x.is_finite() => write!(f, "{}", x),
+            Number::Float(x) if x.abs() >= 1e16 => write!(f, "{:e}", x),
+            Number::Float(x) => {
+                let text = format!("{:.*}", DECIMALS, x);
+                let text = text.trim_end_matches('0').trim_end_matches('.');
+                // `-0.0000000000001` arredonda para `-0`
+                write!(f, "{}", if text == "-0" { "0" } else { text })
+            }
+        }
+    }
+}
+
+/// Base da saída do `calc`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Radix {
+    Decimal,
+    Hex,
+    Binary,
+    Octal,
+}
+
+/// Formata o resultado na base pedida; hex/bin/oct só para inteiros.
+pub fn format_number(value: Number, radix: Radix) -> Result<String, String> {
+    if radix == Radix::Decimal {
+        return Ok(value.to_string());
+    }
+    let n = value
+        .as_int()
+        .ok_or_else(|| t!("calc.not_integer", value))?;
+    let sign = if n < 0 { "-" } else { "" };
+    let abs = n.unsigned_abs();
+    Ok(match radix {
+        Radix::Hex => format!("{}0x{:x}", sign, abs),
+        Radix::Binary => format!("{}0b{:b}", sign, abs),
+        Radix::Octal => format!("{}0o{:o}", sign, abs),
+        Radix::Decimal => unreachable!(),
+    })
+}
+
+// -----------------------------------------------------------------------------
+// TOKENIZER
+// -----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(Number),
+    Ident(String),
+    Op(char),
+    Pow,
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            ' ' | '\t' | '_' => i += 1,
+            '0'..='9' | '.' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.' || chars[i] == '_') {
+                    // Expoente: `1e-3`
+                    if matches!(chars[i], 'e' | 'E')
+                        && !chars[start..i].iter().any(|c| matches!(c, 'x' | 'X' | 'b' | 'B' | 'o' | 'O'))
+                        && matches!(chars.get(i + 1), Some('+' | '-'))
+                    {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().filter(|&&c| c != '_').collect();
+                tokens.push(Token::Num(parse_literal(&text)?));
+            }
+            '$' | 'a'..='z' | 'A'..='Z' => {
+                let start = if c == '$' { i + 1 } else { i };
+                i = start;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                if i == start {
+                    return Err(t!("calc.dollar_without_name").to_string());
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            }
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                tokens.push(Token::Pow);
+                i += 2;
+            }
+            '^' => {
+                tokens.push(Token::Pow);
+                i += 1;
+            }
+            '+' | '-' | '*' | '/' | '%' => {
+                tokens.push(Token::Op(c));
+                i += 1;
+            }
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            _ => return Err(t!("calc.unexpected_char", c)),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Literal numérico: decimal, `0x`, `0b`, `0o` ou com ponto/expoente.
+fn parse_literal(text: &str) -> Result<Number, String> {
+    let lower = text.to_ascii_lowercase();
+    let radix = match lower.get(..2) {
+        Some("0x") => Some(16),
+        Some("0b") => Some(2),
+        Some("0o") => Some(8),
+        _ => None,
+    };
+    let parsed = match radix {
+        Some(radix) => i64::from_str_radix(&lower[2..], radix).ok().map(Number::Int),
+        None => lower
+            .parse::<i64>()
+            .map(Number::Int)
+            .ok()
+            .or_else(|| lower.parse::<f64>().ok().map(Number::Float)),
+    };
+    parsed.ok_or_else(|| t!("calc.invalid_number", text))
+}
+
+// -----------------------------------------------------------------------------
+// PARSER
+// -----------------------------------------------------------------------------
+
+/// Operando com a marca de porcentagem (`10%`), que muda o sentido de `+`/`-`.
+#[derive(Clone, Copy)]
+struct Operand {
+    value: Number,
+    percent: bool,
+}
+
+impl From<Number> for Operand {
+    fn from(value: Number) -> Self {
+        Operand { value, percent: false }
+    }
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    lookup: &'a dyn Fn(&str) -> Option<String>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(t!("calc.expected_found", describe(&expected), describe(&token))),
+            None => Err(t!("calc.expected_at_end", describe(&expected))),
+        }
+    }
+
+    /// expr := term (('+' | '-') term)*
+    fn expr(&mut self) -> Result<Operand, String> {
+        let mut left = self.term()?;
+        while let Some(Token::Op(op @ ('+' | '-'))) = self.peek().cloned() {
+            self.pos += 1;
+            let right = self.term()?;
+            // `80 + 10%` é 80 mais 10% de 80
+            let right = if right.percent { mul(left.value, right.value) } else { right.value };
+            left = if op == '+' { add(left.value, right) } else { sub(left.value, right) }.into();
+        }
+        Ok(left)
+    }
+
+    /// term := unary (('*' | '/' | '%') unary)*
+    fn term(&mut self) -> Result<Operand, String> {
+        let mut left = self.unary()?;
+        while let Some(Token::Op(op @ ('*' | '/' | '%'))) = self.peek().cloned() {
+            self.pos += 1;
+            let right = self.unary()?.value;
+            left = match op {
+                '*' => mul(left.value, right),
+                '/' => div(left.value, right)?,
+                _ => rem(left.value, right)?,
+            }
+            .into();
+        }
+        Ok(left)
+    }
+
+    /// unary := ('-' | '+') unary | power
+    fn unary(&mut self) -> Result<Operand, String> {
+        match self.peek() {
+            Some(Token::Op('-')) => {
+                self.pos += 1;
+                let operand = self.unary()?;
+                Ok(Operand { value: sub(Number::Int(0), operand.value), percent: operand.percent })
+            }
+            Some(Token::Op('+')) => {
+                self.pos += 1;
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    /// power := percent ('^' unary)?   (associativa à direita: `2^3^2` = 2^9)
+    fn power(&mut self) -> Result<Operand, String> {
+        let base = self.percent()?;
+        if self.peek() == Some(&Token::Pow) {
+            self.pos += 1;
+            let exponent = self.unary()?.value;
+            return Ok(pow(base.value, exponent).into());
+        }
+        Ok(base)
+    }
+
+    /// percent := primary '%'?   (`%` seguido de um operando é o resto da
+    /// divisão; `10 % (-3)` precisa dos parênteses)
+    fn percent(&mut self) -> Result<Operand, String> {
+        let value = self.primary()?;
+        if self.peek() == Some(&Token::Op('%'))
+            && !matches!(
+                self.tokens.get(self.pos + 1),
+                Some(Token::Num(_) | Token::Ident(_) | Token::LParen)
+            )
+        {
+            self.pos += 1;
+            return Ok(Operand { value: Number::Float(value.as_f64() / 100.0), percent: true });
+        }
+        Ok(value.into())
+    }
+
+    /// primary := número | nome | nome '(' args ')' | '(' expr ')'
+    fn primary(&mut self) -> Result<Number, String> {
+        match self.next() {
+            Some(Token::Num(n)) => Ok(n),
+            Some(Token::LParen) => {
+                let value = self.expr()?.value;
+                self.expect(Token::RParen)?;
+                Ok(value)
+            }
+            Some(Token::Ident(name)) if self.peek() == Some(&Token::LParen) => {
+                self.pos += 1;
+                let mut args = vec![self.expr()?.value];
+                while self.peek() == Some(&Token::Comma) {
+                    self.pos += 1;
+                    args.push(self.expr()?.value);
+                }
+                self.expect(Token::RParen)?;
+                call(&name, &args)
+            }
+            Some(Token::Ident(name)) => self.variable(&name),
+            Some(token) => Err(t!("calc.unexpected", describe(&token))),
+            None => Err(t!("calc.incomplete").to_string()),
+        }
+    }
+
+    /// Constantes e variáveis de ambiente numéricas (`$n` ou `n`).
+    fn variable(&self, name: &str) -> Result<Number, String> {
+        match name {
+            "pi" => return Ok(Number::Float(std::f64::consts::PI)),
+            "e" => return Ok(Number::Float(std::f64::consts::E)),
+            _ => {}
+        }
+        let value = (self.lookup)(name).ok_or_else(|| t!("calc.undefined_variable", name))?;
+        parse_literal(value.trim()).map_err(|_| t!("calc.not_a_number", name, value))
+    }
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Num(n) => n.to_string(),
+        Token::Ident(name) => name.clone(),
+        Token::Op(c) => format!("'{}'", c),
+        Token::Pow => "'^'".to_string(),
+        Token::LParen => "'('".to_string(),
+        Token::RParen => "')'".to_string(),
+        Token::Comma => "','".to_string(),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ARITHMETIC
+// -----------------------------------------------------------------------------
+
+fn float_op(a: Number, b: Number, op: fn(f64, f64) -> f64) -> Number {
+    Number::Float(op(a.as_f64(), b.as_f64()))
+}
+
+fn add(a: Number, b: Number) -> Number {
+    match (a, b) {
+        (Number::Int(x), Number::Int(y)) if let Some(r) = x.checked_add(y) => Number::Int(r),
+        _ => float_op(a, b, |x, y| x + y),
+    }
+}
+
+fn sub(a: Number, b: Number) -> Number {
+    match (a, b) {
+        (Number::Int(x), Number::Int(y)) if let Some(r) = x.checked_sub(y) => Number::Int(r),
+        _ => float_op(a, b, |x, y| x - y),
+    }
+}
+
+fn mul(a: Number, b: Number) -> Number {
+    match (a, b) {
+        (Number::Int(x), Number::Int(y)) if let Some(r) = x.checked_mul(y) => Number::Int(r),
+        _ => float_op(a, b, |x, y| x * y),
+    }
+}
+
+fn div(a: Number, b: Number) -> Result<Number, String> {
+    if b.as_f64() == 0.0 {
+        return Err(t!("calc.division_by_zero").to_string());
+    }
+    Ok(match (a, b) {
+        (Number::Int(x), Number::Int(y)) if x.checked_rem(y) == Some(0) => Number::Int(x / y),
+        _ => float_op(a, b, |x, y| x / y),
+    })
+}
+
+fn rem(a: Number, b: Number) -> Result<Number, String> {
+    if b.as_f64() == 0.0 {
+        return Err(t!("calc.division_by_zero").to_string());
+    }
+    Ok(match (a, b) {
+        (Number::Int(x), Number::Int(y)) => x.checked_rem(y).map(Number::Int).unwrap_or(Number::Int(0)),
+        _ => float_op(a, b, |x, y| x % y),
+    })
+}
+
+fn pow(a: Number, b: Number) -> Number {
+    match (a, b) {
+        (Number::Int(x), Number::Int(y))
+            if let Ok(y) = u32::try_from(y)
+                && let Some(r) = x.checked_pow(y) =>
+        {
+            Number::Int(r)
+        }
+        _ => float_op(a, b, f64::powf),
+    }
+}
+
+/// Funções disponíveis nas expressões.
+pub const FUNCTIONS: &[&str] = &[
+    "abs", "sqrt", "round", "floor", "ceil", "ln", "log", "log2", "sin", "cos", "tan", "min", "max",
+];
+
+fn call(name: &str, args: &[Number]) -> Result<Number, String> {
+    let arity = match name {
+        "min" | "max" => None,
+        _ if FUNCTIONS.contains(&name) => Some(1),
+        _ => return Err(t!("calc.unknown_function", name, FUNCTIONS.join(", "))),
+    };
+    if arity.is_some_and(|n| n != args.len()) {
+        return Err(t!("calc.one_argument", name));
+    }
+    let x = args[0];
+    let to_int = |f: f64| Number::Float(f).as_int().map(Number::Int).unwrap_or(Number::Float(f));
+    Ok(match name {
+        "abs" => match x {
+            Number::Int(n) if let Some(r) = n.checked_abs() => Number::Int(r),
+            _ => Number::Float(x.as_f64().abs()),
+        },
+        "round" => to_int(x.as_f64().round()),
+        "floor" => to_int(x.as_f64().floor()),
+        "ceil" => to_int(x.as_f64().ceil()),
+        "min" => args.iter().copied().fold(x, |a, b| if b.as_f64() < a.as_f64() { b } else { a }),
+        "max" => args.iter().copied().fold(x, |a, b| if b.as_f64() > a.as_f64() { b } else { a }),
+        _ => {
+            let f = x.as_f64();
+            let result = match name {
+                "sqrt" if f < 0.0 => return Err(t!("calc.negative_sqrt").to_string()),
+                "sqrt" => f.sqrt(),
+                "ln" => f.ln(),
+                "log" => f.log10(),
+                "log2" => f.log2(),
+                "sin" => f.sin(),
+                "cos" => f.cos(),
+                _ => f.tan(),
+            };
+            Number::Float(result)
+        }
+    })
+}
+
+/// Avalia `expr`; nomes de variáveis são resolvidos por `lookup`.
+pub fn eval_with(expr: &str, lookup: &dyn Fn(&str) -> Option<String>) -> Result<Number, String> {
+    let tokens = tokenize(expr)?;
+    if tokens.is_empty() {
+        return Err(t!("calc.empty").to_string());
+    }
+    let mut parser = Parser { tokens, pos: 0, lookup };
+    let result = parser.expr()?;
+    if let Some(token) = parser.peek() {
+        return Err(t!("calc.unexpected", describe(token)));
+    }
+    Ok(result.value)
+}
+
+/// Avalia `expr` com as variáveis de ambiente.
+pub fn eval(expr: &str) -> Result<Number, String> {
+    eval_with(expr, &|name| env::var(name).ok())
+}
+
+// -----------------------------------------------------------------------------
+// BUILTIN
+// -----------------------------------------------------------------------------
+
+/// Expressão de uma linha `= expr`, `=expr` ou `calc expr` (texto cru, antes
+/// das expansões: o `*` não pode virar glob).
+pub fn calc_expression(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    if let Some(rest) = line.strip_prefix('=') {
+        return Some(rest);
+    }
+    line.strip_prefix("calc").filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+}
+
+/// Separa as opções de base (`-x`, `--hex`...) do começo da expressão.
+pub fn parse_calc_args(input: &str) -> (Radix, &str) {
+    let mut radix = Radix::Decimal;
+    let mut rest = input.trim_start();
+    loop {
+        let (word, tail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        radix = match word {
+            "-x" | "--hex" => Radix::Hex,
+            "-b" | "--bin" => Radix::Binary,
+            "-o" | "--oct" => Radix::Octal,
+            _ => return (radix, rest),
+        };
+        rest = tail.trim_start();
+    }
+}
+
+/// Builtin `calc [-x|-b|-o] <expressão>` / `= <expressão>`.
+pub fn handle_calc(input: &str) -> i32 {
+    let (radix, expr) = parse_calc_args(input);
+    if expr.trim().is_empty() {
+        eprintln!("{}", usage("calc"));
+        return 2;
+    }
+    match eval(expr).and_then(|value| format_number(value, radix)) {
+        Ok(text) => {
+            println!("{}", text);
+            0
+        }
+        Err(e) => {
+            eprintln!("calc: {}", e);
+            1
+        }
+    }
+}
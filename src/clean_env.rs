@@ -0,0 +1,76 @@
+//! # Clean Env Module
+//!
+//! Prefixo `clean-env`: executa um comando com um ambiente mínimo (como o
+//! `env -i`), útil para reproduzir problemas do tipo "na minha máquina funciona"
+//! causados por variáveis esquecidas no `.bashrc`.
+//!
+//! - Só as variáveis da lista `keep` (seção `[clean_env]` do `.clios.toml`) e
+//!   as passadas com `-k VAR` chegam ao comando, com os valores atuais.
+//! - As variáveis da seção `[env]` não são injetadas.
+//! - O resto da linha é executado normalmente (pipelines, `&`); o ambiente
+//!   limpo vale para todo processo criado por ela ([`crate::executor`]).
+
+use crate::t;
+
+/// Variáveis mantidas quando a configuração não define `keep`.
+pub const DEFAULT_KEEP: &[&str] = &["PATH", "HOME", "USER", "LOGNAME", "SHELL", "TERM", "LANG"];
+
+/// Opções do `clean-env`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CleanEnvOptions {
+    /// Variáveis extras mantidas (`-k VAR`)
+    pub keep: Vec<String>,
+    /// Resto da linha, como foi digitado (pode ter pipes e `&`)
+    pub command: String,
+}
+
+/// Reconhece `clean-env [-k VAR]... [--] cmd...` no início de `line`.
+///
+/// Retorna `None` se a linha não começa com `clean-env`. O comando é o resto
+/// da linha sem re-tokenizar, então aspas, pipes e `&` chegam intactos.
+pub fn parse_clean_env_line(line: &str) -> Option<Result<CleanEnvOptions, String>> {
+    let mut rest = line.trim_start().strip_prefix("clean-env")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let mut keep = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        let (word, after) = rest.split_at(rest.find(char::is_whitespace).unwrap_or(rest.len()));
+        match word {
+            "-k" | "--keep" => {
+                let after = after.trim_start();
+                let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                if end == 0 {
+                    return Some(Err(t!("clean_env.requires_name").to_string()));
+                }
+                keep.push(after[..end].to_string());
+                rest = &after[end..];
+            }
+            "--" => {
+                rest = after;
+                break;
+            }
+            _ => break,
+        }
+    }
+
+    let command = rest.trim();
+    if command.is_empty() || command == "&" {
+        return Some(Err(t!("args.no_command").to_string()));
+    }
+    Some(Ok(CleanEnvOptions { keep, command: command.to_string() }))
+}
+
+/// Ambiente mínimo: os valores atuais (via `lookup`) das variáveis de `keep`
+/// que estão definidas.
+pub fn minimal_env(keep: &[String], lookup: impl Fn(&str) -> Option<String>) -> Vec<(String, String)> {
+    let mut vars: Vec<_> = keep
+        .iter()
+        .filter_map(|name| lookup(name).map(|value| (name.clone(), value)))
+        .collect();
+    vars.sort();
+    vars.dedup_by(|a, b| a.0 == b.0);
+    vars
+}
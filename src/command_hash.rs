@@ -0,0 +1,239 @@
+//! # Command Hash Module
+//!
+//! Tabela de localização de comandos (como o `hash` do bash).
+//!
+//! A primeira execução de `git` procura o executável nos diretórios do `PATH`
+//! e guarda o caminho; as seguintes usam a tabela, sem varrer diretórios.
+//! O executor, o `type` e o realce de sintaxe consultam a mesma tabela.
+//!
+//! - Nomes com `/` (`./script.sh`, `/bin/ls`) não entram na tabela.
+//! - Um caminho guardado que deixou de existir é procurado de novo.
+//! - Mudar o `PATH` (`export`, `unset`, `path add`, a ativação do
+//!   `[toolchain]`) esvazia a tabela, e instalar ou remover um executável num
+//!   diretório dele (`cargo install`, `npm i -g`) também: a tabela guarda a
+//!   data de modificação de cada diretório ([`PathSnapshot`]). Como o realce
+//!   e o `type` passam por ela, e o autocomplete lê os diretórios a cada
+//!   Tab, nada disso precisa de uma shell nova.
+//! - Os diretórios são consultados (`stat`) a cada comando executado, mas
+//!   no máximo uma vez por [`SNAPSHOT_TTL`] nas consultas do realce, que
+//!   acontecem a cada tecla; a variável `PATH` é comparada sempre.
+//! - Entradas do `PATH` com `~` ou variáveis (`~/bin`, `$HOME/.cargo/bin`)
+//!   são expandidas antes da busca ([`search_path`]).
+//! - `hash -r` esvazia manualmente; `hash` lista a tabela e as estatísticas.
+
+use crate::expansion::{expand_tilde_token, expand_variables_token};
+use crate::registry::usage;
+use crate::t;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+// -----------------------------------------------------------------------------
+// PATH
+// -----------------------------------------------------------------------------
+
+/// Expande `~` e variáveis (`$VAR`, `${VAR}`) em uma entrada do `PATH`.
+pub fn expand_path_entry<'a>(entry: &'a str, home: &str) -> Cow<'a, str> {
+    expand_tilde_token(expand_variables_token(entry, 0, std::process::id()), home)
+}
+
+/// `PATH` atual com cada entrada expandida (usado na busca e no autocomplete).
+pub fn search_path() -> Option<String> {
+    let path_var = env::var("PATH").ok()?;
+    let home = env::var("HOME").unwrap_or_else(|_| "/".to_string());
+    let entries: Vec<_> = path_var.split(':').map(|entry| expand_path_entry(entry, &home)).collect();
+    Some(entries.join(":"))
+}
+
+/// Diretórios do `PATH` já expandidos, na ordem de busca.
+pub fn search_dirs() -> Vec<PathBuf> {
+    search_path()
+        .map(|path| env::split_paths(&path).collect())
+        .unwrap_or_default()
+}
+
+/// O `PATH` expandido e a data de modificação de cada diretório dele (`None`
+/// se o diretório não existe). Criar, remover ou renomear um arquivo muda a
+/// data do diretório, então dois retratos iguais acham os mesmos comandos.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PathSnapshot {
+    pub path_var: Option<String>,
+    pub mtimes: Vec<Option<SystemTime>>,
+}
+
+impl PathSnapshot {
+    /// Retrato de um `PATH` já expandido.
+    pub fn of(path_var: Option<String>) -> Self {
+        let mtimes = path_var
+            .iter()
+            .flat_map(env::split_paths)
+            .map(|dir| fs::metadata(dir).and_then(|m| m.modified()).ok())
+            .collect();
+        PathSnapshot { path_var, mtimes }
+    }
+
+    /// Retrato do `PATH` atual.
+    pub fn current() -> Self {
+        Self::of(search_path())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// TABLE
+// -----------------------------------------------------------------------------
+
+/// Um comando guardado.
+#[derive(Debug, Clone)]
+pub struct HashEntry {
+    /// Caminho completo do executável
+    pub path: PathBuf,
+    /// Quantas vezes foi executado a partir da tabela
+    pub hits: u64,
+}
+
+/// Estado da tabela.
+#[derive(Debug, Default)]
+struct HashTable {
+    entries: HashMap<String, HashEntry>,
+    /// `PATH` (e diretórios dele) usado para preencher a tabela
+    snapshot: PathSnapshot,
+    /// Quando os diretórios do `snapshot` foram consultados
+    checked_at: Option<Instant>,
+    /// Execuções resolvidas pela tabela
+    hits: u64,
+    /// Execuções que precisaram de uma busca completa no `PATH`
+    misses: u64,
+}
+
+static TABLE: Mutex<Option<HashTable>> = Mutex::new(None);
+
+/// Intervalo mínimo entre duas consultas aos diretórios do `PATH` fora de uma
+/// execução (realce, `type`).
+pub const SNAPSHOT_TTL: Duration = Duration::from_secs(1);
+
+/// Acessa a tabela, esvaziando-a se o `PATH` ou um diretório dele mudou.
+///
+/// `fresh` consulta os diretórios agora; sem ele, a consulta só acontece se
+/// a anterior tem mais de [`SNAPSHOT_TTL`] (ou se a variável `PATH` mudou).
+fn with_table<R>(fresh: bool, f: impl FnOnce(&mut HashTable) -> R) -> R {
+    let mut guard = TABLE.lock().unwrap_or_else(|e| e.into_inner());
+    let table = guard.get_or_insert_with(HashTable::default);
+    let path_var = search_path();
+    let stale = table.checked_at.is_none_or(|at| at.elapsed() >= SNAPSHOT_TTL);
+    if fresh || stale || table.snapshot.path_var != path_var {
+        let snapshot = PathSnapshot::of(path_var);
+        if table.snapshot != snapshot {
+            table.entries.clear();
+            table.snapshot = snapshot;
+        }
+        table.checked_at = Some(Instant::now());
+    }
+    f(table)
+}
+
+/// Resolve `name` pela tabela ou pelo `PATH`.
+///
+/// `count` marca uma execução (entra nas estatísticas do `hash`); consultas
+/// do realce e do `type` não contam.
+fn resolve(name: &str, count: bool) -> Option<PathBuf> {
+    if name.is_empty() || name.contains('/') {
+        return None;
+    }
+    with_table(count, |table| {
+        if let Some(entry) = table.entries.get_mut(name)
+            && entry.path.is_file()
+        {
+            if count {
+                entry.hits += 1;
+                table.hits += 1;
+            }
+            return Some(entry.path.clone());
+        }
+
+        if count {
+            table.misses += 1;
+        }
+        let cwd = env::current_dir().unwrap_or_else(|_| PathBuf::from("/"));
+        match which::which_in(name, table.snapshot.path_var.as_ref(), cwd) {
+            Ok(path) => {
+                let hits = u64::from(count);
+                table.entries.insert(name.to_string(), HashEntry { path: path.clone(), hits });
+                Some(path)
+            }
+            Err(_) => {
+                table.entries.remove(name);
+                None
+            }
+        }
+    })
+}
+
+/// Segura a tabela durante um `fork` que continua rodando a shell
+/// (subshell), como o [`crate::jobs::pause_reaper`] faz com a lista de jobs.
+pub fn pause_table() -> impl Sized {
+    TABLE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Caminho de um comando a ser executado (conta nas estatísticas).
+pub fn lookup(name: &str) -> Option<PathBuf> {
+    resolve(name, true)
+}
+
+/// Caminho de um comando, sem contar como execução (realce, `type`).
+pub fn peek(name: &str) -> Option<PathBuf> {
+    resolve(name, false)
+}
+
+/// Caminho guardado de `name`, sem procurar no `PATH`.
+pub fn hashed_path(name: &str) -> Option<PathBuf> {
+    with_table(false, |table| table.entries.get(name).map(|e| e.path.clone()))
+}
+
+/// Esvazia a tabela (`hash -r`).
+pub fn clear() {
+    with_table(false, |table| {
+        table.entries.clear();
+        table.hits = 0;
+        table.misses = 0;
+    });
+}
+
+// -----------------------------------------------------------------------------
+// BUILTIN
+// -----------------------------------------------------------------------------
+
+/// Builtin `hash [-r] [comando...]`.
+pub fn handle_hash(tokens: &[String]) {
+    match tokens.get(1).map(|s| s.as_str()) {
+        None => print_table(),
+        Some("-r") => clear(),
+        Some(flag) if flag.starts_with('-') => eprintln!("{}", usage("hash")),
+        Some(_) => {
+            for name in &tokens[1..] {
+                if peek(name).is_none() {
+                    eprintln!("{}", t!("hash.not_found", name));
+                }
+            }
+        }
+    }
+}
+
+fn print_table() {
+    with_table(true, |table| {
+        if table.entries.is_empty() {
+            println!("{}", t!("hash.empty"));
+        } else {
+            let mut entries: Vec<_> = table.entries.iter().collect();
+            entries.sort_by(|a, b| b.1.hits.cmp(&a.1.hits).then(a.0.cmp(b.0)));
+            println!("{:>6}  {}", "hits", t!("hash.command_header"));
+            for (_, entry) in entries {
+                println!("{:>6}  {}", entry.hits, entry.path.display());
+            }
+        }
+        println!("\x1b[2m{}\x1b[0m", t!("hash.stats", table.entries.len(), table.hits, table.misses));
+    });
+}
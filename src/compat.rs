@@ -0,0 +1,139 @@
+//! # Compat Module
+//!
+//! Exporta aliases e abreviações em sintaxe do bash, para manter os dois
+//! ambientes iguais durante a migração:
+//!
+//! ```bash
+//! alias --export-bash > ~/.bash_aliases   # só os aliases
+//! clios export-compat >> ~/.bashrc        # aliases, abreviações e avisos
+//! ```
+//!
+//! - Alias comum → `alias nome='valor'`
+//! - Alias com placeholders (`{1}`, `{2}`...) → função do bash, com os
+//!   argumentos não usados anexados no final, como na Clios
+//! - Abreviação (`abbr`) → alias
+//! - Comandos e funções Rhai não têm equivalente: ficam listados num
+//!   comentário no fim do arquivo
+
+use crate::t;
+use std::collections::{BTreeMap, HashMap};
+
+/// Nome aceito pelo bash como alias e como função.
+pub fn is_bash_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || "_-.:+@%".contains(c))
+}
+
+/// `texto` entre aspas simples (`'` vira `'\''`).
+pub fn bash_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// Corpo de função do bash para um valor com placeholders; `None` se o valor
+/// não tem nenhum (aí ele vira um alias comum).
+///
+/// Ex: `ssh {1} systemctl restart {2}` → `ssh "${1}" systemctl restart "${2}" "${@:3}"`.
+pub fn template_to_bash(value: &str) -> Option<String> {
+    let mut body = String::with_capacity(value.len() + 16);
+    let mut used: Vec<usize> = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut chars = value.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if quote != Some('\'') => {
+                body.push(c);
+                if let Some((_, next)) = chars.next() {
+                    body.push(next);
+                }
+                continue;
+            }
+            '\'' | '"' if quote.is_none() => quote = Some(c),
+            '\'' | '"' if quote == Some(c) => quote = None,
+            '{' => {
+                let rest = &value[i + 1..];
+                let index = rest
+                    .find('}')
+                    .and_then(|close| rest[..close].parse::<usize>().ok().map(|n| (n, close)));
+                if let Some((n, close)) = index
+                    && n >= 1
+                {
+                    // Aspas do bash conforme o trecho em que o placeholder está
+                    body.push_str(&match quote {
+                        Some('"') => format!("${{{}}}", n),
+                        Some(_) => format!("'\"${{{}}}\"'", n),
+                        None => format!("\"${{{}}}\"", n),
+                    });
+                    used.push(n);
+                    for _ in 0..=close {
+                        chars.next();
+                    }
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        body.push(c);
+    }
+
+    let last = *used.iter().max()?;
+    // Argumentos não usados vão para o fim, na ordem em que vieram
+    for n in (1..last).filter(|n| !used.contains(n)) {
+        body.push_str(&format!(" \"${{{}}}\"", n));
+    }
+    body.push_str(&format!(" \"${{@:{}}}\"", last + 1));
+    Some(body)
+}
+
+/// Uma linha (ou função) do bash para o alias; `None` se o nome não serve no bash.
+pub fn alias_to_bash(name: &str, value: &str) -> Option<String> {
+    if !is_bash_name(name) {
+        return None;
+    }
+    Some(match template_to_bash(value) {
+        Some(body) => format!("{}() {{\n    {}\n}}", name, body),
+        None => format!("alias {}={}", name, bash_quote(value)),
+    })
+}
+
+/// Converte as entradas em ordem alfabética; nomes inválidos viram comentário.
+fn export_entries<'a>(entries: impl Iterator<Item = (&'a String, &'a String)>, out: &mut String) {
+    let sorted: BTreeMap<&String, &String> = entries.collect();
+    for (name, value) in sorted {
+        match alias_to_bash(name, value) {
+            Some(line) => out.push_str(&line),
+            None => out.push_str(&format!("# {}", t!("compat.invalid_name", name))),
+        }
+        out.push('\n');
+    }
+}
+
+/// Saída do `alias --export-bash`.
+pub fn export_aliases_bash(aliases: &HashMap<String, String>) -> String {
+    let mut out = String::new();
+    export_entries(aliases.iter(), &mut out);
+    out
+}
+
+/// Saída do `clios export-compat`. `rhai_only` são os comandos e funções de
+/// plugin, que não têm tradução.
+pub fn export_compat(
+    aliases: &HashMap<String, String>,
+    abbreviations: &BTreeMap<String, String>,
+    rhai_only: &[String],
+) -> String {
+    let mut out = format!("# {}\n", t!("compat.header"));
+    if !aliases.is_empty() {
+        out.push_str(&format!("\n# {}\n", t!("compat.section_aliases")));
+        export_entries(aliases.iter(), &mut out);
+    }
+    if !abbreviations.is_empty() {
+        out.push_str(&format!("\n# {}\n", t!("compat.section_abbr")));
+        export_entries(abbreviations.iter(), &mut out);
+    }
+    if !rhai_only.is_empty() {
+        out.push_str(&format!("\n# {}\n", t!("compat.rhai_only", rhai_only.join(", "))));
+    }
+    out
+}
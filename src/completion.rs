@@ -2,17 +2,34 @@
 //!
 //! Provides autocomplete and syntax highlighting for the shell using rustyline.
 
+use crate::abbr::{abbr_at_cursor, AbbrMap};
+use crate::arg_history::{argument_suggestions, words_before, ARGUMENT_SCAN};
+use crate::arrays::starts_array_assignment;
+use crate::autopair::missing_closers;
+use crate::command_hash;
+use crate::container::complete_container;
+use crate::keys::EditHistory;
+use crate::parser::{analyze_line, incomplete_line, split_command_positions, Incomplete};
+use crate::plugin_registry::{plugin_completions, SharedPluginRegistry};
+use crate::quote::quote_word;
+use crate::recent::{alphabetical, history_references, rank_candidates, FileCandidate};
+use crate::registry::{builtin_names, is_builtin};
+use crate::statements::compound_statement;
+use crate::tasks::complete_task;
+use crate::vimode::ViModeState;
 use rustyline::completion::{Completer, Pair};
 use rustyline::highlight::{CmdKind, Highlighter};
-use rustyline::hint::HistoryHinter;
+use rustyline::hint::{Hinter, HistoryHinter};
+use rustyline::history::SearchDirection;
+use rustyline::validate::{ValidationContext, ValidationResult, Validator};
 use rustyline::Context;
-use rustyline::{Helper, Hinter, Validator};
+use rustyline::Helper;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::sync::{Arc, RwLock};
-use which::which;
+use std::time::SystemTime;
 
 // -----------------------------------------------------------------------------
 // CLIOS HELPER
@@ -27,27 +44,44 @@ use which::which;
 /// * **Completer:** Autocomplete de arquivos e comandos quando aperta TAB.
 /// * **Hinter:** Sugestão cinza baseada no histórico.
 /// * **Highlighter:** Colore o comando enquanto você digita (Verde/Vermelho).
-#[derive(Helper, Hinter, Validator)]
+/// * **Validator:** Segura o Enter enquanto a linha está incompleta.
+#[derive(Helper)]
 pub struct CliosHelper {
     /// O sugestor baseado no histórico (HistoryHinter).
-    #[rustyline(Hinter)]
     pub hinter: HistoryHinter,
 
     /// Armazena a versão colorida do prompt (com códigos ANSI).
-    #[rustyline(Ignore)]
     pub colored_prompt: String,
 
     /// Cor para comandos válidos.
-    #[rustyline(Ignore)]
     pub color_valid: String,
 
     /// Cor para comandos inválidos.
-    #[rustyline(Ignore)]
     pub color_invalid: String,
     
     /// Mapa de aliases para autocomplete (compartilhado com a shell)
-    #[rustyline(Ignore)]
     pub aliases: Arc<RwLock<HashMap<String, String>>>,
+
+    /// Abreviações (`abbr`) para autocomplete (compartilhado com a shell)
+    pub abbreviations: AbbrMap,
+
+    /// Comandos de plugin (`register_command`), com as sugestões de argumento
+    pub plugins: SharedPluginRegistry,
+
+    /// Mostra os fechamentos pendentes de aspas/parênteses como hint.
+    pub auto_pair: bool,
+
+    /// Modo vi atual (`edit_mode = "vi"`), exibido antes do prompt.
+    pub vi_mode: Option<ViModeState>,
+
+    /// Pilha de refazer, esvaziada quando a linha muda por uma edição nova
+    pub edits: EditHistory,
+
+    /// Arquivos recentes antes dos alfabéticos (`recent_files` em `[completion]`).
+    pub recent_files: bool,
+
+    /// Argumentos já usados com o mesmo comando (`argument_history` em `[completion]`).
+    pub argument_history: bool,
 }
 
 impl CliosHelper {
@@ -59,6 +93,13 @@ impl CliosHelper {
             color_valid,
             color_invalid,
             aliases: Arc::new(RwLock::new(HashMap::new())),
+            abbreviations: AbbrMap::default(),
+            plugins: SharedPluginRegistry::default(),
+            auto_pair: false,
+            vi_mode: None,
+            edits: EditHistory::default(),
+            recent_files: true,
+            argument_history: true,
         }
     }
     
@@ -71,10 +112,81 @@ impl CliosHelper {
     }
 }
 
+// -----------------------------------------------------------------------------
+// HINTER IMPLEMENTATION
+// -----------------------------------------------------------------------------
+
+impl Hinter for CliosHelper {
+    type Hint = String;
+
+    fn hint(&self, line: &str, pos: usize, ctx: &Context<'_>) -> Option<String> {
+        // Fechamentos pendentes têm prioridade sobre a sugestão do histórico
+        if self.auto_pair && pos == line.len() {
+            let closers = missing_closers(line);
+            if !closers.is_empty() {
+                return Some(closers);
+            }
+        }
+        self.hinter.hint(line, pos, ctx)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// VALIDATOR IMPLEMENTATION
+// -----------------------------------------------------------------------------
+
+impl Validator for CliosHelper {
+    /// Linha terminando em `|`, `&&`, `||`, com aspas abertas ou com um `case`
+    /// sem `esac` ou `select` sem `done` não é enviada: o Enter quebra a linha e a edição continua,
+    /// como o `PS2` do bash.
+    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        Ok(match incomplete_line(ctx.input()) {
+            // Com auto_pair o Enter já fecha as aspas pendentes
+            Some(Incomplete::UnclosedQuote(_)) if self.auto_pair => ValidationResult::Valid(None),
+            Some(_) => ValidationResult::Incomplete,
+            None => ValidationResult::Valid(None),
+        })
+    }
+}
+
 // -----------------------------------------------------------------------------
 // HIGHLIGHTER IMPLEMENTATION
 // -----------------------------------------------------------------------------
 
+/// Cor dos operadores (`|`, `&&`, `||`, `&`)
+const OPERATOR_COLOR: &str = "\x1b[1;36m";
+
+/// Operador no fim da linha, ainda sem comando depois
+const DANGLING_COLOR: &str = "\x1b[1;37;41m";
+
+/// Aspa sem fechamento e o texto após ela
+const UNCLOSED_QUOTE_COLOR: &str = "\x1b[4;33m";
+
+/// Colore operadores, operador pendente e aspa aberta sobre a cor base da linha.
+fn highlight_syntax(line: &str, base: &str) -> String {
+    let syntax = analyze_line(line);
+    let dangling = syntax.dangling_operator(line).map(|span| span.start);
+
+    let mut out = String::from(base);
+    let mut last = 0;
+    for span in &syntax.operators {
+        let color = if Some(span.start) == dangling { DANGLING_COLOR } else { OPERATOR_COLOR };
+        out.push_str(&line[last..span.start]);
+        out.push_str(&format!("\x1b[0m{}{}\x1b[0m{}", color, span.op, base));
+        last = span.end;
+    }
+
+    match syntax.open_quote {
+        Some(quote) => {
+            out.push_str(&line[last..quote]);
+            out.push_str(&format!("\x1b[0m{}{}", UNCLOSED_QUOTE_COLOR, &line[quote..]));
+        }
+        None => out.push_str(&line[last..]),
+    }
+    out.push_str("\x1b[0m");
+    out
+}
+
 impl Highlighter for CliosHelper {
     fn highlight_prompt<'b, 's: 'b, 'p: 'b>(
         &'s self,
@@ -82,29 +194,32 @@ impl Highlighter for CliosHelper {
         default: bool,
     ) -> Cow<'b, str> {
         if default {
-            Cow::Borrowed(&self.colored_prompt)
+            match &self.vi_mode {
+                Some(state) => Cow::Owned(format!("{}{}", state.get().indicator(), self.colored_prompt)),
+                None => Cow::Borrowed(&self.colored_prompt),
+            }
         } else {
             Cow::Borrowed(prompt)
         }
     }
 
     fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+        self.edits.observe(line);
         let input = line.trim();
         if input.is_empty() {
             return Cow::Borrowed(line);
         }
         let first_word = input.split_whitespace().next().unwrap_or("");
 
-        let is_valid = matches!(
-            first_word,
-            "cd" | "exit" | "pwd" | "alias" | "rhai" | "fg" | "export" | "history" | "source" | "load" | "plugins"
-        ) || which(first_word).is_ok();
+        let is_valid = is_builtin(first_word)
+            || self.plugins.borrow().get(first_word).is_some()
+            || self.plugins.borrow().is_pending_command(first_word)
+            || command_hash::peek(first_word).is_some()
+            || starts_array_assignment(first_word)
+            || compound_statement(input).is_some();
 
-        if is_valid {
-            Cow::Owned(format!("{}{}\x1b[0m", self.color_valid, line))
-        } else {
-            Cow::Owned(format!("{}{}\x1b[0m", self.color_invalid, line))
-        }
+        let base = if is_valid { &self.color_valid } else { &self.color_invalid };
+        Cow::Owned(highlight_syntax(line, base))
     }
 
     fn highlight_char(&self, _line: &str, _pos: usize, _forced: CmdKind) -> bool {
@@ -116,11 +231,6 @@ impl Highlighter for CliosHelper {
 // COMPLETER IMPLEMENTATION
 // -----------------------------------------------------------------------------
 
-/// Lista de builtins para autocomplete
-const BUILTINS: &[&str] = &[
-    "cd", "pwd", "alias", "unalias", "export", "unset", "history",
-    "source", "load", "plugins", "rhai", "fg", "jobs", "type", "help", "version", "exit",
-];
 
 impl Completer for CliosHelper {
     type Candidate = Pair;
@@ -129,20 +239,32 @@ impl Completer for CliosHelper {
         &self,
         line: &str,
         pos: usize,
-        _ctx: &Context,
+        ctx: &Context,
     ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        // Abreviação completa antes do cursor: a expansão é o único candidato
+        if let Ok(abbrs) = self.abbreviations.read()
+            && let Some((start, expansion)) = abbr_at_cursor(&line[..pos], &abbrs)
+        {
+            return Ok((start, vec![Pair {
+                display: expansion.clone(),
+                replacement: format!("{} ", expansion),
+            }]));
+        }
+
         let (start, word_to_complete) = extract_word(line, pos);
         let mut matches = Vec::new();
         
-        // Verifica se é a primeira palavra (comando)
-        let is_first_word = !line[..start].chars().any(|c| !c.is_whitespace());
+        // Verifica se é posição de comando (início da linha ou após |, && e ||)
+        let is_first_word = split_command_positions(&line[..start])
+            .last()
+            .is_none_or(|segment| segment.text.trim().is_empty());
 
         if is_first_word {
             // Autocomplete de comandos: builtins, aliases, e PATH
             let prefix_lower = word_to_complete.to_lowercase();
             
             // 1. Builtins
-            for builtin in BUILTINS {
+            for builtin in builtin_names() {
                 if builtin.to_lowercase().starts_with(&prefix_lower) {
                     matches.push(Pair {
                         display: builtin.to_string(),
@@ -151,7 +273,26 @@ impl Completer for CliosHelper {
                 }
             }
             
-            // 2. Aliases
+            // 2. Comandos de plugin
+            for command in self.plugins.borrow().commands() {
+                if command.name.to_lowercase().starts_with(&prefix_lower) {
+                    matches.push(Pair {
+                        display: format!("{} (plugin)", command.name),
+                        replacement: command.name.clone(),
+                    });
+                }
+            }
+            // ... e os dos plugins ainda não compilados
+            for name in self.plugins.borrow().pending_commands() {
+                if name.to_lowercase().starts_with(&prefix_lower) {
+                    matches.push(Pair {
+                        display: format!("{} (plugin)", name),
+                        replacement: name.to_string(),
+                    });
+                }
+            }
+
+            // 3. Aliases
             if let Ok(aliases) = self.aliases.read() {
                 for alias_name in aliases.keys() {
                     if alias_name.to_lowercase().starts_with(&prefix_lower) {
@@ -163,27 +304,82 @@ impl Completer for CliosHelper {
                 }
             }
             
-            // 3. Comandos do PATH
-            if let Ok(path_var) = env::var("PATH") {
-                for path_dir in path_var.split(':') {
-                    if let Ok(entries) = fs::read_dir(path_dir) {
-                        for entry in entries.flatten() {
-                            if let Ok(name) = entry.file_name().into_string() {
-                                if name.to_lowercase().starts_with(&prefix_lower) {
-                                    // Evita duplicatas
-                                    if !matches.iter().any(|p| p.replacement == name) {
-                                        matches.push(Pair {
-                                            display: name.clone(),
-                                            replacement: name,
-                                        });
-                                    }
-                                }
-                            }
+            // 4. Abreviações
+            if let Ok(abbrs) = self.abbreviations.read() {
+                for abbr_name in abbrs.keys() {
+                    if abbr_name.to_lowercase().starts_with(&prefix_lower) {
+                        matches.push(Pair {
+                            display: format!("{} (abbr)", abbr_name),
+                            replacement: abbr_name.clone(),
+                        });
+                    }
+                }
+            }
+
+            // 5. Comandos do PATH (entradas com `~` e `$VAR` já expandidas)
+            for path_dir in command_hash::search_dirs() {
+                if let Ok(entries) = fs::read_dir(path_dir) {
+                    for entry in entries.flatten() {
+                        if let Ok(name) = entry.file_name().into_string()
+                            && name.to_lowercase().starts_with(&prefix_lower)
+                            // Evita duplicatas
+                            && !matches.iter().any(|p| p.replacement == name)
+                        {
+                            matches.push(Pair {
+                                display: name.clone(),
+                                replacement: name,
+                            });
                         }
                     }
                 }
             }
+        } else if let Some(candidates) = self.completion_provider(&line[..start], word_to_complete) {
+            // Argumento de um comando com provedor (`register_completion`)
+            for candidate in candidates {
+                matches.push(Pair {
+                    replacement: quote_word(&candidate).into_owned(),
+                    display: candidate,
+                });
+            }
+        } else if let Some(hints) = self.plugin_hints(&line[..start], word_to_complete) {
+            // Argumento de comando de plugin: as `completions` do `register_command`
+            for hint in hints {
+                matches.push(Pair {
+                    display: hint.clone(),
+                    replacement: hint,
+                });
+            }
+        } else if is_first_argument_of(&line[..start], "run") {
+            // `run <TAB>`: tarefas do clios.toml do projeto
+            for name in complete_task(word_to_complete) {
+                matches.push(Pair {
+                    display: name.clone(),
+                    replacement: name,
+                });
+            }
+        } else if is_first_argument_of(&line[..start], "inside") {
+            // `inside <TAB>`: containers em execução
+            for name in complete_container(word_to_complete) {
+                matches.push(Pair {
+                    display: name.clone(),
+                    replacement: name,
+                });
+            }
         } else {
+            let history = if self.recent_files || self.argument_history { history_lines(ctx) } else { Vec::new() };
+
+            // Argumentos usados antes com o mesmo comando (`ssh <TAB>`: hosts)
+            if self.argument_history {
+                let home = env::var("HOME").unwrap_or_default();
+                let before = words_before(&line[..start]);
+                for arg in argument_suggestions(history.iter().map(|l| l.as_str()), &before, word_to_complete, &home) {
+                    matches.push(Pair {
+                        replacement: quote_word(&arg).into_owned(),
+                        display: arg,
+                    });
+                }
+            }
+
             // Autocomplete de arquivos (comportamento original)
             let (dir, file_prefix) = if let Some(idx) = word_to_complete.rfind('/') {
                 (&word_to_complete[..idx + 1], &word_to_complete[idx + 1..])
@@ -197,19 +393,33 @@ impl Completer for CliosHelper {
                 dir.to_string()
             };
 
+            let mut candidates = Vec::new();
             if let Ok(entries) = fs::read_dir(&dir_path) {
                 for entry in entries.flatten() {
-                    if let Ok(name) = entry.file_name().into_string() {
-                        if name.to_lowercase().starts_with(&file_prefix.to_lowercase()) {
-                            let replacement = format!("{}{}", dir, name);
-                            matches.push(Pair {
-                                display: name,
-                                replacement,
-                            });
-                        }
+                    if let Ok(name) = entry.file_name().into_string()
+                        && name.to_lowercase().starts_with(&file_prefix.to_lowercase())
+                    {
+                        let modified = entry.metadata().and_then(|m| m.modified()).ok();
+                        candidates.push(FileCandidate { path: format!("{}{}", dir, name), modified });
                     }
                 }
             }
+
+            let ordered = if self.recent_files {
+                let references = history_references(history.iter().map(|l| l.as_str()));
+                rank_candidates(candidates, &references, SystemTime::now())
+            } else {
+                alphabetical(candidates)
+            };
+            for replacement in ordered {
+                if matches.iter().any(|p| p.replacement == replacement) {
+                    continue;
+                }
+                matches.push(Pair {
+                    display: replacement[dir.len()..].to_string(),
+                    replacement,
+                });
+            }
         }
 
         Ok((start, matches))
@@ -220,6 +430,45 @@ impl Completer for CliosHelper {
 // HELPER FUNCTIONS
 // -----------------------------------------------------------------------------
 
+impl CliosHelper {
+    /// Os candidatos do provedor de autocomplete do comando sendo digitado;
+    /// `None` (e o resto do autocomplete) sem provedor ou sem candidatos.
+    fn completion_provider(&self, before_word: &str, word: &str) -> Option<Vec<String>> {
+        let segment = split_command_positions(before_word).pop()?;
+        let mut words: Vec<String> = segment.text.split_whitespace().map(String::from).collect();
+        words.push(word.to_string());
+        plugin_completions(&self.plugins, &words).filter(|candidates| !candidates.is_empty())
+    }
+
+    /// Sugestões do comando de plugin cujo argumento está sendo digitado que
+    /// começam com `word`; `None` (e o autocomplete de arquivos) se nenhuma serve.
+    fn plugin_hints(&self, before_word: &str, word: &str) -> Option<Vec<String>> {
+        let segment = split_command_positions(before_word).pop()?;
+        let name = segment.text.split_whitespace().next()?;
+        let plugins = self.plugins.borrow();
+        let hints: Vec<String> = plugins.get(name)?.completions.iter().filter(|c| c.starts_with(word)).cloned().collect();
+        (!hints.is_empty()).then_some(hints)
+    }
+}
+
+/// As últimas linhas do histórico do editor, da mais nova para trás.
+fn history_lines(ctx: &Context) -> Vec<String> {
+    let history = ctx.history();
+    (0..history.len())
+        .rev()
+        .take(ARGUMENT_SCAN)
+        .filter_map(|i| history.get(i, SearchDirection::Forward).ok().flatten())
+        .map(|result| result.entry.into_owned())
+        .collect()
+}
+
+/// A palavra sendo completada é o primeiro argumento de `command`?
+fn is_first_argument_of(before_word: &str, command: &str) -> bool {
+    split_command_positions(before_word)
+        .last()
+        .is_some_and(|segment| segment.text.split_whitespace().eq([command]))
+}
+
 /// Função auxiliar para pegar a palavra que está sendo digitada (separa por espaços)
 fn extract_word(line: &str, pos: usize) -> (usize, &str) {
     let line_before_cursor = &line[..pos];
@@ -3,9 +3,16 @@
 //! Handles loading and parsing of the `~/.clios.toml` configuration file.
 //! Also defines all configuration structures used throughout the shell.
 
+use crate::container::ContainerSettings;
+use crate::demo::DemoSettings;
+use crate::limits::Limits;
+use crate::t;
+use crate::toolchain::ToolKind;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::env;
-use std::path::Path;
+use std::fmt;
+use std::path::{Path, PathBuf};
 
 // -----------------------------------------------------------------------------
 // PROMPT CONFIGURATION
@@ -15,7 +22,7 @@ use std::path::Path;
 ///
 /// Esta estrutura mapeia a seção `[prompt]` do arquivo de configuração `.clios.toml`.
 /// Permite que o usuário personalize cores, símbolos e informações exibidas.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct ConfigPrompt {
     /// O símbolo exibido no final do prompt (ex: `>`, `$`, `➜`, ``).
     /// * Padrão: `>`
@@ -35,6 +42,36 @@ pub struct ConfigPrompt {
     /// Define se deve mostrar a branch atual do Git.
     /// * Padrão: `true`
     pub show_git: Option<bool>,
+
+    /// Mostra a carga (`load 9.3`) quando a média de 1 minuto passa do
+    /// número de núcleos.
+    /// * Padrão: `true`
+    pub show_load: Option<bool>,
+
+    /// Avisa (`disk 96%`) quando o disco do diretório atual tem menos de 10%
+    /// livre.
+    /// * Padrão: `true`
+    pub show_disk: Option<bool>,
+
+    /// Mostra a versão pedida pelo projeto (`.nvmrc`, `rust-toolchain.toml`,
+    /// `.python-version`).
+    /// * Padrão: `true`
+    pub show_toolchain: Option<bool>,
+
+    /// Diretórios finais mantidos quando o caminho precisa ser encurtado
+    /// para caber no terminal (`~/…/api/src/bin`).
+    /// * Padrão: `3`
+    pub path_components: Option<usize>,
+
+    /// Forma do caminho: "full" (`~/projetos/api/src`), "short" (só `src`)
+    /// ou "fish" (`~/p/a/src`).
+    /// * Padrão: "full"
+    pub path_style: Option<String>,
+
+    /// Reaproveita o prompt anterior quando o diretório, o `HEAD` do git, a
+    /// configuração, o código de saída, os jobs e o minuto são os mesmos.
+    /// * Padrão: `true`
+    pub cache: Option<bool>,
 }
 
 // -----------------------------------------------------------------------------
@@ -44,7 +81,7 @@ pub struct ConfigPrompt {
 /// Configurações do histórico de comandos.
 ///
 /// Esta estrutura mapeia a seção `[history]` do arquivo `.clios.toml`.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct ConfigHistory {
     /// Nome do arquivo onde o histórico será salvo na pasta HOME.
     /// * Padrão: `.clios_history`
@@ -53,6 +90,15 @@ pub struct ConfigHistory {
     /// Número máximo de comandos a serem lembrados.
     /// * Padrão: `1000`
     pub max_entries: Option<usize>,
+
+    /// Esconde tokens e senhas (`TOKEN=...`, `--password ...`, chaves AWS)
+    /// antes de gravar o histórico.
+    /// * Padrão: `true`
+    pub redact: Option<bool>,
+
+    /// Expressões regulares extras de credenciais; com um grupo, só o grupo
+    /// é escondido.
+    pub redact_patterns: Option<Vec<String>>,
 }
 
 // -----------------------------------------------------------------------------
@@ -74,6 +120,263 @@ pub struct ConfigSyntax {
     pub invalid_cmd: Option<String>,
 }
 
+// -----------------------------------------------------------------------------
+// BEHAVIOR CONFIGURATION
+// -----------------------------------------------------------------------------
+
+/// Comportamentos gerais da sessão interativa.
+///
+/// Mapeia a seção `[behavior]` do arquivo `.clios.toml`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ConfigBehavior {
+    /// Pede confirmação (um segundo Ctrl+D) antes de sair com jobs pendentes.
+    /// * Padrão: `true`
+    pub confirm_exit: Option<bool>,
+
+    /// Fecha aspas e parênteses/colchetes/chaves automaticamente ao editar.
+    /// * Padrão: `false`
+    pub auto_pair: Option<bool>,
+
+    /// Reescreve `rm` para `trash` (só `rm -f --real` apaga de verdade).
+    /// * Padrão: `false`
+    pub safe_rm: Option<bool>,
+
+    /// Redireciona a saída dos jobs em background (`&`) para um arquivo por job,
+    /// exibido antes do próximo prompt ou com `jobs --output %N`.
+    /// * Padrão: `false`
+    pub capture_job_output: Option<bool>,
+
+    /// Modo de edição da linha: "emacs" ou "vi" (com indicador `[I]`/`[N]` no prompt).
+    /// * Padrão: "emacs"
+    pub edit_mode: Option<String>,
+
+    /// Explica códigos de saída comuns (127, 126, 130, 137...) quando um comando falha.
+    /// * Padrão: `false`
+    pub explain_errors: Option<bool>,
+
+    /// Guarda o STDOUT dos últimos comandos (`$__`, `out`, Alt+O).
+    /// * Padrão: `false`
+    pub capture_output: Option<bool>,
+
+    /// Lista o diretório (builtin `list`) depois de cada `cd`.
+    /// * Padrão: `false`
+    pub auto_ls: Option<bool>,
+
+    /// Acima de tantas entradas, o `auto_ls` mostra só a contagem.
+    /// * Padrão: 50
+    pub auto_ls_max: Option<usize>,
+
+    /// O Ctrl+Z para o comando em foreground e o continua em background na
+    /// hora, em vez de deixá-lo parado até um `fg`.
+    /// * Padrão: `false`
+    pub stop_then_bg: Option<bool>,
+}
+
+// -----------------------------------------------------------------------------
+// COMPLETION CONFIGURATION
+// -----------------------------------------------------------------------------
+
+/// Autocomplete (TAB).
+///
+/// Mapeia a seção `[completion]` do arquivo `.clios.toml`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ConfigCompletion {
+    /// Nos argumentos, mostra primeiro os arquivos citados nos últimos
+    /// comandos e os modificados nas últimas 24 horas.
+    /// * Padrão: `true`
+    pub recent_files: Option<bool>,
+
+    /// Nos argumentos, sugere os valores já usados com o mesmo comando
+    /// (`ssh <TAB>`: hosts; `git checkout <TAB>`: branches).
+    /// * Padrão: `true`
+    pub argument_history: Option<bool>,
+}
+
+// -----------------------------------------------------------------------------
+// GENERAL CONFIGURATION
+// -----------------------------------------------------------------------------
+
+/// Configurações gerais.
+///
+/// Mapeia a seção `[general]` do arquivo `.clios.toml`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ConfigGeneral {
+    /// Idioma das mensagens ("pt" ou "en").
+    /// * Padrão: o de `LC_ALL` / `LC_MESSAGES` / `LANG`, ou português
+    pub language: Option<String>,
+}
+
+// -----------------------------------------------------------------------------
+// ACCESSIBILITY CONFIGURATION
+// -----------------------------------------------------------------------------
+
+/// Configurações de acessibilidade.
+///
+/// Mapeia a seção `[accessibility]` do arquivo `.clios.toml`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ConfigAccessibility {
+    /// Saída para leitores de tela: prompt e ajuda em texto simples, sem
+    /// glifos do Powerline, molduras nem linhas de progresso.
+    /// * Padrão: `false`
+    pub screen_reader: Option<bool>,
+}
+
+// -----------------------------------------------------------------------------
+// CLEAN ENV CONFIGURATION
+// -----------------------------------------------------------------------------
+
+/// Ambiente mínimo do prefixo `clean-env`.
+///
+/// Mapeia a seção `[clean_env]` do arquivo `.clios.toml`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ConfigCleanEnv {
+    /// Variáveis mantidas (com o valor atual) no comando executado.
+    /// * Padrão: PATH, HOME, USER, LOGNAME, SHELL, TERM, LANG
+    pub keep: Option<Vec<String>>,
+}
+
+// -----------------------------------------------------------------------------
+// LIMITS CONFIGURATION
+// -----------------------------------------------------------------------------
+
+/// Proteção contra fork bombs e jobs descontrolados (`0` desliga o limite).
+///
+/// Mapeia a seção `[limits]` do arquivo `.clios.toml`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ConfigLimits {
+    /// Jobs em background ativos ao mesmo tempo.
+    /// * Padrão: `64`
+    pub max_jobs: Option<usize>,
+
+    /// Processos criados por segundo (pipelines, jobs, subshells...).
+    /// * Padrão: `500`
+    pub max_spawns_per_second: Option<usize>,
+}
+
+impl ConfigLimits {
+    /// Limites em vigor, com os padrões para os campos ausentes.
+    pub fn to_limits(&self) -> Limits {
+        let defaults = Limits::default();
+        Limits {
+            max_jobs: self.max_jobs.unwrap_or(defaults.max_jobs),
+            max_spawns_per_second: self.max_spawns_per_second.unwrap_or(defaults.max_spawns_per_second),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// CONTAINER CONFIGURATION
+// -----------------------------------------------------------------------------
+
+/// Containers do builtin `inside`.
+///
+/// Mapeia a seção `[container]` do arquivo `.clios.toml`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ConfigContainer {
+    /// `docker` ou `podman`.
+    /// * Padrão: `docker` (ou `podman`, se só ele estiver no PATH)
+    pub runtime: Option<String>,
+
+    /// Container usado pelo `inside` quando o nome é omitido (aparece no prompt).
+    /// * Padrão: nenhum
+    pub default: Option<String>,
+}
+
+impl ConfigContainer {
+    pub fn to_settings(&self) -> ContainerSettings {
+        ContainerSettings {
+            runtime: self.runtime.clone(),
+            default: self.default.clone(),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// SUGGEST CONFIGURATION
+// -----------------------------------------------------------------------------
+
+/// Sugestor de comandos do `?? pergunta`.
+///
+/// Mapeia a seção `[suggest]` do arquivo `.clios.toml`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ConfigSuggest {
+    /// Programa externo: recebe a pergunta no STDIN e imprime o comando.
+    pub command: Option<String>,
+
+    /// Função Rhai dos plugins (`fn f(pergunta)`); tem prioridade sobre `command`.
+    pub function: Option<String>,
+}
+
+// -----------------------------------------------------------------------------
+// KEYS CONFIGURATION
+// -----------------------------------------------------------------------------
+
+/// Teclas de desfazer/refazer do editor de linha.
+///
+/// Mapeia a seção `[keys]` do arquivo `.clios.toml`. As teclas são escritas
+/// como `ctrl-z` ou `alt-z`; uma lista vazia desliga a função.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ConfigKeys {
+    /// Desfazer a última edição.
+    /// * Padrão: `["ctrl-_", "ctrl-z"]`
+    pub undo: Option<Vec<String>>,
+
+    /// Refazer o que foi desfeito.
+    /// * Padrão: `["alt-z"]`
+    pub redo: Option<Vec<String>>,
+}
+
+/// Comandos de ativação por ferramenta, rodados ao entrar num projeto.
+///
+/// Mapeia a seção `[toolchain]` do arquivo `.clios.toml`. `{version}` vira a
+/// versão do arquivo do projeto e `{root}` o diretório dele.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ConfigToolchain {
+    /// Ao entrar num diretório com `.nvmrc` / `.node-version`.
+    pub node: Option<String>,
+
+    /// Ao entrar num diretório com `rust-toolchain.toml` / `rust-toolchain`.
+    pub rust: Option<String>,
+
+    /// Ao entrar num diretório com `.python-version`.
+    pub python: Option<String>,
+}
+
+impl ConfigToolchain {
+    /// Comando configurado para a ferramenta.
+    pub fn activation(&self, kind: ToolKind) -> Option<&str> {
+        match kind {
+            ToolKind::Node => self.node.as_deref(),
+            ToolKind::Rust => self.rust.as_deref(),
+            ToolKind::Python => self.python.as_deref(),
+        }
+        .filter(|command| !command.trim().is_empty())
+    }
+}
+
+/// Configurações do modo de apresentação (`demo on` / `--demo`).
+///
+/// Mapeia a seção `[demo]` do arquivo `.clios.toml`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ConfigDemo {
+    /// Recusa comandos de rede mesmo sem `demo on --offline`.
+    /// * Padrão: `false`
+    pub block_network: Option<bool>,
+
+    /// Se definida, só estes comandos rodam no modo de apresentação.
+    pub allow: Option<Vec<String>>,
+}
+
+impl ConfigDemo {
+    /// Estado do modo; `offline` vem do `demo on --offline`.
+    pub fn to_settings(&self, offline: bool) -> DemoSettings {
+        DemoSettings {
+            block_network: offline || self.block_network.unwrap_or(false),
+            allow: self.allow.clone(),
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------
 // VERSION READING STRUCTURES
 // -----------------------------------------------------------------------------
@@ -148,32 +451,129 @@ pub struct CliosConfig {
 
     /// Tema do prompt (powerline ou classic).
     pub theme: Option<String>,
+
+    /// Configurações da seção `[behavior]`.
+    pub behavior: Option<ConfigBehavior>,
+
+    /// Configurações da seção `[completion]`.
+    pub completion: Option<ConfigCompletion>,
+
+    /// Configurações da seção `[general]`.
+    pub general: Option<ConfigGeneral>,
+
+    /// Variáveis da seção `[env]`, passadas a todo comando executado (mas não
+    /// à própria shell).
+    pub env: Option<HashMap<String, String>>,
+
+    /// Configurações da seção `[clean_env]`.
+    pub clean_env: Option<ConfigCleanEnv>,
+
+    /// Configurações da seção `[limits]`.
+    pub limits: Option<ConfigLimits>,
+
+    /// Configurações da seção `[container]`.
+    pub container: Option<ConfigContainer>,
+
+    /// Configurações da seção `[suggest]`.
+    pub suggest: Option<ConfigSuggest>,
+
+    /// Configurações da seção `[keys]`.
+    pub keys: Option<ConfigKeys>,
+
+    /// Configurações da seção `[toolchain]`.
+    pub toolchain: Option<ConfigToolchain>,
+
+    /// Configurações da seção `[demo]`.
+    pub demo: Option<ConfigDemo>,
+
+    /// Configurações da seção `[accessibility]`.
+    pub accessibility: Option<ConfigAccessibility>,
+
+    /// Aliases da seção `[aliases]` (somados aos do `~/.cliosrc`).
+    pub aliases: Option<HashMap<String, String>>,
+
+    /// Plugins Rhai carregados na inicialização, além dos de `~/.clios_plugins`.
+    pub plugins: Option<Vec<String>>,
+
+    /// Compila os plugins de `~/.clios_plugins` só no primeiro uso de um dos
+    /// seus comandos ou funções, em vez de todos na inicialização.
+    /// * Padrão: `true`
+    pub lazy_plugins: Option<bool>,
+
+    /// Nomes dos perfis declarados em `[profile.<nome>]`.
+    #[serde(skip)]
+    pub profiles: Vec<String>,
+
+    /// Perfil aplicado sobre a configuração base (`--profile` / `profile use`).
+    #[serde(skip)]
+    pub active_profile: Option<String>,
+
+    /// Arquivo de onde a configuração foi lida.
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
 }
 
-impl CliosConfig {
+impl Default for CliosConfig {
     /// Retorna a configuração padrão (Default) caso o arquivo não exista.
     ///
     /// # Valores Padrão
     /// * **Prompt:** Símbolo `> `, Cor `blue`, Git `true`.
     /// * **History:** Arquivo `.clios_history`, 1000 entradas.
-    pub fn default() -> Self {
+    fn default() -> Self {
         Self {
             prompt: Some(ConfigPrompt {
                 symbol: Some("> ".to_string()),
                 color: Some("blue".to_string()),
                 show_git: Some(true),
+                show_load: Some(true),
+                show_disk: Some(true),
+                show_toolchain: Some(true),
+                path_components: None,
+                path_style: None,
                 path_color: None,
                 symbol_color: None,
+                cache: Some(true),
             }),
             history: Some(ConfigHistory {
                 file: Some(".clios_history".to_string()),
                 max_entries: Some(1000),
+                redact: Some(true),
+                redact_patterns: None,
             }),
             syntax: Some(ConfigSyntax {
                 valid_cmd: Some("green".to_string()),
                 invalid_cmd: Some("red".to_string()),
             }),
             theme: Some("powerline".to_string()),
+            behavior: Some(ConfigBehavior {
+                confirm_exit: Some(true),
+                auto_pair: Some(false),
+                safe_rm: Some(false),
+                capture_job_output: Some(false),
+                edit_mode: Some("emacs".to_string()),
+                explain_errors: Some(false),
+                capture_output: Some(false),
+                auto_ls: Some(false),
+                auto_ls_max: None,
+                stop_then_bg: Some(false),
+            }),
+            completion: None,
+            general: Some(ConfigGeneral { language: None }),
+            env: None,
+            clean_env: None,
+            limits: None,
+            container: None,
+            suggest: None,
+            keys: None,
+            toolchain: None,
+            demo: None,
+            accessibility: None,
+            aliases: None,
+            plugins: None,
+            lazy_plugins: None,
+            profiles: Vec::new(),
+            active_profile: None,
+            path: None,
         }
     }
 }
@@ -182,37 +582,233 @@ impl CliosConfig {
 // LOADING FUNCTIONS
 // -----------------------------------------------------------------------------
 
+/// Variável com o perfil ativo (lida na inicialização, como `--profile`).
+pub const PROFILE_VAR: &str = "CLIOS_PROFILE";
+
+/// Caminho padrão do arquivo TOML (`$HOME/.clios.toml`).
+pub fn default_config_path() -> PathBuf {
+    env::var("HOME")
+        .map(|p| Path::new(&p).join(".clios.toml"))
+        .unwrap_or_else(|_| Path::new(".clios.toml").to_path_buf())
+}
+
+/// Carrega a configuração do usuário a partir de `~/.clios.toml`.
+///
+/// Atalho para [`load_toml_config_from`] com o caminho padrão e sem perfil.
+pub fn load_toml_config() -> CliosConfig {
+    load_toml_config_from(&default_config_path(), None)
+}
+
+/// Erro ao montar a configuração a partir do TOML.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    /// TOML inválido ou com campos do tipo errado.
+    Syntax(String),
+    /// Perfil pedido que não está no arquivo.
+    UnknownProfile(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Syntax(e) | ConfigError::UnknownProfile(e) => f.write_str(e),
+        }
+    }
+}
+
+/// Mescla `overlay` em `base`: tabelas se combinam chave a chave, o resto é
+/// substituído.
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(inner)), toml::Value::Table(value)) => merge_tables(inner, value),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Monta a configuração a partir do conteúdo TOML, aplicando o perfil pedido.
+///
+/// Cada `[profile.<nome>]` tem as mesmas seções do arquivo (`[profile.work.prompt]`,
+/// `[profile.work.env]`...) e é mesclado sobre a base: só o que o perfil declara
+/// muda.
+pub fn parse_config(contents: &str, profile: Option<&str>) -> Result<CliosConfig, ConfigError> {
+    let mut table: toml::Table = contents.parse().map_err(|e: toml::de::Error| ConfigError::Syntax(e.to_string()))?;
+    let profiles = match table.remove("profile") {
+        Some(toml::Value::Table(profiles)) => profiles,
+        Some(_) => return Err(ConfigError::Syntax(t!("profile.not_a_table").to_string())),
+        None => toml::Table::new(),
+    };
+    let mut names: Vec<String> = profiles.keys().cloned().collect();
+    names.sort();
+
+    if let Some(name) = profile {
+        match profiles.get(name) {
+            Some(toml::Value::Table(overlay)) => merge_tables(&mut table, overlay.clone()),
+            _ => {
+                let available = if names.is_empty() { "-".to_string() } else { names.join(", ") };
+                return Err(ConfigError::UnknownProfile(t!("profile.not_found", name, available)));
+            }
+        }
+    }
+
+    let mut config: CliosConfig =
+        toml::Value::Table(table).try_into().map_err(|e: toml::de::Error| ConfigError::Syntax(e.to_string()))?;
+    config.profiles = names;
+    config.active_profile = profile.map(String::from);
+    Ok(config)
+}
+
+/// A listagem do `profile list`, com o perfil ativo marcado.
+pub fn format_profile_list(path: &Path, profiles: &[String], active: Option<&str>) -> String {
+    let mut out = format!("{}\n", t!("profile.list_header", path.display()));
+    let marker = |on: bool| if on { "\x1b[1;32m*\x1b[0m" } else { " " };
+    out.push_str(&format!("{} \x1b[2m{}\x1b[0m\n", marker(active.is_none()), t!("profile.base")));
+    if profiles.is_empty() {
+        out.push_str(&format!("  {}\n", t!("profile.none")));
+    }
+    for name in profiles {
+        out.push_str(&format!("{} \x1b[1;33m{}\x1b[0m\n", marker(active == Some(name.as_str())), name));
+    }
+    out
+}
+
 /// Carrega a configuração do usuário a partir de um arquivo TOML.
 ///
 /// # Estratégia de Carregamento
-/// 1. Busca pela variável de ambiente `$HOME`.
-/// 2. Tenta abrir o arquivo `$HOME/.clios.toml`.
-/// 3. Se o arquivo existir e for válido, retorna a `CliosConfig` preenchida.
-/// 4. Se o arquivo não existir ou tiver erros de sintaxe, retorna `CliosConfig::default()`
+/// 1. Tenta abrir o arquivo informado (`~/.clios.toml` ou `--config <arquivo>`).
+/// 2. Se o arquivo existir e for válido, usa a `CliosConfig` preenchida, com o
+///    `profile` (`--profile <nome>`) mesclado; um perfil inexistente é avisado e
+///    fica só a base.
+/// 3. Se o arquivo não existir ou tiver erros de sintaxe, usa `CliosConfig::default()`
 ///    e imprime um aviso no stderr (se for erro de sintaxe).
-pub fn load_toml_config() -> CliosConfig {
-    // 1. Constrói o caminho ~/.clios.toml
-    let config_path = env::var("HOME")
-        .map(|p| Path::new(&p).join(".clios.toml"))
-        .unwrap_or_else(|_| Path::new(".clios.toml").to_path_buf());
+/// 4. Por fim aplica as variáveis `CLIOS_*` (ver [`apply_env_overrides`]).
+pub fn load_toml_config_from(config_path: &Path, profile: Option<&str>) -> CliosConfig {
+    let mut config = None;
 
-    // 2. Tenta ler e fazer o parse
+    // 1. Tenta ler e fazer o parse
     if config_path.exists()
-        && let Ok(contents) = std::fs::read_to_string(&config_path) {
-            match toml::from_str::<CliosConfig>(&contents) {
-                Ok(cfg) => return cfg, // Sucesso!
+        && let Ok(contents) = std::fs::read_to_string(config_path) {
+            let parsed = match parse_config(&contents, profile) {
+                Err(ConfigError::UnknownProfile(e)) => {
+                    eprintln!("{}", t!("tag.config_warning", e));
+                    parse_config(&contents, None)
+                }
+                other => other,
+            };
+            match parsed {
+                Ok(cfg) => config = Some(cfg), // Sucesso!
                 Err(e) => {
                     eprintln!(
-                        "\x1b[1;33m[AVISO CONFIG]\x1b[0m Erro no .clios.toml: {}",
-                        e
+                        "{}",
+                        t!("tag.config_warning", t!("config.file_error", config_path.display(), e))
                     );
-                    eprintln!("--> Usando configuração padrão.");
+                    eprintln!("{}", t!("config.using_default"));
                 }
             }
         }
 
-    // 3. Fallback para padrão
-    CliosConfig::default()
+    // 2. Fallback para padrão + variáveis de ambiente
+    let mut config = config.unwrap_or_default();
+    config.path = Some(config_path.to_path_buf());
+    apply_env_overrides(&mut config, |key| env::var(key).ok());
+    config
+}
+
+/// Interpreta um booleano vindo de variável de ambiente.
+fn parse_env_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Acesso a um campo booleano de `[behavior]`.
+type BehaviorFlag = fn(&mut ConfigBehavior) -> &mut Option<bool>;
+
+/// Sobrescreve a configuração com variáveis de ambiente `CLIOS_*`.
+///
+/// Resolvidas depois do TOML, para testar configurações e rodar em containers
+/// sem editar arquivos:
+///
+/// | Variável | Campo |
+/// |----------|-------|
+/// | `CLIOS_THEME` | `theme` |
+/// | `CLIOS_HISTFILE` | `[history] file` |
+/// | `CLIOS_HISTSIZE` | `[history] max_entries` |
+/// | `CLIOS_PROMPT_SYMBOL` | `[prompt] symbol` |
+/// | `CLIOS_PROMPT_COLOR` | `[prompt] color` |
+/// | `CLIOS_CONFIRM_EXIT` | `[behavior] confirm_exit` |
+/// | `CLIOS_AUTO_PAIR` | `[behavior] auto_pair` |
+/// | `CLIOS_SAFE_RM` | `[behavior] safe_rm` |
+/// | `CLIOS_CAPTURE_JOB_OUTPUT` | `[behavior] capture_job_output` |
+/// | `CLIOS_EDIT_MODE` | `[behavior] edit_mode` |
+/// | `CLIOS_EXPLAIN_ERRORS` | `[behavior] explain_errors` |
+/// | `CLIOS_CAPTURE_OUTPUT` | `[behavior] capture_output` |
+/// | `CLIOS_AUTO_LS` | `[behavior] auto_ls` |
+/// | `CLIOS_STOP_THEN_BG` | `[behavior] stop_then_bg` |
+/// | `CLIOS_LANGUAGE` | `[general] language` |
+/// | `CLIOS_SCREEN_READER` | `[accessibility] screen_reader` |
+///
+/// `lookup` busca a variável (em produção, `env::var`).
+pub fn apply_env_overrides(config: &mut CliosConfig, lookup: impl Fn(&str) -> Option<String>) {
+    let warn = |key: &str, value: &str| {
+        eprintln!("{}", t!("tag.config_warning", t!("config.invalid_env", key, value)));
+    };
+
+    if let Some(theme) = lookup("CLIOS_THEME") {
+        config.theme = Some(theme);
+    }
+    if let Some(file) = lookup("CLIOS_HISTFILE") {
+        config.history.get_or_insert_with(Default::default).file = Some(file);
+    }
+    if let Some(size) = lookup("CLIOS_HISTSIZE") {
+        match size.trim().parse() {
+            Ok(n) => config.history.get_or_insert_with(Default::default).max_entries = Some(n),
+            Err(_) => warn("CLIOS_HISTSIZE", &size),
+        }
+    }
+    if let Some(symbol) = lookup("CLIOS_PROMPT_SYMBOL") {
+        config.prompt.get_or_insert_with(Default::default).symbol = Some(symbol);
+    }
+    if let Some(color) = lookup("CLIOS_PROMPT_COLOR") {
+        config.prompt.get_or_insert_with(Default::default).color = Some(color);
+    }
+    if let Some(mode) = lookup("CLIOS_EDIT_MODE") {
+        config.behavior.get_or_insert_with(Default::default).edit_mode = Some(mode);
+    }
+    if let Some(language) = lookup("CLIOS_LANGUAGE") {
+        config.general.get_or_insert_with(Default::default).language = Some(language);
+    }
+
+    if let Some(value) = lookup("CLIOS_SCREEN_READER") {
+        match parse_env_bool(&value) {
+            Some(flag) => config.accessibility.get_or_insert_with(Default::default).screen_reader = Some(flag),
+            None => warn("CLIOS_SCREEN_READER", &value),
+        }
+    }
+
+    let flags: [(&str, BehaviorFlag); 8] = [
+        ("CLIOS_CONFIRM_EXIT", |b| &mut b.confirm_exit),
+        ("CLIOS_AUTO_PAIR", |b| &mut b.auto_pair),
+        ("CLIOS_SAFE_RM", |b| &mut b.safe_rm),
+        ("CLIOS_CAPTURE_JOB_OUTPUT", |b| &mut b.capture_job_output),
+        ("CLIOS_EXPLAIN_ERRORS", |b| &mut b.explain_errors),
+        ("CLIOS_CAPTURE_OUTPUT", |b| &mut b.capture_output),
+        ("CLIOS_AUTO_LS", |b| &mut b.auto_ls),
+        ("CLIOS_STOP_THEN_BG", |b| &mut b.stop_then_bg),
+    ];
+    for (key, field) in flags {
+        if let Some(value) = lookup(key) {
+            match parse_env_bool(&value) {
+                Some(flag) => *field(config.behavior.get_or_insert_with(Default::default)) = Some(flag),
+                None => warn(key, &value),
+            }
+        }
+    }
 }
 
 /// Converte um nome de cor legível (ex: "red") para seu código ANSI correspondente.
@@ -0,0 +1,195 @@
+//! # Container Module
+//!
+//! Builtin `inside`: executa comandos dentro de um container, sem digitar
+//! `docker exec -it` toda vez.
+//!
+//! - `inside web` - abre um shell (`sh`) no container
+//! - `inside web rails console` - executa o comando no container
+//! - Com `default` na seção `[container]` (ou `inside --default web`), o nome
+//!   pode ser omitido: `inside ls /app`. O prompt mostra `🐳 web`.
+//!
+//! O runtime é o `docker` ou, se ele não estiver no `PATH`, o `podman`
+//! (`runtime` na seção `[container]` escolhe). O TAB completa o nome do
+//! container com a saída do `docker ps`, guardada por [`CACHE_TTL`].
+
+use crate::command_hash;
+use crate::pipeline::execute_pipeline;
+use crate::registry::usage;
+use crate::t;
+use std::io::{self, IsTerminal};
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// Comando executado quando `inside` recebe só o container.
+pub const DEFAULT_SHELL: &str = "sh";
+
+/// Validade da lista de containers usada no autocomplete.
+pub const CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// Configuração dos containers (seção `[container]`).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContainerSettings {
+    /// `docker` ou `podman` (`None` = detectar)
+    pub runtime: Option<String>,
+    /// Container usado quando o nome é omitido
+    pub default: Option<String>,
+}
+
+static SETTINGS: RwLock<ContainerSettings> = RwLock::new(ContainerSettings {
+    runtime: None,
+    default: None,
+});
+
+/// Containers em execução e o instante da consulta.
+static CACHE: Mutex<Option<(Instant, Vec<String>)>> = Mutex::new(None);
+
+/// Define a configuração (seção `[container]`).
+pub fn set_settings(settings: ContainerSettings) {
+    if let Ok(mut current) = SETTINGS.write() {
+        *current = settings;
+    }
+}
+
+fn settings() -> ContainerSettings {
+    SETTINGS.read().map(|s| s.clone()).unwrap_or_default()
+}
+
+/// Troca o container padrão desta sessão (`inside --default`).
+pub fn set_default_container(name: Option<String>) {
+    if let Ok(mut current) = SETTINGS.write() {
+        current.default = name;
+    }
+}
+
+/// Container padrão atual.
+pub fn default_container() -> Option<String> {
+    settings().default
+}
+
+/// Runtime em uso: o configurado, senão `docker` (ou `podman` se só ele existir).
+pub fn runtime() -> String {
+    settings().runtime.unwrap_or_else(|| {
+        if command_hash::peek("docker").is_none() && command_hash::peek("podman").is_some() {
+            "podman".to_string()
+        } else {
+            "docker".to_string()
+        }
+    })
+}
+
+/// Texto do indicador do prompt (`🐳 web`).
+pub fn container_label(name: Option<&str>) -> Option<String> {
+    name.map(|name| format!("🐳 {}", name))
+}
+
+// -----------------------------------------------------------------------------
+// CONTAINER LIST
+// -----------------------------------------------------------------------------
+
+/// Nomes da saída de `docker ps --format {{.Names}}`, em ordem alfabética.
+pub fn parse_ps_names(output: &str) -> Vec<String> {
+    let mut names: Vec<String> = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Containers em execução (consulta o runtime no máximo a cada [`CACHE_TTL`]).
+///
+/// Sem runtime ou sem daemon a lista fica vazia, e o erro também é guardado
+/// para o TAB não esperar o `docker` de novo a cada tecla.
+pub fn running_containers() -> Vec<String> {
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((at, names)) = cache.as_ref()
+        && at.elapsed() < CACHE_TTL
+    {
+        return names.clone();
+    }
+    let names = Command::new(runtime())
+        .args(["ps", "--format", "{{.Names}}"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .map(|out| parse_ps_names(&String::from_utf8_lossy(&out.stdout)))
+        .unwrap_or_default();
+    *cache = Some((Instant::now(), names.clone()));
+    names
+}
+
+/// Candidatos do TAB para o nome do container.
+pub fn complete_container(prefix: &str) -> Vec<String> {
+    running_containers()
+        .into_iter()
+        .filter(|name| name.starts_with(prefix))
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
+// BUILTIN
+// -----------------------------------------------------------------------------
+
+/// Container e comando de um `inside`: o primeiro argumento é o container se
+/// estiver em `running` (ou se não houver padrão); senão tudo é comando e vale
+/// o container padrão.
+pub fn inside_target(args: &[String], running: &[String], default: Option<&str>) -> Option<(String, Vec<String>)> {
+    match (args.split_first(), default) {
+        (Some((first, rest)), _) if running.contains(first) => Some((first.clone(), rest.to_vec())),
+        (_, Some(default)) => Some((default.to_string(), args.to_vec())),
+        (Some((first, rest)), None) => Some((first.clone(), rest.to_vec())),
+        (None, None) => None,
+    }
+}
+
+/// Tokens do `exec`; sem comando, abre o [`DEFAULT_SHELL`]. `tty` pede um
+/// terminal no container (`-t`).
+pub fn exec_command(runtime: &str, container: &str, command: &[String], tty: bool) -> Vec<String> {
+    let mut tokens = vec![runtime.to_string(), "exec".to_string(), "-i".to_string()];
+    if tty {
+        tokens.push("-t".to_string());
+    }
+    tokens.push(container.to_string());
+    if command.is_empty() {
+        tokens.push(DEFAULT_SHELL.to_string());
+    } else {
+        tokens.extend(command.iter().cloned());
+    }
+    tokens
+}
+
+/// Builtin `inside [--default nome|-] [container] [comando...]`.
+pub fn handle_inside(tokens: &[String]) -> i32 {
+    let args = &tokens[1..];
+    if args.first().map(|s| s.as_str()) == Some("--default") {
+        match args.get(1).map(|s| s.as_str()) {
+            None => match default_container() {
+                Some(name) => println!("{}", name),
+                None => println!("{}", t!("inside.no_default")),
+            },
+            Some("-") => set_default_container(None),
+            Some(name) => set_default_container(Some(name.to_string())),
+        }
+        return 0;
+    }
+    if args.first().is_some_and(|a| a.starts_with('-')) {
+        eprintln!("{}", usage("inside"));
+        return 2;
+    }
+
+    let default = default_container();
+    // Sem padrão o primeiro argumento é sempre o container: nem consulta o runtime
+    let running = if default.is_some() { running_containers() } else { Vec::new() };
+    let Some((container, command)) = inside_target(args, &running, default.as_deref()) else {
+        eprintln!("{}", usage("inside"));
+        return 2;
+    };
+    let tty = io::stdin().is_terminal() && io::stdout().is_terminal();
+    execute_pipeline(vec![exec_command(&runtime(), &container, &command, tty)])
+}
@@ -0,0 +1,136 @@
+//! # Crash Module
+//!
+//! Camada de recuperação para erros internos (panics) da shell.
+//!
+//! ## Fluxo
+//! 1. Um panic hook captura mensagem, local e backtrace (sem imprimir nada)
+//!    dos panics dentro de um `run_guarded`. Os das outras threads (um
+//!    `parallel`, o reaper de jobs...) seguem para o hook anterior, que os
+//!    imprime como sempre.
+//! 2. `run_guarded` isola o comando; se ele entrar em pânico, o terminal é
+//!    restaurado (termios + process group) antes de qualquer outra coisa.
+//! 3. O relatório completo vai para `~/.clios_crash/<id>.log` e o usuário vê
+//!    apenas uma linha com o ID de referência.
+//!
+//! Panics **não** fazem parte do fluxo normal: erros esperados devem ser
+//! tratados com `Result`/mensagens, e esta camada existe só para bugs.
+
+use crate::t;
+use crate::terminal::restore_terminal;
+use chrono::Local;
+use std::backtrace::Backtrace;
+use std::cell::{Cell, RefCell};
+use std::env;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+thread_local! {
+    /// Quantos `run_guarded` estão ativos nesta thread.
+    static GUARDED: Cell<usize> = const { Cell::new(0) };
+    /// Último panic capturado pelo hook nesta thread (mensagem + local + backtrace).
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Sequência dos IDs de crash deste processo.
+static CRASH_SEQUENCE: AtomicU32 = AtomicU32::new(0);
+
+/// Relatório de um erro interno recuperado.
+#[derive(Debug, Clone)]
+pub struct CrashReport {
+    /// Identificador curto exibido ao usuário.
+    pub id: String,
+    /// Arquivo com o relatório completo (se pôde ser gravado).
+    pub log_path: Option<PathBuf>,
+}
+
+/// Instala o panic hook que alimenta os relatórios de crash. Fora de um
+/// `run_guarded`, o panic vai para o hook que estava instalado antes.
+pub fn install_panic_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if GUARDED.get() == 0 {
+            previous(info);
+            return;
+        }
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}", l.file(), l.line()))
+            .unwrap_or_else(|| "desconhecido".to_string());
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panic sem mensagem".to_string());
+
+        let details = format!(
+            "mensagem: {}\nlocal: {}\n\nbacktrace:\n{}",
+            message,
+            location,
+            Backtrace::force_capture()
+        );
+        LAST_PANIC.set(Some(details));
+    }));
+}
+
+/// Diretório onde os relatórios são gravados (`~/.clios_crash`).
+pub fn crash_dir() -> PathBuf {
+    env::var("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join(".clios_crash")
+}
+
+/// Gera um ID de referência curto e ordenável (data + PID + sequência), único
+/// mesmo para dois crashes no mesmo segundo.
+pub fn new_crash_id() -> String {
+    let sequence = CRASH_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    format!("{}-{}-{}", Local::now().format("%Y%m%d-%H%M%S"), std::process::id(), sequence)
+}
+
+/// Executa `f` isolando panics.
+///
+/// Em caso de panic: restaura o terminal, grava o relatório e retorna o
+/// `CrashReport`. O `context` (normalmente a linha de comando) entra no log.
+pub fn run_guarded<F: FnOnce()>(context: &str, f: F) -> Result<(), CrashReport> {
+    GUARDED.set(GUARDED.get() + 1);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    GUARDED.set(GUARDED.get() - 1);
+    match result {
+        Ok(()) => Ok(()),
+        Err(_) => {
+            restore_terminal();
+
+            let id = new_crash_id();
+            let details = LAST_PANIC
+                .take()
+                .unwrap_or_else(|| "sem detalhes capturados".to_string());
+
+            let report = format!(
+                "Clios Shell v{} - erro interno {}\ncomando: {}\n{}\n",
+                env!("CARGO_PKG_VERSION"),
+                id,
+                context,
+                details
+            );
+
+            let dir = crash_dir();
+            let path = dir.join(format!("{}.log", id));
+            let log_path = (fs::create_dir_all(&dir).is_ok() && fs::write(&path, report).is_ok())
+                .then_some(path);
+
+            Err(CrashReport { id, log_path })
+        }
+    }
+}
+
+/// Imprime o aviso padrão de erro interno para o usuário.
+pub fn report_crash(report: &CrashReport) {
+    eprintln!("{}", t!("crash.recovered", report.id));
+    match &report.log_path {
+        Some(path) => eprintln!("{}", t!("crash.details", path.display())),
+        None => eprintln!("{}", t!("crash.write_failed", crash_dir().display())),
+    }
+}
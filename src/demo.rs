@@ -0,0 +1,120 @@
+//! # Demo Module
+//!
+//! Modo de apresentação, para gravações de tela e pareamento:
+//!
+//! - O prompt vira só o diretório e o `❯`, sem usuário, máquina nem ícones
+//!   que dependem de fonte, com uma linha em branco entre um comando e outro
+//! - Nada vai para o histórico (nem para o arquivo, nem para o Ctrl+R)
+//! - Com `--offline` (ou `block_network` em `[demo]`), comandos que usam a rede
+//!   são recusados
+//! - Com `allow` em `[demo]`, só os comandos da lista rodam
+//!
+//! ```bash
+//! demo on --offline   # liga (também: clios-shell --demo)
+//! demo off
+//! ```
+
+use crate::parser::split_command_positions;
+use crate::prompt::tilde_path;
+use crate::t;
+
+/// Programas e builtins que falam com a rede.
+pub const NETWORK_COMMANDS: &[&str] = &[
+    "curl", "wget", "ssh", "scp", "sftp", "rsync", "ftp", "telnet", "nc", "ncat", "ping", "dig", "nslookup", "host",
+    "fetch", "myip", "ping-lite", "remote",
+];
+
+/// Subcomandos do `git` que usam a rede.
+const GIT_NETWORK: &[&str] = &["push", "pull", "fetch", "clone", "ls-remote"];
+
+/// Prefixos que executam o comando seguinte (`sudo curl ...`).
+const WRAPPERS: &[&str] = &["sudo", "env", "time", "nohup", "nice", "timeout", "xargs", "exec", "command"];
+
+/// Comandos que rodam mesmo fora do `allow`, para não prender a sessão.
+const ALWAYS_ALLOWED: &[&str] = &["demo", "exit", "cd", "clear"];
+
+/// Estado do modo de apresentação.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DemoSettings {
+    /// Recusa os comandos de [`NETWORK_COMMANDS`] (e `git push`, `git pull`...).
+    pub block_network: bool,
+    /// Se definida, só estes comandos rodam.
+    pub allow: Option<Vec<String>>,
+}
+
+/// Ação pedida ao builtin `demo`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DemoAction {
+    On { offline: bool },
+    Off,
+    Status,
+}
+
+/// Interpreta `demo [on [--offline] | off]`.
+pub fn parse_demo_args(args: &[String]) -> Result<DemoAction, String> {
+    match args {
+        [] => Ok(DemoAction::Status),
+        [cmd] if cmd == "on" => Ok(DemoAction::On { offline: false }),
+        [cmd, flag] if cmd == "on" && flag == "--offline" => Ok(DemoAction::On { offline: true }),
+        [cmd] if cmd == "off" => Ok(DemoAction::Off),
+        [other, ..] => Err(t!("args.invalid_argument", other)),
+    }
+}
+
+/// O comando de um segmento, pulando prefixos como `sudo`, atribuições
+/// (`VAR=x`), flags e números (`timeout 5 curl`).
+fn segment_command(words: &[String]) -> Option<(&str, Option<&str>)> {
+    let mut iter = words.iter().map(|w| w.as_str());
+    let mut wrapped = false;
+    while let Some(word) = iter.next() {
+        if WRAPPERS.contains(&word) {
+            wrapped = true;
+            continue;
+        }
+        if word.contains('=') && !word.starts_with('=') {
+            continue;
+        }
+        if wrapped && (word.starts_with('-') || word.parse::<f64>().is_ok()) {
+            continue;
+        }
+        let command = word.rsplit('/').next().unwrap_or(word);
+        return Some((command, iter.find(|w| !w.starts_with('-'))));
+    }
+    None
+}
+
+/// Motivo para recusar a linha no modo de apresentação, se houver.
+pub fn blocked_reason(line: &str, settings: &DemoSettings) -> Option<String> {
+    for segment in split_command_positions(line) {
+        // `( curl ... )` é checado aqui e de novo no subshell
+        let text = segment.text.trim_start().trim_start_matches(['(', ' ']);
+        let words = shlex::split(text).unwrap_or_else(|| text.split_whitespace().map(String::from).collect());
+        let Some((command, sub)) = segment_command(&words) else {
+            continue;
+        };
+        if let Some(allow) = &settings.allow
+            && !ALWAYS_ALLOWED.contains(&command)
+            && !allow.iter().any(|a| a == command)
+        {
+            return Some(t!("demo.not_allowed", command));
+        }
+        let network = NETWORK_COMMANDS.contains(&command)
+            || (command == "git" && sub.is_some_and(|s| GIT_NETWORK.contains(&s)));
+        if settings.block_network && network {
+            let shown = match sub {
+                Some(sub) if command == "git" => format!("git {}", sub),
+                _ => command.to_string(),
+            };
+            return Some(t!("demo.network_blocked", shown));
+        }
+    }
+    None
+}
+
+/// O prompt do modo de apresentação: o diretório (com `~`) e o `❯`.
+pub fn demo_prompt(last_exit_code: i32) -> String {
+    let cwd = std::env::current_dir().map(|p| p.display().to_string()).unwrap_or_default();
+    let path = tilde_path(&cwd, &std::env::var("HOME").unwrap_or_default());
+    let arrow = if last_exit_code == 0 { "\x1b[1;32m" } else { "\x1b[1;31m" };
+    format!("\x1b[1;34m{}\x1b[0m {}❯\x1b[0m ", path, arrow)
+}
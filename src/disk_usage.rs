@@ -0,0 +1,182 @@
+//! # Disk Usage Module
+//!
+//! Builtin `usage [dir]`: um ncdu simplificado. Calcula o tamanho de cada
+//! entrada do diretório (as subpastas em paralelo, com `rayon`) e mostra a
+//! lista ordenada com barras proporcionais.
+//!
+//! O tamanho é o ocupado em disco (blocos alocados, como o `du`), não o
+//! aparente; a varredura não sai do sistema de arquivos nem segue symlinks.
+//!
+//! Também fornece o aviso de disco cheio do prompt ([`low_disk_label`]): com
+//! `show_disk` em `[prompt]` (o padrão), o prompt mostra `disk 96%` quando o
+//! sistema de arquivos do diretório atual tem menos de 10% livre.
+
+use crate::archive::human_size;
+use crate::registry::usage;
+use crate::signals::{is_interrupted, take_interrupt, EXIT_INTERRUPTED};
+use crate::t;
+use nix::sys::statvfs::statvfs;
+use rayon::prelude::*;
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Entradas mostradas sem `-n` quando a saída é um terminal.
+pub const DEFAULT_LIMIT: usize = 20;
+
+/// Largura da barra.
+const BAR_WIDTH: usize = 20;
+
+/// Livre abaixo disso (fração do total) acende o aviso do prompt.
+const LOW_DISK_FREE: f64 = 0.10;
+
+/// Uma entrada do diretório e o que ela ocupa.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageEntry {
+    pub name: String,
+    pub is_dir: bool,
+    /// Bytes em disco
+    pub size: u64,
+    /// Arquivos contados (1 para um arquivo)
+    pub files: u64,
+    /// Entradas que não puderam ser lidas
+    pub errors: u64,
+}
+
+/// Soma os blocos de `path` (recursivo para diretórios).
+pub fn measure(path: &Path) -> UsageEntry {
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let mut entry = UsageEntry { name, is_dir: false, size: 0, files: 0, errors: 0 };
+    // Hard links contam uma vez só, como no `du` (o `target/` do cargo tem muitos)
+    let mut linked = HashSet::new();
+    for item in WalkDir::new(path).same_file_system(true) {
+        if is_interrupted() {
+            break;
+        }
+        match item.and_then(|i| i.metadata()) {
+            Ok(meta) => {
+                if meta.nlink() > 1 && !meta.is_dir() && !linked.insert((meta.dev(), meta.ino())) {
+                    continue;
+                }
+                entry.size += meta.blocks() * 512;
+                if meta.is_dir() {
+                    entry.is_dir = true;
+                } else {
+                    entry.files += 1;
+                }
+            }
+            Err(_) => entry.errors += 1,
+        }
+    }
+    entry
+}
+
+/// Mede as entradas de `dir` em paralelo, da maior para a menor.
+pub fn scan(dir: &Path) -> io::Result<Vec<UsageEntry>> {
+    let paths: Vec<PathBuf> = fs::read_dir(dir)?.filter_map(|e| e.ok().map(|e| e.path())).collect();
+    let mut entries: Vec<UsageEntry> = paths.par_iter().map(|p| measure(p)).collect();
+    sort_entries(&mut entries);
+    Ok(entries)
+}
+
+/// Maior primeiro; empates pelo nome.
+pub fn sort_entries(entries: &mut [UsageEntry]) {
+    entries.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+}
+
+/// Barra proporcional a `fraction` (0..=1).
+pub fn bar(fraction: f64, width: usize) -> String {
+    let filled = (fraction.clamp(0.0, 1.0) * width as f64).round() as usize;
+    format!("\x1b[36m{}\x1b[90m{}\x1b[0m", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+// -----------------------------------------------------------------------------
+// PROMPT
+// -----------------------------------------------------------------------------
+
+/// Texto do aviso de disco (`disk 96%`): só com menos de 10% livre.
+pub fn low_disk_label(available: u64, total: u64) -> Option<String> {
+    if total == 0 || available as f64 >= total as f64 * LOW_DISK_FREE {
+        return None;
+    }
+    let used = (total - available) as f64 * 100.0 / total as f64;
+    Some(format!("disk {:.0}%", used))
+}
+
+/// [`low_disk_label`] do sistema de arquivos do diretório atual (um `statvfs`,
+/// barato o bastante para rodar a cada prompt).
+pub fn current_low_disk_label() -> Option<String> {
+    let stat = statvfs(".").ok()?;
+    let block = stat.fragment_size() as u64;
+    low_disk_label(stat.blocks_available() as u64 * block, stat.blocks() as u64 * block)
+}
+
+// -----------------------------------------------------------------------------
+// BUILTIN
+// -----------------------------------------------------------------------------
+
+/// Builtin `usage [-n N | -a] [dir]`.
+pub fn handle_usage(tokens: &[String]) -> i32 {
+    let mut limit = io::stdout().is_terminal().then_some(DEFAULT_LIMIT);
+    let mut dir = None;
+    let mut args = tokens[1..].iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-n" => match args.next().and_then(|n| n.parse().ok()) {
+                Some(n) => limit = Some(n),
+                None => {
+                    eprintln!("{}", usage("usage"));
+                    return 2;
+                }
+            },
+            "-a" | "--all" => limit = None,
+            flag if flag.starts_with('-') => {
+                eprintln!("{}", usage("usage"));
+                return 2;
+            }
+            _ if dir.is_some() => {
+                eprintln!("{}", usage("usage"));
+                return 2;
+            }
+            _ => dir = Some(PathBuf::from(arg)),
+        }
+    }
+    let dir = dir.unwrap_or_else(|| PathBuf::from("."));
+
+    let entries = match scan(&dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("usage: {}: {}", dir.display(), e);
+            return 1;
+        }
+    };
+    if take_interrupt() {
+        eprintln!();
+        return EXIT_INTERRUPTED;
+    }
+
+    let total: u64 = entries.iter().map(|e| e.size).sum();
+    let files: u64 = entries.iter().map(|e| e.files).sum();
+    let errors: u64 = entries.iter().map(|e| e.errors).sum();
+    let shown = limit.unwrap_or(entries.len()).min(entries.len());
+    for entry in &entries[..shown] {
+        let fraction = if total == 0 { 0.0 } else { entry.size as f64 / total as f64 };
+        let name = if entry.is_dir {
+            format!("\x1b[1;34m{}/\x1b[0m", entry.name)
+        } else {
+            entry.name.clone()
+        };
+        println!("{:>10}  {} {:>3.0}%  {}", human_size(entry.size), bar(fraction, BAR_WIDTH), fraction * 100.0, name);
+    }
+    if shown < entries.len() {
+        println!("\x1b[90m{}\x1b[0m", t!("usage.more", entries.len() - shown));
+    }
+    println!("\x1b[1m{:>10}\x1b[0m  {}", human_size(total), t!("usage.total", dir.display(), files));
+    if errors > 0 {
+        eprintln!("{}", t!("usage.errors", errors));
+    }
+    0
+}
@@ -0,0 +1,120 @@
+//! # Each Module
+//!
+//! Builtin `each`: um `xargs` simplificado. Lê linhas (do STDIN ou da pipeline
+//! anterior) e executa um comando por linha.
+//!
+//! ```bash
+//! find . -name '*.log' | each 'gzip {}'
+//! cat hosts.txt | each -P 4 'ping -c1 {}'
+//! ```
+//!
+//! `{}` é trocado pela linha já com aspas, então nomes com espaço ou aspas não
+//! quebram o comando (o problema clássico do `xargs`). Sem `{}`, a linha vai no
+//! fim. Com `-P n` os comandos rodam em paralelo pelo mesmo mecanismo do
+//! builtin `parallel` (apenas comandos externos).
+//!
+//! O código de saída é o número de comandos que falharam (máximo 100).
+
+use crate::jobs::JobList;
+use crate::parallel::{run_parallel, ParallelOptions};
+use crate::quote::{join_words, quote_word};
+use crate::signals::{take_interrupt, EXIT_INTERRUPTED};
+use crate::t;
+
+/// Limite do código de saída agregado
+const MAX_FAILED_EXIT: i32 = 100;
+
+/// Opções do `each`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EachOptions {
+    /// Processos simultâneos (1 = sequencial, pela própria shell)
+    pub parallelism: usize,
+    /// Linha de comando com `{}`
+    pub template: String,
+}
+
+/// Interpreta os argumentos do `each` (sem o nome do builtin).
+pub fn parse_each_args(args: &[String]) -> Result<EachOptions, String> {
+    let mut parallelism = 1;
+    let mut i = 0;
+
+    while i < args.len() {
+        match args[i].as_str() {
+            "-P" => {
+                let value = args.get(i + 1).ok_or_else(|| t!("args.requires_number", "-P"))?;
+                parallelism = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| t!("each.invalid_parallelism", value))?;
+                i += 2;
+            }
+            "--" => {
+                i += 1;
+                break;
+            }
+            _ => break,
+        }
+    }
+
+    // `each 'gzip {}'` (um argumento) ou `each gzip {}` (vários)
+    let template = match &args[i..] {
+        [] => return Err(t!("args.no_command").to_string()),
+        [single] => single.clone(),
+        rest => join_words(rest),
+    };
+
+    Ok(EachOptions {
+        parallelism,
+        template,
+    })
+}
+
+/// Monta a linha de comando para uma linha de entrada.
+pub fn fill_template(template: &str, line: &str) -> String {
+    let quoted = quote_word(line);
+    if template.contains("{}") {
+        template.replace("{}", &quoted)
+    } else {
+        format!("{} {}", template, quoted)
+    }
+}
+
+/// Executa o comando para cada linha.
+///
+/// `run` executa uma linha de comando na shell (modo sequencial) e devolve o
+/// código de saída.
+pub fn run_each(
+    opts: &EachOptions,
+    lines: impl Iterator<Item = String>,
+    run: &mut dyn FnMut(&str) -> i32,
+    jobs: &JobList,
+) -> i32 {
+    let lines = lines.filter(|l| !l.is_empty());
+
+    if opts.parallelism > 1 {
+        let Some(template) = shlex::split(&opts.template).filter(|t| !t.is_empty()) else {
+            eprintln!("{}", t!("each.invalid_command", opts.template));
+            return 2;
+        };
+        let parallel = ParallelOptions {
+            jobs: opts.parallelism,
+            keep_order: false,
+            template,
+            args: Some(lines.collect()),
+        };
+        return run_parallel(&parallel, jobs);
+    }
+
+    let mut failed = 0;
+    for line in lines {
+        let code = run(&fill_template(&opts.template, &line));
+        if code == EXIT_INTERRUPTED || take_interrupt() {
+            return EXIT_INTERRUPTED;
+        }
+        if code != 0 {
+            failed += 1;
+        }
+    }
+    failed.min(MAX_FAILED_EXIT)
+}
@@ -0,0 +1,109 @@
+//! # Env List Module
+//!
+//! Builtin `env`: o ambiente em ordem alfabética, colorido no terminal.
+//!
+//! - `env` - todas as variáveis
+//! - `env PADRÃO` - só os nomes que contêm o padrão (sem diferenciar
+//!   maiúsculas), ou que casam com ele se houver `*`/`?` (`env 'GIT_*'`)
+//! - `env --json [PADRÃO]` - um objeto JSON
+//!
+//! A lista é o ambiente que um programa receberia: inclui o `[env]` do
+//! `.clios.toml` e, dentro de um `clean-env`, mostra só o ambiente mínimo.
+//! As demais formas do `env` do sistema (`env VAR=x cmd`, `env -i ...`,
+//! `env programa`) continuam indo para o programa externo.
+
+use crate::executor::child_env;
+use crate::t;
+use glob::{MatchOptions, Pattern};
+use std::collections::BTreeMap;
+use std::io::{self, IsTerminal};
+
+/// O que o `env` nativo deve mostrar.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EnvOptions {
+    pub pattern: Option<String>,
+    pub json: bool,
+}
+
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '['])
+}
+
+/// Interpreta os argumentos; `None` quando eles são do `env` do sistema
+/// (atribuições, flags dele, um programa a executar ou mais de uma palavra).
+pub fn parse_env_args(args: &[String]) -> Option<EnvOptions> {
+    let mut opts = EnvOptions::default();
+    for arg in args {
+        match arg.as_str() {
+            "--json" => opts.json = true,
+            word if word.starts_with('-') || word.contains('=') => return None,
+            word if opts.pattern.is_none() => opts.pattern = Some(word.to_string()),
+            _ => return None,
+        }
+    }
+    // `env python` executa o python, como no sistema
+    if let Some(pattern) = &opts.pattern
+        && !is_glob(pattern)
+        && which::which(pattern).is_ok()
+    {
+        return None;
+    }
+    Some(opts)
+}
+
+/// Se o nome da variável passa no filtro.
+pub fn matches_pattern(name: &str, pattern: &str) -> bool {
+    if is_glob(pattern)
+        && let Ok(glob) = Pattern::new(pattern)
+    {
+        let options = MatchOptions { case_sensitive: false, ..Default::default() };
+        return glob.matches_with(name, options);
+    }
+    name.to_lowercase().contains(&pattern.to_lowercase())
+}
+
+/// As variáveis que passam no filtro, em ordem.
+pub fn filter_env(vars: BTreeMap<String, String>, pattern: Option<&str>) -> BTreeMap<String, String> {
+    match pattern {
+        Some(pattern) => vars.into_iter().filter(|(name, _)| matches_pattern(name, pattern)).collect(),
+        None => vars,
+    }
+}
+
+/// Uma linha `NOME=valor` por variável; com `color`, o nome em destaque.
+pub fn format_env(vars: &BTreeMap<String, String>, color: bool) -> String {
+    let mut out = String::new();
+    for (name, value) in vars {
+        if color {
+            out.push_str(&format!("\x1b[1;36m{}\x1b[0m\x1b[2m=\x1b[0m{}\n", name, value));
+        } else {
+            out.push_str(&format!("{}={}\n", name, value));
+        }
+    }
+    out
+}
+
+/// Builtin `env [--json] [padrão]`.
+pub fn handle_env(opts: &EnvOptions) -> i32 {
+    let vars = filter_env(child_env(), opts.pattern.as_deref());
+    if let Some(pattern) = &opts.pattern
+        && vars.is_empty()
+    {
+        eprintln!("env: {}", t!("env.no_match", pattern));
+        return 1;
+    }
+    if opts.json {
+        return match serde_json::to_string_pretty(&vars) {
+            Ok(json) => {
+                println!("{}", json);
+                0
+            }
+            Err(e) => {
+                eprintln!("{}", t!("tag.error", e));
+                1
+            }
+        };
+    }
+    print!("{}", format_env(&vars, io::stdout().is_terminal()));
+    0
+}
@@ -0,0 +1,377 @@
+//! # Executor Module
+//!
+//! Ponto único de criação de processos externos.
+//!
+//! Pipelines, jobs em background, `parallel`, subshells `$(...)` e o
+//! `shell_exec` do Rhai montam seus processos aqui, então todos seguem as
+//! mesmas regras:
+//!
+//! - **Ambiente:** o da shell (`export`) mais as variáveis da seção `[env]` do
+//!   `.clios.toml`, que valem só para os comandos executados. Dentro de um
+//!   `clean-env` ([`with_clean_env`]), só o ambiente mínimo.
+//! - **Diretório:** o diretório atual da shell.
+//! - **Localização:** o programa é procurado pela tabela do `hash`
+//!   ([`crate::command_hash`]), sem varrer o `PATH` a cada execução.
+//! - **Process group:** [`ProcessGroup::Shell`] (recebe Ctrl+C junto com a
+//!   shell) ou [`ProcessGroup::Own`] (jobs em background).
+//! - **Erros:** [`ExecError`] traduz a falha do `spawn` para a mensagem e o
+//!   código de saída POSIX (127 não encontrado, 126 não executável).
+//! - **Limites:** todo processo passa por [`limits::admit_spawn`], que recusa
+//!   a criação quando há processos demais por segundo ([`crate::limits`]).
+//!
+//! ## Subshells
+//! `$(...)`, `( ... )` e builtins com `&` (`cd /tmp &`) rodam em
+//! [`subshell`]: um `fork` da própria shell. O filho começa com uma cópia de
+//! todo o estado (diretório, variáveis, aliases, escopo do Rhai) e o que ele
+//! muda (`cd`, `export`, `alias`, `let` do Rhai) se perde quando ele termina.
+//! Só a saída (em `$(...)`) e o código de saída voltam para a shell.
+
+use crate::command_hash;
+use crate::jobs::pause_reaper;
+use crate::limits::{self, LimitError};
+use crate::t;
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fmt;
+use nix::libc;
+use nix::sys::wait::{self, WaitStatus};
+use nix::unistd::{self, ForkResult, Pid};
+use std::io::{self, Read, Write};
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::process::{Child, Command, ExitStatus, Output};
+use std::sync::RwLock;
+
+// -----------------------------------------------------------------------------
+// CONSTANTES
+// -----------------------------------------------------------------------------
+
+/// Código de saída padrão POSIX para "comando não encontrado"
+pub const EXIT_COMMAND_NOT_FOUND: i32 = 127;
+
+/// Código de saída padrão POSIX para "comando não executável"
+pub const EXIT_NOT_EXECUTABLE: i32 = 126;
+
+/// Base do código de saída de um processo morto por sinal (128 + sinal)
+pub const EXIT_SIGNAL_BASE: i32 = 128;
+
+/// Código de saída para erros genéricos
+const EXIT_ERROR: i32 = 1;
+
+/// Variáveis da seção `[env]`, injetadas em todo processo criado.
+static INJECTED_ENV: RwLock<Vec<(String, String)>> = RwLock::new(Vec::new());
+
+/// Ambiente mínimo do `clean-env` em andamento (substitui todo o ambiente).
+static CLEAN_ENV: RwLock<Option<Vec<(String, String)>>> = RwLock::new(None);
+
+// -----------------------------------------------------------------------------
+// ENVIRONMENT
+// -----------------------------------------------------------------------------
+
+/// Define as variáveis injetadas nos comandos (seção `[env]` da configuração).
+pub fn set_injected_env(vars: &HashMap<String, String>) {
+    if let Ok(mut env) = INJECTED_ENV.write() {
+        let mut vars: Vec<_> = vars.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        vars.sort();
+        *env = vars;
+    }
+}
+
+/// Variáveis injetadas atualmente.
+pub fn injected_env() -> Vec<(String, String)> {
+    INJECTED_ENV.read().map(|env| env.clone()).unwrap_or_default()
+}
+
+/// Executa `f` com todo processo criado recebendo apenas `vars` como ambiente.
+pub fn with_clean_env<R>(vars: Vec<(String, String)>, f: impl FnOnce() -> R) -> R {
+    let previous = CLEAN_ENV.write().ok().and_then(|mut env| env.replace(vars));
+    let result = f();
+    if let Ok(mut env) = CLEAN_ENV.write() {
+        *env = previous;
+    }
+    result
+}
+
+/// Ambiente mínimo ativo, se houver um `clean-env` em andamento.
+pub fn clean_env() -> Option<Vec<(String, String)>> {
+    CLEAN_ENV.read().ok().and_then(|env| env.clone())
+}
+
+/// Ambiente que um processo criado agora receberia: o da shell com o `[env]`
+/// por cima, ou só o mínimo durante um `clean-env`.
+pub fn child_env() -> BTreeMap<String, String> {
+    match clean_env() {
+        Some(vars) => vars.into_iter().collect(),
+        None => env::vars_os()
+            .map(|(k, v)| (k.to_string_lossy().into_owned(), v.to_string_lossy().into_owned()))
+            .chain(injected_env())
+            .collect(),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// COMMAND BUILDING
+// -----------------------------------------------------------------------------
+
+/// Process group do processo criado.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProcessGroup {
+    /// Mesmo grupo da shell: recebe o Ctrl+C do terminal
+    Shell,
+    /// Grupo próprio (PGID = PID): não recebe sinais do terminal
+    Own,
+    /// Job em foreground: entra no grupo `pgid` (0 cria um, PGID = PID) e
+    /// recebe o terminal, com o Ctrl+C e o Ctrl+Z
+    Foreground(i32),
+}
+
+/// Monta o `Command` de `tokens` (programa + argumentos) com o ambiente da
+/// shell e o process group pedido.
+///
+/// `tokens` não pode ser vazio.
+pub fn command(tokens: &[String], group: ProcessGroup) -> Command {
+    // Caminho pela tabela do `hash`; o programa ainda recebe o nome como argv[0]
+    let mut command = match command_hash::lookup(&tokens[0]) {
+        Some(path) => {
+            let mut command = Command::new(path);
+            command.arg0(&tokens[0]);
+            command
+        }
+        None => Command::new(&tokens[0]),
+    };
+    command.args(&tokens[1..]);
+    match clean_env() {
+        Some(vars) => command.env_clear().envs(vars),
+        None => command.envs(injected_env()),
+    };
+    match group {
+        ProcessGroup::Shell => {}
+        ProcessGroup::Own => {
+            command.process_group(0);
+        }
+        ProcessGroup::Foreground(pgid) => {
+            command.process_group(pgid);
+            // O terminal muda de dono antes do `exec`: um programa que lê o
+            // teclado logo de cara seria parado com SIGTTIN
+            unsafe {
+                command.pre_exec(|| {
+                    libc::tcsetpgrp(libc::STDIN_FILENO, libc::getpgrp());
+                    libc::signal(libc::SIGTTOU, libc::SIG_DFL);
+                    Ok(())
+                });
+            }
+        }
+    }
+    command
+}
+
+// -----------------------------------------------------------------------------
+// ERRORS
+// -----------------------------------------------------------------------------
+
+/// Falha ao iniciar um processo.
+#[derive(Debug)]
+pub struct ExecError {
+    /// Programa que não pôde ser executado
+    pub program: String,
+    /// Erro do sistema
+    pub error: io::Error,
+}
+
+impl ExecError {
+    /// Código de saída POSIX correspondente.
+    pub fn exit_code(&self) -> i32 {
+        if resource_error(&self.error).is_some() {
+            return EXIT_ERROR;
+        }
+        match self.error.kind() {
+            io::ErrorKind::NotFound => EXIT_COMMAND_NOT_FOUND,
+            _ => EXIT_NOT_EXECUTABLE,
+        }
+    }
+}
+
+impl fmt::Display for ExecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(message) = resource_error(&self.error) {
+            return f.write_str(&message);
+        }
+        let message = match self.error.kind() {
+            io::ErrorKind::NotFound => t!("exec.not_found", self.program),
+            io::ErrorKind::PermissionDenied => t!("exec.permission_denied", self.program),
+            _ => t!("exec.failed", self.program, self.error),
+        };
+        f.write_str(&message)
+    }
+}
+
+/// Mensagem para falhas por falta de recursos: limite da shell
+/// ([`LimitError`]) ou do sistema (`EAGAIN`, geralmente o `ulimit -u`).
+fn resource_error(error: &io::Error) -> Option<String> {
+    if let Some(limit) = error.get_ref().and_then(|e| e.downcast_ref::<LimitError>()) {
+        return Some(limit.to_string());
+    }
+    (error.raw_os_error() == Some(nix::libc::EAGAIN)).then(|| match limits::nproc_limit() {
+        Some(nproc) => t!("limits.eagain", nproc),
+        None => t!("exec.fork_failed").to_string(),
+    })
+}
+
+/// Mensagem de uma falha ao criar um subshell ou job.
+pub fn fork_error(error: &io::Error) -> String {
+    resource_error(error).unwrap_or_else(|| t!("exec.fork_failed").to_string())
+}
+
+/// Registra um novo processo nos limites da shell.
+fn admit() -> io::Result<()> {
+    limits::admit_spawn().map_err(io::Error::other)
+}
+
+fn exec_error(command: &Command, error: io::Error) -> ExecError {
+    ExecError {
+        program: command.get_program().to_string_lossy().into_owned(),
+        error,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// EXECUTION
+// -----------------------------------------------------------------------------
+
+/// Inicia o processo sem esperar.
+pub fn spawn(command: &mut Command) -> Result<Child, ExecError> {
+    admit().and_then(|_| command.spawn()).map_err(|e| exec_error(command, e))
+}
+
+/// Executa `tokens` e captura STDOUT/STDERR.
+pub fn capture(tokens: &[String]) -> Result<Output, ExecError> {
+    let mut command = command(tokens, ProcessGroup::Shell);
+    admit().and_then(|_| command.output()).map_err(|e| exec_error(&command, e))
+}
+
+/// Substitui o processo atual por `tokens` (filho de um `fork`).
+///
+/// Só retorna se o `exec` falhar.
+pub fn exec_replace(tokens: &[String], group: ProcessGroup) -> ExecError {
+    let mut command = command(tokens, group);
+    let error = command.exec();
+    exec_error(&command, error)
+}
+
+/// Código de saída de um processo, com `128 + sinal` para mortes por sinal.
+pub fn exit_code(status: ExitStatus) -> i32 {
+    status
+        .code()
+        .or_else(|| status.signal().map(|sig| EXIT_SIGNAL_BASE + sig))
+        .unwrap_or(EXIT_ERROR)
+}
+
+/// Código de saída de um status do `waitpid`, como o `$?`: `128 + sinal`
+/// quando o processo foi morto ou parado por um sinal. `None` enquanto ele
+/// não terminou nem parou.
+pub fn wait_status_code(status: WaitStatus) -> Option<i32> {
+    match status {
+        WaitStatus::Exited(_, code) => Some(code),
+        WaitStatus::Signaled(_, sig, _) | WaitStatus::Stopped(_, sig) => Some(EXIT_SIGNAL_BASE + sig as i32),
+        _ => None,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// SUBSHELLS
+// -----------------------------------------------------------------------------
+
+/// Como a shell acompanha um subshell.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SubshellMode {
+    /// Espera o fim, com a saída indo para o terminal (`( ... )`)
+    Foreground,
+    /// Espera o fim e captura o STDOUT (`$(...)`)
+    Capture,
+    /// Não espera; o filho ganha um process group próprio (`cd /tmp &`)
+    Background,
+}
+
+/// Resultado de um subshell.
+#[derive(Debug)]
+pub struct SubshellResult {
+    pub pid: Pid,
+    /// Código de saída (0 em [`SubshellMode::Background`], que não espera)
+    pub code: i32,
+    /// STDOUT capturado (só em [`SubshellMode::Capture`])
+    pub stdout: String,
+}
+
+/// Executa `run` numa cópia da shell (`fork`) e devolve o código de saída dela.
+///
+/// Nada do que `run` muda no processo (diretório, ambiente, estado da shell)
+/// volta para o processo atual.
+pub fn subshell(mode: SubshellMode, run: impl FnOnce() -> i32) -> io::Result<SubshellResult> {
+    admit()?;
+    let pipe = match mode {
+        SubshellMode::Capture => Some(io::pipe()?),
+        _ => None,
+    };
+    // O buffer pendente não pode ser impresso duas vezes (pai e filho)
+    let _ = io::stdout().flush();
+
+    // O filho continua rodando a shell, então não pode herdar uma trava no
+    // meio do uso: as travas globais que ele usa (lista de jobs, tabela de
+    // comandos, ambiente injetado e a saída) ficam com esta thread durante o
+    // `fork`, sempre nesta ordem. A trava do ambiente da std (`env::set_var`,
+    // `env::var`) não pode ser segurada daqui; o invariante é que só esta
+    // thread e o reaper (parado aqui) mexem no ambiente. As threads de
+    // `parallel` e `remote` terminam antes do builtin voltar, e as demais
+    // (`range`, compressão, entrada do `out`) só escrevem em pipes
+    let reaper = pause_reaper();
+    let table = command_hash::pause_table();
+    let env = (INJECTED_ENV.read(), CLEAN_ENV.read());
+    let output = (io::stdout().lock(), io::stderr().lock());
+    let forked = unsafe { unistd::fork() };
+    drop(output);
+    drop(env);
+    drop(table);
+    drop(reaper);
+    match forked.map_err(io::Error::from)? {
+        ForkResult::Child => {
+            if mode == SubshellMode::Background {
+                let _ = unistd::setpgid(Pid::from_raw(0), Pid::from_raw(0));
+            }
+            if let Some((reader, writer)) = pipe {
+                drop(reader);
+                let _ = unistd::dup2_stdout(&writer);
+            }
+            let code = run();
+            let _ = io::stdout().flush();
+            std::process::exit(code);
+        }
+        ForkResult::Parent { child } => {
+            let mut stdout = String::new();
+            if let Some((mut reader, writer)) = pipe {
+                drop(writer);
+                let _ = reader.read_to_string(&mut stdout);
+            }
+            let code = match mode {
+                SubshellMode::Background => {
+                    let _ = unistd::setpgid(child, child);
+                    0
+                }
+                _ => wait_subshell(child),
+            };
+            Ok(SubshellResult { pid: child, code, stdout })
+        }
+    }
+}
+
+/// Espera o subshell e converte o status em código de saída.
+fn wait_subshell(child: Pid) -> i32 {
+    loop {
+        match wait::waitpid(child, None) {
+            Ok(status) => match wait_status_code(status) {
+                Some(code) => return code,
+                None => continue,
+            },
+            Err(nix::errno::Errno::EINTR) => continue,
+            Err(_) => return EXIT_ERROR,
+        }
+    }
+}
@@ -1,16 +1,24 @@
 //! # Expansion Module
 //!
 //! Handles all types of shell expansions:
-//! - Variable expansion ($HOME, $USER, ${VAR})
+//! - Variable expansion ($HOME, $USER, ${VAR}, $__)
+//! - Array expansion (${files[0]}, ${files[@]}, ${#files[@]})
+//! - Brace range expansion ({1..10}, {01..20..5})
 //! - Tilde expansion (~)
 //! - Glob expansion (*.rs, src/*)
 //! - Subshell expansion ($(command))
 //! - Alias expansion
 
+use crate::arrays::{array_element, array_len, array_words};
+use crate::executor;
+use crate::last_output;
+use crate::quote::quote_word;
+use crate::range::brace_range;
+use crate::t;
 use glob::glob;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::env;
-use std::process::Command;
 
 // -----------------------------------------------------------------------------
 // VARIABLE EXPANSION
@@ -25,6 +33,9 @@ use std::process::Command;
 /// - `$?` - Código de saída do último comando
 /// - `$$` - PID da shell atual
 ///
+/// E os arrays da shell (ver módulo arrays): `${nome[i]}`, `${nome[@]}`
+/// (aqui, separados por espaço), `${#nome[@]}` e `${#VAR}` (o tamanho).
+///
 /// # Exemplo
 /// * Entrada: `echo Backup_$USER.tar.gz`
 /// * Saída: `echo Backup_pedro.tar.gz`
@@ -37,71 +48,108 @@ pub fn expand_variables(tokens: Vec<String>) -> Vec<String> {
 pub fn expand_variables_with_state(tokens: Vec<String>, last_exit_code: i32, shell_pid: u32) -> Vec<String> {
     tokens
         .into_iter()
-        .map(|token| {
-            // Otimização: Se não tem '$', retorna o token original imediatamente
-            if !token.contains('$') {
-                return token;
-            }
+        .map(|token| match expand_variables_token(&token, last_exit_code, shell_pid) {
+            Cow::Borrowed(_) => token,
+            Cow::Owned(expanded) => expanded,
+        })
+        .collect()
+}
 
-            let mut output = String::new();
-            let mut chars = token.chars().peekable();
-
-            while let Some(c) = chars.next() {
-                if c == '$' {
-                    // Variáveis especiais de um único caractere
-                    if let Some(&next_c) = chars.peek() {
-                        match next_c {
-                            '?' => {
-                                chars.next(); // Consome '?'
-                                output.push_str(&last_exit_code.to_string());
-                                continue;
-                            }
-                            '$' => {
-                                chars.next(); // Consome '$'
-                                output.push_str(&shell_pid.to_string());
-                                continue;
-                            }
-                            _ => {}
-                        }
+/// Expande as variáveis de um único token.
+///
+/// Retorna `Cow::Borrowed` quando o token não contém `$`, evitando qualquer
+/// alocação no caso mais comum (argumentos literais).
+pub fn expand_variables_token(token: &str, last_exit_code: i32, shell_pid: u32) -> Cow<'_, str> {
+    // Otimização: Se não tem '$', retorna o token original imediatamente
+    if !token.contains('$') {
+        return Cow::Borrowed(token);
+    }
+
+    let mut output = String::with_capacity(token.len());
+    let mut chars = token.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '$' {
+            // Variáveis especiais de um único caractere
+            if let Some(&next_c) = chars.peek() {
+                match next_c {
+                    '?' => {
+                        chars.next(); // Consome '?'
+                        output.push_str(&last_exit_code.to_string());
+                        continue;
                     }
-                    
-                    // Início de uma variável normal
-                    let mut var_name = String::new();
-                    let mut is_bracketed = false;
-
-                    if let Some(&'{') = chars.peek() {
-                        is_bracketed = true;
-                        chars.next(); // Consome '{'
+                    '$' => {
+                        chars.next(); // Consome '$'
+                        output.push_str(&shell_pid.to_string());
+                        continue;
                     }
+                    _ => {}
+                }
+            }
 
-                    // Lê o nome da variável (Letras, Números ou Underline)
-                    while let Some(&next_c) = chars.peek() {
-                        if next_c.is_alphanumeric() || next_c == '_' {
-                            var_name.push(next_c);
-                            chars.next();
-                        } else {
-                            if is_bracketed && next_c == '}' {
-                                chars.next(); // Consome '}' final
-                            }
-                            break;
-                        }
-                    }
+            // Início de uma variável normal
+            let mut var_name = String::new();
+            let mut is_bracketed = false;
+            let mut is_length = false;
+
+            if let Some(&'{') = chars.peek() {
+                is_bracketed = true;
+                chars.next(); // Consome '{'
+                // `${#VAR}` / `${#nome[@]}`: tamanho
+                if let Some(&'#') = chars.peek() {
+                    is_length = true;
+                    chars.next();
+                }
+            }
 
-                    // Se extraiu um nome válido, busca no Sistema Operacional
-                    if !var_name.is_empty() {
-                        if let Ok(val) = env::var(&var_name) {
-                            output.push_str(&val);
-                        }
-                    } else {
-                        output.push('$');
+            // Lê o nome da variável (Letras, Números ou Underline)
+            while let Some(&next_c) = chars.peek() {
+                if next_c.is_alphanumeric() || next_c == '_' {
+                    var_name.push(next_c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+
+            // `${nome[i]}`: índice de um array
+            let mut subscript = None;
+            if is_bracketed && chars.peek() == Some(&'[') {
+                chars.next();
+                subscript = Some(chars.by_ref().take_while(|&c| c != ']').collect::<String>());
+            }
+            if is_bracketed && chars.peek() == Some(&'}') {
+                chars.next(); // Consome '}' final
+            }
+
+            // Se extraiu um nome válido, busca no Sistema Operacional
+            if !var_name.is_empty() {
+                let value = match subscript.as_deref() {
+                    Some(index) => array_element(&var_name, index),
+                    None if var_name == "__" || var_name == "CLIOS_LAST_OUTPUT" => {
+                        // Saída do último comando (`capture_output`), fora do ambiente
+                        last_output::last_text()
+                    }
+                    // Um array sem índice é o primeiro elemento
+                    None => env::var(&var_name).ok().or_else(|| array_element(&var_name, "0")),
+                };
+                if !is_length {
+                    if let Some(val) = value {
+                        output.push_str(&val);
                     }
+                } else if matches!(subscript.as_deref(), Some("@" | "*")) {
+                    output.push_str(&array_len(&var_name).to_string());
                 } else {
-                    output.push(c);
+                    output.push_str(&value.map_or(0, |v| v.chars().count()).to_string());
                 }
+            } else {
+                output.push('$');
             }
-            output
-        })
-        .collect()
+        } else {
+            output.push(c);
+        }
+    }
+    Cow::Owned(output)
 }
 
 // -----------------------------------------------------------------------------
@@ -121,18 +169,25 @@ pub fn expand_tilde(tokens: Vec<String>) -> Vec<String> {
 
     tokens
         .into_iter()
-        .map(|t| {
-            if t == "~" {
-                home.clone()
-            } else if t.starts_with("~/") {
-                format!("{}{}", home, &t[1..])
-            } else {
-                t
-            }
-        })
+        .map(|t| expand_tilde_token(Cow::Owned(t), &home).into_owned())
         .collect()
 }
 
+/// Expande o til de um único token, reaproveitando a string quando não há `~`.
+pub fn expand_tilde_token<'a>(token: Cow<'a, str>, home: &str) -> Cow<'a, str> {
+    if token == "~" {
+        Cow::Owned(home.to_string())
+    } else if let Some(rest) = token.strip_prefix("~/") {
+        let mut expanded = String::with_capacity(home.len() + rest.len() + 1);
+        expanded.push_str(home);
+        expanded.push('/');
+        expanded.push_str(rest);
+        Cow::Owned(expanded)
+    } else {
+        token
+    }
+}
+
 // -----------------------------------------------------------------------------
 // GLOB EXPANSION
 // -----------------------------------------------------------------------------
@@ -146,31 +201,82 @@ pub fn expand_tilde(tokens: Vec<String>) -> Vec<String> {
 /// * Se encontrar arquivos: Substitui o token pela lista de arquivos.
 /// * Se NÃO encontrar: Mantém o token original.
 pub fn expand_globs(tokens: Vec<String>) -> Vec<String> {
-    let mut expanded_tokens = Vec::new();
+    let mut expanded_tokens = Vec::with_capacity(tokens.len());
     for token in tokens {
-        if token.contains('*') || token.contains('?') {
-            match glob(&token) {
-                Ok(paths) => {
-                    let mut found = false;
-                    for p in paths.flatten() {
-                        if let Some(s) = p.to_str() {
-                            expanded_tokens.push(s.to_string());
-                            found = true;
-                        }
-                    }
-                    if !found {
-                        expanded_tokens.push(token);
-                    }
-                }
-                Err(_) => {
-                    expanded_tokens.push(token);
+        expand_glob_into(token, &mut expanded_tokens);
+    }
+    expanded_tokens
+}
+
+/// Expande um único token direto no vetor de saída.
+///
+/// Os caminhos encontrados são convertidos sem cópia extra (`into_string`),
+/// o que importa quando um padrão casa dezenas de milhares de arquivos.
+fn expand_glob_into(token: String, out: &mut Vec<String>) {
+    if !token.contains('*') && !token.contains('?') {
+        out.push(token);
+        return;
+    }
+
+    match glob(&token) {
+        Ok(paths) => {
+            let before = out.len();
+            out.extend(
+                paths
+                    .flatten()
+                    .filter_map(|p| p.into_os_string().into_string().ok()),
+            );
+            if out.len() == before {
+                out.push(token);
+            }
+        }
+        Err(_) => out.push(token),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// SINGLE-PASS EXPANSION
+// -----------------------------------------------------------------------------
+
+/// Pipeline de expansão em passagem única: arrays → chaves → variáveis → til
+/// → globs.
+///
+/// Em vez de reconstruir um `Vec<String>` inteiro a cada etapa, cada token
+/// atravessa todas as etapas como `Cow<str>` e só é alocado quando alguma
+/// expansão realmente o modifica. O resultado é escrito direto no vetor final.
+pub fn expand_tokens(tokens: Vec<String>, last_exit_code: i32, shell_pid: u32) -> Vec<String> {
+    let home = env::var("HOME").unwrap_or_else(|_| "/".to_string());
+    let mut out = Vec::with_capacity(tokens.len());
+
+    for token in tokens {
+        // `${nome[@]}` sozinho: um argumento por elemento, sem globs
+        if let Some(items) = array_words(&token) {
+            out.extend(items);
+            continue;
+        }
+        // `{1..10..2}`: uma palavra por número, cada uma expandida à parte
+        match brace_range(&token) {
+            Some(words) => {
+                for word in words {
+                    expand_word_into(word, last_exit_code, shell_pid, &home, &mut out);
                 }
             }
-        } else {
-            expanded_tokens.push(token);
+            None => expand_word_into(token, last_exit_code, shell_pid, &home, &mut out),
         }
     }
-    expanded_tokens
+    out
+}
+
+/// Variáveis, til e globs de uma palavra, escrita em `out`.
+fn expand_word_into(token: String, last_exit_code: i32, shell_pid: u32, home: &str, out: &mut Vec<String>) {
+    let expanded = expand_variables_token(&token, last_exit_code, shell_pid);
+    let expanded = expand_tilde_token(expanded, home);
+
+    let owned = match expanded {
+        Cow::Borrowed(_) => token,
+        Cow::Owned(s) => s,
+    };
+    expand_glob_into(owned, out);
 }
 
 // -----------------------------------------------------------------------------
@@ -187,6 +293,17 @@ pub fn expand_globs(tokens: Vec<String>) -> Vec<String> {
 /// * Execução: Roda `date`, captura "Sáb Dez 14..."
 /// * Saída: `echo Hoje é Sáb Dez 14...`
 pub fn expand_subshells(input: &str) -> String {
+    expand_subshells_with(input, &mut |_| None)
+}
+
+/// Variante de [`expand_subshells`] que recebe quem executa o subshell.
+///
+/// A shell passa um `run_subshell` que roda a linha inteira numa cópia dela
+/// mesma ([`executor::subshell`]): builtins, `&&`, pipes e `rhai` funcionam, e
+/// um `cd`/`export` lá dentro não muda a shell. Se ele retornar `None`, cai no
+/// comportamento antigo: executa a linha como um único comando externo (ou
+/// reexecuta o binário da Clios para `rhai`).
+pub fn expand_subshells_with(input: &str, run_subshell: &mut dyn FnMut(&str) -> Option<String>) -> String {
     let mut output = String::new();
     let mut chars = input.chars().peekable();
 
@@ -217,13 +334,13 @@ pub fn expand_subshells(input: &str) -> String {
 
             if closed {
                 if inner.trim().is_empty() {
-                    eprintln!("\x1b[1;33m[AVISO]\x1b[0m Subshell vazio: $()");
+                    eprintln!("{}", t!("tag.warning", t!("subshell.empty")));
                 } else {
-                    let result = execute_and_capture(&inner);
+                    let result = execute_and_capture(&inner, run_subshell);
                     output.push_str(&result);
                 }
             } else {
-                eprintln!("\x1b[1;31m[ERRO SINTAXE]\x1b[0m Subshell não fechado: $({}", inner);
+                eprintln!("{}", t!("tag.syntax", t!("subshell.unclosed", inner)));
                 output.push_str("$(");
                 output.push_str(&inner);
             }
@@ -235,11 +352,16 @@ pub fn expand_subshells(input: &str) -> String {
 }
 
 /// Executa um comando e captura sua saída (STDOUT) como string.
-fn execute_and_capture(cmd_line: &str) -> String {
+fn execute_and_capture(cmd_line: &str, run_subshell: &mut dyn FnMut(&str) -> Option<String>) -> String {
+    // Subshell da própria shell: estado isolado, saída capturada
+    if let Some(captured) = run_subshell(cmd_line) {
+        return captured;
+    }
+
     let tokens = match shlex::split(cmd_line) {
         Some(t) => t,
         None => {
-            eprintln!("\x1b[1;31m[ERRO]\x1b[0m Falha ao processar subshell: '{}'", cmd_line);
+            eprintln!("{}", t!("tag.error", t!("subshell.parse_failed", cmd_line)));
             return String::new();
         }
     };
@@ -248,39 +370,39 @@ fn execute_and_capture(cmd_line: &str) -> String {
     }
 
     let prog = &tokens[0];
-    let args = &tokens[1..];
 
-    // Truque para recursão Rhai
+    // Fallback: reexecuta a própria Clios
     if prog == "rhai"
         && let Ok(myself) = env::current_exe() {
-            let output = Command::new(myself).arg("-c").arg(cmd_line).output();
+            let myself = myself.to_string_lossy().into_owned();
+            let output = executor::capture(&[myself, "-c".to_string(), cmd_line.to_string()]);
 
             return match output {
                 Ok(out) => {
                     if !out.status.success() {
-                        eprintln!("\x1b[1;33m[AVISO]\x1b[0m Comando rhai no subshell falhou");
+                        eprintln!("{}", t!("tag.warning", t!("subshell.rhai_failed")));
                     }
                     String::from_utf8_lossy(&out.stdout).trim().to_string()
                 },
                 Err(e) => {
-                    eprintln!("\x1b[1;31m[ERRO]\x1b[0m Falha ao executar subshell rhai: {}", e);
+                    eprintln!("{}", t!("tag.error", t!("subshell.rhai_exec_failed", e)));
                     String::new()
                 }
             };
         }
 
     // Execução normal
-    let output = Command::new(prog).args(args).output();
+    let output = executor::capture(&tokens);
 
     match output {
         Ok(out) => {
             if !out.status.success() {
-                eprintln!("\x1b[1;33m[AVISO]\x1b[0m Comando '{}' no subshell retornou erro", prog);
+                eprintln!("{}", t!("tag.warning", t!("subshell.command_failed", prog)));
             }
             String::from_utf8_lossy(&out.stdout).trim().to_string()
         },
         Err(e) => {
-            eprintln!("\x1b[1;31m[ERRO]\x1b[0m Comando '{}' não encontrado no subshell: {}", prog, e);
+            eprintln!("{}", t!("tag.error", t!("subshell.command_not_found", prog, e.error)));
             String::new()
         }
     }
@@ -296,48 +418,114 @@ fn execute_and_capture(cmd_line: &str) -> String {
 /// Em vez de expandir token a token (que acontece tarde demais),
 /// expandimos a string bruta antes do parser lógico rodar.
 ///
-/// Inclui proteção contra aliases recursivos infinitos.
+/// Inclui proteção contra aliases recursivos: cada nome visitado é registrado,
+/// então ciclos indiretos (`a -> b -> a`) são detectados na hora e reportados
+/// com o caminho completo.
 pub fn expand_alias_string(input: &str, aliases: &HashMap<String, String>) -> String {
-    expand_alias_string_with_depth(input, aliases, 0)
+    match expand_alias_checked(input, aliases) {
+        Ok(expanded) => expanded,
+        Err(cycle) => {
+            eprintln!("{}", t!("tag.warning", t!("exec.alias_cycle", cycle.join(" -> "))));
+            input.to_string()
+        }
+    }
 }
 
-fn expand_alias_string_with_depth(input: &str, aliases: &HashMap<String, String>, depth: usize) -> String {
-    // Prevenir recursão infinita (máximo 10 níveis)
-    if depth > 10 {
-        eprintln!("\x1b[1;33m[AVISO]\x1b[0m Alias recursivo detectado, interrompendo expansão");
-        return input.to_string();
-    }
+/// Versão sem efeitos colaterais de [`expand_alias_string`].
+///
+/// Retorna `Err(caminho)` quando encontra um ciclo indireto, onde `caminho`
+/// lista os aliases visitados terminando no nome repetido (ex: `["a", "b", "a"]`).
+pub fn expand_alias_checked(input: &str, aliases: &HashMap<String, String>) -> Result<String, Vec<String>> {
+    let mut visited: Vec<String> = Vec::new();
+    let mut current = input.to_string();
+
+    loop {
+        let trimmed = current.trim_start();
+
+        // Acha onde termina a primeira palavra (o nome do comando)
+        let end_idx = trimmed
+            .char_indices()
+            .find(|(_, c)| c.is_whitespace())
+            .map(|(i, _)| i)
+            .unwrap_or(trimmed.len());
+
+        let first_word = &trimmed[..end_idx];
+
+        let Some(val) = aliases.get(first_word) else {
+            return Ok(current);
+        };
+
+        if visited.iter().any(|v| v == first_word) {
+            visited.push(first_word.to_string());
+            return Err(visited);
+        }
 
-    let trimmed = input.trim_start();
-
-    // Acha onde termina a primeira palavra (o nome do comando)
-    let end_idx = trimmed
-        .char_indices()
-        .find(|(_, c)| c.is_whitespace())
-        .map(|(i, _)| i)
-        .unwrap_or(trimmed.len());
-
-    let first_word = &trimmed[..end_idx];
-
-    if let Some(val) = aliases.get(first_word) {
-        let remainder = &trimmed[end_idx..];
-        let expanded = format!("{}{}", val, remainder);
-        
-        // Verificar se o alias expandido começa com o mesmo comando (recursão direta)
-        let expanded_first_word = expanded.split_whitespace()
-            .next()
-            .unwrap_or("");
-        
+        let expanded = fill_alias_template(val, &trimmed[end_idx..]);
+
+        // Alias que começa com o próprio nome (ex: ls='ls -la'): para aqui
+        let expanded_first_word = expanded.split_whitespace().next().unwrap_or("");
         if expanded_first_word == first_word {
-            //eprintln!("\x1b[1;33m[AVISO]\x1b[0m Alias '{}' se refere a si mesmo, usando comando original", first_word);
-            return input.to_string();
+            return Ok(current);
         }
-        
-        // Tentar expandir recursivamente
-        expand_alias_string_with_depth(&expanded, aliases, depth + 1)
-    } else {
-        input.to_string()
+
+        visited.push(first_word.to_string());
+        current = expanded;
+    }
+}
+
+/// Preenche os placeholders posicionais (`{1}`, `{2}`, ...) do valor de um alias.
+///
+/// `rest` é o texto que vem depois do nome do alias na invocação. Argumentos
+/// não usados por nenhum placeholder são anexados no final, como num alias
+/// comum. Placeholders sem argumento correspondente viram string vazia.
+///
+/// Ex: `deploy='ssh {1} systemctl restart {2}'` + `deploy web nginx`
+/// → `ssh web systemctl restart nginx`.
+pub fn fill_alias_template(value: &str, rest: &str) -> String {
+    if !value.contains('{') {
+        return format!("{}{}", value, rest);
+    }
+
+    let args: Vec<String> = shlex::split(rest)
+        .unwrap_or_else(|| rest.split_whitespace().map(|s| s.to_string()).collect());
+    let mut used = vec![false; args.len()];
+    let mut output = String::with_capacity(value.len() + rest.len());
+    let mut remaining = value;
+    let mut found_placeholder = false;
+
+    while let Some(open) = remaining.find('{') {
+        let after = &remaining[open + 1..];
+        let index = after
+            .find('}')
+            .and_then(|close| after[..close].parse::<usize>().ok().map(|n| (n, close)));
+
+        match index {
+            Some((n, close)) if n >= 1 => {
+                found_placeholder = true;
+                output.push_str(&remaining[..open]);
+                if let Some(arg) = args.get(n - 1) {
+                    used[n - 1] = true;
+                    output.push_str(&quote_word(arg));
+                }
+                remaining = &after[close + 1..];
+            }
+            _ => {
+                output.push_str(&remaining[..=open]);
+                remaining = after;
+            }
+        }
+    }
+    output.push_str(remaining);
+
+    if !found_placeholder {
+        return format!("{}{}", value, rest);
     }
+
+    for (arg, _) in args.iter().zip(&used).filter(|(_, used)| !**used) {
+        output.push(' ');
+        output.push_str(&quote_word(arg));
+    }
+    output
 }
 
 // -----------------------------------------------------------------------------
@@ -371,6 +559,8 @@ pub fn split_logical_operators(input: &str) -> Vec<LogicalPart> {
 
     let mut in_single_quote = false;
     let mut in_double_quote = false;
+    // Profundidade de grupos `( ... )`: operadores lá dentro são do subshell
+    let mut depth = 0usize;
 
     let mut chars = input.chars().peekable();
 
@@ -388,8 +578,14 @@ pub fn split_logical_operators(input: &str) -> Vec<LogicalPart> {
                 }
                 current_part.push(c);
             }
+            '(' | ')' => {
+                if !in_single_quote && !in_double_quote {
+                    depth = if c == '(' { depth + 1 } else { depth.saturating_sub(1) };
+                }
+                current_part.push(c);
+            }
             '&' => {
-                if !in_single_quote && !in_double_quote
+                if !in_single_quote && !in_double_quote && depth == 0
                     && let Some(&'&') = chars.peek() {
                         if !current_part.trim().is_empty() {
                             parts.push(LogicalPart {
@@ -404,7 +600,7 @@ pub fn split_logical_operators(input: &str) -> Vec<LogicalPart> {
                 current_part.push(c);
             }
             '|' => {
-                if !in_single_quote && !in_double_quote
+                if !in_single_quote && !in_double_quote && depth == 0
                     && let Some(&'|') = chars.peek() {
                         if !current_part.trim().is_empty() {
                             parts.push(LogicalPart {
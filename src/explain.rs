@@ -0,0 +1,39 @@
+//! # Explain Module
+//!
+//! Explicação curta dos códigos de saída comuns (opção `explain_errors` da
+//! seção `[behavior]`).
+//!
+//! Depois de um comando que falhou, a shell imprime uma linha como
+//! `↳ código 127: comando não encontrado (verifique o nome e o PATH)`.
+//! Códigos sem significado fixo (como o `1`) não geram explicação.
+
+use crate::t;
+use nix::sys::signal::Signal;
+
+/// Códigos acima deste valor indicam morte por sinal (128 + número do sinal).
+const SIGNAL_EXIT_BASE: i32 = 128;
+
+/// Explicação de um código de saída, se ele tiver um significado conhecido.
+pub fn explain_exit_code(code: i32) -> Option<String> {
+    let text = match code {
+        2 => t!("explain.misuse").to_string(),
+        126 => t!("explain.not_executable").to_string(),
+        127 => t!("explain.not_found").to_string(),
+        _ if code > SIGNAL_EXIT_BASE => explain_signal(code - SIGNAL_EXIT_BASE)?,
+        _ => return None,
+    };
+    Some(t!("explain.line", code, text))
+}
+
+/// Explicação da morte por um sinal.
+fn explain_signal(number: i32) -> Option<String> {
+    let signal = Signal::try_from(number).ok()?;
+    Some(match signal {
+        Signal::SIGINT => t!("explain.sigint").to_string(),
+        Signal::SIGKILL => t!("explain.sigkill").to_string(),
+        Signal::SIGSEGV => t!("explain.sigsegv").to_string(),
+        Signal::SIGPIPE => t!("explain.sigpipe").to_string(),
+        Signal::SIGTERM => t!("explain.sigterm").to_string(),
+        other => t!("explain.signal", other.as_str()),
+    })
+}
@@ -0,0 +1,413 @@
+//! # Fetch Module
+//!
+//! Builtin `fetch`: requisições HTTP rápidas sem lembrar as flags do `curl`.
+//!
+//! - `fetch api.github.com/repos/rust-lang/rust` - GET; JSON sai indentado e
+//!   colorido (na ordem em que veio, sem reordenar as chaves)
+//! - `fetch -X POST -d @body.json -H 'Authorization: Bearer x' <url>`
+//! - `fetch https://exemplo.com/imagem.iso` - respostas binárias vão para um
+//!   arquivo no diretório atual, com linha de progresso; `-o` escolhe o nome
+//!
+//! URLs sem esquema ganham `https://` (`http://` para `localhost` e IPs de
+//! loopback). Status fora de 2xx dá código de saída 1, mas o corpo ainda é
+//! mostrado: APIs costumam explicar o erro nele.
+
+use crate::archive::{human_size, Progress, ProgressReader};
+use crate::registry::usage;
+use crate::signals::{take_interrupt, EXIT_INTERRUPTED};
+use crate::t;
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{CONTENT_DISPOSITION, CONTENT_LENGTH, CONTENT_TYPE};
+use reqwest::Method;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Cores do JSON: chaves, strings, números e `true`/`false`/`null`.
+const KEY_COLOR: &str = "\x1b[1;34m";
+const STRING_COLOR: &str = "\x1b[32m";
+const NUMBER_COLOR: &str = "\x1b[33m";
+const LITERAL_COLOR: &str = "\x1b[35m";
+const RESET: &str = "\x1b[0m";
+
+/// Opções do `fetch`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FetchOptions {
+    pub url: String,
+    /// `-X`; sem ele, GET (POST quando há `-d`)
+    pub method: Option<String>,
+    pub headers: Vec<(String, String)>,
+    /// `-d`: texto, `@arquivo` ou `@-` (STDIN)
+    pub data: Option<String>,
+    /// `-o`: grava o corpo nesse arquivo, seja texto ou binário
+    pub output: Option<String>,
+    /// `-i`: mostra status e cabeçalhos
+    pub include: bool,
+}
+
+/// Interpreta os argumentos do `fetch`.
+pub fn parse_fetch_args(args: &[String]) -> Result<FetchOptions, String> {
+    let mut opts = FetchOptions::default();
+    let mut url = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| args.next().cloned().ok_or_else(|| t!("args.requires_value", flag));
+        match arg.as_str() {
+            "-X" | "--request" => opts.method = Some(value(arg)?.to_ascii_uppercase()),
+            "-d" | "--data" => opts.data = Some(value(arg)?),
+            "-o" | "--output" => opts.output = Some(value(arg)?),
+            "-H" | "--header" => {
+                let header = value(arg)?;
+                let parsed = parse_header(&header).ok_or_else(|| t!("fetch.invalid_header", header))?;
+                opts.headers.push(parsed);
+            }
+            "-i" | "--include" => opts.include = true,
+            flag if flag.starts_with('-') => return Err(t!("args.unknown_option", flag)),
+            _ if url.is_some() => return Err(t!("args.extra_argument", arg)),
+            _ => url = Some(arg.clone()),
+        }
+    }
+    opts.url = normalize_url(&url.ok_or(t!("fetch.missing_url"))?);
+    Ok(opts)
+}
+
+/// `Nome: valor` -> (`Nome`, `valor`).
+pub fn parse_header(text: &str) -> Option<(String, String)> {
+    let (name, value) = text.split_once(':')?;
+    let name = name.trim();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return None;
+    }
+    Some((name.to_string(), value.trim().to_string()))
+}
+
+/// Completa o esquema: `https://` em geral, `http://` para endereços locais.
+pub fn normalize_url(url: &str) -> String {
+    if url.contains("://") {
+        return url.to_string();
+    }
+    let host = url.split(['/', '?', '#']).next().unwrap_or("");
+    let local = ["localhost", "127.0.0.1", "0.0.0.0", "[::1]"]
+        .iter()
+        .any(|l| host == *l || host.strip_prefix(l).is_some_and(|rest| rest.starts_with(':')));
+    format!("{}://{}", if local { "http" } else { "https" }, url)
+}
+
+// -----------------------------------------------------------------------------
+// RESPONSE BODY
+// -----------------------------------------------------------------------------
+
+/// `Content-Type` que pode ir para o terminal como texto.
+pub fn is_text_type(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+    mime.starts_with("text/")
+        || ["json", "xml", "javascript", "yaml", "x-www-form-urlencoded"]
+            .iter()
+            .any(|kind| mime.contains(kind))
+}
+
+/// Sem `Content-Type`, olha o começo do corpo: NUL ou UTF-8 inválido (fora um
+/// caractere cortado no fim da amostra) é binário.
+pub fn looks_binary(sample: &[u8]) -> bool {
+    sample.contains(&0) || std::str::from_utf8(sample).is_err_and(|e| e.error_len().is_some())
+}
+
+/// JSON indentado com 2 espaços e, com `color`, colorido. A formatação é feita
+/// sobre o texto, então a ordem das chaves e os números ficam como vieram.
+/// `None` se `text` não é JSON válido.
+pub fn pretty_json(text: &str, color: bool) -> Option<String> {
+    serde_json::from_str::<serde::de::IgnoredAny>(text).ok()?;
+
+    let paint = |out: &mut String, token: &str, code: &str| {
+        if color {
+            out.push_str(code);
+            out.push_str(token);
+            out.push_str(RESET);
+        } else {
+            out.push_str(token);
+        }
+    };
+    let newline = |out: &mut String, depth: usize| {
+        out.push('\n');
+        out.push_str(&"  ".repeat(depth));
+    };
+
+    let mut out = String::new();
+    // true = objeto, false = array
+    let mut stack: Vec<bool> = Vec::new();
+    let mut expect_key = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                let mut token = String::from('"');
+                let mut escaped = false;
+                for c in chars.by_ref() {
+                    token.push(c);
+                    if escaped {
+                        escaped = false;
+                    } else if c == '\\' {
+                        escaped = true;
+                    } else if c == '"' {
+                        break;
+                    }
+                }
+                paint(&mut out, &token, if expect_key { KEY_COLOR } else { STRING_COLOR });
+            }
+            '{' | '[' => {
+                out.push(c);
+                while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                    chars.next();
+                }
+                let close = if c == '{' { '}' } else { ']' };
+                if chars.peek() == Some(&close) {
+                    out.push(close);
+                    chars.next();
+                    continue;
+                }
+                stack.push(c == '{');
+                expect_key = c == '{';
+                newline(&mut out, stack.len());
+            }
+            '}' | ']' => {
+                stack.pop();
+                newline(&mut out, stack.len());
+                out.push(c);
+                expect_key = false;
+            }
+            ',' => {
+                out.push(',');
+                newline(&mut out, stack.len());
+                expect_key = stack.last() == Some(&true);
+            }
+            ':' => {
+                out.push_str(": ");
+                expect_key = false;
+            }
+            c if c.is_whitespace() => {}
+            _ => {
+                let mut token = String::from(c);
+                while let Some(&next) = chars.peek() {
+                    if matches!(next, ',' | '}' | ']' | ':') || next.is_whitespace() {
+                        break;
+                    }
+                    token.push(next);
+                    chars.next();
+                }
+                let code = if c == '-' || c.is_ascii_digit() { NUMBER_COLOR } else { LITERAL_COLOR };
+                paint(&mut out, &token, code);
+            }
+        }
+    }
+    Some(out)
+}
+
+// -----------------------------------------------------------------------------
+// DOWNLOADS
+// -----------------------------------------------------------------------------
+
+/// Nome do arquivo de um download: o `filename` do `Content-Disposition` ou o
+/// último trecho do caminho da URL (`download` se não houver).
+pub fn download_name(url: &str, disposition: Option<&str>) -> String {
+    let from_header = disposition.and_then(|d| {
+        d.split(';').map(str::trim).find_map(|part| {
+            let value = part.strip_prefix("filename*=").or_else(|| part.strip_prefix("filename="))?;
+            let value = value.strip_prefix("UTF-8''").unwrap_or(value);
+            Some(value.trim_matches('"').to_string())
+        })
+    });
+    let from_url = || {
+        let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+        let path = rest.split(['?', '#']).next().unwrap_or("");
+        path.split_once('/').map(|(_, path)| path.rsplit('/').next().unwrap_or("").to_string())
+    };
+    // Só o nome: um `filename="../../x"` não escolhe onde gravar
+    [from_header, from_url()]
+        .into_iter()
+        .flatten()
+        .find_map(|name| {
+            let name = Path::new(&name).file_name()?.to_string_lossy().into_owned();
+            (!name.is_empty()).then_some(name)
+        })
+        .unwrap_or_else(|| "download".to_string())
+}
+
+/// `nome`, ou `nome.1`, `nome.2`... se já existir (sem `-o`, nada é sobrescrito).
+fn unique_path(name: &str) -> PathBuf {
+    let mut path = PathBuf::from(name);
+    let mut n = 1;
+    while path.exists() {
+        path = PathBuf::from(format!("{}.{}", name, n));
+        n += 1;
+    }
+    path
+}
+
+/// Grava `reader` em `path` com a linha de progresso; um download que falhou
+/// não deixa arquivo pela metade.
+fn save(reader: impl Read, path: &Path, total: u64) -> io::Result<u64> {
+    let mut progress = Progress::new("fetch", total);
+    let result = File::create(path).and_then(|file| {
+        let mut out = BufWriter::new(file);
+        let written = io::copy(&mut ProgressReader { inner: reader, progress: &mut progress }, &mut out)?;
+        out.flush()?;
+        Ok(written)
+    });
+    progress.finish();
+    if result.is_err() {
+        let _ = fs::remove_file(path);
+    }
+    result
+}
+
+// -----------------------------------------------------------------------------
+// BUILTIN
+// -----------------------------------------------------------------------------
+
+/// Corpo do `-d` (`@arquivo` lê o arquivo, `@-` o STDIN).
+fn read_data(data: &str) -> io::Result<Vec<u8>> {
+    match data.strip_prefix('@') {
+        Some("-") => {
+            let mut body = Vec::new();
+            io::stdin().read_to_end(&mut body)?;
+            Ok(body)
+        }
+        Some(path) => fs::read(path),
+        None => Ok(data.as_bytes().to_vec()),
+    }
+}
+
+fn send(opts: &FetchOptions, body: Option<Vec<u8>>) -> Result<Response, String> {
+    let client = Client::builder()
+        .user_agent(concat!("clios-shell/", env!("CARGO_PKG_VERSION")))
+        .connect_timeout(CONNECT_TIMEOUT)
+        // Downloads grandes não podem cair no timeout padrão de 30s
+        .timeout(None)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let method = match &opts.method {
+        Some(name) => Method::from_bytes(name.as_bytes()).map_err(|_| t!("fetch.invalid_method", name))?,
+        None if body.is_some() => Method::POST,
+        None => Method::GET,
+    };
+    let mut request = client.request(method, &opts.url);
+    for (name, value) in &opts.headers {
+        request = request.header(name, value);
+    }
+    if let Some(body) = body {
+        let has_type = opts.headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("content-type"));
+        if !has_type {
+            let json = serde_json::from_slice::<serde::de::IgnoredAny>(&body).is_ok();
+            request = request.header(CONTENT_TYPE, if json { "application/json" } else { "application/x-www-form-urlencoded" });
+        }
+        request = request.body(body);
+    }
+    request.send().map_err(|e| e.to_string())
+}
+
+fn print_head(response: &Response) {
+    println!("\x1b[1m{:?} {}\x1b[0m", response.version(), response.status());
+    for (name, value) in response.headers() {
+        println!("\x1b[36m{}\x1b[0m: {}", name, String::from_utf8_lossy(value.as_bytes()));
+    }
+    println!();
+}
+
+/// Erro de leitura do corpo: 130 se foi o Ctrl+C.
+fn report_error(e: io::Error) -> i32 {
+    if take_interrupt() {
+        eprintln!();
+        return EXIT_INTERRUPTED;
+    }
+    eprintln!("fetch: {}", e);
+    1
+}
+
+/// Builtin `fetch [-X método] [-H 'k: v']... [-d dados|@arquivo] [-o arquivo] [-i] <url>`.
+pub fn handle_fetch(tokens: &[String]) -> i32 {
+    let opts = match parse_fetch_args(&tokens[1..]) {
+        Ok(opts) => opts,
+        Err(e) => {
+            eprintln!("fetch: {}", e);
+            eprintln!("{}", usage("fetch"));
+            return 2;
+        }
+    };
+    let body = match opts.data.as_deref().map(read_data).transpose() {
+        Ok(body) => body,
+        Err(e) => {
+            eprintln!("fetch: {}: {}", opts.data.as_deref().unwrap_or(""), e);
+            return 1;
+        }
+    };
+    let response = match send(&opts, body) {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("fetch: {}", e);
+            return 1;
+        }
+    };
+
+    let ok = response.status().is_success();
+    if opts.include {
+        print_head(&response);
+    } else if !ok {
+        eprintln!("\x1b[1;31mHTTP {}\x1b[0m", response.status());
+    }
+    let status = if ok { 0 } else { 1 };
+    let header = |name| response.headers().get(name).and_then(|v| v.to_str().ok()).map(String::from);
+    let content_type = header(CONTENT_TYPE);
+    let disposition = header(CONTENT_DISPOSITION);
+    let total = header(CONTENT_LENGTH).and_then(|len| len.parse().ok()).unwrap_or(0);
+
+    if let Some(output) = &opts.output {
+        return match save(response, Path::new(output), total) {
+            Ok(bytes) => {
+                eprintln!("{}", t!("fetch.saved", output, human_size(bytes)));
+                status
+            }
+            Err(e) => report_error(e),
+        };
+    }
+
+    let mut reader = BufReader::new(response);
+    let text = match content_type.as_deref() {
+        Some(content_type) => is_text_type(content_type),
+        None => match reader.fill_buf() {
+            Ok(sample) => !looks_binary(sample),
+            Err(e) => return report_error(e),
+        },
+    };
+
+    if !text {
+        // Uma página de erro binária não vira arquivo
+        if !ok {
+            return status;
+        }
+        let path = unique_path(&download_name(&opts.url, disposition.as_deref()));
+        return match save(reader, &path, total) {
+            Ok(bytes) => {
+                eprintln!("{}", t!("fetch.saved", path.display(), human_size(bytes)));
+                0
+            }
+            Err(e) => report_error(e),
+        };
+    }
+
+    let mut bytes = Vec::new();
+    let mut progress = Progress::hidden();
+    if let Err(e) = (ProgressReader { inner: reader, progress: &mut progress }).read_to_end(&mut bytes) {
+        return report_error(e);
+    }
+    let body = String::from_utf8_lossy(&bytes);
+    let json = content_type.as_deref().is_some_and(|t| t.contains("json")) || body.trim_start().starts_with(['{', '[']);
+    match json.then(|| pretty_json(&body, io::stdout().is_terminal())).flatten() {
+        Some(pretty) => println!("{}", pretty),
+        None if body.is_empty() || body.ends_with('\n') => print!("{}", body),
+        None => println!("{}", body),
+    }
+    status
+}
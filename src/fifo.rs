@@ -0,0 +1,355 @@
+//! # Fifo Module
+//!
+//! Named pipes (FIFOs) sem decorar `mkfifo`/`cat`:
+//!
+//! - `mkfifo [-m modo] caminho...` - cria FIFOs em qualquer lugar
+//! - `pipe send nome [comando...]` / `pipe recv nome [comando...]` - FIFOs com
+//!   nome guardadas em [`pipes_dir`], para duas sessões da Clios trocarem
+//!   dados: `make 2>&1 | pipe send build` em um terminal e
+//!   `pipe recv build grep error` em outro
+//!
+//! A FIFO é criada por quem chegar primeiro. A abertura bloqueia até o outro
+//! lado conectar, e acontece no processo filho (não na shell), então Ctrl+C
+//! desiste da espera normalmente.
+
+use crate::executor::{self, ProcessGroup};
+use crate::pipeline::spawn_pipeline_capture;
+use crate::registry::usage;
+use crate::signals::EXIT_INTERRUPTED;
+use crate::t;
+use nix::fcntl::{self, OFlag};
+use nix::sys::stat::Mode;
+use nix::unistd::{dup2_stdin, dup2_stdout};
+use std::env;
+use std::ffi::CString;
+use std::fs;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Permissões padrão de uma FIFO nova (antes da umask).
+pub const DEFAULT_MODE: u32 = 0o666;
+
+// -----------------------------------------------------------------------------
+// FIFOS
+// -----------------------------------------------------------------------------
+
+/// Diretório das pipes com nome (`$XDG_DATA_HOME/clios/pipes`).
+pub fn pipes_dir() -> PathBuf {
+    let data_home = env::var("XDG_DATA_HOME")
+        .ok()
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            env::var("HOME")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from("."))
+                .join(".local/share")
+        });
+    data_home.join("clios/pipes")
+}
+
+/// Valida o nome (vira nome de arquivo, então nada de `/` ou `..`).
+pub fn valid_pipe_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.')
+        && !name.starts_with('.')
+}
+
+/// O caminho existe e é uma FIFO?
+pub fn is_fifo(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_fifo())
+}
+
+/// Cria uma FIFO em `path` com `mode` (a umask se aplica).
+pub fn make_fifo(path: &Path, mode: u32) -> io::Result<()> {
+    nix::unistd::mkfifo(path, Mode::from_bits_truncate(mode as _)).map_err(io::Error::from)
+}
+
+/// Caminho da pipe `name`, criando o diretório e a FIFO se ainda não existem.
+pub fn ensure_pipe(name: &str) -> io::Result<PathBuf> {
+    if !valid_pipe_name(name) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, t!("pipe.invalid_name", name)));
+    }
+    let dir = pipes_dir();
+    // Só o dono lê e escreve nas pipes
+    fs::DirBuilder::new().recursive(true).mode(0o700).create(&dir)?;
+    let path = dir.join(name);
+    if !path.exists() {
+        match make_fifo(&path, 0o600) {
+            // Outra sessão criou ao mesmo tempo
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+            result => result?,
+        }
+    }
+    if !is_fifo(&path) {
+        return Err(io::Error::other(t!("pipe.not_fifo", path.display())));
+    }
+    Ok(path)
+}
+
+/// Pipes com nome existentes, em ordem alfabética.
+pub fn list_pipes() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(pipes_dir()) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_fifo()))
+        .filter_map(|e| e.file_name().into_string().ok())
+        .collect();
+    names.sort();
+    names
+}
+
+// -----------------------------------------------------------------------------
+// COMMANDS
+// -----------------------------------------------------------------------------
+
+/// Lado da FIFO ligado ao comando.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    /// O STDOUT do comando vai para a FIFO
+    Send,
+    /// O STDIN do comando vem da FIFO
+    Recv,
+}
+
+/// Monta `tokens` com o STDOUT (`Send`) ou o STDIN (`Recv`) ligado à FIFO.
+///
+/// A FIFO é aberta no filho, antes do `exec`: a espera pelo outro lado não
+/// trava a shell e o Ctrl+C interrompe o comando como qualquer outro.
+pub fn fifo_command(tokens: &[String], path: &Path, direction: Direction) -> io::Result<Command> {
+    let path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let mut command = executor::command(tokens, ProcessGroup::Shell);
+    // SAFETY: depois do fork só há chamadas de sistema (open, dup2, close),
+    // sem alocação; o caminho foi convertido antes.
+    unsafe {
+        command.pre_exec(move || {
+            let flags = match direction {
+                Direction::Send => OFlag::O_WRONLY,
+                Direction::Recv => OFlag::O_RDONLY,
+            };
+            let fd = fcntl::open(path.as_c_str(), flags, Mode::empty())?;
+            match direction {
+                Direction::Send => dup2_stdout(&fd)?,
+                Direction::Recv => dup2_stdin(&fd)?,
+            }
+            Ok(())
+        });
+    }
+    Ok(command)
+}
+
+/// Executa `tokens` ligado à FIFO; `input` é o STDIN do `send` (saída dos
+/// estágios anteriores de uma pipeline).
+pub fn run_fifo_command(tokens: &[String], path: &Path, direction: Direction, input: Option<Stdio>) -> i32 {
+    let mut command = match fifo_command(tokens, path, direction) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("pipe: {}", e);
+            return 1;
+        }
+    };
+    if let Some(input) = input {
+        command.stdin(input);
+    }
+    let mut child = match executor::spawn(&mut command) {
+        Ok(child) => child,
+        // Ctrl+C enquanto o filho esperava o outro lado
+        Err(e) if e.error.kind() == io::ErrorKind::Interrupted => return EXIT_INTERRUPTED,
+        Err(e) => {
+            eprintln!("{}", t!("tag.error", e));
+            return e.exit_code();
+        }
+    };
+    child.wait().map(executor::exit_code).unwrap_or(1)
+}
+
+// -----------------------------------------------------------------------------
+// BUILTINS
+// -----------------------------------------------------------------------------
+
+/// Ação do builtin `pipe`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PipeAction {
+    /// `pipe send nome [comando...]` (padrão: `cat`)
+    Send { name: String, command: Vec<String> },
+    /// `pipe recv nome [comando...]` (padrão: `cat`)
+    Recv { name: String, command: Vec<String> },
+    /// `pipe list` (ou só `pipe`)
+    List,
+    /// `pipe rm nome...`
+    Remove(Vec<String>),
+    /// `pipe path nome`
+    Path(String),
+}
+
+/// Interpreta os argumentos do `pipe` (sem o nome do builtin).
+pub fn parse_pipe_args(args: &[String]) -> Result<PipeAction, String> {
+    let Some(action) = args.first() else {
+        return Ok(PipeAction::List);
+    };
+    let name = args.get(1).cloned();
+    let rest = args.get(2..).unwrap_or_default();
+    let command = if rest.is_empty() {
+        vec!["cat".to_string()]
+    } else {
+        rest.to_vec()
+    };
+
+    match (action.as_str(), name) {
+        ("list" | "ls", None) => Ok(PipeAction::List),
+        ("send", Some(name)) => Ok(PipeAction::Send { name, command }),
+        ("recv", Some(name)) => Ok(PipeAction::Recv { name, command }),
+        ("path", Some(name)) if rest.is_empty() => Ok(PipeAction::Path(name)),
+        ("rm", Some(_)) => Ok(PipeAction::Remove(args[1..].to_vec())),
+        ("send" | "recv" | "path" | "rm", None) => Err(t!("pipe.requires_name", action)),
+        _ => Err(t!("pipe.unknown_action", action)),
+    }
+}
+
+/// Builtin `pipe`; `input` é a saída dos estágios anteriores (`... | pipe send nome`).
+pub fn run_pipe(action: PipeAction, input: Option<Stdio>) -> i32 {
+    match action {
+        PipeAction::List => {
+            let names = list_pipes();
+            if names.is_empty() {
+                println!("{}", t!("pipe.none", pipes_dir().display()));
+            }
+            for name in names {
+                println!("{}", name);
+            }
+            0
+        }
+        PipeAction::Path(name) => match ensure_pipe(&name) {
+            Ok(path) => {
+                println!("{}", path.display());
+                0
+            }
+            Err(e) => {
+                eprintln!("pipe: {}", e);
+                1
+            }
+        },
+        PipeAction::Remove(names) => {
+            let mut code = 0;
+            for name in names {
+                let path = pipes_dir().join(&name);
+                if !valid_pipe_name(&name) || !is_fifo(&path) {
+                    eprintln!("{}", t!("pipe.not_found", name));
+                    code = 1;
+                } else if let Err(e) = fs::remove_file(&path) {
+                    eprintln!("pipe: {}: {}", name, e);
+                    code = 1;
+                }
+            }
+            code
+        }
+        PipeAction::Send { name, command } => match ensure_pipe(&name) {
+            Ok(path) => run_fifo_command(&command, &path, Direction::Send, input),
+            Err(e) => {
+                eprintln!("pipe: {}", e);
+                1
+            }
+        },
+        PipeAction::Recv { name, command } => match ensure_pipe(&name) {
+            Ok(path) => run_fifo_command(&command, &path, Direction::Recv, None),
+            Err(e) => {
+                eprintln!("pipe: {}", e);
+                1
+            }
+        },
+    }
+}
+
+/// `... | pipe send nome [comando...]`: a saída dos estágios anteriores
+/// (`source`) vai para a FIFO.
+pub fn send_pipeline(tokens: &[String], source: &[Vec<String>]) -> i32 {
+    let action = match parse_pipe_args(&tokens[1..]) {
+        Ok(action @ PipeAction::Send { .. }) => action,
+        Ok(_) | Err(_) => {
+            eprintln!("{}", usage("pipe"));
+            return 2;
+        }
+    };
+    let mut child = match spawn_pipeline_capture(source) {
+        Ok(Some(child)) => child,
+        Ok(None) => return 0,
+        Err(code) => return code,
+    };
+    let input = child.stdout.take().map(Stdio::from);
+    let code = run_pipe(action, input);
+    let _ = child.wait();
+    code
+}
+
+/// `pipe recv nome [comando...] | ...`: troca o primeiro estágio por
+/// `cat <fifo>` (mais o comando, se houver). `None` se não é um `pipe recv`.
+pub fn recv_stages(tokens: &[String]) -> Option<Result<Vec<Vec<String>>, String>> {
+    if tokens.first().map(|s| s.as_str()) != Some("pipe") || tokens.get(1).map(|s| s.as_str()) != Some("recv") {
+        return None;
+    }
+    let Some(name) = tokens.get(2) else {
+        return Some(Err(usage("pipe")));
+    };
+    Some(ensure_pipe(name).map_err(|e| format!("pipe: {}", e)).map(|path| {
+        let mut stages = vec![vec!["cat".to_string(), path.display().to_string()]];
+        if tokens.len() > 3 {
+            stages.push(tokens[3..].to_vec());
+        }
+        stages
+    }))
+}
+
+/// Builtin `pipe [ação] [nome] [comando...]`.
+pub fn handle_pipe(tokens: &[String], input: Option<Stdio>) -> i32 {
+    match parse_pipe_args(&tokens[1..]) {
+        Ok(action) => run_pipe(action, input),
+        Err(e) => {
+            eprintln!("pipe: {}", e);
+            eprintln!("{}", usage("pipe"));
+            2
+        }
+    }
+}
+
+/// Builtin `mkfifo [-m modo] caminho...`.
+pub fn handle_mkfifo(tokens: &[String]) -> i32 {
+    let mut args = &tokens[1..];
+    let mut explicit_mode = None;
+    if args.first().map(|s| s.as_str()) == Some("-m") {
+        match args.get(1).and_then(|m| u32::from_str_radix(m, 8).ok()) {
+            Some(m) if m <= 0o7777 => explicit_mode = Some(m),
+            _ => {
+                eprintln!("{}", usage("mkfifo"));
+                return 2;
+            }
+        }
+        args = &args[2..];
+    }
+    if args.is_empty() || args[0].starts_with('-') {
+        eprintln!("{}", usage("mkfifo"));
+        return 2;
+    }
+
+    let mut code = 0;
+    for path in args {
+        // Com `-m` o modo vale exatamente, sem a umask (como no coreutils)
+        let result = make_fifo(Path::new(path), explicit_mode.unwrap_or(DEFAULT_MODE)).and_then(|()| match explicit_mode {
+            Some(mode) => fs::set_permissions(path, fs::Permissions::from_mode(mode)),
+            None => Ok(()),
+        });
+        if let Err(e) = result {
+            eprintln!("mkfifo: {}: {}", path, e);
+            code = 1;
+        }
+    }
+    code
+}
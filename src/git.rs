@@ -0,0 +1,456 @@
+//! # Git Module
+//!
+//! Detecção do repositório (com cache, usada também pelo prompt) e os
+//! builtins `gstatus`, `gbranches` e `glog`, que mostram a saída do git em
+//! tabelas alinhadas e coloridas (ou em JSON com `--json`).
+//!
+//! A raiz do repositório é procurada subindo a partir do diretório atual até
+//! achar um `.git` (diretório, ou arquivo em worktrees e submódulos) e fica
+//! guardada enquanto o diretório não mudar: fora de um repositório, o prompt
+//! não executa o `git` a cada linha.
+
+use crate::executor::{self, ProcessGroup};
+use crate::registry::usage;
+use crate::t;
+use serde::Serialize;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Mutex;
+
+/// Commits mostrados pelo `glog` sem `-n`.
+pub const DEFAULT_LOG_COUNT: usize = 15;
+
+/// Último diretório consultado e a raiz do repositório dele.
+static REPO: Mutex<Option<(PathBuf, Option<PathBuf>)>> = Mutex::new(None);
+
+// -----------------------------------------------------------------------------
+// REPOSITORY DETECTION
+// -----------------------------------------------------------------------------
+
+/// Raiz do repositório que contém `dir`, subindo pelos diretórios pais.
+pub fn find_repo_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|candidate| candidate.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Raiz do repositório do diretório atual (recalculada só quando ele muda).
+pub fn current_repo() -> Option<PathBuf> {
+    let cwd = env::current_dir().ok()?;
+    let mut cache = REPO.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((dir, root)) = cache.as_ref()
+        && *dir == cwd
+    {
+        return root.clone();
+    }
+    let root = find_repo_root(&cwd);
+    *cache = Some((cwd, root.clone()));
+    root
+}
+
+/// Executa `git args...` e devolve o STDOUT (erros do git vão para o STDERR).
+fn git_output(args: &[&str]) -> Result<String, i32> {
+    let mut tokens = vec!["git".to_string()];
+    tokens.extend(args.iter().map(|a| a.to_string()));
+    let mut command = executor::command(&tokens, ProcessGroup::Shell);
+    command.stderr(Stdio::inherit());
+    let output = command.output().map_err(|e| {
+        eprintln!("{}", t!("tag.error", e));
+        1
+    })?;
+    if !output.status.success() {
+        return Err(executor::exit_code(output.status));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// -----------------------------------------------------------------------------
+// PARSING
+// -----------------------------------------------------------------------------
+
+/// Arquivo alterado (`git status --porcelain`).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileStatus {
+    /// Estado no índice (coluna X: `M`, `A`, `D`, `R`, `?`...)
+    pub index: char,
+    /// Estado na árvore de trabalho (coluna Y)
+    pub worktree: char,
+    pub path: String,
+    /// Caminho anterior de um arquivo renomeado ou copiado
+    pub from: Option<String>,
+}
+
+impl FileStatus {
+    pub fn is_untracked(&self) -> bool {
+        self.index == '?'
+    }
+
+    /// Conflito de merge (`UU`, `AA`, `DD`...).
+    pub fn is_conflict(&self) -> bool {
+        self.index == 'U'
+            || self.worktree == 'U'
+            || (self.index == 'A' && self.worktree == 'A')
+            || (self.index == 'D' && self.worktree == 'D')
+    }
+}
+
+/// Estado do repositório.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RepoStatus {
+    pub branch: Option<String>,
+    pub upstream: Option<String>,
+    pub ahead: u32,
+    pub behind: u32,
+    pub files: Vec<FileStatus>,
+}
+
+/// Interpreta `git status --porcelain=v1 --branch`.
+pub fn parse_status(output: &str) -> RepoStatus {
+    let mut status = RepoStatus::default();
+    for line in output.lines() {
+        if let Some(header) = line.strip_prefix("## ") {
+            parse_branch_header(header, &mut status);
+            continue;
+        }
+        let mut chars = line.chars();
+        let (Some(index), Some(worktree)) = (chars.next(), chars.next()) else {
+            continue;
+        };
+        let Some(rest) = line.get(3..) else {
+            continue;
+        };
+        let (from, path) = match rest.split_once(" -> ") {
+            Some((from, to)) => (Some(unquote(from)), unquote(to)),
+            None => (None, unquote(rest)),
+        };
+        status.files.push(FileStatus { index, worktree, path, from });
+    }
+    status
+}
+
+/// `main...origin/main [ahead 1, behind 2]`, `HEAD (no branch)`, `No commits yet on main`.
+fn parse_branch_header(header: &str, status: &mut RepoStatus) {
+    let header = header.strip_prefix("No commits yet on ").unwrap_or(header);
+    let (names, track) = match header.split_once(" [") {
+        Some((names, track)) => (names, track.trim_end_matches(']')),
+        None => (header, ""),
+    };
+    let (branch, upstream) = match names.split_once("...") {
+        Some((branch, upstream)) => (branch, Some(upstream.to_string())),
+        None => (names, None),
+    };
+    status.branch = (!branch.starts_with("HEAD")).then(|| branch.to_string());
+    status.upstream = upstream;
+    for part in track.split(", ") {
+        if let Some(n) = part.strip_prefix("ahead ") {
+            status.ahead = n.parse().unwrap_or(0);
+        } else if let Some(n) = part.strip_prefix("behind ") {
+            status.behind = n.parse().unwrap_or(0);
+        }
+    }
+}
+
+/// Caminhos com espaços ou acentos vêm entre aspas no porcelain.
+fn unquote(path: &str) -> String {
+    match path.strip_prefix('"').and_then(|p| p.strip_suffix('"')) {
+        Some(inner) => inner.replace("\\\"", "\"").replace("\\\\", "\\"),
+        None => path.to_string(),
+    }
+}
+
+/// Uma branch local (`git for-each-ref`).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Branch {
+    pub name: String,
+    /// Branch em uso (`HEAD`)
+    pub current: bool,
+    pub upstream: Option<String>,
+    /// `ahead 1, behind 2`, `gone`...
+    pub track: Option<String>,
+    /// Data do último commit, relativa (`2 days ago`)
+    pub date: String,
+    pub subject: String,
+}
+
+/// Formato do `for-each-ref` lido por [`parse_branches`] (campos separados por NUL).
+pub const BRANCH_FORMAT: &str =
+    "%(HEAD)%00%(refname:short)%00%(upstream:short)%00%(upstream:track,nobracket)%00%(committerdate:relative)%00%(subject)";
+
+pub fn parse_branches(output: &str) -> Vec<Branch> {
+    let non_empty = |s: &str| (!s.is_empty()).then(|| s.to_string());
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\0').collect();
+            let [head, name, upstream, track, date, subject] = fields.as_slice() else {
+                return None;
+            };
+            Some(Branch {
+                name: name.to_string(),
+                current: *head == "*",
+                upstream: non_empty(upstream),
+                track: non_empty(track),
+                date: date.to_string(),
+                subject: subject.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Um commit (`git log`).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Commit {
+    pub hash: String,
+    pub author: String,
+    /// Data relativa (`3 hours ago`)
+    pub date: String,
+    /// Branches e tags que apontam para o commit
+    pub refs: Vec<String>,
+    pub subject: String,
+}
+
+/// Formato do `git log` lido por [`parse_log`] (campos separados por NUL).
+pub const LOG_FORMAT: &str = "%h%x00%an%x00%ar%x00%D%x00%s";
+
+pub fn parse_log(output: &str) -> Vec<Commit> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\0').collect();
+            let [hash, author, date, refs, subject] = fields.as_slice() else {
+                return None;
+            };
+            Some(Commit {
+                hash: hash.to_string(),
+                author: author.to_string(),
+                date: date.to_string(),
+                refs: refs.split(", ").filter(|r| !r.is_empty()).map(String::from).collect(),
+                subject: subject.to_string(),
+            })
+        })
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
+// TABLES
+// -----------------------------------------------------------------------------
+
+/// Célula da tabela: texto e cor ANSI (`"1;32"`, ou vazio).
+type Cell = (String, &'static str);
+
+/// Alinha as colunas; colunas vazias em todas as linhas somem.
+pub fn render_table(rows: &[Vec<Cell>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|c| {
+            rows.iter()
+                .filter_map(|row| row.get(c))
+                .map(|(text, _)| text.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut out = String::new();
+    for row in rows {
+        let mut line = String::new();
+        for (c, (text, color)) in row.iter().enumerate() {
+            if widths[c] == 0 {
+                continue;
+            }
+            let padding = widths[c] - text.chars().count() + 2;
+            if color.is_empty() {
+                line.push_str(text);
+            } else {
+                line.push_str(&format!("\x1b[{}m{}\x1b[0m", color, text));
+            }
+            line.push_str(&" ".repeat(padding));
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// Nome do estado de uma coluna do porcelain.
+fn state_name(code: char) -> &'static str {
+    match code {
+        'M' => t!("git.modified"),
+        'A' => t!("git.added"),
+        'D' => t!("git.deleted"),
+        'R' => t!("git.renamed"),
+        'C' => t!("git.copied"),
+        'T' => t!("git.typechange"),
+        _ => "",
+    }
+}
+
+fn status_rows(status: &RepoStatus) -> Vec<Vec<Cell>> {
+    status
+        .files
+        .iter()
+        .map(|file| {
+            let path = match &file.from {
+                Some(from) => format!("{} → {}", from, file.path),
+                None => file.path.clone(),
+            };
+            let code = format!("{}{}", file.index, file.worktree);
+            if file.is_conflict() {
+                return vec![(code, "1;31"), (t!("git.conflict").to_string(), "1;31"), (String::new(), ""), (path, "1;31")];
+            }
+            if file.is_untracked() {
+                return vec![(code, "2"), (t!("git.untracked").to_string(), "2"), (String::new(), ""), (path, "")];
+            }
+            vec![
+                (code, "1"),
+                (state_name(file.index).to_string(), "32"),
+                (state_name(file.worktree).to_string(), "31"),
+                (path, ""),
+            ]
+        })
+        .collect()
+}
+
+fn branch_line(status: &RepoStatus) -> String {
+    let mut line = format!(
+        "\x1b[1;35m{}\x1b[0m",
+        status.branch.clone().unwrap_or_else(|| t!("git.detached").to_string())
+    );
+    if let Some(upstream) = &status.upstream {
+        line.push_str(&format!(" → {}", upstream));
+    }
+    if status.ahead > 0 {
+        line.push_str(&format!(" \x1b[32m↑{}\x1b[0m", status.ahead));
+    }
+    if status.behind > 0 {
+        line.push_str(&format!(" \x1b[31m↓{}\x1b[0m", status.behind));
+    }
+    line
+}
+
+fn branch_rows(branches: &[Branch]) -> Vec<Vec<Cell>> {
+    branches
+        .iter()
+        .map(|b| {
+            let (marker, color) = if b.current { ("*", "1;32") } else { (" ", "") };
+            vec![
+                (marker.to_string(), color),
+                (b.name.clone(), color),
+                (b.upstream.clone().unwrap_or_default(), "36"),
+                (b.track.as_ref().map(|t| format!("[{}]", t)).unwrap_or_default(), "33"),
+                (b.date.clone(), "2"),
+                (b.subject.clone(), ""),
+            ]
+        })
+        .collect()
+}
+
+fn log_rows(commits: &[Commit]) -> Vec<Vec<Cell>> {
+    commits
+        .iter()
+        .map(|c| {
+            let subject = if c.refs.is_empty() {
+                c.subject.clone()
+            } else {
+                format!("({}) {}", c.refs.join(", "), c.subject)
+            };
+            vec![
+                (c.hash.clone(), "33"),
+                (c.date.clone(), "32"),
+                (c.author.clone(), "34"),
+                (subject, ""),
+            ]
+        })
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
+// BUILTINS
+// -----------------------------------------------------------------------------
+
+fn print_json<T: Serialize>(value: &T) -> i32 {
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => {
+            println!("{}", json);
+            0
+        }
+        Err(e) => {
+            eprintln!("{}", t!("tag.error", e));
+            1
+        }
+    }
+}
+
+/// Builtins `gstatus`, `gbranches` e `glog [-n N]`, todos com `--json`.
+pub fn handle_git_builtin(tokens: &[String]) -> i32 {
+    let name = tokens[0].as_str();
+    let mut json = false;
+    let mut count = DEFAULT_LOG_COUNT;
+    let mut args = tokens[1..].iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "-n" if name == "glog" => match args.next().and_then(|n| n.parse().ok()) {
+                Some(n) => count = n,
+                None => {
+                    eprintln!("{}", usage(name));
+                    return 2;
+                }
+            },
+            _ => {
+                eprintln!("{}", usage(name));
+                return 2;
+            }
+        }
+    }
+
+    if current_repo().is_none() {
+        eprintln!("{}", t!("git.not_repo", name));
+        return 128;
+    }
+
+    match name {
+        "gstatus" => {
+            let status = match git_output(&["status", "--porcelain=v1", "--branch"]) {
+                Ok(output) => parse_status(&output),
+                Err(code) => return code,
+            };
+            if json {
+                return print_json(&status);
+            }
+            println!("{}", branch_line(&status));
+            if status.files.is_empty() {
+                println!("\x1b[2m{}\x1b[0m", t!("git.clean"));
+            } else {
+                print!("{}", render_table(&status_rows(&status)));
+            }
+            0
+        }
+        "gbranches" => {
+            let format = format!("--format={}", BRANCH_FORMAT);
+            let branches = match git_output(&["for-each-ref", "--sort=-committerdate", &format, "refs/heads"]) {
+                Ok(output) => parse_branches(&output),
+                Err(code) => return code,
+            };
+            if json {
+                return print_json(&branches);
+            }
+            print!("{}", render_table(&branch_rows(&branches)));
+            0
+        }
+        _ => {
+            let count = format!("-n{}", count);
+            let format = format!("--format={}", LOG_FORMAT);
+            let commits = match git_output(&["log", &count, &format]) {
+                Ok(output) => parse_log(&output),
+                Err(code) => return code,
+            };
+            if json {
+                return print_json(&commits);
+            }
+            print!("{}", render_table(&log_rows(&commits)));
+            0
+        }
+    }
+}
@@ -0,0 +1,276 @@
+//! # Hashsum Module
+//!
+//! Builtin `hashsum`: checksums iguais em qualquer sistema, sem depender de
+//! qual `sha256sum`/`shasum`/`md5` o container trouxe.
+//!
+//! - `hashsum arquivo.iso` - SHA-256 (`-a md5|sha512|blake3` troca)
+//! - `hashsum -c SHA256SUMS` - confere uma lista gerada por `hashsum` ou pelo
+//!   `sha256sum` (`hash  nome`), inclusive no formato BSD
+//!   (`SHA256 (nome) = hash`)
+//!
+//! A saída segue o formato do coreutils, então a lista gerada aqui também é
+//! conferida pelo `sha256sum -c`.
+
+use crate::registry::usage;
+use crate::t;
+use md5::Md5;
+use sha2::{Digest, Sha256, Sha512};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+/// Algoritmos suportados.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Algorithm {
+    Md5,
+    Sha256,
+    Sha512,
+    Blake3,
+}
+
+impl Algorithm {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().replace('-', "").as_str() {
+            "md5" => Some(Algorithm::Md5),
+            "sha256" => Some(Algorithm::Sha256),
+            "sha512" => Some(Algorithm::Sha512),
+            "blake3" | "b3" => Some(Algorithm::Blake3),
+            _ => None,
+        }
+    }
+
+    /// Nome usado no formato BSD (`SHA256 (arquivo) = ...`).
+    pub fn tag(self) -> &'static str {
+        match self {
+            Algorithm::Md5 => "MD5",
+            Algorithm::Sha256 => "SHA256",
+            Algorithm::Sha512 => "SHA512",
+            Algorithm::Blake3 => "BLAKE3",
+        }
+    }
+
+    /// Algoritmo pelo tamanho do hash em hex (64 é SHA-256, não BLAKE3).
+    fn from_hex_len(len: usize) -> Option<Self> {
+        match len {
+            32 => Some(Algorithm::Md5),
+            64 => Some(Algorithm::Sha256),
+            128 => Some(Algorithm::Sha512),
+            _ => None,
+        }
+    }
+}
+
+enum Hasher {
+    Md5(Md5),
+    Sha256(Sha256),
+    Sha512(Sha512),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    fn new(algorithm: Algorithm) -> Self {
+        match algorithm {
+            Algorithm::Md5 => Hasher::Md5(Md5::new()),
+            Algorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            Algorithm::Sha512 => Hasher::Sha512(Sha512::new()),
+            Algorithm::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Md5(h) => h.update(data),
+            Hasher::Sha256(h) => h.update(data),
+            Hasher::Sha512(h) => h.update(data),
+            Hasher::Blake3(h) => {
+                h.update(data);
+            }
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        let bytes: Vec<u8> = match self {
+            Hasher::Md5(h) => h.finalize().to_vec(),
+            Hasher::Sha256(h) => h.finalize().to_vec(),
+            Hasher::Sha512(h) => h.finalize().to_vec(),
+            Hasher::Blake3(h) => h.finalize().as_bytes().to_vec(),
+        };
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+/// Hash em hex de tudo que `reader` produzir.
+pub fn hash_reader(algorithm: Algorithm, mut reader: impl Read) -> io::Result<String> {
+    let mut hasher = Hasher::new(algorithm);
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => hasher.update(&buf[..n]),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(hasher.finalize_hex())
+}
+
+/// Hash de um arquivo (`-` é o STDIN).
+pub fn hash_file(algorithm: Algorithm, path: &str) -> io::Result<String> {
+    if path == "-" {
+        return hash_reader(algorithm, io::stdin().lock());
+    }
+    if Path::new(path).is_dir() {
+        return Err(io::Error::other(t!("hashsum.is_dir")));
+    }
+    hash_reader(algorithm, File::open(path)?)
+}
+
+// -----------------------------------------------------------------------------
+// CHECK
+// -----------------------------------------------------------------------------
+
+/// Linha de uma lista de checksums.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SumLine {
+    /// Algoritmo indicado na linha (formato BSD)
+    pub algorithm: Option<Algorithm>,
+    pub hash: String,
+    pub file: String,
+}
+
+/// Interpreta `hash  nome`, `hash *nome` (modo binário) ou
+/// `SHA256 (nome) = hash`.
+pub fn parse_sum_line(line: &str) -> Option<SumLine> {
+    let line = line.trim_end_matches(['\r', '\n']);
+    // Formato BSD
+    if let Some((tag, rest)) = line.split_once(" (")
+        && let Some((file, hash)) = rest.rsplit_once(") = ")
+        && let Some(algorithm) = Algorithm::from_name(tag)
+    {
+        return Some(SumLine { algorithm: Some(algorithm), hash: hash.to_ascii_lowercase(), file: file.to_string() });
+    }
+    let (hash, file) = line.split_once(' ')?;
+    let file = file.strip_prefix([' ', '*'])?;
+    if hash.is_empty() || file.is_empty() || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(SumLine { algorithm: None, hash: hash.to_ascii_lowercase(), file: file.to_string() })
+}
+
+/// Confere a lista `sums`; devolve o código de saída (1 se algo não confere).
+fn check_sums(sums_path: &str, forced: Option<Algorithm>, quiet: bool) -> i32 {
+    let reader: Box<dyn BufRead> = if sums_path == "-" {
+        Box::new(BufReader::new(io::stdin()))
+    } else {
+        match File::open(sums_path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(e) => {
+                eprintln!("hashsum: {}: {}", sums_path, e);
+                return 1;
+            }
+        }
+    };
+
+    let (mut checked, mut failed, mut bad_lines) = (0, 0, 0);
+    for line in reader.lines().map_while(Result::ok) {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some(sum) = parse_sum_line(&line) else {
+            bad_lines += 1;
+            continue;
+        };
+        let Some(algorithm) = forced.or(sum.algorithm).or_else(|| Algorithm::from_hex_len(sum.hash.len())) else {
+            bad_lines += 1;
+            continue;
+        };
+        checked += 1;
+        match hash_file(algorithm, &sum.file) {
+            Ok(hash) if hash == sum.hash => {
+                if !quiet {
+                    println!("{}: \x1b[32m{}\x1b[0m", sum.file, t!("hashsum.ok"));
+                }
+            }
+            Ok(_) => {
+                failed += 1;
+                println!("{}: \x1b[1;31m{}\x1b[0m", sum.file, t!("hashsum.failed"));
+            }
+            Err(e) => {
+                failed += 1;
+                println!("{}: \x1b[1;31m{}\x1b[0m ({})", sum.file, t!("hashsum.failed"), e);
+            }
+        }
+    }
+
+    if bad_lines > 0 {
+        eprintln!("{}", t!("hashsum.bad_lines", bad_lines, sums_path));
+    }
+    if failed > 0 {
+        eprintln!("{}", t!("hashsum.summary", failed, checked));
+    }
+    if checked == 0 {
+        eprintln!("{}", t!("hashsum.nothing_checked", sums_path));
+        return 1;
+    }
+    i32::from(failed > 0)
+}
+
+// -----------------------------------------------------------------------------
+// BUILTIN
+// -----------------------------------------------------------------------------
+
+/// Builtin `hashsum [-a alg] [--tag] [arquivos...]` / `hashsum -c lista`.
+pub fn handle_hashsum(tokens: &[String]) -> i32 {
+    let mut algorithm = None;
+    let mut check = None;
+    let mut bsd_tag = false;
+    let mut quiet = false;
+    let mut files = Vec::new();
+    let mut args = tokens[1..].iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-a" | "--algorithm" => match args.next().and_then(|name| Algorithm::from_name(name)) {
+                Some(a) => algorithm = Some(a),
+                None => {
+                    eprintln!("{}", t!("hashsum.invalid_algorithm"));
+                    return 2;
+                }
+            },
+            "-c" | "--check" => match args.next() {
+                Some(list) => check = Some(list.clone()),
+                None => {
+                    eprintln!("{}", usage("hashsum"));
+                    return 2;
+                }
+            },
+            "--tag" => bsd_tag = true,
+            "-q" | "--quiet" => quiet = true,
+            "-" => files.push(arg.clone()),
+            flag if flag.starts_with('-') => {
+                eprintln!("{}", usage("hashsum"));
+                return 2;
+            }
+            _ => files.push(arg.clone()),
+        }
+    }
+
+    if let Some(list) = check {
+        return check_sums(&list, algorithm, quiet);
+    }
+    let algorithm = algorithm.unwrap_or(Algorithm::Sha256);
+    if files.is_empty() {
+        files.push("-".to_string());
+    }
+    let mut status = 0;
+    for file in &files {
+        match hash_file(algorithm, file) {
+            Ok(hash) if bsd_tag => println!("{} ({}) = {}", algorithm.tag(), file, hash),
+            Ok(hash) => println!("{}  {}", hash, file),
+            Err(e) => {
+                eprintln!("hashsum: {}: {}", file, e);
+                status = 1;
+            }
+        }
+    }
+    status
+}
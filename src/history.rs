@@ -0,0 +1,288 @@
+//! # History Module
+//!
+//! Metadados do histórico de comandos, o relatório `history stats` e a
+//! expansão do histórico no prompt (`!!`, `!$`).
+//!
+//! O arquivo de histórico do `rustyline` guarda apenas o texto dos comandos.
+//! Para estatísticas, cada comando interativo também é registrado em um arquivo
+//! irmão em JSON Lines (`<arquivo_do_historico>_meta.jsonl`) com horário,
+//! diretório e código de saída.
+
+use crate::quote::quote_word;
+use crate::redact::REDACTED;
+use crate::t;
+use chrono::{Local, TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Largura máxima das barras do relatório
+const BAR_WIDTH: usize = 30;
+
+/// Quantas linhas cada ranking exibe
+const TOP_N: usize = 10;
+
+// -----------------------------------------------------------------------------
+// HISTORY METADATA
+// -----------------------------------------------------------------------------
+
+/// Uma linha do arquivo de metadados.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HistoryEntry {
+    /// Linha digitada
+    pub cmd: String,
+    /// Horário (Unix epoch, segundos)
+    pub ts: i64,
+    /// Diretório onde o comando foi executado
+    pub cwd: String,
+    /// Código de saída
+    pub exit: i32,
+}
+
+/// Caminho do arquivo de metadados para um arquivo de histórico (relativo ao HOME).
+pub fn meta_path(history_file: &str) -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    Path::new(&home).join(format!("{}_meta.jsonl", history_file))
+}
+
+/// Acrescenta uma entrada ao arquivo de metadados (erros são ignorados).
+pub fn append_entry(path: &Path, entry: &HistoryEntry) {
+    if let Ok(line) = serde_json::to_string(entry)
+        && let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path)
+    {
+        let _ = writeln!(file, "{}", line);
+    }
+}
+
+/// Lê todas as entradas válidas (linhas corrompidas são puladas).
+pub fn load_entries(path: &Path) -> Vec<HistoryEntry> {
+    let Ok(file) = File::open(path) else {
+        return Vec::new();
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|l| serde_json::from_str(&l).ok())
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
+// STATISTICS
+// -----------------------------------------------------------------------------
+
+/// Estatísticas agregadas do histórico.
+#[derive(Debug, Default)]
+pub struct HistoryStats {
+    pub total: usize,
+    /// (comando, execuções) ordenado por execuções
+    pub top_commands: Vec<(String, usize)>,
+    /// Execuções por hora do dia (0..24)
+    pub by_hour: [usize; 24],
+    /// (comando, taxa de falha 0.0..1.0, execuções) ordenado pela taxa
+    pub failure_rates: Vec<(String, f64, usize)>,
+    /// (diretório, execuções) ordenado por execuções
+    pub top_dirs: Vec<(String, usize)>,
+}
+
+fn sorted_counts(map: HashMap<String, usize>) -> Vec<(String, usize)> {
+    let mut v: Vec<(String, usize)> = map.into_iter().collect();
+    v.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    v
+}
+
+/// Calcula as estatísticas a partir das entradas.
+pub fn compute_stats(entries: &[HistoryEntry]) -> HistoryStats {
+    let mut commands: HashMap<String, usize> = HashMap::new();
+    let mut failures: HashMap<String, usize> = HashMap::new();
+    let mut dirs: HashMap<String, usize> = HashMap::new();
+    let mut by_hour = [0usize; 24];
+
+    for entry in entries {
+        let name = entry.cmd.split_whitespace().next().unwrap_or("").to_string();
+        if name.is_empty() {
+            continue;
+        }
+        *commands.entry(name.clone()).or_default() += 1;
+        if entry.exit != 0 {
+            *failures.entry(name).or_default() += 1;
+        }
+        *dirs.entry(entry.cwd.clone()).or_default() += 1;
+
+        if let Some(time) = Local.timestamp_opt(entry.ts, 0).single() {
+            by_hour[time.hour() as usize] += 1;
+        }
+    }
+
+    let mut failure_rates: Vec<(String, f64, usize)> = commands
+        .iter()
+        .map(|(name, &runs)| {
+            let failed = failures.get(name).copied().unwrap_or(0);
+            (name.clone(), failed as f64 / runs as f64, runs)
+        })
+        .filter(|(_, rate, _)| *rate > 0.0)
+        .collect();
+    failure_rates.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    HistoryStats {
+        total: entries.len(),
+        top_commands: sorted_counts(commands),
+        by_hour,
+        failure_rates,
+        top_dirs: sorted_counts(dirs),
+    }
+}
+
+/// Desenha uma barra proporcional a `value / max`.
+pub fn bar(value: usize, max: usize) -> String {
+    if max == 0 {
+        return String::new();
+    }
+    let len = (value * BAR_WIDTH).div_ceil(max);
+    "█".repeat(len)
+}
+
+/// Imprime o relatório `history stats`.
+pub fn print_stats(history_file: &str) {
+    let path = meta_path(history_file);
+    let entries = load_entries(&path);
+    if entries.is_empty() {
+        println!("{}", t!("history.no_metadata", path.display()));
+        println!("{}", t!("history.tracking_from_now"));
+        return;
+    }
+
+    let stats = compute_stats(&entries);
+    println!("\x1b[1;36m=== {} ===\x1b[0m", t!("history.stats_title", stats.total));
+
+    println!("\n\x1b[1;32m{}\x1b[0m", t!("history.top_commands"));
+    let max = stats.top_commands.first().map(|c| c.1).unwrap_or(0);
+    for (name, count) in stats.top_commands.iter().take(TOP_N) {
+        println!("  {:<16} {:>5}  {}", name, count, bar(*count, max));
+    }
+
+    println!("\n\x1b[1;32m{}\x1b[0m", t!("history.busiest_hours"));
+    let max = stats.by_hour.iter().copied().max().unwrap_or(0);
+    for (hour, count) in stats.by_hour.iter().enumerate().filter(|(_, c)| **c > 0) {
+        println!("  {:02}h              {:>5}  {}", hour, count, bar(*count, max));
+    }
+
+    println!("\n\x1b[1;32m{}\x1b[0m", t!("history.failure_rates"));
+    if stats.failure_rates.is_empty() {
+        println!("  {}", t!("history.no_failures"));
+    }
+    for (name, rate, runs) in stats.failure_rates.iter().take(TOP_N) {
+        println!(
+            "  {:<16} {:>4.0}%  {} ({})",
+            name,
+            rate * 100.0,
+            bar((rate * 100.0) as usize, 100),
+            t!("history.runs", runs)
+        );
+    }
+
+    println!("\n\x1b[1;32m{}\x1b[0m", t!("history.by_directory"));
+    let home = std::env::var("HOME").unwrap_or_default();
+    let max = stats.top_dirs.first().map(|d| d.1).unwrap_or(0);
+    for (dir, count) in stats.top_dirs.iter().take(TOP_N) {
+        let short = if !home.is_empty() { dir.replacen(&home, "~", 1) } else { dir.clone() };
+        println!("  {:<30} {:>5}  {}", short, count, bar(*count, max));
+    }
+}
+
+// -----------------------------------------------------------------------------
+// HISTORY EXPANSION
+// -----------------------------------------------------------------------------
+
+/// Expande as referências ao histórico de uma linha digitada, como no bash:
+///
+/// - `!!` - o comando anterior
+/// - `!$` - a última palavra do comando anterior
+/// - `!n` / `!-n` - o comando `n` do histórico / o `n`-ésimo mais recente
+/// - `!texto` - o comando mais recente que começa com `texto`
+///
+/// `history` vai do mais antigo ao mais recente. `Ok(None)`: a linha não tem
+/// referências. Nada é expandido entre aspas simples, depois de `\` nem com
+/// o `!` seguido de espaço, `=` ou `(` (`[ ! -f x ]`, `a != b`).
+///
+/// Uma referência a um trecho gravado escondido (`***`, ver [`crate::redact`])
+/// é um erro: rodar o comando com `***` no lugar da credencial não serve.
+pub fn expand_history(line: &str, history: &[String]) -> Result<Option<String>, String> {
+    let mut out = String::with_capacity(line.len());
+    let mut expanded = false;
+    let mut in_single = false;
+    let mut in_double = false;
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if !in_single => {
+                out.push(c);
+                if let Some((_, next)) = chars.next() {
+                    out.push(next);
+                }
+                continue;
+            }
+            '\'' if !in_double => in_single = !in_single,
+            '"' if !in_single => in_double = !in_double,
+            '!' if !in_single => {
+                let rest = &line[i + 1..];
+                let Some(reference) = history_reference(rest) else {
+                    out.push(c);
+                    continue;
+                };
+                let text = resolve_reference(reference, history).ok_or_else(|| t!("history.event_not_found", format!("!{}", reference)))?;
+                if text.contains(REDACTED) {
+                    return Err(t!("history.event_redacted", format!("!{}", reference)));
+                }
+                out.push_str(&text);
+                expanded = true;
+                // O `!` já foi; pula o resto da referência
+                for _ in 0..reference.chars().count() {
+                    chars.next();
+                }
+                continue;
+            }
+            _ => {}
+        }
+        out.push(c);
+    }
+    Ok(expanded.then_some(out))
+}
+
+/// A referência logo depois de um `!` (`!`, `$`, `12`, `-2`, `git`); `None`
+/// se o `!` é só um `!`.
+fn history_reference(rest: &str) -> Option<&str> {
+    let end = match rest.chars().next()? {
+        '!' | '$' => 1,
+        c if c.is_whitespace() || matches!(c, '=' | '(') => return None,
+        '-' => 1 + rest[1..].find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len() - 1),
+        c if c.is_ascii_digit() => rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len()),
+        _ => rest.find(|c: char| c.is_whitespace() || "|&;<>()'\"".contains(c)).unwrap_or(rest.len()),
+    };
+    let reference = &rest[..end];
+    (!reference.is_empty() && reference != "-").then_some(reference)
+}
+
+/// O texto de uma referência; `None` se o histórico não tem o comando.
+fn resolve_reference(reference: &str, history: &[String]) -> Option<String> {
+    let last = history.last();
+    match reference {
+        "!" => last.cloned(),
+        "$" => {
+            let last = last?;
+            let words = shlex::split(last).unwrap_or_else(|| last.split_whitespace().map(String::from).collect());
+            words.last().map(|word| quote_word(word).into_owned())
+        }
+        _ => {
+            if let Some(back) = reference.strip_prefix('-') {
+                let back: usize = back.parse().ok()?;
+                history.len().checked_sub(back).and_then(|i| history.get(i)).cloned()
+            } else if let Ok(n) = reference.parse::<usize>() {
+                n.checked_sub(1).and_then(|i| history.get(i)).cloned()
+            } else {
+                history.iter().rev().find(|entry| entry.starts_with(reference)).cloned()
+            }
+        }
+    }
+}
@@ -0,0 +1,689 @@
+//! # I18n Module
+//!
+//! Catálogo de mensagens da shell (português e inglês).
+//!
+//! O idioma vem de `[general] language` no `.clios.toml` ou, se não houver,
+//! de `LC_ALL` / `LC_MESSAGES` / `LANG`. O padrão é português.
+//!
+//! ## Uso
+//! ```ignore
+//! eprintln!("{}", t!("unalias.not_found", name));
+//! ```
+//! Cada `{}` do texto recebe um argumento, na ordem. Mensagens novas entram no
+//! [`CATALOG`] com as duas traduções; uma chave ausente aparece como ela mesma,
+//! o que facilita achar o esquecimento.
+
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// -----------------------------------------------------------------------------
+// LANGUAGE
+// -----------------------------------------------------------------------------
+
+/// Idiomas suportados.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Lang {
+    Pt,
+    En,
+}
+
+/// Idioma ativo (0 = pt, 1 = en).
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Interpreta um código de idioma (`pt`, `pt_BR.UTF-8`, `en`, `en_US`...).
+pub fn parse_lang(code: &str) -> Option<Lang> {
+    let code = code.trim().to_lowercase();
+    if code.starts_with("pt") {
+        Some(Lang::Pt)
+    } else if code.starts_with("en") {
+        Some(Lang::En)
+    } else {
+        None
+    }
+}
+
+/// Escolhe o idioma: configuração explícita, depois as variáveis de locale.
+///
+/// `lookup` busca a variável de ambiente (em produção, `env::var`).
+pub fn detect_language(configured: Option<&str>, lookup: impl Fn(&str) -> Option<String>) -> Lang {
+    if let Some(lang) = configured.and_then(parse_lang) {
+        return lang;
+    }
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| lookup(var).filter(|v| !v.is_empty()))
+        .find_map(|v| parse_lang(&v))
+        .unwrap_or(Lang::Pt)
+}
+
+/// Define o idioma ativo.
+pub fn set_language(lang: Lang) {
+    CURRENT.store(lang as u8, Ordering::Relaxed);
+}
+
+/// Define o idioma ativo a partir da configuração e do ambiente do processo.
+pub fn init_language(configured: Option<&str>) {
+    set_language(detect_language(configured, |var| std::env::var(var).ok()));
+}
+
+/// Idioma ativo.
+pub fn current_language() -> Lang {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Lang::En,
+        _ => Lang::Pt,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// LOOKUP
+// -----------------------------------------------------------------------------
+
+/// Texto de uma chave no idioma informado.
+pub fn tr_in(lang: Lang, key: &'static str) -> &'static str {
+    match CATALOG.iter().find(|(k, _, _)| *k == key) {
+        Some((_, pt, en)) => match lang {
+            Lang::Pt => pt,
+            Lang::En => en,
+        },
+        None => key,
+    }
+}
+
+/// Texto de uma chave no idioma ativo.
+pub fn tr(key: &'static str) -> &'static str {
+    tr_in(current_language(), key)
+}
+
+/// Troca cada `{}` do texto pelos argumentos, na ordem.
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(pos) = rest.find("{}") {
+        out.push_str(&rest[..pos]);
+        match args.next() {
+            Some(arg) => out.push_str(&arg.to_string()),
+            None => out.push_str("{}"),
+        }
+        rest = &rest[pos + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Mensagem traduzida: `t!("chave")` ou `t!("chave", arg1, arg2)`.
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::tr($key)
+    };
+    ($key:expr, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::fill($crate::i18n::tr($key), &[$(&$arg as &dyn ::std::fmt::Display),+])
+    };
+}
+
+// -----------------------------------------------------------------------------
+// CATALOG
+// -----------------------------------------------------------------------------
+
+/// (chave, português, inglês)
+pub const CATALOG: &[(&str, &str, &str)] = &[
+    // Geral
+    ("usage", "Uso: {}", "Usage: {}"),
+    ("tag.error", "\x1b[1;31m[ERRO]\x1b[0m {}", "\x1b[1;31m[ERROR]\x1b[0m {}"),
+    ("tag.warning", "\x1b[1;33m[AVISO]\x1b[0m {}", "\x1b[1;33m[WARNING]\x1b[0m {}"),
+    ("tag.syntax", "\x1b[1;31m[ERRO SINTAXE]\x1b[0m {}", "\x1b[1;31m[SYNTAX ERROR]\x1b[0m {}"),
+    ("tag.config_error", "\x1b[1;31m[ERRO CONFIG]\x1b[0m {}", "\x1b[1;31m[CONFIG ERROR]\x1b[0m {}"),
+    ("tag.plugin_error", "\x1b[1;31m[ERRO PLUGIN]\x1b[0m {}", "\x1b[1;31m[PLUGIN ERROR]\x1b[0m {}"),
+    ("tag.config_warning", "\x1b[1;33m[AVISO CONFIG]\x1b[0m {}", "\x1b[1;33m[CONFIG WARNING]\x1b[0m {}"),
+    ("args.unknown_option", "opção desconhecida: {}", "unknown option: {}"),
+    ("args.requires_value", "{} requer um valor", "{} requires a value"),
+    ("args.extra_argument", "argumento extra: {}", "extra argument: {}"),
+    ("args.invalid_argument", "argumento inválido: '{}'", "invalid argument: '{}'"),
+    ("args.no_command", "nenhum comando informado", "no command given"),
+    ("args.requires_number", "{} requer um número", "{} requires a number"),
+    ("args.requires_seconds", "{} requer um valor em segundos", "{} requires a value in seconds"),
+    ("args.invalid_number", "número inválido para {}: '{}'", "invalid number for {}: '{}'"),
+    // Execução
+    ("exec.not_found", "comando não encontrado: '{}'", "command not found: '{}'"),
+    ("exec.permission_denied", "permissão negada: '{}'", "permission denied: '{}'"),
+    ("exec.failed", "erro ao executar '{}': {}", "failed to run '{}': {}"),
+    ("exec.parse_failed", "Falha ao processar: '{}'", "Could not parse: '{}'"),
+    ("exec.fork_failed", "Fork falhou - Sistema sem recursos", "Fork failed - system out of resources"),
+    ("exec.alias_cycle", "Ciclo de alias detectado: {}", "Alias cycle detected: {}"),
+    // Pipeline e redirecionamento
+    ("pipe.redirect_open", "\x1b[1;31m[ERRO REDIRECIONAMENTO]\x1b[0m Falha ao abrir '{}': {}", "\x1b[1;31m[REDIRECT ERROR]\x1b[0m Could not open '{}': {}"),
+    ("pipe.redirect_missing", "Operador '{}' requer um arquivo", "Operator '{}' requires a file"),
+    ("pipe.empty", "Pipeline vazio detectado", "Empty pipeline"),
+    ("pipe.empty_command", "Comando vazio no pipeline (posição {})", "Empty command in pipeline (position {})"),
+    // Subshell
+    ("subshell.empty", "Subshell vazio: $()", "Empty subshell: $()"),
+    ("subshell.unclosed", "Subshell não fechado: $({}", "Unclosed subshell: $({}"),
+    ("subshell.parse_failed", "Falha ao processar subshell: '{}'", "Could not parse subshell: '{}'"),
+    ("subshell.rhai_failed", "Comando rhai no subshell falhou", "Rhai command in subshell failed"),
+    ("subshell.rhai_failed_with", "Comando rhai no subshell falhou: {}", "Rhai command in subshell failed: {}"),
+    ("subshell.rhai_exec_failed", "Falha ao executar subshell rhai: {}", "Could not run rhai subshell: {}"),
+    ("subshell.command_failed", "Comando '{}' no subshell retornou erro", "Command '{}' in subshell returned an error"),
+    ("subshell.command_not_found", "Comando '{}' não encontrado no subshell: {}", "Command '{}' not found in subshell: {}"),
+    // Configuração
+    ("config.file_error", "Erro no {}: {}", "Error in {}: {}"),
+    ("config.using_default", "--> Usando configuração padrão.", "--> Using default configuration."),
+    ("config.invalid_env", "Valor inválido em {}: '{}'", "Invalid value in {}: '{}'"),
+    ("config.rc_unclosed_quotes", "{} Linha {}: Aspas não fechadas.", "{} Line {}: Unclosed quotes."),
+    ("config.rc_content", "--> Conteúdo: {}", "--> Content: {}"),
+    ("include.cycle", "ciclo de include: {}", "include cycle: {}"),
+    // Linha de comando
+    ("cli.c_requires_command", "Erro: -c requer um comando entre aspas", "Error: -c requires a quoted command"),
+    ("cli.flag_requires_value", "Erro: {} requer um valor", "Error: {} requires a value"),
+    ("cli.file_not_found", "Erro: Arquivo não encontrado '{}'", "Error: File not found '{}'"),
+    ("cli.running_rhai", "--- Executando Script Rhai ---", "--- Running Rhai Script ---"),
+    ("cli.rhai_error", "Erro no script Rhai: {}", "Rhai script error: {}"),
+    ("cli.welcome", "Bem-vindo ao Clios Shell v1.0 (Final Release) ", "Welcome to Clios Shell v1.0 (Final Release) "),
+    ("cli.welcome_hint", "Digite 'create' para iniciar um projeto ou 'rhai' para scripts.", "Type 'create' to start a project or 'rhai' for scripts."),
+    ("cli.pending_jobs", "Há {} job(s) pendente(s). Use 'exit' ou Ctrl+D novamente para sair.", "There are {} pending job(s). Use 'exit' or Ctrl+D again to quit."),
+    // Modo em lote
+    ("batch.read_failed", "Erro ao ler a lista de comandos '{}': {}", "Could not read the command list '{}': {}"),
+    ("batch.failed", "{} comando(s) falharam ({} executados):", "{} command(s) failed ({} run):"),
+    ("batch.failure", "linha {} (código {}): {}", "line {} (exit {}): {}"),
+    ("batch.skipped", "{} comando(s) não executados", "{} command(s) not run"),
+    // Builtins
+    ("cd.no_previous", "Erro: Nenhuma pasta anterior definida", "Error: No previous directory"),
+    ("exit.numeric_required", "exit: argumento numérico necessário: '{}'", "exit: numeric argument required: '{}'"),
+    ("exit.stopped_jobs", "Há jobs parados. (use 'jobs' para ver; 'exit' novamente para sair)", "There are stopped jobs. (use 'jobs' to list; 'exit' again to quit)"),
+    ("exit.on_exit_failed", "Erro no Plugin (Função on_exit): {}", "Plugin error (on_exit function): {}"),
+    ("cd.on_cd_failed", "Erro no Plugin (Função on_cd): {}", "Plugin error (on_cd function): {}"),
+    ("fix_terminal.no_state", "fix-terminal: nenhum estado de terminal salvo (sessão não interativa)", "fix-terminal: no saved terminal state (non-interactive session)"),
+    ("help.not_found", "help: nenhuma ajuda para '{}' (veja `help`)", "help: no help for '{}' (see `help`)"),
+    ("help.usage", "Uso:", "Usage:"),
+    ("help.flags", "Flags:", "Flags:"),
+    ("help.examples", "Exemplos:", "Examples:"),
+    ("help.arguments", "Argumentos:", "Arguments:"),
+    ("help.title", "Comandos Internos", "Built-in Commands"),
+    ("help.section.navigation", "Navegação:", "Navigation:"),
+    ("help.row.cd", "cd [dir]\tMudar diretório (cd - para anterior)", "cd [dir]\tChange directory (cd - for previous)"),
+    ("help.row.pwd", "pwd\tExibir diretório atual", "pwd\tShow the current directory"),
+    ("help.section.aliases", "Aliases:", "Aliases:"),
+    ("help.row.alias_list", "alias\tListar todos os aliases", "alias\tList all aliases"),
+    ("help.row.alias_add", "alias x='cmd'\tCriar alias", "alias x='cmd'\tCreate an alias"),
+    ("help.row.abbr", "abbr x 'cmd'\tAbreviação expandida ao digitar espaço", "abbr x 'cmd'\tAbbreviation expanded when typing space"),
+    ("help.row.unalias", "unalias <nome>\tRemover alias", "unalias <name>\tRemove an alias"),
+    ("help.row.export_compat", "export-compat\tAliases e abreviações para o bash", "export-compat\tAliases and abbreviations for bash"),
+    ("help.section.variables", "Variáveis:", "Variables:"),
+    ("help.row.export", "export VAR=val\tDefinir variável de ambiente", "export VAR=val\tSet an environment variable"),
+    ("help.row.unset", "unset VAR\tRemover variável de ambiente", "unset VAR\tRemove an environment variable"),
+    ("help.row.set", "set -o pipefail\tOpções da shell (set -o: listar)", "set -o pipefail\tShell options (set -o: list)"),
+    ("help.row.include", "include <file>\tRodar arquivo no formato do ~/.cliosrc", "include <file>\tRun a file in the ~/.cliosrc format"),
+    ("help.row.env", "env [padrão]\tListar variáveis (--json)", "env [pattern]\tList variables (--json)"),
+    ("help.section.plugins", "Plugins (Rhai):", "Plugins (Rhai):"),
+    ("help.row.source", "source <file>\tCarregar plugin Rhai", "source <file>\tLoad a Rhai plugin"),
+    ("help.row.plugins", "plugins\tListar plugins carregados", "plugins\tList loaded plugins"),
+    ("help.row.plugin_test", "plugin test [p]\tRodar as funções test_* dos plugins", "plugin test [p]\tRun the plugins' test_* functions"),
+    ("help.row.rhai_code", "rhai <código>\tExecutar código Rhai inline", "rhai <code>\tRun inline Rhai code"),
+    ("help.row.rhai_repl", "rhai\tEntrar no modo REPL Rhai", "rhai\tEnter the Rhai REPL"),
+    ("help.row.rhai_debug", "rhai debug <f>\tDepurar script (breakpoints/passos)", "rhai debug <f>\tDebug a script (breakpoints/steps)"),
+    ("help.section.other", "Outros:", "Other:"),
+    ("help.row.history", "history\tExibir histórico de comandos", "history\tShow the command history"),
+    ("help.row.history_stats", "history stats\tEstatísticas de uso do histórico", "history stats\tHistory usage statistics"),
+    ("help.row.trash", "trash <arqs>\tMover arquivos para a lixeira (XDG)", "trash <files>\tMove files to the trash (XDG)"),
+    ("help.row.restore", "restore [nome]\tListar/restaurar itens da lixeira", "restore [name]\tList/restore trashed items"),
+    ("help.row.watch", "watch -n 2 cmd\tReexecutar comando a cada intervalo", "watch -n 2 cmd\tRe-run a command at an interval"),
+    ("help.row.repeat", "repeat <n> cmd\tExecutar comando n vezes", "repeat <n> cmd\tRun a command n times"),
+    ("help.row.run", "run <tarefa>\tTarefa do clios.toml do projeto", "run <task>\tTask from the project's clios.toml"),
+    ("help.row.loop", "loop [..] cmd\tRepetir até sucesso/falha (--until-*)", "loop [..] cmd\tRepeat until success/failure (--until-*)"),
+    ("help.row.parallel", "parallel ...\tExecutar por argumento (-j4 cmd ::: a b)", "parallel ...\tRun per argument (-j4 cmd ::: a b)"),
+    ("help.row.each", "each 'cmd {}'\tExecutar comando por linha (... | each)", "each 'cmd {}'\tRun a command per line (... | each)"),
+    ("help.row.range", "range 1 100 5\tNúmeros de 1 a 100, de 5 em 5 (seq)", "range 1 100 5\tNumbers from 1 to 100, step 5 (seq)"),
+    ("help.row.clean_env", "clean-env cmd\tExecutar com ambiente mínimo (env -i)", "clean-env cmd\tRun with a minimal environment (env -i)"),
+    ("help.row.out", "out [n] [cmd]\tSaída guardada do último comando ($__)", "out [n] [cmd]\tSaved output of the last command ($__)"),
+    ("help.row.pipe", "pipe send <n>\tEnviar a outra sessão (pipe recv <n>)", "pipe send <n>\tSend to another session (pipe recv <n>)"),
+    ("help.row.mkfifo", "mkfifo <arq>\tCriar named pipe (FIFO)", "mkfifo <file>\tCreate a named pipe (FIFO)"),
+    ("help.row.remote", "remote run <h>\tExecutar via ssh (add, copy, default)", "remote run <h>\tRun via ssh (add, copy, default)"),
+    ("help.row.inside", "inside <c> cmd\tExecutar no container (docker exec -it)", "inside <c> cmd\tRun in a container (docker exec -it)"),
+    ("help.row.gstatus", "gstatus\tEstado do git em tabela (--json)", "gstatus\tGit status as a table (--json)"),
+    ("help.row.gbranches", "gbranches/glog\tBranches / commits em tabela (--json)", "gbranches/glog\tBranches / commits as tables (--json)"),
+    ("help.row.open", "open <alvo>\tAbrir arquivo ou URL no app padrão", "open <target>\tOpen a file or URL in the default app"),
+    ("help.row.calc", "= <expr>\tCalculadora (calc -x/-b: hex/binário)", "= <expr>\tCalculator (calc -x/-b: hex/binary)"),
+    ("help.row.when", "when now +3d\tDatas, epoch e durações (--diff)", "when now +3d\tDates, epoch and durations (--diff)"),
+    ("help.row.path", "path add <dir>\tVer/editar o $PATH (rm, dedupe, --save)", "path add <dir>\tView/edit $PATH (rm, dedupe, --save)"),
+    ("help.row.extract", "extract <arq>\tExtrair tar.gz/xz/zst, zip (-C destino)", "extract <file>\tExtract tar.gz/xz/zst, zip (-C target)"),
+    ("help.row.compress", "compress\tCompactar (formato pela extensão da saída)", "compress\tCompress (format from output extension)"),
+    ("help.row.hashsum", "hashsum <arq>\tSHA-256/MD5/BLAKE3 (-c confere lista)", "hashsum <file>\tSHA-256/MD5/BLAKE3 (-c checks a list)"),
+    ("help.row.fetch", "fetch <url>\tHTTP rápido (JSON colorido, downloads)", "fetch <url>\tQuick HTTP (colored JSON, downloads)"),
+    ("help.row.sysinfo", "sysinfo\tSistema, CPU, memória, disco e carga", "sysinfo\tSystem, CPU, memory, disk and load"),
+    ("help.row.procs", "procs [padrão]\tProcessos por CPU/memória (--kill)", "procs [pattern]\tProcesses by CPU/memory (--kill)"),
+    ("help.row.list", "list [dir]\tConteúdo do diretório em colunas", "list [dir]\tDirectory contents in columns"),
+    ("help.row.usage", "usage [dir]\tEspaço em disco por pasta", "usage [dir]\tDisk usage per folder"),
+    ("help.row.ports", "ports\tSockets escutando e o processo dono", "ports\tListening sockets and their owner"),
+    ("help.row.myip", "myip\tIPs locais e o público", "myip\tLocal and public IPs"),
+    ("help.row.ping_lite", "ping-lite <host>\tTempo de conexão TCP", "ping-lite <host>\tTCP connection time"),
+    ("help.row.secret", "secret set <n>\tCofre cifrado de tokens (get, list)", "secret set <n>\tEncrypted token vault (get, list)"),
+    ("help.row.suggest", "?? <pergunta>\tSugestão de comando ([suggest])", "?? <question>\tCommand suggestion ([suggest])"),
+    ("help.row.keys", "keys [tecla]\tAtalhos do editor (desfazer, kill-ring)", "keys [key]\tEditor bindings (undo, kill-ring)"),
+    ("help.row.tldr", "tldr <cmd>\tExemplos de uso (Alt+H: man/tldr da linha)", "tldr <cmd>\tExamples (Alt+H: man/tldr for the line)"),
+    ("help.row.type", "type <cmd>\tMostrar tipo do comando", "type <cmd>\tShow the command type"),
+    ("help.row.hash", "hash [-r]\tTabela de comandos do PATH (-r: limpar)", "hash [-r]\tPATH command table (-r: clear)"),
+    ("help.row.fg", "fg [%N|PID]\tTrazer job para foreground (padrão: atual)", "fg [%N|PID]\tBring a job to the foreground"),
+    ("help.row.bg", "bg [%N|PID]\tContinuar job parado em background", "bg [%N|PID]\tContinue a stopped job in background"),
+    ("help.row.wait", "wait [%N|PID]\tEsperar jobs em background terminarem", "wait [%N|PID]\tWait for background jobs to finish"),
+    ("help.row.disown", "disown [%N|-a]\tTirar jobs da lista (sobrevivem à saída)", "disown [%N|-a]\tDrop jobs from the list (survive exit)"),
+    ("help.row.jobs", "jobs [-o %N|-w]\tJobs (-o: saída capturada; -w: CPU/mem)", "jobs [-o %N|-w]\tJobs (-o: captured output; -w: CPU/mem)"),
+    ("help.row.fix_terminal", "fix-terminal\tRestaurar terminal após app quebrar", "fix-terminal\tRestore the terminal after a crash"),
+    ("help.row.session", "session <ação>\tSalvar/restaurar sessão (save, restore)", "session <action>\tSave/restore a session (save, restore)"),
+    ("help.row.profile", "profile use <p>\tTrocar de perfil (work, minimal...)", "profile use <p>\tSwitch profile (work, minimal...)"),
+    ("help.row.demo", "demo on|off\tModo de apresentação (gravações)", "demo on|off\tPresentation mode (recordings)"),
+    ("help.row.version", "version\tExibir versão da shell", "version\tShow the shell version"),
+    ("help.row.help", "help [cmd]\tEsta ajuda / página de ajuda de um builtin", "help [cmd]\tThis help / a builtin's help page"),
+    ("help.row.trap", "trap 'cmd' EXIT\tExecutar comando ao sair da shell", "trap 'cmd' EXIT\tRun a command when the shell exits"),
+    ("help.row.exit", "exit [código]\tSair da shell", "exit [code]\tExit the shell"),
+    ("help.section.operators", "Operadores:", "Operators:"),
+    ("help.row.op_pipe", "cmd1 | cmd2\tPipeline (conectar stdout -> stdin)", "cmd1 | cmd2\tPipeline (connect stdout -> stdin)"),
+    ("help.row.op_and", "cmd1 && cmd2\tExecutar cmd2 se cmd1 sucesso", "cmd1 && cmd2\tRun cmd2 if cmd1 succeeds"),
+    ("help.row.op_redirect", "cmd > file\tRedirecionar stdout para arquivo", "cmd > file\tRedirect stdout to a file"),
+    ("help.row.op_append", "cmd >> file\tAppend stdout ao arquivo", "cmd >> file\tAppend stdout to a file"),
+    ("help.row.op_stderr", "cmd 2> file\tRedirecionar stderr para arquivo", "cmd 2> file\tRedirect stderr to a file"),
+    ("help.row.op_background", "cmd &\tExecutar em background", "cmd &\tRun in background"),
+    ("history.empty", "Histórico vazio ou arquivo não encontrado: {}", "History is empty or file not found: {}"),
+    ("history.no_metadata", "Sem metadados de histórico ainda: {}", "No history metadata yet: {}"),
+    ("history.tracking_from_now", "Os comandos digitados a partir de agora serão contabilizados.", "Commands typed from now on will be counted."),
+    ("history.stats_title", "Estatísticas do Histórico ({} comandos)", "History Statistics ({} commands)"),
+    ("history.top_commands", "Comandos mais usados:", "Most used commands:"),
+    ("history.busiest_hours", "Horários de maior uso:", "Busiest hours:"),
+    ("history.failure_rates", "Taxa de falha por comando:", "Failure rate by command:"),
+    ("history.no_failures", "Nenhuma falha registrada.", "No failures recorded."),
+    ("history.runs", "{} execuções", "{} runs"),
+    ("history.by_directory", "Uso por diretório:", "Usage by directory:"),
+    ("history.event_not_found", "{}: evento não encontrado no histórico", "{}: event not found"),
+    ("history.event_redacted", "{}: o comando foi gravado com uma credencial escondida (***); digite-o de novo", "{}: the command was saved with a hidden credential (***); type it again"),
+    ("plugins.header", "Comandos de Plugins Ativos:", "Active Plugin Commands:"),
+    ("plugins.none", "Nenhum plugin carregado na memória.", "No plugin loaded."),
+    ("plugins.commands", "Comandos registrados:", "Registered commands:"),
+    ("plugins.functions", "Funções:", "Functions:"),
+    ("plugin.file_not_found", "Arquivo não encontrado: {}", "File not found: {}"),
+    ("plugin.compile_failed", "Falha ao compilar '{}': {}", "Could not compile '{}': {}"),
+    ("plugin.compile_failed_details", "Falha ao compilar '{}'\n  Detalhes: {}", "Could not compile '{}'\n  Details: {}"),
+    ("plugin.init_failed", "Falha ao carregar '{}': {}", "Could not load '{}': {}"),
+    ("plugin.invalid_command_name", "register_command: nome de comando inválido: '{}'", "register_command: invalid command name: '{}'"),
+    ("plugin.invalid_completion_name", "register_completion: nome de comando inválido: '{}'", "register_completion: invalid command name: '{}'"),
+    ("plugin.invalid_command_options", "register_command: opção inválida para '{}': {}", "register_command: invalid option for '{}': {}"),
+    ("plugin.command_failed", "Comando '{}': {}", "Command '{}': {}"),
+    ("plugin.conflict", "'{}' existe em {} e em {}; vale o de {}", "'{}' is defined in {} and in {}; using the one from {}"),
+    ("plugin.function_failed", "Erro no Plugin (Função {}): {}", "Plugin error (function {}): {}"),
+    ("plugin.no_description", "comando de plugin", "plugin command"),
+    ("plugin_test.assert", "assert falhou", "assertion failed"),
+    ("plugin_test.assert_eq", "assert_eq falhou: esquerda = {}, direita = {}", "assert_eq failed: left = {}, right = {}"),
+    ("plugin_test.assert_ne", "assert_ne falhou: os dois lados são {}", "assert_ne failed: both sides are {}"),
+    ("plugin_test.ok", "ok", "ok"),
+    ("plugin_test.fail", "FALHA", "FAIL"),
+    ("plugin_test.no_tests", "nenhuma função test_*", "no test_* function"),
+    ("plugin_test.no_plugins", "plugin test: nenhum plugin encontrado: {}", "plugin test: no plugin found: {}"),
+    ("plugin_test.none_found", "Nenhum teste encontrado (funções test_* sem parâmetros).", "No tests found (test_* functions without parameters)."),
+    ("plugin_test.summary", "{} teste(s): {} ok, {} falha(s)", "{} test(s): {} passed, {} failed"),
+    ("alias.format", "Erro: Use alias nome=valor", "Error: Use alias name=value"),
+    ("compat.header", "Gerado por `clios export-compat`: aliases e abreviações da Clios em sintaxe do bash", "Generated by `clios export-compat`: Clios aliases and abbreviations in bash syntax"),
+    ("compat.section_aliases", "Aliases", "Aliases"),
+    ("compat.section_abbr", "Abreviações (abbr), como aliases", "Abbreviations (abbr), as aliases"),
+    ("compat.invalid_name", "'{}' ignorado: nome inválido no bash", "'{}' skipped: not a valid bash name"),
+    ("compat.rhai_only", "Sem equivalente no bash (Rhai): {}", "No bash equivalent (Rhai): {}"),
+    ("unalias.removed", "Alias '{}' removido.", "Alias '{}' removed."),
+    ("unalias.not_found", "Alias '{}' não encontrado.", "Alias '{}' not found."),
+    ("export.format", "Erro: Use formato VAR=VALOR", "Error: Use the format VAR=VALUE"),
+    ("prompt.level", "nível {}", "level {}"),
+    ("prompt.remote", "remoto {}", "remote {}"),
+    ("prompt.exit_code", "código {}", "exit {}"),
+    ("demo.not_allowed", "'{}' não está na lista do modo de apresentação (allow em [demo])", "'{}' is not in the demo mode list (allow in [demo])"),
+    ("demo.network_blocked", "'{}' usa a rede; bloqueado no modo de apresentação offline", "'{}' uses the network; blocked in offline demo mode"),
+    ("demo.on", "Modo de apresentação ligado (histórico pausado)", "Demo mode on (history paused)"),
+    ("demo.on_offline", "Modo de apresentação ligado, sem rede (histórico pausado)", "Demo mode on, offline (history paused)"),
+    ("demo.off", "Modo de apresentação desligado", "Demo mode off"),
+    ("demo.status_on", "modo de apresentação: ligado", "demo mode: on"),
+    ("demo.status_off", "modo de apresentação: desligado", "demo mode: off"),
+    ("demo.status_network", "comandos de rede: bloqueados", "network commands: blocked"),
+    ("demo.status_allow", "comandos permitidos: {}", "allowed commands: {}"),
+    ("profile.not_found", "perfil '{}' não existe (disponíveis: {})", "no profile '{}' (available: {})"),
+    ("profile.not_a_table", "'profile' deve ser uma tabela ([profile.<nome>])", "'profile' must be a table ([profile.<name>])"),
+    ("profile.list_header", "Perfis em {}:", "Profiles in {}:"),
+    ("profile.none", "(nenhum perfil declarado)", "(no profiles declared)"),
+    ("profile.base", "(base, sem perfil)", "(base, no profile)"),
+    ("profile.switched", "Perfil ativo: {}", "Active profile: {}"),
+    ("profile.cleared", "Sem perfil: configuração base", "No profile: base configuration"),
+    ("tasks.no_file", "nenhum {} neste diretório ou acima", "no {} in this directory or above"),
+    ("tasks.empty_task", "a tarefa '{}' não tem comandos", "task '{}' has no commands"),
+    ("tasks.not_found", "tarefa '{}' não existe (disponíveis: {})", "no task '{}' (available: {})"),
+    ("tasks.list_header", "Tarefas de {}:", "Tasks in {}:"),
+    ("tasks.none", "(nenhuma tarefa em [tasks])", "(no tasks in [tasks])"),
+    ("env.no_match", "nenhuma variável com '{}'", "no variable matching '{}'"),
+    ("rhai.interrupted", "^C Rhai interrompido", "^C Rhai interrupted"),
+    ("rhai.error", "Erro Rhai: {}", "Rhai error: {}"),
+    // Rastro de erros e debugger do Rhai
+    ("rhai_debug.at", "  em {}", "  at {}"),
+    ("rhai_debug.line", "linha ", "line "),
+    ("rhai_debug.start", "Depurando {} (help: comandos)", "Debugging {} (help: commands)"),
+    ("rhai_debug.end", "Fim do script", "Script finished"),
+    ("rhai_debug.hit", "Breakpoint #{}: {}", "Breakpoint #{}: {}"),
+    ("rhai_debug.returned", "Retorno da função: {}", "Function returned: {}"),
+    ("rhai_debug.failed", "Função falhou: {}", "Function failed: {}"),
+    ("rhai_debug.added", "Breakpoint #{} criado: {}", "Breakpoint #{} added: {}"),
+    ("rhai_debug.invalid_breakpoint", "breakpoint inválido: {}", "invalid breakpoint: {}"),
+    ("rhai_debug.invalid_number", "número inválido: {}", "invalid number: {}"),
+    ("rhai_debug.unknown_command", "comando desconhecido: '{}' (help: comandos)", "unknown command: '{}' (help: commands)"),
+    ("rhai_debug.no_variable", "variável não encontrada: {}", "variable not found: {}"),
+    ("rhai_debug.top_level", "(código de topo)", "(top level)"),
+    ("rhai_debug.help",
+        "Comandos do debugger:\n  s, step           próximo passo, entrando nas funções (Enter também)\n  n, next           próximo comando, sem entrar nas funções\n  o, over           próxima expressão, sem entrar nas funções\n  f, finish         até o fim da função atual\n  c, continue       até o próximo breakpoint\n  b [linha|função]  cria um breakpoint (sem argumento, lista os criados)\n  d [n]             apaga o breakpoint n (sem argumento, todos)\n  p [variável]      mostra uma variável (sem argumento, todas)\n  bt                chamadas em andamento\n  l [linha]         código em volta da linha\n  q, quit           encerra o script",
+        "Debugger commands:\n  s, step           next step, into functions (Enter too)\n  n, next           next statement, over functions\n  o, over           next expression, over functions\n  f, finish         run to the end of the current function\n  c, continue       run to the next breakpoint\n  b [line|function] add a breakpoint (no argument: list them)\n  d [n]             delete breakpoint n (no argument: all)\n  p [variable]      show a variable (no argument: all)\n  bt                current call stack\n  l [line]          source around the line\n  q, quit           stop the script"),
+    ("rhai.invalid_signal", "kill_job: sinal inválido: '{}'", "kill_job: invalid signal: '{}'"),
+    ("rhai.repl_enter", "Entrando no modo Rhai (Digite 'exit' para sair)", "Entering Rhai mode (type 'exit' to leave)"),
+    ("rhai.repl_failed", "Falha ao iniciar REPL: {}", "Could not start REPL: {}"),
+    ("trap.unsupported", "trap: evento não suportado: '{}' (apenas EXIT)", "trap: unsupported event: '{}' (EXIT only)"),
+    ("type.alias", "{} is aliased to '{}'", "{} is aliased to '{}'"),
+    ("type.builtin", "{} is a shell builtin", "{} is a shell builtin"),
+    ("type.plugin", "{} is a plugin command", "{} is a plugin command"),
+    ("type.path", "{} is {}", "{} is {}"),
+    ("type.hashed", "{} is hashed ({})", "{} is hashed ({})"),
+    ("hash.not_found", "hash: {}: não encontrado", "hash: {}: not found"),
+    ("hash.empty", "hash: tabela vazia", "hash: hash table empty"),
+    ("hash.command_header", "comando", "command"),
+    ("hash.stats", "{} comando(s) na tabela, {} execução(ões) pela tabela, {} busca(s) no PATH", "{} command(s) hashed, {} run(s) from the table, {} PATH search(es)"),
+    ("type.not_found", "{}: not found", "{}: not found"),
+    ("version.built_with", "Desenvolvido em Rust", "Built with Rust"),
+    ("abbr.not_found", "abbr: {}: não encontrada", "abbr: {}: not found"),
+    ("abbr.save_failed", "abbr: falha ao salvar: {}", "abbr: could not save: {}"),
+    ("session.none", "Nenhuma sessão salva em {}", "No saved session in {}"),
+    ("session.saved", "Sessão '{}' salva em {}", "Session '{}' saved to {}"),
+    ("session.invalid_name", "nome de sessão inválido: '{}'", "invalid session name: '{}'"),
+    ("session.not_found", "sessão '{}' não encontrada", "session '{}' not found"),
+    ("session.corrupted", "sessão '{}' corrompida: {}", "session '{}' is corrupted: {}"),
+    // Jobs
+    ("jobs.none", "Nenhum job em background", "No background jobs"),
+    ("jobs.header", "Jobs em background:", "Background jobs:"),
+    ("jobs.command_column", "Comando", "Command"),
+    ("jobs.reaper_failed", "Falha ao iniciar o recolhimento de jobs: {}", "Could not start the job reaper: {}"),
+    ("jobs.started", "[Background Job {}]", "[Background Job {}]"),
+    ("jobs.paused", "[Job {}] Pausado (Ctrl+Z)", "[Job {}] Stopped (Ctrl+Z)"),
+    ("jobs.continued", "[Job {}] Pausado (Ctrl+Z), continuando em background", "[Job {}] Stopped (Ctrl+Z), continuing in background"),
+    ("jobs.no_current", "fg: nenhum job atual", "fg: no current job"),
+    ("jobs.fg_not_found", "fg: {}: job não encontrado", "fg: {}: no such job"),
+    ("jobs.resumed", "[Job {}] Continuando em background: {}", "[Job {}] Continuing in background: {}"),
+    ("jobs.bg_not_found", "bg: {}: job não encontrado", "bg: {}: no such job"),
+    ("jobs.bg_not_stopped", "bg: job {} já está rodando em background", "bg: job {} is already running in background"),
+    ("jobs.wait_not_found", "wait: {}: job não encontrado", "wait: {}: no such job"),
+    ("jobs.disown_not_found", "disown: {}: job não encontrado", "disown: {}: no such job"),
+    ("jobs.disown_no_current", "disown: nenhum job atual", "disown: no current job"),
+    ("set.on", "ligada", "on"),
+    ("set.off", "desligada", "off"),
+    ("set.unknown_option", "set: {}: opção desconhecida", "set: {}: invalid option name"),
+    ("jobs.no_stopped", "bg: nenhum job parado", "bg: no stopped job"),
+    ("jobs.done", "Concluído", "Done"),
+    ("jobs.exit_code", "Saiu com código {}", "Exit {}"),
+    ("jobs.signaled", "Morto por {}", "Killed by {}"),
+    ("jobs.not_found", "jobs: {}: job não encontrado", "jobs: {}: no such job"),
+    ("jobs.not_captured", "jobs: {}: saída não capturada (ative capture_job_output em [behavior])", "jobs: {}: output not captured (enable capture_job_output in [behavior])"),
+    ("jobs.watch_header", "Jobs em background (qualquer tecla sai)", "Background jobs (press any key to quit)"),
+    ("jobs.watch_time", "TEMPO", "TIME"),
+    ("jobs.watch_command", "COMANDO", "COMMAND"),
+    ("jobs.watch_all_done", "jobs: todos os jobs terminaram", "jobs: all jobs have finished"),
+    ("jobs.watch_needs_terminal", "jobs: --watch precisa de um terminal", "jobs: --watch needs a terminal"),
+    ("jobs.exec_failed", "Erro ao executar '{}': {}", "Failed to run '{}': {}"),
+    // Saída dos comandos
+    ("out.disabled", "out: a captura de saída está desligada (capture_output em [behavior])", "out: output capture is off (capture_output in [behavior])"),
+    ("out.empty", "out: nenhuma saída guardada na posição {}", "out: no saved output at position {}"),
+    ("out.lines", "linhas", "lines"),
+    ("pipe.none", "nenhuma pipe em {}", "no pipes in {}"),
+    ("pipe.not_found", "pipe: {}: pipe não encontrada", "pipe: {}: no such pipe"),
+    ("pipe.invalid_name", "nome inválido: {} (use letras, números, '-', '_' e '.')", "invalid name: {} (use letters, digits, '-', '_' and '.')"),
+    ("pipe.requires_name", "{} requer o nome da pipe", "{} requires the pipe name"),
+    ("pipe.unknown_action", "ação desconhecida: {}", "unknown action: {}"),
+    ("pipe.not_fifo", "{} existe e não é uma FIFO", "{} exists and is not a FIFO"),
+    ("remote.none", "nenhum host salvo (remote add <nome> <user@host>)", "no saved hosts (remote add <name> <user@host>)"),
+    ("remote.not_found", "{}: host não encontrado", "{}: no such host"),
+    ("remote.no_default", "nenhum host padrão (remote default <nome>)", "no default host (remote default <name>)"),
+    ("remote.invalid_name", "remote: nome inválido: {} (use letras, números, '-', '_' e '.')", "remote: invalid name: {} (use letters, digits, '-', '_' and '.')"),
+    ("remote.needs_command", "remote: informe o comando para executar em vários hosts", "remote: a command is required to run on several hosts"),
+    ("remote.copy_many_sources", "remote: {}: a origem deve ser um único host", "remote: {}: the source must be a single host"),
+    ("remote.failed", "terminou com código {}", "exited with code {}"),
+    ("remote.save_failed", "remote: falha ao salvar: {}", "remote: could not save: {}"),
+    ("git.not_repo", "{}: não é um repositório git", "{}: not a git repository"),
+    ("git.clean", "nada a commitar, árvore de trabalho limpa", "nothing to commit, working tree clean"),
+    ("git.detached", "(HEAD destacado)", "(detached HEAD)"),
+    ("git.modified", "modificado", "modified"),
+    ("git.added", "novo", "added"),
+    ("git.deleted", "apagado", "deleted"),
+    ("git.renamed", "renomeado", "renamed"),
+    ("git.copied", "copiado", "copied"),
+    ("git.typechange", "tipo alterado", "type changed"),
+    ("git.untracked", "não rastreado", "untracked"),
+    ("git.conflict", "conflito", "conflict"),
+    ("inside.no_default", "nenhum container padrão (inside --default <nome>)", "no default container (inside --default <name>)"),
+    ("open.not_found", "open: {}: arquivo não encontrado", "open: {}: no such file"),
+    ("open.failed", "open: não foi possível abrir {} ({})", "open: could not open {} ({})"),
+    ("calc.not_integer", "{} não é inteiro (hex/bin/oct só valem para inteiros)", "{} is not an integer (hex/bin/oct only work with integers)"),
+    ("calc.dollar_without_name", "'$' sem nome de variável", "'$' without a variable name"),
+    ("calc.unexpected_char", "caractere inesperado: '{}'", "unexpected character: '{}'"),
+    ("calc.invalid_number", "número inválido: {}", "invalid number: {}"),
+    ("calc.expected_found", "esperado {}, encontrado {}", "expected {}, found {}"),
+    ("calc.expected_at_end", "esperado {} no fim da expressão", "expected {} at the end of the expression"),
+    ("calc.unexpected", "{} inesperado", "unexpected {}"),
+    ("calc.incomplete", "expressão incompleta", "incomplete expression"),
+    ("calc.undefined_variable", "variável não definida: {}", "undefined variable: {}"),
+    ("calc.not_a_number", "{} não é um número: '{}'", "{} is not a number: '{}'"),
+    ("calc.division_by_zero", "divisão por zero", "division by zero"),
+    ("calc.unknown_function", "função desconhecida: {} (use {})", "unknown function: {} (use {})"),
+    ("calc.one_argument", "{}() recebe 1 argumento", "{}() takes 1 argument"),
+    ("calc.negative_sqrt", "raiz de número negativo", "square root of a negative number"),
+    ("calc.empty", "expressão vazia", "empty expression"),
+    ("when.now", "agora", "now"),
+    ("when.ago", "há {}", "{} ago"),
+    ("when.in", "daqui a {}", "in {}"),
+    ("when.relative", "relativo", "relative"),
+    ("when.diff_args", "--diff recebe um ou dois instantes", "--diff takes one or two instants"),
+    ("when.format_required", "-f requer um formato", "-f requires a format"),
+    ("when.invalid_date", "data ou hora inválida: '{}'", "invalid date or time: '{}'"),
+    ("when.invalid_offset", "deslocamento inválido: '{}' (use +3d, -2h, +1mo...)", "invalid offset: '{}' (use +3d, -2h, +1mo...)"),
+    ("when.out_of_range", "data fora do intervalo: '{}'", "date out of range: '{}'"),
+    ("when.invalid_format", "formato inválido: '{}'", "invalid format: '{}'"),
+    ("path.duplicate", "repetida", "duplicate"),
+    ("path.missing", "não existe", "missing"),
+    ("path.saved", "salvo em {}", "saved to {}"),
+    ("path.save_failed", "path: falha ao salvar: {}", "path: could not save: {}"),
+    ("path.directory_required", "{} requer um diretório", "{} requires a directory"),
+    ("path.invalid_action", "ação inválida: {}", "invalid action: {}"),
+    ("path.prepend_add_only", "--prepend só vale para add", "--prepend only applies to add"),
+    ("archive.unknown_format", "formato não reconhecido (tar, tar.gz, tar.xz, tar.zst, zip, gz, xz, zst)", "unknown format (tar, tar.gz, tar.xz, tar.zst, zip, gz, xz, zst)"),
+    ("archive.unknown_output", "compress: {}: use a extensão .tar, .tar.gz, .tar.xz, .tar.zst, .zip, .gz, .xz ou .zst", "compress: {}: use a .tar, .tar.gz, .tar.xz, .tar.zst, .zip, .gz, .xz or .zst extension"),
+    ("archive.exists", "{} já existe (use -f para sobrescrever)", "{} already exists (use -f to overwrite)"),
+    ("archive.single_file", ".gz, .xz e .zst guardam um único arquivo; para diretórios use .tar.gz, .tar.xz ou .tar.zst", ".gz, .xz and .zst hold a single file; for directories use .tar.gz, .tar.xz or .tar.zst"),
+    ("archive.unsafe_path", "ignorado (caminho fora do destino): {}", "skipped (path outside the destination): {}"),
+    ("archive.interrupted", "interrompido", "interrupted"),
+    ("archive.extracted", "{}: {} entradas extraídas em {}", "{}: {} entries extracted to {}"),
+    ("archive.created", "{}: {} entradas, {}", "{}: {} entries, {}"),
+    ("hashsum.ok", "OK", "OK"),
+    ("hashsum.failed", "FALHOU", "FAILED"),
+    ("hashsum.is_dir", "é um diretório", "is a directory"),
+    ("hashsum.summary", "hashsum: {} de {} não conferem", "hashsum: {} of {} did not match"),
+    ("hashsum.bad_lines", "hashsum: {} linha(s) inválida(s) em {}", "hashsum: {} invalid line(s) in {}"),
+    ("hashsum.nothing_checked", "hashsum: {}: nenhum checksum encontrado", "hashsum: {}: no checksums found"),
+    ("hashsum.invalid_algorithm", "hashsum: algoritmo inválido (use sha256, sha512, md5 ou blake3)", "hashsum: invalid algorithm (use sha256, sha512, md5 or blake3)"),
+    ("fetch.saved", "salvo em {} ({})", "saved to {} ({})"),
+    ("fetch.invalid_header", "cabeçalho inválido: '{}' (use 'Nome: valor')", "invalid header: '{}' (use 'Name: value')"),
+    ("fetch.missing_url", "faltou a URL", "missing URL"),
+    ("fetch.invalid_method", "método inválido: {}", "invalid method: {}"),
+    ("sysinfo.os", "Sistema", "OS"),
+    ("sysinfo.kernel", "Kernel", "Kernel"),
+    ("sysinfo.uptime", "Ligado há", "Uptime"),
+    ("sysinfo.cpu", "CPU", "CPU"),
+    ("sysinfo.cores", "{} núcleos", "{} cores"),
+    ("sysinfo.memory", "Memória", "Memory"),
+    ("sysinfo.swap", "Swap", "Swap"),
+    ("sysinfo.disk", "Disco", "Disk"),
+    ("sysinfo.load", "Carga", "Load"),
+    ("procs.user", "USUÁRIO", "USER"),
+    ("procs.command", "COMANDO", "COMMAND"),
+    ("procs.none", "procs: nenhum processo encontrado", "procs: no matching processes"),
+    ("procs.kill_prompt", "Encerrar quais processos?", "Which processes should be terminated?"),
+    ("select.prompt", "Escolha:", "Choose:"),
+    ("select.expected_select", "esperado 'select'", "expected 'select'"),
+    ("select.missing_name", "select: falta o nome da variável", "select: missing variable name"),
+    ("select.expected_in", "select: esperado 'in' depois do nome", "select: expected 'in' after the name"),
+    ("select.expected_do", "select: esperado 'do' depois das opções", "select: expected 'do' after the options"),
+    ("select.missing_done", "select: falta o 'done'", "select: missing 'done'"),
+    ("select.trailing_text", "select: texto depois do 'done': {}", "select: text after 'done': {}"),
+    ("case.expected_case", "esperado 'case'", "expected 'case'"),
+    ("case.missing_word", "case: falta a palavra testada", "case: missing the word to match"),
+    ("case.expected_in", "case: esperado 'in' depois da palavra", "case: expected 'in' after the word"),
+    ("case.missing_esac", "case: falta o 'esac'", "case: missing 'esac'"),
+    ("case.invalid_pattern", "case: padrão inválido (use 'padrão) comandos ;;')", "case: invalid pattern (use 'pattern) commands ;;')"),
+    ("case.missing_paren", "case: falta o ')' depois de '{}'", "case: missing ')' after '{}'"),
+    ("case.trailing_text", "case: texto depois do 'esac': {}", "case: text after 'esac': {}"),
+    ("procs.invalid_sort", "--sort recebe cpu, mem, pid ou name", "--sort takes cpu, mem, pid or name"),
+    ("procs.force_needs_kill", "-9 só vale com --kill", "-9 only works with --kill"),
+    ("procs.kill_needs_terminal", "procs: --kill precisa de um terminal", "procs: --kill needs a terminal"),
+    ("procs.killed", "{} enviado para {} ({})", "{} sent to {} ({})"),
+    ("range.expected_args", "esperado [início] fim [passo]", "expected [start] end [step]"),
+    ("range.invalid_numbers", "início, fim e passo devem ser inteiros (passo diferente de zero)", "start, end and step must be integers (step other than zero)"),
+    ("list.more", "… mais {} entradas", "… {} more entries"),
+    ("list.too_many", "{} entradas (list para ver)", "{} entries (list to show them)"),
+    ("usage.more", "… mais {} entradas (-a mostra todas)", "… {} more entries (-a shows all)"),
+    ("usage.total", "{} ({} arquivos)", "{} ({} files)"),
+    ("usage.errors", "usage: {} entradas não puderam ser lidas", "usage: {} entries could not be read"),
+    ("ports.address", "ENDEREÇO", "ADDRESS"),
+    ("ports.port", "PORTA", "PORT"),
+    ("ports.process", "PROCESSO", "PROCESS"),
+    ("ports.none", "ports: nenhum socket escutando", "ports: no listening sockets"),
+    ("ports.unsupported", "ports: /proc/net não está disponível neste sistema", "ports: /proc/net is not available on this system"),
+    ("ports.hidden_owner", "sockets de processos de outros usuários aparecem sem dono (rode como root para ver todos)", "sockets owned by other users' processes show no owner (run as root to see all)"),
+    ("myip.public", "pública", "public"),
+    ("myip.public_failed", "myip: IP público indisponível: {}", "myip: public IP unavailable: {}"),
+    ("myip.invalid_response", "{}: resposta inválida", "{}: invalid response"),
+    ("ping.requires_port", "-p requer uma porta", "-p requires a port"),
+    ("ping.missing_host", "faltou o host", "missing host"),
+    ("ping.unclosed_ipv6", "IPv6 sem ']'", "IPv6 address without ']'"),
+    ("ping.invalid_port", "porta inválida: {}", "invalid port: {}"),
+    ("ping.zero_count", "-c deve ser maior que zero", "-c must be greater than zero"),
+    ("ping.no_address", "nenhum endereço encontrado", "no address found"),
+    ("ping.header", "TCP {} ({}) porta {}", "TCP {} ({}) port {}"),
+    ("ping.reply", "{}: seq={} tempo={} ms", "{}: seq={} time={} ms"),
+    ("ping.refused", "{}: seq={} tempo={} ms (porta fechada)", "{}: seq={} time={} ms (port closed)"),
+    ("ping.timeout", "{}: seq={} sem resposta em {}s", "{}: seq={} no answer within {}s"),
+    ("ping.summary", "{} tentativas, {} respostas, {}% de perda", "{} attempts, {} answered, {}% loss"),
+    ("ping.times", "mín/média/máx = {}/{}/{} ms", "min/avg/max = {}/{}/{} ms"),
+    ("secret.passphrase", "Senha do cofre:", "Vault passphrase:"),
+    ("secret.new_passphrase", "Nova senha do cofre:", "New vault passphrase:"),
+    ("secret.confirm_passphrase", "Confirme a senha:", "Confirm passphrase:"),
+    ("secret.value_prompt", "Valor de {}:", "Value for {}:"),
+    ("secret.wrong_passphrase", "senha incorreta", "wrong passphrase"),
+    ("secret.corrupt", "{} não é um cofre válido: {}", "{} is not a valid vault: {}"),
+    ("secret.needs_terminal", "a senha do cofre precisa de um terminal", "the vault passphrase needs a terminal"),
+    ("secret.cancelled", "cancelado", "cancelled"),
+    ("secret.not_found", "{}: segredo não encontrado", "{}: no such secret"),
+    ("secret.invalid_name", "nome inválido: '{}' (use letras, números, _, - e .)", "invalid name: '{}' (use letters, digits, _, - and .)"),
+    ("secret.saved", "Segredo '{}' guardado.", "Secret '{}' saved."),
+    ("secret.updated", "Segredo '{}' atualizado.", "Secret '{}' updated."),
+    ("secret.removed", "Segredo '{}' removido.", "Secret '{}' removed."),
+    ("secret.empty", "Nenhum segredo guardado.", "No secrets stored."),
+    ("secret.locked", "Cofre trancado: a senha será pedida de novo.", "Vault locked: the passphrase will be asked again."),
+    ("redact.invalid_pattern", "redact_patterns: padrão inválido '{}': {}", "redact_patterns: invalid pattern '{}': {}"),
+    ("suggest.not_configured", "nenhum sugestor configurado (seção [suggest] do .clios.toml: command ou function)", "no suggester configured ([suggest] section of .clios.toml: command or function)"),
+    ("suggest.empty_query", "uso: ?? <pergunta>", "usage: ?? <question>"),
+    ("suggest.no_suggestion", "o sugestor não devolveu nenhum comando", "the suggester returned no command"),
+    ("suggest.invalid_command", "comando inválido em [suggest]: {}", "invalid command in [suggest]: {}"),
+    ("suggest.command_failed", "{} terminou com código {}", "{} exited with code {}"),
+    ("suggest.function_missing", "função Rhai '{}' não encontrada (nenhum plugin carregado)", "Rhai function '{}' not found (no plugin loaded)"),
+    ("suggest.function_failed", "função Rhai '{}': {}", "Rhai function '{}': {}"),
+    ("suggest.waiting", "consultando {}...", "asking {}..."),
+    ("keys.invalid", "[keys]: tecla inválida '{}' (use ctrl-x ou alt-x)", "[keys]: invalid key '{}' (use ctrl-x or alt-x)"),
+    ("keys.section_move", "Movimento e busca", "Movement and search"),
+    ("keys.section_undo", "Desfazer e refazer", "Undo and redo"),
+    ("keys.section_kill", "Apagar e colar (kill-ring)", "Kill and yank (kill-ring)"),
+    ("keys.section_shell", "Linha e shell", "Line and shell"),
+    ("keys.line_start", "Início da linha", "Start of line"),
+    ("keys.line_end", "Fim da linha", "End of line"),
+    ("keys.word_back", "Palavra anterior", "Previous word"),
+    ("keys.word_forward", "Próxima palavra", "Next word"),
+    ("keys.history_prev", "Comando anterior do histórico", "Previous history entry"),
+    ("keys.history_next", "Próximo comando do histórico", "Next history entry"),
+    ("keys.history_search", "Buscar no histórico", "Search history"),
+    ("keys.clear_screen", "Limpar a tela", "Clear the screen"),
+    ("keys.delete_char", "Apagar o caractere sob o cursor", "Delete the character under the cursor"),
+    ("keys.vi_normal", "Modo normal", "Normal mode"),
+    ("keys.vi_insert", "Inserir antes do cursor", "Insert before the cursor"),
+    ("keys.vi_append", "Inserir depois do cursor", "Append after the cursor"),
+    ("keys.vi_put", "Colar depois do cursor", "Put after the cursor"),
+    ("keys.expand_abbr", "Expandir a abreviação (abbr) e inserir o espaço", "Expand the abbreviation (abbr) and insert the space"),
+    ("keys.skip_closer", "Pular o fechamento já digitado (auto_pair)", "Skip over the existing closer (auto_pair)"),
+    ("keys.cancel_line", "Descartar a linha", "Discard the line"),
+    ("keys.exit", "Sair (com a linha vazia)", "Exit (on an empty line)"),
+    ("keys.not_found", "nenhum atalho para '{}'", "no binding for '{}'"),
+    ("keys.undo", "Desfazer a última edição", "Undo the last edit"),
+    ("keys.redo", "Refazer o que foi desfeito", "Redo what was undone"),
+    ("keys.kill_end", "Apagar até o fim da linha", "Kill to end of line"),
+    ("keys.kill_start", "Apagar até o início da linha", "Kill to start of line"),
+    ("keys.kill_word_back", "Apagar a palavra anterior", "Kill previous word"),
+    ("keys.kill_word", "Apagar a próxima palavra", "Kill next word"),
+    ("keys.yank", "Colar o último texto apagado", "Yank the last killed text"),
+    ("keys.yank_pop", "Após o Ctrl+Y: trocar pelo trecho anterior", "After Ctrl+Y: cycle to older killed text"),
+    ("keys.complete", "Completar", "Complete"),
+    ("keys.accept_hint", "Aceitar a sugestão do histórico", "Accept the history hint"),
+    ("keys.docs", "man/tldr do comando sendo digitado", "man/tldr for the command being typed"),
+    ("keys.last_output", "Inserir a saída do último comando", "Insert the last command's output"),
+    ("keys.config_hint", "Desfazer/refazer mudam na seção [keys] do .clios.toml (ex: undo = [\"ctrl-z\"]).", "Undo/redo keys are set in the [keys] section of .clios.toml (e.g. undo = [\"ctrl-z\"])."),
+    // Aninhamento
+    ("nesting.deep", "Clios aninhada em {} níveis (CLIOS_LEVEL); use `exit` para voltar ao nível anterior", "Clios nested {} levels deep (CLIOS_LEVEL); use `exit` to go back one level"),
+    ("nesting.too_deep", "Clios aninhada em mais de {} níveis; recusando iniciar (recursão no .cliosrc ou em um alias?)", "Clios nested more than {} levels deep; refusing to start (recursion in .cliosrc or an alias?)"),
+    // Limites
+    ("limits.jobs", "limite de {} jobs em background atingido (max_jobs em [limits])", "limit of {} background jobs reached (max_jobs in [limits])"),
+    ("limits.spawns", "mais de {} processos criados no último segundo; possível fork bomb (max_spawns_per_second em [limits])", "more than {} processes created in the last second; possible fork bomb (max_spawns_per_second in [limits])"),
+    ("limits.eagain", "o sistema recusou um novo processo: limite de processos do usuário atingido (ulimit -u {})", "the system refused a new process: user process limit reached (ulimit -u {})"),
+    ("limits.ulimit", "ulimit -u é {}, menor que max_jobs ({}) em [limits]: o sistema vai recusar processos antes da shell", "ulimit -u is {}, lower than max_jobs ({}) in [limits]: the system will refuse processes before the shell does"),
+    // Lixeira
+    ("trash.empty", "Lixeira vazia: {}", "Trash is empty: {}"),
+    ("trash.restored", "Restaurado: {}", "Restored: {}"),
+    ("trash.file_not_found", "{}: arquivo não encontrado", "{}: no such file"),
+    ("trash.invalid_path", "{}: caminho inválido", "{}: invalid path"),
+    ("trash.not_in_trash", "{}: não está na lixeira", "{}: not in the trash"),
+    ("trash.invalid_info", "{}: .trashinfo inválido", "{}: invalid .trashinfo"),
+    ("trash.already_exists", "{}: já existe", "{}: already exists"),
+    // Falhas internas
+    ("crash.recovered", "\x1b[1;31m[ERRO INTERNO]\x1b[0m A Clios se recuperou de uma falha (ref: {}).", "\x1b[1;31m[INTERNAL ERROR]\x1b[0m Clios recovered from a failure (ref: {})."),
+    ("crash.details", "--> Detalhes em: {}", "--> Details in: {}"),
+    ("crash.write_failed", "--> Não foi possível gravar o relatório em {}", "--> Could not write the report to {}"),
+    ("signals.install_failed", "Falha ao instalar handler de SIGINT: {}", "Could not install SIGINT handler: {}"),
+    // Explicação de códigos de saída
+    ("explain.line", "\x1b[2m↳ código {}: {}\x1b[0m", "\x1b[2m↳ exit code {}: {}\x1b[0m"),
+    ("explain.misuse", "uso incorreto do comando (argumentos inválidos)", "command misuse (invalid arguments)"),
+    ("explain.not_executable", "o comando existe mas não pode ser executado (permissão ou formato)", "command found but not executable (permission or format)"),
+    ("explain.not_found", "comando não encontrado (verifique o nome e o PATH)", "command not found (check the name and PATH)"),
+    ("explain.sigint", "interrompido com Ctrl+C (SIGINT)", "interrupted with Ctrl+C (SIGINT)"),
+    ("explain.sigkill", "morto com SIGKILL - possivelmente pelo OOM killer (falta de memória)", "killed with SIGKILL - possibly by the OOM killer (out of memory)"),
+    ("explain.sigsegv", "falha de segmentação (acesso inválido à memória, SIGSEGV)", "segmentation fault (invalid memory access, SIGSEGV)"),
+    ("explain.sigpipe", "o leitor do pipe fechou antes do fim (SIGPIPE)", "the pipe reader closed early (SIGPIPE)"),
+    ("explain.sigterm", "encerrado com SIGTERM", "terminated with SIGTERM"),
+    ("explain.signal", "encerrado pelo sinal {}", "terminated by signal {}"),
+    // Automação
+    ("each.invalid_command", "each: comando inválido: '{}'", "each: invalid command: '{}'"),
+    ("each.invalid_parallelism", "paralelismo inválido: '{}'", "invalid parallelism: '{}'"),
+    ("watch.invalid_interval", "intervalo inválido: '{}'", "invalid interval: '{}'"),
+    ("watch.requires_glob", "--on-change requer um glob", "--on-change requires a glob"),
+    ("repeat.invalid_delay", "atraso inválido: '{}'", "invalid delay: '{}'"),
+    ("parallel.invalid_jobs", "número de jobs inválido: '{}'", "invalid number of jobs: '{}'"),
+    ("clean_env.requires_name", "-k requer o nome de uma variável", "-k requires a variable name"),
+    // tldr
+    ("tldr.invalid_name", "nome inválido: '{}'", "invalid name: '{}'"),
+    ("tldr.connection_failed", "erro de conexão: {}", "connection error: {}"),
+    ("tldr.not_found", "{}: página não encontrada", "{}: page not found"),
+    // Bench
+    ("bench.system", "Sistema: {} {} | Diretório: {}", "System: {} {} | Directory: {}"),
+    ("bench.prompts", "Prompt ({} renderizações):", "Prompt ({} renders):"),
+    ("bench.expansion", "Expansão ({} tokens sintéticos):", "Expansion ({} synthetic tokens):"),
+    ("bench.path", "PATH ({} varreduras):", "PATH ({} scans):"),
+    ("bench.per_second", "{} itens/s", "{} items/s"),
+    ("bench.variables", "variáveis", "variables"),
+    ("bench.tilde", "til", "tilde"),
+    ("bench.three_passes", "3 passagens", "3 passes"),
+    ("bench.single_pass", "passagem única", "single pass"),
+    ("bench.globs_single_pass", "globs (passagem única)", "globs (single pass)"),
+    ("bench.no_temp_dir", "ignorado: sem diretório temporário", "skipped: no temporary directory"),
+    ("bench.full_scan", "varredura completa", "full scan"),
+    ("bench.size", "tamanho", "size"),
+    ("bench.path_size", "{} diretórios, {} entradas", "{} directories, {} entries"),
+    ("bench.attach", "Anexe este relatório ao abrir uma issue de performance.", "Attach this report when opening a performance issue."),
+];
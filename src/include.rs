@@ -0,0 +1,62 @@
+//! # Include Module
+//!
+//! `include`: o ~/.cliosrc dividido em arquivos menores, no mesmo formato.
+//!
+//! ```bash
+//! # ~/.cliosrc
+//! include clios/aliases.clios        # relativo ao diretório deste arquivo
+//! include clios/work.clios
+//! include ~/.clios.d/*.clios         # vários, em ordem alfabética
+//! ```
+//!
+//! Um caminho relativo é resolvido a partir do diretório do arquivo que tem
+//! o `include` (o arquivo de verdade, depois dos links simbólicos, então um
+//! ~/.cliosrc que aponta para um repositório de dotfiles acha os vizinhos
+//! dele). Digitado no prompt, é relativo ao diretório atual.
+//!
+//! Um arquivo que já está sendo lido não é lido de novo: `a` que inclui `b`
+//! que inclui `a` mostra o ciclo e segue com o resto de `b`.
+
+use crate::t;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Os arquivos rc sendo lidos agora, do mais externo ao atual.
+#[derive(Debug, Default)]
+pub struct IncludeStack {
+    /// Caminhos canônicos
+    files: Vec<PathBuf>,
+}
+
+impl IncludeStack {
+    /// O caminho de `name` para um `include` feito agora.
+    pub fn resolve(&self, name: &str) -> PathBuf {
+        let path = Path::new(name);
+        match self.files.last().and_then(|file| file.parent()) {
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path.to_path_buf(),
+        }
+    }
+
+    /// Começa a ler `path`. Falha se ele não existe ou se já está sendo
+    /// lido (o erro mostra o ciclo).
+    pub fn enter(&mut self, path: &Path) -> Result<(), String> {
+        let canonical = fs::canonicalize(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        if let Some(start) = self.files.iter().position(|file| *file == canonical) {
+            let chain: Vec<String> = self.files[start..].iter().chain([&canonical]).map(|f| f.display().to_string()).collect();
+            return Err(t!("include.cycle", chain.join(" → ")));
+        }
+        self.files.push(canonical);
+        Ok(())
+    }
+
+    /// Terminou de ler o arquivo atual.
+    pub fn leave(&mut self) {
+        self.files.pop();
+    }
+
+    /// Quantos arquivos estão sendo lidos.
+    pub fn depth(&self) -> usize {
+        self.files.len()
+    }
+}
@@ -2,14 +2,48 @@
 //!
 //! Handles job control with low-level Unix process management.
 //! Uses `nix` crate for fork/exec and signal handling.
+//!
+//! Um job em background que termina é anunciado logo antes do próximo prompt,
+//! como no bash (`[1]+ Concluído  sleep 10`). O processo é recolhido antes,
+//! assim que termina: o `SIGCHLD` acorda uma thread ([`start_reaper`]) que
+//! atualiza a lista, e nenhum job fica zumbi esperando o usuário apertar Enter.
+//!
+//! Com `capture_job_output = true` em `[behavior]`, o STDOUT/STDERR de cada job
+//! em background vai para um arquivo próprio em vez de se misturar com a linha
+//! sendo editada. A saída aparece junto com o aviso de que o job terminou,
+//! ou a qualquer momento com `jobs --output %N`.
+//!
+//! Na sessão interativa, cada comando em foreground ganha um process group
+//! próprio e o terminal: Ctrl+Z para só o comando, que vira um job parado
+//! (`fg` o traz de volta, `bg` o continua em background). Uma pipeline é um
+//! job só, com todos os comandos no mesmo process group. Com `stop_then_bg = true` em `[behavior]`, o job
+//! parado continua rodando em background na hora.
+//!
+//! `jobs --watch` é um `top` dos jobs: CPU e memória de cada um (somando os
+//! processos do process group, lidos de `/proc`), atualizados a cada segundo
+//! até uma tecla ser pressionada.
 
-use nix::sys::signal::{self, SigHandler, Signal};
+use nix::sys::signal::{self, SaFlags, SigAction, SigHandler, SigSet, Signal};
+use nix::errno::Errno;
 use nix::sys::wait::{self, WaitPidFlag, WaitStatus};
+use crate::archive::human_size;
+use crate::executor::{self, ProcessGroup};
+use crate::limits::{admit_spawn, check_job_slot};
+use crate::procs::truncate;
+use crate::registry::usage;
+use crate::signals::{is_interrupted, raise_interrupt, take_interrupt, EXIT_INTERRUPTED};
+use crate::t;
+use crate::terminal::{restore_terminal, terminal_width, KeyReader};
 use nix::unistd;
 use std::collections::HashMap;
-use std::process::Command;
-use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::fs;
+use std::io::{self, IsTerminal, Read, Write};
+use std::os::fd::IntoRawFd;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
 
 // -----------------------------------------------------------------------------
 // JOB TRACKING
@@ -18,16 +52,52 @@ use std::time::Instant;
 /// Representa um job em background
 #[derive(Debug, Clone)]
 pub struct BackgroundJob {
-    /// PID do processo
+    /// PID do processo (numa pipeline, o do primeiro comando, que é o PGID)
     pub pid: i32,
+    /// PID do último comando da pipeline, que dá o código de saída; num
+    /// comando só, o próprio `pid`
+    pub last: i32,
     /// Comando que está sendo executado
     pub command: String,
     /// Hora de início
     pub started: Instant,
     /// Status atual
     pub status: JobStatus,
+    /// Arquivo com a saída capturada (`capture_job_output`)
+    pub output: Option<PathBuf>,
+    /// Quando saiu do foreground pela última vez (Ctrl+Z)
+    pub suspended: Option<Instant>,
+}
+
+/// Job concluído que ainda não foi anunciado no prompt
+#[derive(Debug, Clone)]
+pub struct FinishedJob {
+    /// A posição dele no `jobs` (`%N`) quando terminou
+    pub number: usize,
+    /// `+` para o job atual, `-` para o anterior, senão espaço
+    pub mark: char,
+    pub pid: i32,
+    pub command: String,
+    pub exit: JobExit,
+    /// Saída capturada ainda não exibida
+    pub output: Option<PathBuf>,
+}
+
+/// Como um job terminou
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JobExit {
+    Code(i32),
+    Signal(Signal),
 }
 
+/// Jobs concluídos aguardando o próximo prompt
+static FINISHED_JOBS: Mutex<Vec<FinishedJob>> = Mutex::new(Vec::new());
+
+/// Processos fora da lista que ainda são filhos da shell e são recolhidos em
+/// silêncio para não virarem zumbis: os largados com `disown` e o resto de
+/// uma pipeline cujo último comando já terminou (`-pgid`, o grupo todo)
+static UNREAPED: Mutex<Vec<i32>> = Mutex::new(Vec::new());
+
 /// Status de um job
 #[derive(Debug, Clone, PartialEq)]
 pub enum JobStatus {
@@ -50,9 +120,12 @@ pub fn add_job(jobs: &JobList, pid: i32, command: String) {
     if let Ok(mut list) = jobs.lock() {
         list.insert(pid, BackgroundJob {
             pid,
+            last: pid,
             command,
             started: Instant::now(),
             status: JobStatus::Running,
+            output: None,
+            suspended: None,
         });
     }
 }
@@ -67,129 +140,918 @@ pub fn remove_job(jobs: &JobList, pid: i32) {
 
 /// Atualiza o status de jobs (verifica se terminaram)
 pub fn update_jobs(jobs: &JobList) {
-    if let Ok(mut list) = jobs.lock() {
-        let pids: Vec<i32> = list.keys().cloned().collect();
-        
-        for pid in pids {
+    if let Ok(mut unreaped) = UNREAPED.lock() {
+        // Com `-pgid`, cada chamada recolhe um processo do grupo
+        unreaped.retain(|&pid| loop {
             match wait::waitpid(unistd::Pid::from_raw(pid), Some(WaitPidFlag::WNOHANG)) {
-                Ok(WaitStatus::Exited(_, _)) | Ok(WaitStatus::Signaled(_, _, _)) => {
-                    if let Some(job) = list.get_mut(&pid) {
-                        job.status = JobStatus::Done;
-                    }
-                }
+                Ok(WaitStatus::StillAlive) => break true,
+                Ok(_) => continue,
+                Err(_) => break false,
+            }
+        });
+    }
+
+    if let Ok(mut list) = jobs.lock() {
+        // Jobs que saíram do foreground (Ctrl+Z) desde a última atualização
+        if let Ok(mut suspended) = SUSPENDED_JOBS.lock() {
+            for job in suspended.drain(..) {
+                list.insert(job.pid, job);
+            }
+        }
+
+        let pids: Vec<(i32, i32)> = list.values().map(|j| (j.pid, j.last)).collect();
+        let mut exits = HashMap::new();
+
+        for (pid, last) in pids {
+            let exit = match poll_job(pid, last) {
+                Ok(WaitStatus::Exited(_, code)) => JobExit::Code(code),
+                Ok(WaitStatus::Signaled(_, sig, _)) => JobExit::Signal(sig),
                 Ok(WaitStatus::Stopped(_, _)) => {
                     if let Some(job) = list.get_mut(&pid) {
                         job.status = JobStatus::Stopped;
                     }
+                    continue;
                 }
-                _ => {}
+                _ => continue,
+            };
+            if let Some(job) = list.get_mut(&pid) {
+                job.status = JobStatus::Done;
+                exits.insert(pid, exit);
             }
         }
-        
-        // Remove jobs concluídos
+        if exits.is_empty() {
+            return;
+        }
+
+        // Remove jobs concluídos, guardando o aviso (e a saída capturada) para
+        // o próximo prompt; o número e a marca são os que o `jobs` mostrava
+        let recent = recent_jobs(&list);
+        let numbers: HashMap<i32, usize> = jobs_in_order(&list).iter().enumerate().map(|(i, j)| (j.pid, i + 1)).collect();
+        let Ok(mut finished) = FINISHED_JOBS.lock() else {
+            return;
+        };
+        let mut done: Vec<&mut BackgroundJob> = list.values_mut().filter(|j| j.status == JobStatus::Done).collect();
+        done.sort_by_key(|j| numbers[&j.pid]);
+        for job in done {
+            finished.push(FinishedJob {
+                number: numbers[&job.pid],
+                mark: match recent.iter().position(|&pid| pid == job.pid) {
+                    Some(0) => '+',
+                    Some(1) => '-',
+                    _ => ' ',
+                },
+                pid: job.pid,
+                command: job.command.clone(),
+                exit: exits.get(&job.pid).copied().unwrap_or(JobExit::Code(0)),
+                output: job.output.take(),
+            });
+        }
         list.retain(|_, job| job.status != JobStatus::Done);
     }
 }
 
+/// O que mudou no job desde a última checagem, sem esperar. Numa pipeline, o
+/// job termina com o último comando (o resto do grupo vai para [`UNREAPED`])
+/// e para quando qualquer um dos comandos para.
+fn poll_job(pid: i32, last: i32) -> nix::Result<WaitStatus> {
+    let flags = Some(WaitPidFlag::WNOHANG | WaitPidFlag::WUNTRACED);
+    if pid == last {
+        return wait::waitpid(unistd::Pid::from_raw(pid), flags);
+    }
+    loop {
+        match wait::waitpid(unistd::Pid::from_raw(-pid), flags)? {
+            WaitStatus::Stopped(member, _) if !is_stopped(member) => continue,
+            status @ (WaitStatus::Exited(member, _) | WaitStatus::Signaled(member, ..)) => {
+                if member.as_raw() != last {
+                    continue;
+                }
+                if let Ok(mut unreaped) = UNREAPED.lock() {
+                    unreaped.push(-pid);
+                }
+                return Ok(status);
+            }
+            status => return Ok(status),
+        }
+    }
+}
+
+/// O processo está parado agora? Um comando de pipeline parado com Ctrl+Z e
+/// continuado pelo `fg`/`bg` antes da shell ler o aviso deixa um aviso de
+/// parada velho para trás. Sem `/proc`, vale o aviso.
+fn is_stopped(pid: unistd::Pid) -> bool {
+    match fs::read_to_string(format!("/proc/{}/stat", pid)) {
+        // O nome do comando, entre parênteses, pode ter espaços
+        Ok(stat) => stat.rsplit(')').next().and_then(|rest| rest.trim().chars().next()).is_some_and(|s| s == 'T' || s == 't'),
+        Err(_) => true,
+    }
+}
+
+/// Ordena os jobs por ordem de início (a mesma numeração de `%N`).
+fn jobs_in_order(list: &HashMap<i32, BackgroundJob>) -> Vec<&BackgroundJob> {
+    let mut ordered: Vec<&BackgroundJob> = list.values().collect();
+    ordered.sort_by_key(|j| (j.started, j.pid));
+    ordered
+}
+
+/// Resolve uma especificação de job: `%N` (posição em `jobs`), `%+` ou `%%`
+/// (o job atual), `%-` (o anterior) ou um PID.
+pub fn resolve_job_spec(jobs: &JobList, spec: &str) -> Option<i32> {
+    let list = jobs.lock().ok()?;
+    match spec.strip_prefix('%') {
+        Some("+" | "%") => recent_jobs(&list).first().copied(),
+        Some("-") => recent_jobs(&list).get(1).copied(),
+        Some(index) => {
+            let index: usize = index.parse().ok()?;
+            jobs_in_order(&list).get(index.checked_sub(1)?).map(|j| j.pid)
+        }
+        None => spec.parse().ok().filter(|pid| list.contains_key(pid)),
+    }
+}
+
+/// Os PIDs na ordem do `%+`/`%-`: primeiro os parados, do último parado com
+/// Ctrl+Z para trás, depois os outros, do último iniciado para trás.
+fn recent_jobs(list: &HashMap<i32, BackgroundJob>) -> Vec<i32> {
+    let mut ordered: Vec<&BackgroundJob> = list.values().collect();
+    ordered.sort_by_key(|j| std::cmp::Reverse((j.status == JobStatus::Stopped, j.suspended.unwrap_or(j.started), j.pid)));
+    ordered.into_iter().map(|j| j.pid).collect()
+}
+
+/// O job atual (`%+`, o do `fg` sem argumentos), como no POSIX: o último
+/// parado com Ctrl+Z ou, sem nenhum parado, o último mandado para o background.
+pub fn current_job(jobs: &JobList) -> Option<i32> {
+    let list = jobs.lock().ok()?;
+    recent_jobs(&list).first().copied()
+}
+
+/// O último job parado com Ctrl+Z (o do `bg` sem argumentos).
+pub fn stopped_job(jobs: &JobList) -> Option<i32> {
+    let list = jobs.lock().ok()?;
+    list.values()
+        .filter(|j| j.status == JobStatus::Stopped)
+        .max_by_key(|j| (j.suspended.unwrap_or(j.started), j.pid))
+        .map(|j| j.pid)
+}
+
+// -----------------------------------------------------------------------------
+// FOREGROUND JOBS
+// -----------------------------------------------------------------------------
+
+/// Job control nos comandos em foreground (só na sessão interativa).
+static JOB_CONTROL: AtomicBool = AtomicBool::new(false);
+
+/// Opção `stop_then_bg`: o Ctrl+Z manda o job direto para o background.
+static STOP_THEN_BG: AtomicBool = AtomicBool::new(false);
+
+/// Jobs que saíram do foreground, esperando entrar na lista no próximo
+/// [`update_jobs`] (quem espera o comando não tem acesso à lista).
+static SUSPENDED_JOBS: Mutex<Vec<BackgroundJob>> = Mutex::new(Vec::new());
+
+/// Liga o job control dos comandos em foreground. Sem efeito quando o STDIN
+/// não é um terminal.
+pub fn enable_job_control() {
+    if !io::stdin().is_terminal() {
+        return;
+    }
+    // A shell passa o terminal adiante e o pega de volta sem ser suspensa
+    let _ = unsafe { signal::signal(Signal::SIGTTOU, SigHandler::SigIgn) };
+    JOB_CONTROL.store(true, Ordering::Relaxed);
+}
+
+/// Desliga o job control: num subshell em background, os comandos não podem
+/// tomar o terminal.
+pub fn disable_job_control() {
+    JOB_CONTROL.store(false, Ordering::Relaxed);
+}
+
+pub fn job_control_enabled() -> bool {
+    JOB_CONTROL.load(Ordering::Relaxed)
+}
+
+/// Liga ou desliga a opção `stop_then_bg`.
+pub fn set_stop_then_bg(enabled: bool) {
+    STOP_THEN_BG.store(enabled, Ordering::Relaxed);
+}
+
+/// Espera o `job` em foreground terminar (numa pipeline, todos os comandos)
+/// ou ser parado, e devolve o código de saída (o do último comando).
+///
+/// Parado com Ctrl+Z, o job entra na lista de jobs (código 148) ou, com
+/// `stop_then_bg`, recebe `SIGCONT` e continua em background (código 0). Quem
+/// chama devolve o terminal à shell depois.
+pub fn wait_foreground(job: BackgroundJob) -> i32 {
+    wait_foreground_statuses(job).0
+}
+
+/// O [`wait_foreground`], devolvendo também o código de cada processo que
+/// terminou, pelo PID (o `$PIPESTATUS` de uma pipeline).
+pub fn wait_foreground_statuses(mut job: BackgroundJob) -> (i32, HashMap<i32, i32>) {
+    let pipeline = job.last != job.pid;
+    // Numa pipeline, espera o grupo todo (nenhum comando fica sem o terminal);
+    // o código é o do último comando
+    let target = unistd::Pid::from_raw(if pipeline { -job.pid } else { job.pid });
+    let mut last_status = WaitStatus::Exited(target, 0);
+    let mut exits = HashMap::new();
+    let status = loop {
+        match wait::waitpid(target, Some(WaitPidFlag::WUNTRACED)) {
+            Err(Errno::EINTR) => continue,
+            Ok(WaitStatus::Stopped(member, _)) if pipeline && !is_stopped(member) => continue,
+            Ok(status @ (WaitStatus::Exited(member, _) | WaitStatus::Signaled(member, ..))) if pipeline => {
+                exits.extend(executor::wait_status_code(status).map(|code| (member.as_raw(), code)));
+                if member.as_raw() == job.last {
+                    last_status = status;
+                }
+            }
+            Err(Errno::ECHILD) if pipeline => break Ok(last_status),
+            status => break status,
+        }
+    };
+    let code = match status {
+        Ok(WaitStatus::Exited(_, code)) => code,
+        Ok(WaitStatus::Signaled(_, sig, _)) => {
+            // A shell não recebe o Ctrl+C do job: laços e scripts param igual
+            if sig == Signal::SIGINT {
+                raise_interrupt();
+            }
+            executor::EXIT_SIGNAL_BASE + sig as i32
+        }
+        Ok(WaitStatus::Stopped(_, sig)) => {
+            let pgid = unistd::Pid::from_raw(job.pid);
+            job.suspended = Some(Instant::now());
+            let code = if STOP_THEN_BG.load(Ordering::Relaxed) && signal::killpg(pgid, Signal::SIGCONT).is_ok() {
+                job.status = JobStatus::Running;
+                println!("\n{}", t!("jobs.continued", job.pid));
+                0
+            } else {
+                job.status = JobStatus::Stopped;
+                println!("\n{}", t!("jobs.paused", job.pid));
+                executor::EXIT_SIGNAL_BASE + sig as i32
+            };
+            if let Ok(mut suspended) = SUSPENDED_JOBS.lock() {
+                suspended.push(job);
+            }
+            code
+        }
+        _ => 1,
+    };
+    if !pipeline && let Ok(WaitStatus::Exited(..) | WaitStatus::Signaled(..)) = status {
+        exits.insert(target.as_raw(), code);
+    }
+    (code, exits)
+}
+
+/// Traz o job `pid` de volta ao foreground (`fg`) e espera por ele.
+pub fn resume_foreground(jobs: &JobList, pid: i32) -> i32 {
+    let Some(mut job) = jobs.lock().ok().and_then(|mut list| list.remove(&pid)) else {
+        return 1;
+    };
+    println!("{}", job.command);
+    let pgid = unistd::Pid::from_raw(pid);
+    let _ = unsafe { signal::signal(Signal::SIGTTOU, SigHandler::SigIgn) };
+    let _ = unistd::tcsetpgrp(io::stdin(), pgid);
+    let _ = signal::killpg(pgid, Signal::SIGCONT);
+    job.status = JobStatus::Running;
+    let code = wait_foreground(job);
+    restore_terminal();
+    code
+}
+
+/// Continua o job parado `pid` em background (`bg`): `SIGCONT` para o
+/// process group, sem passar o terminal. Devolve `false` se o job não estava
+/// parado.
+pub fn resume_background(jobs: &JobList, pid: i32) -> bool {
+    let Ok(mut list) = jobs.lock() else {
+        return false;
+    };
+    let Some(job) = list.get_mut(&pid).filter(|j| j.status == JobStatus::Stopped) else {
+        return false;
+    };
+    let target = unistd::Pid::from_raw(pid);
+    // Um job do `&` pode não liderar um process group
+    if signal::killpg(target, Signal::SIGCONT).is_err() && signal::kill(target, Signal::SIGCONT).is_err() {
+        return false;
+    }
+    job.status = JobStatus::Running;
+    println!("{}", t!("jobs.resumed", pid, job.command));
+    true
+}
+
+// -----------------------------------------------------------------------------
+// REAPER
+// -----------------------------------------------------------------------------
+
+/// Ponta de escrita do self-pipe do `SIGCHLD` (-1 antes do [`start_reaper`]).
+static SIGCHLD_PIPE: AtomicI32 = AtomicI32::new(-1);
+
+/// Travado pela thread do reaper enquanto ela atualiza a lista (ver
+/// [`pause_reaper`]).
+static REAPER: Mutex<()> = Mutex::new(());
+
+/// Handler de `SIGCHLD`: só acorda a thread do reaper (async-signal-safe).
+extern "C" fn handle_sigchld(_: nix::libc::c_int) {
+    let fd = SIGCHLD_PIPE.load(Ordering::Relaxed);
+    if fd < 0 {
+        return;
+    }
+    // O `write` não pode mudar o errno de quem o sinal interrompeu
+    let saved = Errno::last_raw();
+    // SAFETY: um byte de um buffer válido, num fd que não bloqueia
+    let _ = unsafe { nix::libc::write(fd, [0u8].as_ptr().cast(), 1) };
+    Errno::set_raw(saved);
+}
+
+/// Recolhe os jobs em background assim que terminam ou param: o handler de
+/// `SIGCHLD` escreve num pipe e uma thread, acordada por ele, roda o
+/// [`update_jobs`]. O aviso de job concluído continua para o próximo prompt.
+///
+/// Chamado uma vez na inicialização, com a lista de jobs da sessão.
+pub fn start_reaper(jobs: &JobList) -> io::Result<()> {
+    let (mut reader, writer) = io::pipe()?;
+    // Com o pipe cheio o handler descarta o byte: a thread já vai acordar
+    nix::fcntl::fcntl(&writer, nix::fcntl::FcntlArg::F_SETFL(nix::fcntl::OFlag::O_NONBLOCK))?;
+
+    let jobs = Arc::clone(jobs);
+    thread::Builder::new().name("clios-reaper".to_string()).spawn(move || {
+        let mut wakeups = [0u8; 64];
+        loop {
+            // Uma leitura junta todos os `SIGCHLD` que chegaram desde a última
+            match reader.read(&mut wakeups) {
+                Ok(0) => break,
+                Ok(_) => {
+                    let _paused = REAPER.lock();
+                    update_jobs(&jobs);
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(_) => break,
+            }
+        }
+    })?;
+
+    SIGCHLD_PIPE.store(writer.into_raw_fd(), Ordering::Relaxed);
+    // SA_RESTART: o `waitpid` e as leituras interrompidas recomeçam sozinhos
+    let action = SigAction::new(SigHandler::Handler(handle_sigchld), SaFlags::SA_RESTART, SigSet::empty());
+    unsafe { signal::sigaction(Signal::SIGCHLD, &action) }?;
+    Ok(())
+}
+
+/// Segura o reaper durante um `fork` que continua rodando a shell (subshell):
+/// sem isso, o filho poderia herdar a lista de jobs travada no meio de uma
+/// atualização e nunca mais conseguir usá-la.
+pub fn pause_reaper() -> Option<MutexGuard<'static, ()>> {
+    REAPER.lock().ok()
+}
+
+// -----------------------------------------------------------------------------
+// OUTPUT CAPTURE
+// -----------------------------------------------------------------------------
+
+/// Diretório dos arquivos de saída dos jobs desta shell.
+pub fn job_output_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("clios-jobs-{}", std::process::id()))
+}
+
+/// Arquivo de saída de um job.
+pub fn job_output_path(pid: i32) -> PathBuf {
+    job_output_dir().join(format!("{}.log", pid))
+}
+
+/// Os jobs que terminaram e ainda não foram anunciados (esvazia a fila).
+pub fn take_finished_jobs() -> Vec<FinishedJob> {
+    FINISHED_JOBS.lock().map(|mut finished| std::mem::take(&mut *finished)).unwrap_or_default()
+}
+
+/// O aviso de um job que terminou, como no bash: `[1]+ Concluído  sleep 10`.
+pub fn finished_job_message(job: &FinishedJob) -> String {
+    let status = match job.exit {
+        JobExit::Code(0) => t!("jobs.done").to_string(),
+        JobExit::Code(code) => t!("jobs.exit_code", code),
+        JobExit::Signal(sig) => t!("jobs.signaled", sig.as_str()),
+    };
+    format!("[{}]{} {}  {}", job.number, job.mark, status, job.command)
+}
+
+/// Anuncia os jobs que terminaram desde o último prompt, com a saída
+/// capturada de cada um.
+pub fn report_finished_jobs(jobs: &JobList) {
+    update_jobs(jobs);
+    for job in take_finished_jobs() {
+        println!("\x1b[1;36m{}\x1b[0m", finished_job_message(&job));
+        let Some(output) = &job.output else {
+            continue;
+        };
+        if let Ok(content) = fs::read(output) {
+            use std::io::Write;
+            let _ = std::io::stdout().write_all(&content);
+            if !content.is_empty() && !content.ends_with(b"\n") {
+                println!();
+            }
+        }
+        let _ = fs::remove_file(output);
+    }
+}
+
+/// `jobs --output <%N|PID>`: mostra a saída capturada até agora.
+pub fn show_job_output(jobs: &JobList, spec: &str) {
+    update_jobs(jobs);
+    let Some(pid) = resolve_job_spec(jobs, spec) else {
+        eprintln!("{}", t!("jobs.not_found", spec));
+        return;
+    };
+    let output = jobs
+        .lock()
+        .ok()
+        .and_then(|list| list.get(&pid).and_then(|j| j.output.clone()));
+    match output {
+        Some(path) => match fs::read(&path) {
+            Ok(content) => {
+                use std::io::Write;
+                let _ = std::io::stdout().write_all(&content);
+            }
+            Err(e) => eprintln!("jobs: {}: {}", path.display(), e),
+        },
+        None => eprintln!("{}", t!("jobs.not_captured", spec)),
+    }
+}
+
+/// Conta quantos jobs ainda não terminaram (rodando ou parados)
+pub fn count_active_jobs(jobs: &JobList) -> usize {
+    update_jobs(jobs);
+    jobs.lock().map(|list| list.len()).unwrap_or(0)
+}
+
+/// Conta quantos jobs estão parados (Ctrl+Z)
+pub fn count_stopped_jobs(jobs: &JobList) -> usize {
+    update_jobs(jobs);
+    jobs.lock()
+        .map(|list| list.values().filter(|j| j.status == JobStatus::Stopped).count())
+        .unwrap_or(0)
+}
+
+/// Encerra os jobs ao sair da shell: SIGHUP (+ SIGCONT para os parados acordarem
+/// e receberem o sinal), como fazem as shells POSIX.
+pub fn hangup_jobs(jobs: &JobList) {
+    if let Ok(mut list) = jobs.lock() {
+        for job in list.values() {
+            signal_job(job.pid, Signal::SIGHUP);
+            if job.status == JobStatus::Stopped {
+                signal_job(job.pid, Signal::SIGCONT);
+            }
+        }
+        list.clear();
+    }
+    let _ = fs::remove_dir_all(job_output_dir());
+}
+
 /// Lista todos os jobs ativos
 pub fn list_jobs(jobs: &JobList) {
     update_jobs(jobs);
     
     if let Ok(list) = jobs.lock() {
         if list.is_empty() {
-            println!("Nenhum job em background");
+            println!("{}", t!("jobs.none"));
             return;
         }
         
-        println!("Jobs em background:");
-        println!("{:>5}  {:>10}  {}", "PID", "Status", "Comando");
-        println!("{:-<40}", "");
+        println!("{}", t!("jobs.header"));
+        println!("{:>5}  {:>5}  {:>10}  {}", "Job", "PID", "Status", t!("jobs.command_column"));
+        println!("{:-<47}", "");
         
-        for job in list.values() {
+        let recent = recent_jobs(&list);
+        for (i, job) in jobs_in_order(&list).into_iter().enumerate() {
             let status_str = match job.status {
                 JobStatus::Running => "Running",
                 JobStatus::Stopped => "Stopped",
                 JobStatus::Done => "Done",
             };
             let elapsed = job.started.elapsed().as_secs();
-            println!("{:>5}  {:>10}  {} ({}s)", job.pid, status_str, job.command, elapsed);
+            // `+` no job atual e `-` no anterior, como no bash
+            let mark = match recent.iter().position(|&pid| pid == job.pid) {
+                Some(0) => "+",
+                Some(1) => "-",
+                _ => " ",
+            };
+            println!("{:>5}  {:>5}  {:>10}  {} ({}s)", format!("%{}{}", i + 1, mark), job.pid, status_str, job.command, elapsed);
         }
     }
 }
 
+// -----------------------------------------------------------------------------
+// JOB MONITOR
+// -----------------------------------------------------------------------------
+
+/// Intervalo entre as atualizações do `jobs --watch`.
+pub const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Consumo de um job: a soma dos processos do process group dele (o job e
+/// os filhos que ele criou).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct JobUsage {
+    /// Tempo de CPU acumulado, em ticks do relógio (`utime + stime`)
+    pub ticks: u64,
+    /// Memória residente, em páginas
+    pub rss_pages: u64,
+}
+
+/// O process group, o tempo de CPU e a memória de um `/proc/<pid>/stat`.
+pub fn parse_proc_stat(content: &str) -> Option<(i32, JobUsage)> {
+    // O nome do comando (campo 2) vem entre parênteses e pode ter espaços
+    let fields: Vec<&str> = content.rsplit_once(')')?.1.split_whitespace().collect();
+    // A contagem começa no campo 3 (estado)
+    let field = |n: usize| fields.get(n - 3).and_then(|f| f.parse::<u64>().ok());
+    let pgrp = fields.get(5 - 3)?.parse().ok()?;
+    let usage = JobUsage { ticks: field(14)? + field(15)?, rss_pages: field(24)? };
+    Some((pgrp, usage))
+}
+
+/// Consumo por process group de todos os processos em `/proc`.
+fn usage_by_group() -> HashMap<i32, JobUsage> {
+    let mut groups: HashMap<i32, JobUsage> = HashMap::new();
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return groups;
+    };
+    for entry in entries.flatten() {
+        if !entry.file_name().to_string_lossy().bytes().all(|b| b.is_ascii_digit()) {
+            continue;
+        }
+        if let Some((pgrp, usage)) = fs::read_to_string(entry.path().join("stat")).ok().as_deref().and_then(parse_proc_stat) {
+            let total = groups.entry(pgrp).or_default();
+            total.ticks += usage.ticks;
+            total.rss_pages += usage.rss_pages;
+        }
+    }
+    groups
+}
+
+/// Percentual de um núcleo usado entre duas leituras.
+pub fn cpu_percent(before: u64, after: u64, elapsed: Duration, ticks_per_second: u64) -> f64 {
+    let seconds = elapsed.as_secs_f64();
+    if seconds <= 0.0 || ticks_per_second == 0 {
+        return 0.0;
+    }
+    after.saturating_sub(before) as f64 / ticks_per_second as f64 / seconds * 100.0
+}
+
+/// `_SC_CLK_TCK` e o tamanho da página, com os valores usuais como reserva.
+fn system_units() -> (u64, u64) {
+    let value = |var, default| unistd::sysconf(var).ok().flatten().and_then(|v| u64::try_from(v).ok()).unwrap_or(default);
+    (value(unistd::SysconfVar::CLK_TCK, 100), value(unistd::SysconfVar::PAGE_SIZE, 4096))
+}
+
+fn draw_monitor(jobs: &[BackgroundJob], usage: &HashMap<i32, JobUsage>, cpu: &HashMap<i32, f64>, page_size: u64) {
+    let mut out = String::from("\x1b[H\x1b[2J");
+    out.push_str(&format!(
+        "\x1b[1;36m{}\x1b[0m    {}\n\n",
+        t!("jobs.watch_header"),
+        chrono::Local::now().format("%H:%M:%S")
+    ));
+    out.push_str(&format!(
+        "\x1b[1m{:>4}  {:>7}  {:>8}  {:>6}  {:>9}  {:>7}  {}\x1b[0m\n",
+        "Job", "PID", "Status", "CPU%", "MEM", t!("jobs.watch_time"), t!("jobs.watch_command")
+    ));
+    // Job, PID, status, CPU, memória e tempo ocupam a parte fixa da linha
+    let command_width = terminal_width().map(|w| w.saturating_sub(4 + 7 + 8 + 6 + 9 + 7 + 12).max(10));
+    for (i, job) in jobs.iter().enumerate() {
+        let (status, color) = match job.status {
+            JobStatus::Running => ("Running", "\x1b[32m"),
+            JobStatus::Stopped => ("Stopped", "\x1b[33m"),
+            JobStatus::Done => ("Done", "\x1b[90m"),
+        };
+        let memory = usage.get(&job.pid).map(|u| human_size(u.rss_pages * page_size)).unwrap_or_else(|| "-".to_string());
+        let cpu = cpu.get(&job.pid).map(|c| format!("{:.1}", c)).unwrap_or_else(|| "-".to_string());
+        let command = command_width.map_or_else(|| job.command.clone(), |w| truncate(&job.command, w));
+        out.push_str(&format!(
+            "{:>4}  \x1b[36m{:>7}\x1b[0m  {}{:>8}\x1b[0m  {:>6}  {:>9}  {:>7}  {}\n",
+            format!("%{}", i + 1),
+            job.pid,
+            color,
+            status,
+            cpu,
+            memory,
+            format_elapsed(job.started.elapsed()),
+            command
+        ));
+    }
+    print!("{}", out);
+    let _ = io::stdout().flush();
+}
+
+/// Tempo de execução curto: `42s`, `3m05s`, `2h07m`.
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// `jobs --watch`: a tabela dos jobs com CPU e memória, atualizada a cada
+/// segundo até uma tecla (ou Ctrl+C), ou até todos os jobs terminarem.
+pub fn watch_jobs(jobs: &JobList) {
+    if !io::stdout().is_terminal() {
+        eprintln!("{}", t!("jobs.watch_needs_terminal"));
+        return;
+    }
+    let Some(keys) = KeyReader::new() else {
+        eprintln!("{}", t!("jobs.watch_needs_terminal"));
+        return;
+    };
+    let (ticks_per_second, page_size) = system_units();
+    // Tela alternativa sem cursor, como o `top`: o scrollback continua intacto
+    print!("\x1b[?1049h\x1b[?25l");
+
+    let mut previous: Option<(Instant, HashMap<i32, JobUsage>)> = None;
+    let finished = loop {
+        update_jobs(jobs);
+        let current: Vec<BackgroundJob> = match jobs.lock() {
+            Ok(list) => jobs_in_order(&list).into_iter().cloned().collect(),
+            Err(_) => break false,
+        };
+        if current.is_empty() {
+            break true;
+        }
+
+        let now = Instant::now();
+        let usage = usage_by_group();
+        let cpu: HashMap<i32, f64> = match &previous {
+            Some((at, before)) => current
+                .iter()
+                .filter_map(|job| {
+                    let after = usage.get(&job.pid)?.ticks;
+                    let before = before.get(&job.pid).map_or(after, |u| u.ticks);
+                    Some((job.pid, cpu_percent(before, after, now - *at, ticks_per_second)))
+                })
+                .collect(),
+            None => HashMap::new(),
+        };
+        draw_monitor(&current, &usage, &cpu, page_size);
+        previous = Some((now, usage));
+
+        if keys.key_pressed(WATCH_INTERVAL) || take_interrupt() {
+            break false;
+        }
+    };
+
+    print!("\x1b[?25h\x1b[?1049l");
+    let _ = io::stdout().flush();
+    if finished {
+        println!("{}", t!("jobs.watch_all_done"));
+    }
+}
+
+// -----------------------------------------------------------------------------
+// SCRIPT ACCESS
+// -----------------------------------------------------------------------------
+
+/// Um job como os plugins o veem (`list_jobs()` no Rhai).
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobInfo {
+    /// A posição no `jobs` (`%N`)
+    pub number: usize,
+    pub pid: i32,
+    pub command: String,
+    pub status: JobStatus,
+    pub elapsed: Duration,
+    /// Tempo de CPU do process group, em segundos
+    pub cpu_seconds: f64,
+    /// Memória residente do process group, em bytes
+    pub memory: u64,
+}
+
+/// Os jobs ativos, na ordem do `jobs`, com o consumo de cada um.
+pub fn job_snapshot(jobs: &JobList) -> Vec<JobInfo> {
+    update_jobs(jobs);
+    let Ok(list) = jobs.lock() else {
+        return Vec::new();
+    };
+    let usage = usage_by_group();
+    let (ticks_per_second, page_size) = system_units();
+    jobs_in_order(&list)
+        .into_iter()
+        .enumerate()
+        .map(|(i, job)| {
+            let used = usage.get(&job.pid).copied().unwrap_or_default();
+            JobInfo {
+                number: i + 1,
+                pid: job.pid,
+                command: job.command.clone(),
+                status: job.status.clone(),
+                elapsed: job.started.elapsed(),
+                cpu_seconds: used.ticks as f64 / ticks_per_second.max(1) as f64,
+                memory: used.rss_pages * page_size,
+            }
+        })
+        .collect()
+}
+
+/// Manda `sig` para o job `pid` (o process group inteiro, quando ele lidera
+/// um); um job parado recebe também `SIGCONT`, para tratar o sinal. Devolve
+/// `false` se o job não existe ou o sinal não pôde ser enviado.
+pub fn kill_job(jobs: &JobList, pid: i32, sig: Signal) -> bool {
+    let stopped = match jobs.lock() {
+        Ok(list) => match list.get(&pid) {
+            Some(job) => job.status == JobStatus::Stopped,
+            None => return false,
+        },
+        Err(_) => return false,
+    };
+    let sent = signal_job(pid, sig);
+    if sent && stopped && sig != Signal::SIGCONT {
+        signal_job(pid, Signal::SIGCONT);
+    }
+    sent
+}
+
+/// `sig` para o process group do job (todos os comandos de uma pipeline) ou,
+/// se ele não lidera um, só para o processo.
+fn signal_job(pid: i32, sig: Signal) -> bool {
+    let target = unistd::Pid::from_raw(pid);
+    signal::killpg(target, sig).is_ok() || signal::kill(target, sig).is_ok()
+}
+
+/// O código de saída de um job que terminou, como o `$?` (`128 + sinal`
+/// quando foi morto).
+pub fn exit_code(exit: JobExit) -> i32 {
+    match exit {
+        JobExit::Code(code) => code,
+        JobExit::Signal(sig) => executor::EXIT_SIGNAL_BASE + sig as i32,
+    }
+}
+
+/// Espera o job `pid` terminar e devolve o código de saída. `None` se o job
+/// não existe, se `timeout` passou ou se veio um Ctrl+C. O aviso de job
+/// concluído continua para o próximo prompt.
+pub fn wait_job(jobs: &JobList, pid: i32, timeout: Option<Duration>) -> Option<i32> {
+    let started = Instant::now();
+    loop {
+        update_jobs(jobs);
+        let running = jobs.lock().ok()?.contains_key(&pid);
+        if !running {
+            let finished = FINISHED_JOBS.lock().ok()?;
+            return finished.iter().rev().find(|j| j.pid == pid).map(|j| exit_code(j.exit));
+        }
+        if timeout.is_some_and(|t| started.elapsed() >= t) || is_interrupted() {
+            return None;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
+
+/// Builtin `wait [%N|PID...]`: espera os jobs em background terminarem.
+///
+/// Sem argumentos, espera todos os que estão rodando (um parado esperaria
+/// para sempre) e sai com 0; com argumentos, sai com o código do último, como
+/// no POSIX: 127 para um job que não existe. Ctrl+C para de esperar.
+pub fn handle_wait(tokens: &[String], jobs: &JobList) -> i32 {
+    update_jobs(jobs);
+    let targets: Vec<Result<i32, &String>> = if tokens.len() == 1 {
+        match jobs.lock() {
+            Ok(list) => jobs_in_order(&list).into_iter().filter(|j| j.status == JobStatus::Running).map(|j| Ok(j.pid)).collect(),
+            Err(_) => return 1,
+        }
+    } else {
+        tokens[1..].iter().map(|spec| resolve_job_spec(jobs, spec).ok_or(spec)).collect()
+    };
+
+    let mut code = 0;
+    for target in targets {
+        code = match target {
+            Ok(pid) => match wait_job(jobs, pid, None) {
+                Some(code) => code,
+                None if take_interrupt() => return EXIT_INTERRUPTED,
+                None => 127,
+            },
+            Err(spec) => {
+                eprintln!("{}", t!("jobs.wait_not_found", spec));
+                127
+            }
+        };
+    }
+    if tokens.len() == 1 { 0 } else { code }
+}
+
+/// Tira o job `pid` da lista: a shell não o anuncia, não espera por ele nem
+/// manda SIGHUP ao sair. Um job parado recebe SIGCONT, já que nenhum `fg` ou
+/// `bg` o alcançaria depois.
+pub fn disown_job(jobs: &JobList, pid: i32) -> bool {
+    let Some(job) = jobs.lock().ok().and_then(|mut list| list.remove(&pid)) else {
+        return false;
+    };
+    if job.status == JobStatus::Stopped {
+        signal_job(pid, Signal::SIGCONT);
+    }
+    if let Ok(mut unreaped) = UNREAPED.lock() {
+        unreaped.push(pid);
+    }
+    true
+}
+
+/// Builtin `disown [-a] [%N|PID...]`: sem argumentos, o job atual; `-a`, todos.
+/// As especificações são resolvidas antes de qualquer remoção, que muda a
+/// numeração de `%N`.
+pub fn handle_disown(tokens: &[String], jobs: &JobList) -> i32 {
+    update_jobs(jobs);
+    let targets: Vec<Result<i32, &String>> = match &tokens[1..] {
+        [] => match current_job(jobs) {
+            Some(pid) => vec![Ok(pid)],
+            None => {
+                eprintln!("{}", t!("jobs.disown_no_current"));
+                return 1;
+            }
+        },
+        [flag] if flag == "-a" => match jobs.lock() {
+            Ok(list) => list.keys().map(|&pid| Ok(pid)).collect(),
+            Err(_) => return 1,
+        },
+        specs if specs.iter().any(|s| s.starts_with('-') && s != "%-") => {
+            eprintln!("{}", usage("disown"));
+            return 2;
+        }
+        specs => specs.iter().map(|spec| resolve_job_spec(jobs, spec).ok_or(spec)).collect(),
+    };
+
+    let mut code = 0;
+    for target in targets {
+        match target {
+            Ok(pid) => {
+                disown_job(jobs, pid);
+            }
+            Err(spec) => {
+                eprintln!("{}", t!("jobs.disown_not_found", spec));
+                code = 1;
+            }
+        }
+    }
+    code
+}
+
 // -----------------------------------------------------------------------------
 // JOB CONTROL EXECUTION
 // -----------------------------------------------------------------------------
 
-/// Execução de baixo nível com controle total de Processos (Job Control - Nível 5).
+/// Execução de baixo nível de um job em background (`comando &`).
 ///
 /// Diferente do `execute_pipeline` (que usa a std lib), esta função usa `nix` para
-/// chamar `fork` e `exec` manualmente. Isso é necessário para manipular
-/// **Process Groups** e definir quem é o "dono" do terminal.
+/// chamar `fork` e `exec` manualmente. Isso é necessário para dar ao job um
+/// **Process Group** próprio: o `Ctrl+C` e o `Ctrl+Z` do terminal não chegam
+/// a ele, e o `fg` depois entrega o terminal ao grupo.
 ///
-/// # A Dança do Terminal (Terminal Handoff)
-/// Para que o `Ctrl+C` vá para o processo certo, precisamos transferir a posse
-/// do terminal (STDIN) da Shell para o Processo Filho.
+/// Com `capture_output`, o job escreve STDOUT/STDERR em [`job_output_path`]
+/// em vez do terminal.
 ///
-/// 1. **Shell:** Ignora `SIGTTOU` (para não ser suspensa ao mexer no terminal).
-/// 2. **Fork:** Cria uma cópia do processo.
-/// 3. **Pai & Filho:** Ambos tentam setar o `setpgid` (para evitar race conditions).
-/// 4. **Pai:** Dá o terminal pro filho (`tcsetpgrp`) e espera (`waitpid`).
-/// 5. **Pai:** Quando o filho morre/para, pega o terminal de volta.
-pub fn execute_job_control(tokens: Vec<String>, background: bool, jobs: &JobList) {
-    // Segurança: Ignorar SIGTTOU na shell
-    unsafe { signal::signal(Signal::SIGTTOU, SigHandler::SigIgn) }.unwrap();
-
-    // Guarda o comando original para registro
-    let command = tokens.join(" ");
+/// Retorna 0, ou 1 se o job não pôde ser criado (limites de
+/// [`crate::limits`] ou falha do `fork`). Um comando que roda na frente passa
+/// pelo `execute_pipeline`, que espera com [`wait_foreground`].
+pub fn execute_job_control(tokens: Vec<String>, command: &str, jobs: &JobList, capture_output: bool) -> i32 {
+    // Limites contra fork bombs: jobs ativos e processos por segundo
+    if let Err(e) = check_job_slot(count_active_jobs(jobs)).and_then(|_| admit_spawn()) {
+        eprintln!("{}", t!("tag.error", e));
+        return 1;
+    }
+
+    let capture_output = capture_output && fs::create_dir_all(job_output_dir()).is_ok();
+    // Calculado antes do fork: no filho, `process::id()` já é o PID do job
+    let output_dir = job_output_dir();
 
     match unsafe { unistd::fork() } {
         Ok(unistd::ForkResult::Parent { child, .. }) => {
             // --- CÓDIGO DO PAI (SHELL) ---
-            let pgid = child;
+            let _ = unistd::setpgid(child, child);
 
-            let _ = unistd::setpgid(child, pgid);
-
-            if !background {
-                let _ = unistd::tcsetpgrp(std::io::stdin(), pgid);
-
-                match wait::waitpid(child, Some(WaitPidFlag::WUNTRACED)) {
-                    Ok(WaitStatus::Stopped(_, _sig)) => {
-                        println!("\n[Job {}] Pausado (Ctrl+Z)", child);
-                    }
-                    Ok(WaitStatus::Signaled(_, sig, _)) => {
-                        println!("\n[Job {}] Morto pelo sinal: {:?}", child, sig);
-                    }
-                    _ => {}
-                }
-
-                let shell_pgid = unistd::getpid();
-                let _ = unistd::tcsetpgrp(std::io::stdin(), shell_pgid);
-            } else {
-                // Adiciona job à lista
-                add_job(jobs, child.as_raw(), command.clone());
-                println!("[Background Job {}]", child);
+            // Adiciona job à lista
+            add_job(jobs, child.as_raw(), command.to_string());
+            if capture_output
+                && let Ok(mut list) = jobs.lock()
+                && let Some(job) = list.get_mut(&child.as_raw())
+            {
+                job.output = Some(job_output_path(child.as_raw()));
             }
+            println!("{}", t!("jobs.started", child));
+            0
         }
         Ok(unistd::ForkResult::Child) => {
             // --- CÓDIGO DO FILHO (COMANDO) ---
             let pid = unistd::getpid();
             let _ = unistd::setpgid(pid, pid);
 
-            if !background {
-                let _ = unistd::tcsetpgrp(std::io::stdin(), pid);
-            }
+            let _ = unsafe { signal::signal(Signal::SIGTTOU, SigHandler::SigDfl) };
+            let _ = unsafe { signal::signal(Signal::SIGINT, SigHandler::SigDfl) };
 
-            unsafe { signal::signal(Signal::SIGTTOU, SigHandler::SigDfl) }.unwrap();
-            unsafe { signal::signal(Signal::SIGINT, SigHandler::SigDfl) }.unwrap();
-
-            use std::os::unix::process::CommandExt;
+            // Saída capturada: STDOUT e STDERR vão para o arquivo do job
+            if capture_output
+                && let Ok(file) = fs::File::create(output_dir.join(format!("{}.log", pid)))
+            {
+                let _ = unistd::dup2_stdout(&file);
+                let _ = unistd::dup2_stderr(&file);
+            }
 
-            let err = Command::new(&tokens[0]).args(&tokens[1..]).exec();
+            let err = executor::exec_replace(&tokens, ProcessGroup::Own);
 
-            eprintln!("Erro ao executar '{}': {}", tokens[0], err);
-            std::process::exit(1);
+            eprintln!("{}", t!("jobs.exec_failed", tokens[0], err.error));
+            std::process::exit(err.exit_code());
+        }
+        Err(e) => {
+            println!("{}", executor::fork_error(&e.into()));
+            1
         }
-        Err(_) => println!("Fork falhou - Sistema sem recursos"),
     }
 }
@@ -0,0 +1,433 @@
+//! # Keys Module
+//!
+//! Registro dos atalhos do editor de linha, desfazer/refazer e o builtin
+//! `keys` (também `help keys`).
+//!
+//! [`active_bindings`] monta a tabela de atalhos ativos a partir da
+//! configuração: os padrões do `rustyline` (emacs ou vi, só documentados) e os
+//! instalados pela shell (abreviações, Alt+H, Alt+O, fechamentos do
+//! `auto_pair`, desfazer/refazer do `[keys]`). O `main` liga as teclas
+//! percorrendo essa mesma tabela, então a listagem não tem como divergir do
+//! que está ativo.
+//!
+//! O `rustyline` já desfaz (`Ctrl+_`) e tem um kill-ring (`Ctrl+K`, `Ctrl+U`,
+//! `Ctrl+W` e `Alt+D` guardam o texto apagado; `Ctrl+Y` cola e `Alt+Y` troca
+//! pelo anterior), mas não expõe o "refazer". Aqui:
+//!
+//! - As teclas de desfazer (padrão `Ctrl+_` e `Ctrl+Z`, seção `[keys]`) passam
+//!   pelo [`UndoKey`], que guarda a linha antes de desfazer
+//! - As de refazer (padrão `Alt+Z`) passam pelo [`RedoKey`], que devolve a
+//!   linha guardada
+//! - Qualquer outra edição esvazia a pilha de refazer: o highlighter chama
+//!   [`EditHistory::observe`] a cada redesenho, e uma mudança que não veio de
+//!   desfazer/refazer é uma edição nova
+
+use crate::autopair::CLOSERS;
+use crate::config::{CliosConfig, ConfigKeys};
+use crate::t;
+use rustyline::{Cmd, ConditionalEventHandler, Event, EventContext, KeyCode, KeyEvent, Modifiers, Movement, RepeatCount};
+use std::sync::{Arc, Mutex};
+
+/// Teclas de desfazer sem `[keys] undo`.
+pub const DEFAULT_UNDO: &[&str] = &["ctrl-_", "ctrl-z"];
+
+/// Teclas de refazer sem `[keys] redo`.
+pub const DEFAULT_REDO: &[&str] = &["alt-z"];
+
+/// Tecla a partir do nome da configuração (`ctrl-z`, `alt-z`, `c-_`, `m-z`).
+pub fn parse_key(spec: &str) -> Option<KeyEvent> {
+    let spec = spec.trim().to_lowercase();
+    let (modifier, key) = spec.rsplit_once('-').filter(|(_, k)| !k.is_empty()).unwrap_or(("", &spec));
+    let mut chars = key.chars();
+    let (Some(c), None) = (chars.next(), chars.next()) else {
+        return None;
+    };
+    match modifier {
+        "ctrl" | "c" => Some(KeyEvent::ctrl(c)),
+        "alt" | "meta" | "m" => Some(KeyEvent::alt(c)),
+        _ => None,
+    }
+}
+
+/// Nome da tecla na listagem (`Ctrl+Z`, `Alt+Backspace`, `Tab`, `→`).
+pub fn event_label(event: &KeyEvent) -> String {
+    let KeyEvent(code, mods) = *event;
+    let key = match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) if mods.is_empty() => c.to_string(),
+        KeyCode::Char(c) => c.to_uppercase().to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        other => format!("{:?}", other),
+    };
+    let mut label = String::new();
+    if mods.contains(Modifiers::CTRL) {
+        label.push_str("Ctrl+");
+    }
+    if mods.contains(Modifiers::ALT) {
+        label.push_str("Alt+");
+    }
+    label.push_str(&key);
+    label
+}
+
+/// Teclas configuradas de desfazer e refazer (ou as padrão).
+pub fn configured_keys(config: Option<&ConfigKeys>) -> (Vec<String>, Vec<String>) {
+    let pick = |keys: Option<&Vec<String>>, default: &[&str]| match keys {
+        Some(keys) => keys.clone(),
+        None => default.iter().map(|k| k.to_string()).collect(),
+    };
+    (
+        pick(config.and_then(|k| k.undo.as_ref()), DEFAULT_UNDO),
+        pick(config.and_then(|k| k.redo.as_ref()), DEFAULT_REDO),
+    )
+}
+
+// -----------------------------------------------------------------------------
+// UNDO / REDO
+// -----------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct EditState {
+    /// Linhas desfeitas, a mais recente no fim
+    redo: Vec<String>,
+    /// Linha do último redesenho
+    last_line: String,
+    /// A próxima mudança vem de desfazer/refazer (não esvazia a pilha)
+    expect_change: bool,
+}
+
+/// Pilha de refazer, compartilhada entre os handlers de tecla e o highlighter.
+#[derive(Debug, Clone, Default)]
+pub struct EditHistory(Arc<Mutex<EditState>>);
+
+impl EditHistory {
+    /// Chamado a cada redesenho da linha.
+    pub fn observe(&self, line: &str) {
+        let Ok(mut state) = self.0.lock() else {
+            return;
+        };
+        if state.last_line == line {
+            return;
+        }
+        if !state.expect_change {
+            state.redo.clear();
+        }
+        state.expect_change = false;
+        state.last_line = line.to_string();
+    }
+
+    /// Guarda `line` antes de desfazê-la.
+    pub fn push_undone(&self, line: &str) {
+        if let Ok(mut state) = self.0.lock() {
+            if state.redo.last().map(String::as_str) != Some(line) {
+                state.redo.push(line.to_string());
+            }
+            state.expect_change = true;
+        }
+    }
+
+    /// A última linha desfeita, se ainda dá para refazer.
+    pub fn pop_redo(&self) -> Option<String> {
+        let mut state = self.0.lock().ok()?;
+        let line = state.redo.pop()?;
+        state.expect_change = true;
+        Some(line)
+    }
+}
+
+/// Desfaz a última edição, guardando a linha para o refazer.
+pub struct UndoKey {
+    pub history: EditHistory,
+}
+
+impl ConditionalEventHandler for UndoKey {
+    fn handle(&self, _evt: &Event, n: RepeatCount, _positive: bool, ctx: &EventContext) -> Option<Cmd> {
+        self.history.push_undone(ctx.line());
+        Some(Cmd::Undo(n))
+    }
+}
+
+/// Refaz o último desfazer (a troca da linha inteira também pode ser desfeita).
+pub struct RedoKey {
+    pub history: EditHistory,
+}
+
+impl ConditionalEventHandler for RedoKey {
+    fn handle(&self, _evt: &Event, _n: RepeatCount, _positive: bool, _ctx: &EventContext) -> Option<Cmd> {
+        // Sem nada para refazer a tecla não faz nada (o padrão do rustyline
+        // para Alt+tecla engoliria a tecla seguinte)
+        match self.history.pop_redo() {
+            Some(line) => Some(Cmd::Replace(Movement::WholeBuffer, Some(line))),
+            None => Some(Cmd::Noop),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// REGISTRY
+// -----------------------------------------------------------------------------
+
+/// Grupo da listagem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum KeyGroup {
+    Move,
+    Undo,
+    Kill,
+    Shell,
+}
+
+impl KeyGroup {
+    pub fn title(self) -> &'static str {
+        match self {
+            KeyGroup::Move => t!("keys.section_move"),
+            KeyGroup::Undo => t!("keys.section_undo"),
+            KeyGroup::Kill => t!("keys.section_kill"),
+            KeyGroup::Shell => t!("keys.section_shell"),
+        }
+    }
+}
+
+/// O que o atalho faz.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyAction {
+    /// Padrão do `rustyline` (grupo e chave da descrição); não é ligado pela shell
+    Editor(KeyGroup, &'static str),
+    Undo,
+    Redo,
+    /// Expande a abreviação antes do cursor (`abbr`)
+    ExpandAbbr,
+    /// man/tldr do comando sendo digitado
+    Docs,
+    /// Insere a saída do último comando (`capture_output`)
+    LastOutput,
+    /// Pula o fechamento já presente (`auto_pair`)
+    SkipCloser,
+}
+
+impl KeyAction {
+    pub fn group(self) -> KeyGroup {
+        match self {
+            KeyAction::Editor(group, _) => group,
+            KeyAction::Undo | KeyAction::Redo => KeyGroup::Undo,
+            _ => KeyGroup::Shell,
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            KeyAction::Editor(_, key) => t!(key),
+            KeyAction::Undo => t!("keys.undo"),
+            KeyAction::Redo => t!("keys.redo"),
+            KeyAction::ExpandAbbr => t!("keys.expand_abbr"),
+            KeyAction::Docs => t!("keys.docs"),
+            KeyAction::LastOutput => t!("keys.last_output"),
+            KeyAction::SkipCloser => t!("keys.skip_closer"),
+        }
+    }
+}
+
+/// De onde vem o atalho.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyOrigin {
+    /// Padrão do `rustyline`
+    Editor,
+    /// Ligado pela shell
+    Shell,
+    /// Seção `[keys]` do `.clios.toml`
+    Config,
+}
+
+impl KeyOrigin {
+    pub fn label(self) -> &'static str {
+        match self {
+            KeyOrigin::Editor => "rustyline",
+            KeyOrigin::Shell => "clios",
+            KeyOrigin::Config => "[keys]",
+        }
+    }
+}
+
+/// Um atalho ativo: uma ou mais teclas com a mesma ação.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyBinding {
+    pub keys: Vec<KeyEvent>,
+    pub action: KeyAction,
+    pub origin: KeyOrigin,
+}
+
+impl KeyBinding {
+    /// As teclas, separadas por ` / `.
+    pub fn label(&self) -> String {
+        self.keys.iter().map(event_label).collect::<Vec<_>>().join(" / ")
+    }
+
+    /// Ligado pela shell (os do `rustyline` já estão no editor).
+    pub fn is_installed(&self) -> bool {
+        !matches!(self.action, KeyAction::Editor(..))
+    }
+}
+
+/// Padrões do modo emacs do `rustyline`.
+fn emacs_defaults() -> Vec<(KeyEvent, KeyGroup, &'static str)> {
+    use KeyGroup::*;
+    vec![
+        (KeyEvent::ctrl('a'), Move, "keys.line_start"),
+        (KeyEvent::ctrl('e'), Move, "keys.line_end"),
+        (KeyEvent::alt('b'), Move, "keys.word_back"),
+        (KeyEvent::alt('f'), Move, "keys.word_forward"),
+        (KeyEvent(KeyCode::Up, Modifiers::NONE), Move, "keys.history_prev"),
+        (KeyEvent(KeyCode::Down, Modifiers::NONE), Move, "keys.history_next"),
+        (KeyEvent::ctrl('r'), Move, "keys.history_search"),
+        (KeyEvent::ctrl('l'), Move, "keys.clear_screen"),
+        (KeyEvent::ctrl('k'), Kill, "keys.kill_end"),
+        (KeyEvent::ctrl('u'), Kill, "keys.kill_start"),
+        (KeyEvent::ctrl('w'), Kill, "keys.kill_word_back"),
+        (KeyEvent(KeyCode::Backspace, Modifiers::ALT), Kill, "keys.kill_word_back"),
+        (KeyEvent::alt('d'), Kill, "keys.kill_word"),
+        (KeyEvent::ctrl('y'), Kill, "keys.yank"),
+        (KeyEvent::alt('y'), Kill, "keys.yank_pop"),
+    ]
+}
+
+/// Padrões do modo vi do `rustyline` (as letras valem no modo normal).
+fn vi_defaults() -> Vec<(KeyEvent, KeyGroup, &'static str)> {
+    use KeyGroup::*;
+    let key = |c: char| KeyEvent(KeyCode::Char(c), Modifiers::NONE);
+    vec![
+        (KeyEvent(KeyCode::Esc, Modifiers::NONE), Move, "keys.vi_normal"),
+        (key('i'), Move, "keys.vi_insert"),
+        (key('a'), Move, "keys.vi_append"),
+        (key('0'), Move, "keys.line_start"),
+        (key('$'), Move, "keys.line_end"),
+        (key('b'), Move, "keys.word_back"),
+        (key('w'), Move, "keys.word_forward"),
+        (key('k'), Move, "keys.history_prev"),
+        (key('j'), Move, "keys.history_next"),
+        (KeyEvent::ctrl('r'), Move, "keys.history_search"),
+        (key('u'), Undo, "keys.undo"),
+        (key('x'), Kill, "keys.delete_char"),
+        (key('D'), Kill, "keys.kill_end"),
+        (key('p'), Kill, "keys.vi_put"),
+    ]
+}
+
+/// Atalhos ativos para a configuração, na ordem da listagem.
+pub fn active_bindings(config: &CliosConfig) -> Vec<KeyBinding> {
+    let behavior = config.behavior.as_ref();
+    let vi = behavior.and_then(|b| b.edit_mode.as_deref()).is_some_and(|m| m.eq_ignore_ascii_case("vi"));
+    let auto_pair = behavior.and_then(|b| b.auto_pair).unwrap_or(false);
+
+    let mut installed = Vec::new();
+    let (undo, redo) = configured_keys(config.keys.as_ref());
+    let origin = if config.keys.is_some() { KeyOrigin::Config } else { KeyOrigin::Shell };
+    for (specs, action) in [(undo, KeyAction::Undo), (redo, KeyAction::Redo)] {
+        let keys: Vec<KeyEvent> = specs.iter().filter_map(|spec| parse_key(spec)).collect();
+        if !keys.is_empty() {
+            installed.push(KeyBinding { keys, action, origin });
+        }
+    }
+    let shell = |key: KeyEvent, action| KeyBinding { keys: vec![key], action, origin: KeyOrigin::Shell };
+    installed.push(shell(KeyEvent(KeyCode::Char(' '), Modifiers::NONE), KeyAction::ExpandAbbr));
+    installed.push(shell(KeyEvent::alt('h'), KeyAction::Docs));
+    installed.push(shell(KeyEvent::alt('o'), KeyAction::LastOutput));
+    if auto_pair {
+        installed.push(KeyBinding {
+            keys: CLOSERS.iter().map(|c| KeyEvent(KeyCode::Char(*c), Modifiers::NONE)).collect(),
+            action: KeyAction::SkipCloser,
+            origin: KeyOrigin::Shell,
+        });
+    }
+
+    // Padrões do editor, menos as teclas que a shell tomou para si
+    let taken = |key: &KeyEvent| installed.iter().any(|b| b.keys.contains(key));
+    let mut defaults = if vi { vi_defaults() } else { emacs_defaults() };
+    defaults.extend([
+        (KeyEvent(KeyCode::Tab, Modifiers::NONE), KeyGroup::Shell, "keys.complete"),
+        (KeyEvent(KeyCode::Right, Modifiers::NONE), KeyGroup::Shell, "keys.accept_hint"),
+        (KeyEvent::ctrl('c'), KeyGroup::Shell, "keys.cancel_line"),
+        (KeyEvent::ctrl('d'), KeyGroup::Shell, "keys.exit"),
+    ]);
+    let mut bindings: Vec<KeyBinding> = defaults
+        .into_iter()
+        .filter(|(key, _, _)| !taken(key))
+        .map(|(key, group, description)| KeyBinding {
+            keys: vec![key],
+            action: KeyAction::Editor(group, description),
+            origin: KeyOrigin::Editor,
+        })
+        .collect();
+    bindings.extend(installed);
+    bindings.sort_by_key(|b| b.action.group());
+    bindings
+}
+
+/// Teclas do `[keys]` que não são reconhecidas (aviso na inicialização).
+pub fn invalid_keys(config: &CliosConfig) -> Vec<String> {
+    let (undo, redo) = configured_keys(config.keys.as_ref());
+    undo.into_iter().chain(redo).filter(|spec| parse_key(spec).is_none()).collect()
+}
+
+// -----------------------------------------------------------------------------
+// BUILTIN
+// -----------------------------------------------------------------------------
+
+/// Listagem agrupada dos atalhos.
+pub fn format_bindings(bindings: &[KeyBinding]) -> String {
+    let mut page = String::new();
+    let mut group = None;
+    for binding in bindings {
+        let current = binding.action.group();
+        if group != Some(current) {
+            if group.is_some() {
+                page.push('\n');
+            }
+            page.push_str(&format!("\x1b[1;32m{}\x1b[0m\n", current.title()));
+            group = Some(current);
+        }
+        page.push_str(&format!(
+            "  \x1b[1;33m{:<22}\x1b[0m {:<48} \x1b[90m{}\x1b[0m\n",
+            binding.label(),
+            binding.action.description(),
+            binding.origin.label()
+        ));
+    }
+    page
+}
+
+/// Atalhos que casam com o filtro do `keys`: a tecla (`ctrl-z`) ou um trecho
+/// do nome ou da descrição.
+pub fn filter_bindings(bindings: Vec<KeyBinding>, filter: &str) -> Vec<KeyBinding> {
+    let key = parse_key(filter);
+    let text = filter.to_lowercase();
+    bindings
+        .into_iter()
+        .filter(|b| match key {
+            Some(key) => b.keys.contains(&key),
+            None => b.label().to_lowercase().contains(&text) || b.action.description().to_lowercase().contains(&text),
+        })
+        .collect()
+}
+
+/// Builtin `keys [tecla | texto]` (e `help keys`).
+pub fn handle_keys(tokens: &[String], config: &CliosConfig) -> i32 {
+    let bindings = active_bindings(config);
+    let Some(filter) = tokens.get(1) else {
+        print!("{}", format_bindings(&bindings));
+        println!("\n{}", t!("keys.config_hint"));
+        return 0;
+    };
+    let found = filter_bindings(bindings, filter);
+    if found.is_empty() {
+        eprintln!("keys: {}", t!("keys.not_found", filter));
+        return 1;
+    }
+    print!("{}", format_bindings(&found));
+    0
+}
@@ -0,0 +1,224 @@
+//! # Last Output Module
+//!
+//! Saída dos últimos comandos (opção `capture_output` da seção `[behavior]`).
+//!
+//! Com a opção ligada, o STDOUT de cada comando em primeiro plano passa pela
+//! shell a caminho do terminal e uma cópia fica guardada, sem precisar rodar o
+//! comando de novo para filtrar o resultado:
+//!
+//! - `$__` / `$CLIOS_LAST_OUTPUT` - saída do último comando
+//! - `out` - imprime a saída; `out 2` a do penúltimo; `out grep erro` usa a
+//!   saída como entrada de outro comando; `out -l` lista o que está guardado
+//! - **Alt+O** - insere a saída na linha sendo editada (uma linha por argumento)
+//!
+//! ## Limitações
+//! O comando escreve num pipe em vez do terminal, então programas que mudam de
+//! comportamento fora de um TTY (cores do `ls`, colunas) escrevem como num
+//! pipe. Programas interativos ([`INTERACTIVE`]) e saídas redirecionadas
+//! (`> arquivo`) não são capturados.
+
+use crate::executor::{self, ProcessGroup};
+use crate::quote::quote_word;
+use crate::registry::usage;
+use crate::t;
+use rustyline::{Cmd, ConditionalEventHandler, Event, EventContext, RepeatCount};
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::process::{Child, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Saídas guardadas (a mais antiga sai primeiro).
+pub const MAX_ENTRIES: usize = 10;
+
+/// Bytes guardados por saída; de uma saída maior fica o final.
+pub const MAX_BYTES: usize = 1024 * 1024;
+
+/// Programas que precisam do terminal e nunca têm a saída capturada.
+pub const INTERACTIVE: &[&str] = &[
+    "vim", "vi", "nvim", "nano", "emacs", "less", "more", "man", "top", "htop", "btop", "ssh", "tmux",
+    "screen", "fzf", "watch",
+];
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static OUTPUTS: Mutex<VecDeque<CapturedOutput>> = Mutex::new(VecDeque::new());
+
+/// Saída de um comando.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedOutput {
+    /// Linha do comando
+    pub command: String,
+    /// STDOUT (no máximo [`MAX_BYTES`], o final da saída)
+    pub bytes: Vec<u8>,
+    /// A saída passou de [`MAX_BYTES`] e o começo foi descartado
+    pub truncated: bool,
+}
+
+/// Liga ou desliga a captura (opção `capture_output`).
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// A pipeline `commands` deve ter a saída capturada?
+pub fn should_capture(commands: &[Vec<String>]) -> bool {
+    is_enabled()
+        && !commands
+            .iter()
+            .filter_map(|tokens| tokens.first())
+            .any(|program| INTERACTIVE.contains(&program.rsplit('/').next().unwrap_or(program)))
+}
+
+// -----------------------------------------------------------------------------
+// RING BUFFER
+// -----------------------------------------------------------------------------
+
+/// Guarda uma saída, descartando a mais antiga se já houver [`MAX_ENTRIES`].
+pub fn record(command: String, mut bytes: Vec<u8>, truncated: bool) {
+    let truncated = truncated || bytes.len() > MAX_BYTES;
+    if bytes.len() > MAX_BYTES {
+        bytes.drain(..bytes.len() - MAX_BYTES);
+    }
+    let mut outputs = OUTPUTS.lock().unwrap_or_else(|e| e.into_inner());
+    if outputs.len() >= MAX_ENTRIES {
+        outputs.pop_front();
+    }
+    outputs.push_back(CapturedOutput { command, bytes, truncated });
+}
+
+/// Saída guardada: 1 = do último comando, 2 = do penúltimo...
+pub fn nth(n: usize) -> Option<CapturedOutput> {
+    let outputs = OUTPUTS.lock().unwrap_or_else(|e| e.into_inner());
+    n.checked_sub(1).and_then(|i| outputs.iter().rev().nth(i).cloned())
+}
+
+/// Texto da última saída, sem a quebra de linha final (`$__`).
+pub fn last_text() -> Option<String> {
+    let last = nth(1)?;
+    Some(String::from_utf8_lossy(&last.bytes).trim_end_matches('\n').to_string())
+}
+
+/// Copia o STDOUT de `child` para o terminal e para o buffer, até o fim.
+pub fn tee_child(child: &mut Child, command: String) {
+    let Some(mut stdout) = child.stdout.take() else {
+        return;
+    };
+    let mut captured = Vec::new();
+    let mut truncated = false;
+    let mut chunk = [0u8; 8192];
+    let mut terminal = io::stdout();
+    loop {
+        match stdout.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => {
+                let _ = terminal.write_all(&chunk[..n]);
+                let _ = terminal.flush();
+                captured.extend_from_slice(&chunk[..n]);
+                // Mantém o buffer limitado durante a leitura
+                if captured.len() > 2 * MAX_BYTES {
+                    captured.drain(..captured.len() - MAX_BYTES);
+                    truncated = true;
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(_) => break,
+        }
+    }
+    record(command, captured, truncated);
+}
+
+// -----------------------------------------------------------------------------
+// BUILTIN
+// -----------------------------------------------------------------------------
+
+/// Builtin `out [-l] [N] [comando...]`.
+pub fn handle_out(tokens: &[String]) -> i32 {
+    if !is_enabled() {
+        eprintln!("{}", t!("out.disabled"));
+        return 1;
+    }
+    let mut args = &tokens[1..];
+    if args.first().map(|s| s.as_str()) == Some("-l") {
+        list_outputs();
+        return 0;
+    }
+
+    let mut n = 1;
+    if let Some(index) = args.first().and_then(|s| s.parse::<usize>().ok()) {
+        n = index;
+        args = &args[1..];
+    }
+    let Some(output) = nth(n) else {
+        eprintln!("{}", t!("out.empty", n));
+        return 1;
+    };
+
+    if args.is_empty() {
+        let _ = io::stdout().write_all(&output.bytes);
+        return 0;
+    }
+    if args[0].starts_with('-') {
+        eprintln!("{}", usage("out"));
+        return 2;
+    }
+    feed_command(args, output.bytes)
+}
+
+/// Executa `tokens` com `input` no STDIN.
+fn feed_command(tokens: &[String], input: Vec<u8>) -> i32 {
+    let mut command = executor::command(tokens, ProcessGroup::Shell);
+    command.stdin(Stdio::piped());
+    let mut child = match executor::spawn(&mut command) {
+        Ok(child) => child,
+        Err(e) => {
+            eprintln!("{}", t!("tag.error", e));
+            return e.exit_code();
+        }
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // Escrita em outra thread: o comando pode parar de ler antes do fim
+        std::thread::spawn(move || {
+            let _ = stdin.write_all(&input);
+        });
+    }
+    child.wait().map(executor::exit_code).unwrap_or(1)
+}
+
+fn list_outputs() {
+    let outputs = OUTPUTS.lock().unwrap_or_else(|e| e.into_inner());
+    for (i, output) in outputs.iter().rev().enumerate() {
+        let lines = output.bytes.iter().filter(|&&b| b == b'\n').count();
+        let marker = if output.truncated { "+" } else { "" };
+        let command = output.command.replace('\n', " ");
+        println!("{:>3}  {:>6}{:1} {}  {}", i + 1, lines, marker, t!("out.lines"), command);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// EDITOR
+// -----------------------------------------------------------------------------
+
+/// Última saída como argumentos: uma linha não vazia por argumento, com aspas
+/// quando preciso.
+pub fn output_as_args(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| quote_word(line).into_owned())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Handler do Alt+O: insere a última saída no cursor.
+pub struct InsertLastOutput;
+
+impl ConditionalEventHandler for InsertLastOutput {
+    fn handle(&self, _evt: &Event, _n: RepeatCount, _positive: bool, _ctx: &EventContext) -> Option<Cmd> {
+        let text = output_as_args(&last_text()?);
+        (!text.is_empty()).then_some(Cmd::Insert(1, text))
+    }
+}
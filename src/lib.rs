@@ -1,12 +1,76 @@
+pub mod abbr;
+pub mod accessibility;
+pub mod archive;
+pub mod arg_history;
+pub mod arrays;
+pub mod autopair;
+pub mod batch;
+pub mod bench;
 pub mod builtins;
+pub mod calc;
+pub mod clean_env;
+pub mod command_hash;
+pub mod compat;
 pub mod completion;
 pub mod config;
+pub mod container;
+pub mod crash;
+pub mod demo;
+pub mod disk_usage;
+pub mod each;
+pub mod env_list;
+pub mod executor;
 pub mod expansion;
+pub mod fetch;
+pub mod fifo;
+pub mod git;
+pub mod hashsum;
+pub mod explain;
+pub mod history;
+pub mod i18n;
+pub mod include;
 pub mod jobs;
+pub mod keys;
+pub mod last_output;
+pub mod limits;
+pub mod listing;
+pub mod nesting;
+pub mod net;
+pub mod open;
+pub mod options;
+pub mod parallel;
+pub mod parser;
+pub mod path_env;
 pub mod pipeline;
+pub mod plugin_registry;
+pub mod plugin_test;
+pub mod procs;
+pub mod range;
+pub mod recent;
 pub mod prompt;
+pub mod prompt_cache;
+pub mod quote;
+pub mod redact;
+pub mod registry;
+pub mod remote;
+pub mod repeat;
+pub mod rhai_debug;
 pub mod rhai_integration;
+pub mod secrets;
+pub mod session;
 pub mod shell;
+pub mod signals;
+pub mod statements;
+pub mod suggest;
+pub mod system_info;
+pub mod tasks;
+pub mod terminal;
+pub mod tldr;
+pub mod toolchain;
+pub mod trash;
+pub mod vimode;
+pub mod watch;
+pub mod when;
 
 #[cfg(test)]
 pub mod tests;
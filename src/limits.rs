@@ -0,0 +1,167 @@
+//! # Limits Module
+//!
+//! Proteção contra fork bombs e jobs descontrolados (seção `[limits]` do
+//! `.clios.toml`).
+//!
+//! Um plugin ou alias com defeito que cria processos sem parar derrubaria a
+//! sessão (e às vezes a máquina). A shell recusa, com uma mensagem clara:
+//!
+//! - **Jobs:** mais que `max_jobs` jobs em background ativos ao mesmo tempo.
+//! - **Processos por segundo:** mais que `max_spawns_per_second` processos
+//!   criados no último segundo (pipelines, jobs, subshells, `parallel`...).
+//!
+//! `0` desliga o limite. Ao iniciar, [`ulimit_warning`] avisa quando o
+//! `ulimit -u` do usuário é menor que o limite de jobs configurado (o sistema
+//! barraria os processos antes da shell).
+
+use crate::t;
+use nix::sys::resource::{getrlimit, Resource, RLIM_INFINITY};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// Jobs em background ativos ao mesmo tempo (padrão).
+pub const DEFAULT_MAX_JOBS: usize = 64;
+
+/// Processos criados por segundo (padrão).
+pub const DEFAULT_MAX_SPAWNS_PER_SECOND: usize = 500;
+
+/// Limites em vigor (`0` = sem limite).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Limits {
+    pub max_jobs: usize,
+    pub max_spawns_per_second: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_jobs: DEFAULT_MAX_JOBS,
+            max_spawns_per_second: DEFAULT_MAX_SPAWNS_PER_SECOND,
+        }
+    }
+}
+
+static LIMITS: RwLock<Limits> = RwLock::new(Limits {
+    max_jobs: DEFAULT_MAX_JOBS,
+    max_spawns_per_second: DEFAULT_MAX_SPAWNS_PER_SECOND,
+});
+
+static SPAWNS: Mutex<SpawnWindow> = Mutex::new(SpawnWindow::new());
+
+/// Define os limites (seção `[limits]` da configuração).
+pub fn set_limits(limits: Limits) {
+    if let Ok(mut current) = LIMITS.write() {
+        *current = limits;
+    }
+}
+
+/// Limites em vigor.
+pub fn limits() -> Limits {
+    LIMITS.read().map(|l| *l).unwrap_or_default()
+}
+
+// -----------------------------------------------------------------------------
+// ERRORS
+// -----------------------------------------------------------------------------
+
+/// Um limite foi atingido.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LimitError {
+    /// Jobs em background ativos (limite)
+    Jobs(usize),
+    /// Processos criados no último segundo (limite)
+    Spawns(usize),
+}
+
+impl fmt::Display for LimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            LimitError::Jobs(max) => t!("limits.jobs", max),
+            LimitError::Spawns(max) => t!("limits.spawns", max),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl std::error::Error for LimitError {}
+
+// -----------------------------------------------------------------------------
+// CHECKS
+// -----------------------------------------------------------------------------
+
+/// Janela de um segundo com os instantes dos últimos processos criados.
+#[derive(Debug)]
+pub struct SpawnWindow {
+    times: VecDeque<Instant>,
+}
+
+impl SpawnWindow {
+    pub const fn new() -> Self {
+        Self { times: VecDeque::new() }
+    }
+
+    /// Registra um processo em `now`, se couber no limite (`0` = sem limite).
+    pub fn admit(&mut self, now: Instant, max: usize) -> bool {
+        if max == 0 {
+            return true;
+        }
+        while self
+            .times
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= Duration::from_secs(1))
+        {
+            self.times.pop_front();
+        }
+        if self.times.len() >= max {
+            return false;
+        }
+        self.times.push_back(now);
+        true
+    }
+}
+
+impl Default for SpawnWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Registra a criação de um processo, recusando se passar do limite por segundo.
+pub fn admit_spawn() -> Result<(), LimitError> {
+    let max = limits().max_spawns_per_second;
+    let mut window = SPAWNS.lock().unwrap_or_else(|e| e.into_inner());
+    if window.admit(Instant::now(), max) {
+        Ok(())
+    } else {
+        Err(LimitError::Spawns(max))
+    }
+}
+
+/// Verifica se cabe mais um job em background, com `active` jobs ativos.
+pub fn check_job_slot(active: usize) -> Result<(), LimitError> {
+    let max = limits().max_jobs;
+    if max > 0 && active >= max {
+        Err(LimitError::Jobs(max))
+    } else {
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ULIMIT
+// -----------------------------------------------------------------------------
+
+/// Limite de processos do usuário (`ulimit -u`), se houver.
+pub fn nproc_limit() -> Option<u64> {
+    let (soft, _) = getrlimit(Resource::RLIMIT_NPROC).ok()?;
+    (soft != RLIM_INFINITY).then_some(soft)
+}
+
+/// Aviso quando o `ulimit -u` é menor que o limite de jobs.
+pub fn ulimit_warning(limits: Limits, nproc: Option<u64>) -> Option<String> {
+    let nproc = nproc?;
+    let max_jobs = limits.max_jobs as u64;
+    (max_jobs == 0 || max_jobs >= nproc).then(|| t!("limits.ulimit", nproc, limits.max_jobs))
+}
@@ -0,0 +1,219 @@
+//! # Listing Module
+//!
+//! Builtin `list [-a] [-n N] [dir]`: o conteúdo de um diretório em colunas,
+//! como um `ls` curto, sem depender do programa externo. Diretórios vêm
+//! primeiro (em azul, com `/`), executáveis em verde (`*`) e links em ciano
+//! (`@`). Fora de um terminal, sai um nome por linha, sem cores.
+//!
+//! É também o que o `auto_ls` de `[behavior]` mostra depois de um `cd`: um
+//! diretório com mais de `auto_ls_max` entradas mostra só a contagem.
+
+use crate::accessibility::screen_reader;
+use crate::registry::usage;
+use crate::t;
+use crate::terminal::terminal_width;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use unicode_width::UnicodeWidthStr;
+
+/// Entradas listadas pelo `auto_ls` sem `auto_ls_max`.
+pub const DEFAULT_AUTO_LS_MAX: usize = 50;
+
+/// Espaço entre as colunas.
+const COLUMN_GAP: usize = 2;
+
+/// Tipo de uma entrada, para a cor e o sufixo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    Dir,
+    Link,
+    Executable,
+    File,
+}
+
+/// Uma entrada do diretório.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListEntry {
+    pub name: String,
+    pub kind: EntryKind,
+}
+
+impl ListEntry {
+    /// O nome com o sufixo do tipo (`src/`, `build.sh*`, `latest@`).
+    pub fn label(&self) -> String {
+        let suffix = match self.kind {
+            EntryKind::Dir => "/",
+            EntryKind::Link => "@",
+            EntryKind::Executable => "*",
+            EntryKind::File => "",
+        };
+        format!("{}{}", self.name, suffix)
+    }
+
+    fn color(&self) -> &'static str {
+        match self.kind {
+            EntryKind::Dir => "\x1b[1;34m",
+            EntryKind::Link => "\x1b[36m",
+            EntryKind::Executable => "\x1b[32m",
+            EntryKind::File => "",
+        }
+    }
+}
+
+/// As entradas de `dir` (sem as ocultas, a não ser com `all`), com os
+/// diretórios primeiro e o resto em ordem alfabética.
+pub fn read_entries(dir: &Path, all: bool) -> io::Result<Vec<ListEntry>> {
+    let mut entries = Vec::new();
+    for item in fs::read_dir(dir)? {
+        let item = item?;
+        let name = item.file_name().to_string_lossy().into_owned();
+        if !all && name.starts_with('.') {
+            continue;
+        }
+        let kind = match item.file_type() {
+            Ok(t) if t.is_symlink() => EntryKind::Link,
+            Ok(t) if t.is_dir() => EntryKind::Dir,
+            _ => match item.metadata() {
+                Ok(meta) if meta.permissions().mode() & 0o111 != 0 => EntryKind::Executable,
+                _ => EntryKind::File,
+            },
+        };
+        entries.push(ListEntry { name, kind });
+    }
+    sort_entries(&mut entries);
+    Ok(entries)
+}
+
+/// Diretórios primeiro; dentro de cada grupo, nomes sem diferenciar maiúsculas.
+pub fn sort_entries(entries: &mut [ListEntry]) {
+    entries.sort_by_cached_key(|e| (e.kind != EntryKind::Dir, e.name.to_lowercase()));
+}
+
+/// Colunas em que as larguras cabem em `width`, preenchidas de cima para
+/// baixo como no `ls`: devolve a largura de cada coluna.
+pub fn column_layout(widths: &[usize], width: usize) -> Vec<usize> {
+    for cols in (1..=widths.len()).rev() {
+        let rows = widths.len().div_ceil(cols);
+        // Com menos colunas que o pedido (ex.: 5 itens em 4 colunas), pula
+        if widths.len().div_ceil(rows) != cols {
+            continue;
+        }
+        let columns: Vec<usize> = widths.chunks(rows).map(|c| c.iter().copied().max().unwrap_or(0)).collect();
+        let total = columns.iter().sum::<usize>() + COLUMN_GAP * (cols - 1);
+        if total <= width || cols == 1 {
+            return columns;
+        }
+    }
+    Vec::new()
+}
+
+/// As entradas em colunas para um terminal de `width` colunas (`None`: um
+/// nome por linha, sem cores).
+pub fn format_entries(entries: &[ListEntry], width: Option<usize>) -> String {
+    let labels: Vec<String> = entries.iter().map(ListEntry::label).collect();
+    let Some(width) = width else {
+        return labels.iter().map(|l| format!("{}\n", l)).collect();
+    };
+    let widths: Vec<usize> = labels.iter().map(|l| l.width()).collect();
+    let columns = column_layout(&widths, width);
+    if columns.is_empty() {
+        return String::new();
+    }
+    let rows = entries.len().div_ceil(columns.len());
+    let mut out = String::new();
+    for row in 0..rows {
+        let mut line = String::new();
+        for (col, column_width) in columns.iter().enumerate() {
+            let i = col * rows + row;
+            let Some(entry) = entries.get(i) else {
+                break;
+            };
+            if col > 0 {
+                line.push_str(&" ".repeat(COLUMN_GAP));
+            }
+            match entry.color() {
+                "" => line.push_str(&labels[i]),
+                color => line.push_str(&format!("{}{}\x1b[0m", color, labels[i])),
+            }
+            // Sem espaços sobrando no fim da linha
+            if entries.get(i + rows).is_some() {
+                line.push_str(&" ".repeat(column_width - widths[i]));
+            }
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Largura para [`format_entries`]: a do terminal, ou `None` fora dele e no
+/// modo para leitores de tela.
+fn output_width() -> Option<usize> {
+    if screen_reader() || !io::stdout().is_terminal() {
+        return None;
+    }
+    terminal_width()
+}
+
+/// O `auto_ls` depois de um `cd`: lista `dir` ou, acima de `max` entradas, só
+/// diz quantas são.
+pub fn auto_list(dir: &Path, max: usize) {
+    let Ok(entries) = read_entries(dir, false) else {
+        return;
+    };
+    if entries.len() > max {
+        println!("\x1b[90m{}\x1b[0m", t!("list.too_many", entries.len()));
+        return;
+    }
+    print!("{}", format_entries(&entries, output_width()));
+}
+
+// -----------------------------------------------------------------------------
+// BUILTIN
+// -----------------------------------------------------------------------------
+
+/// Builtin `list [-a] [-n N] [dir]`.
+pub fn handle_list(tokens: &[String]) -> i32 {
+    let mut all = false;
+    let mut limit = None;
+    let mut dir = None;
+    let mut args = tokens[1..].iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-a" | "--all" => all = true,
+            "-n" => match args.next().and_then(|n| n.parse().ok()) {
+                Some(n) => limit = Some(n),
+                None => {
+                    eprintln!("{}", usage("list"));
+                    return 2;
+                }
+            },
+            flag if flag.starts_with('-') => {
+                eprintln!("{}", usage("list"));
+                return 2;
+            }
+            _ if dir.is_some() => {
+                eprintln!("{}", usage("list"));
+                return 2;
+            }
+            _ => dir = Some(PathBuf::from(arg)),
+        }
+    }
+    let dir = dir.unwrap_or_else(|| PathBuf::from("."));
+
+    let entries = match read_entries(&dir, all) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("list: {}: {}", dir.display(), e);
+            return 1;
+        }
+    };
+    let shown = limit.unwrap_or(entries.len()).min(entries.len());
+    print!("{}", format_entries(&entries[..shown], output_width()));
+    if shown < entries.len() {
+        println!("\x1b[90m{}\x1b[0m", t!("list.more", entries.len() - shown));
+    }
+    0
+}
@@ -19,86 +19,241 @@
 //!
 //! # Comando Único
 //! clios -c "echo Hello World"
+//!
+//! # Relatório de performance
+//! clios bench
+//!
+//! # Aliases e abreviações em sintaxe do bash
+//! clios export-compat > ~/.clios_compat.sh
+//!
+//! # Lista de comandos (arquivo ou `-` para stdin), para CI e provisionamento
+//! clios --command-file passos.txt --fail-fast --trace
+//!
+//! # Retomar uma sessão salva com `session save <nome>`
+//! clios --session <nome>
+//!
+//! # Script Rhai no debugger (breakpoints na linha 12 e em `deploy`)
+//! clios --rhai-debug script.rhai 12 deploy
 //! ```
 
 // --- MODULE DECLARATIONS ---
 // Moved to lib.rs
 
 // --- IMPORTS ---
+use clios_shell::abbr::{expand_line, AbbrExpander};
+use clios_shell::accessibility::screen_reader;
+use clios_shell::autopair::{missing_closers, SkipCloser};
+use clios_shell::batch::{format_summary, read_commands, run_batch, BatchOptions};
+use clios_shell::bench::run_bench_command;
 use clios_shell::completion::CliosHelper;
-use clios_shell::config::{get_color_ansi, load_toml_config};
-use clios_shell::prompt::{build_powerline_prompt, get_git_branch, get_powerline_segments};
-use clios_shell::rhai_integration::run_rhai_script;
+use clios_shell::crash::{install_panic_hook, report_crash, run_guarded};
+use clios_shell::demo::demo_prompt;
+use clios_shell::config::{default_config_path, get_color_ansi, load_toml_config_from, PROFILE_VAR};
+use clios_shell::explain::explain_exit_code;
+use clios_shell::redact::Redactor;
+use clios_shell::prompt::{build_accessible_prompt, build_classic_prompt, build_powerline_prompt, get_powerline_segments};
+use clios_shell::prompt_cache::{PromptCache, PromptKey};
+use clios_shell::rhai_debug::{debug_script, format_trace, set_rhai_debug};
+use clios_shell::rhai_integration::{flush_queued_output, run_rhai_script, script_engine};
+use clios_shell::history::{append_entry, expand_history, meta_path, HistoryEntry};
+use clios_shell::i18n::init_language;
+use clios_shell::limits::{limits, nproc_limit, ulimit_warning};
+use clios_shell::jobs::{count_active_jobs, enable_job_control, report_finished_jobs, start_reaper};
+use clios_shell::keys::{active_bindings, invalid_keys, EditHistory, KeyAction, RedoKey, UndoKey};
+use clios_shell::last_output::InsertLastOutput;
+use clios_shell::nesting::{init_level, DEEP_LEVEL, MAX_LEVEL};
 use clios_shell::shell::CliosShell;
+use clios_shell::suggest::parse_query;
+use clios_shell::signals::{clear_interrupt, install_signal_handlers, EXIT_INTERRUPTED};
+use clios_shell::statements::StatementBuffer;
+use clios_shell::t;
+use clios_shell::terminal::save_terminal_state;
+use clios_shell::tldr::DocsLookup;
+use clios_shell::vimode::{ViMode, ViModeState, ViModeTracker};
 
 use rustyline::error::ReadlineError;
 use rustyline::history::DefaultHistory;
-use rustyline::Editor;
+use rustyline::{ConditionalEventHandler, EditMode, Editor, Event, EventHandler};
+use std::collections::HashMap;
 use std::env;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 // -----------------------------------------------------------------------------
 // MAIN FUNCTION
 // -----------------------------------------------------------------------------
 
 fn main() -> rustyline::Result<()> {
-    // 1. Load configuration
-    let loaded_config = load_toml_config();
+    // 0. Ctrl+C nunca derruba a shell (ver módulo signals)
+    install_signal_handlers();
+    save_terminal_state();
+    install_panic_hook();
+
+    // --- COMMAND LINE ARGUMENTS ---
+    let mut args: Vec<String> = env::args().collect();
+
+    // --config <arquivo.toml> / --rc <arquivo>: alternativas a ~/.clios.toml e
+    // ~/.cliosrc (também via CLIOS_CONFIG / CLIOS_RC)
+    let config_path = take_flag_value(&mut args, "--config")
+        .or_else(|| env::var("CLIOS_CONFIG").ok())
+        .map(PathBuf::from)
+        .unwrap_or_else(default_config_path);
+    let rc_path = take_flag_value(&mut args, "--rc").or_else(|| env::var("CLIOS_RC").ok());
+    let session = take_flag_value(&mut args, "--session");
+    // --profile <nome>: um dos [profile.<nome>] do .clios.toml (ou CLIOS_PROFILE)
+    let profile = take_flag_value(&mut args, "--profile").or_else(|| env::var(PROFILE_VAR).ok());
+    // --demo: começa no modo de apresentação
+    let demo = take_flag(&mut args, "--demo");
+    // --rhai-debug: rastros completos nos erros Rhai; com um .rhai, o debugger
+    let rhai_debug = take_flag(&mut args, "--rhai-debug");
+    set_rhai_debug(rhai_debug);
+    // --command-file <arquivo|->: modo em lote, com --fail-fast e --trace
+    let command_file = take_flag_value(&mut args, "--command-file");
+    let batch_options = BatchOptions {
+        fail_fast: take_flag(&mut args, "--fail-fast"),
+        trace: take_flag(&mut args, "--trace"),
+    };
+
+    // Nível de aninhamento: exporta CLIOS_LEVEL e barra recursões infinitas
+    let level = init_level();
+
+    // 1. Load configuration (TOML + variáveis CLIOS_*)
+    // O idioma do ambiente já vale para avisos do próprio carregamento
+    init_language(None);
+    let loaded_config = load_toml_config_from(&config_path, profile.as_deref().filter(|p| !p.is_empty()));
+    // Shells filhas herdam o perfil que de fato foi aplicado
+    unsafe {
+        match &loaded_config.active_profile {
+            Some(name) => env::set_var(PROFILE_VAR, name),
+            None => env::remove_var(PROFILE_VAR),
+        }
+    }
+    init_language(loaded_config.general.as_ref().and_then(|g| g.language.as_deref()));
+
+    if level > MAX_LEVEL {
+        eprintln!("{}", t!("tag.error", t!("nesting.too_deep", MAX_LEVEL)));
+        std::process::exit(1);
+    }
 
     // 2. Initialize the Shell
     let mut shell = CliosShell::new(loaded_config);
+    // Jobs em background recolhidos assim que terminam (ver módulo jobs)
+    if let Err(e) = start_reaper(&shell.jobs) {
+        eprintln!("{}", t!("tag.warning", t!("jobs.reaper_failed", e)));
+    }
 
     // Load auto-plugins from ~/.clios_plugins
     shell.load_auto_plugins();
+    shell.load_config_plugins();
+    if demo {
+        shell.enable_demo(false);
+    }
 
-    // Load user config from ~/.cliosrc
-    shell.load_config();
+    // Load user config from ~/.cliosrc (ou --rc)
+    match &rc_path {
+        Some(path) => shell.load_rc_file(Path::new(path)),
+        None => shell.load_config(),
+    }
 
-    // --- COMMAND LINE ARGUMENTS ---
-    let args: Vec<String> = env::args().collect();
+    // --session <nome>: restaura o contexto salvo antes de qualquer outro modo
+    if let Some(name) = session
+        && let Err(e) = shell.restore_session(&name)
+    {
+        eprintln!("session: {}", e);
+    }
+
+    if let Some(source) = command_file {
+        let commands = match read_commands(&source) {
+            Ok(commands) => commands,
+            Err(e) => {
+                eprintln!("{}", t!("batch.read_failed", source, e));
+                std::process::exit(1);
+            }
+        };
+        let report = run_batch(&mut shell, &commands, batch_options);
+        eprint!("{}", format_summary(&report));
+        if shell.exit_requested.is_none() {
+            shell.last_exit_code = report.exit_code();
+        }
+        std::process::exit(shell.shutdown());
+    }
 
     if args.len() > 1 {
         // CASE A: Flag -c (Single command)
         if args[1] == "-c" {
             if args.len() > 2 {
                 let command = &args[2];
-                let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                    shell.process_input_line(command);
-                }));
+                if let Err(report) = run_guarded(command, || shell.process_input_line(command)) {
+                    report_crash(&report);
+                    std::process::exit(70);
+                }
             } else {
-                eprintln!("Erro: -c requer um comando entre aspas");
+                eprintln!("{}", t!("cli.c_requires_command"));
                 std::process::exit(1);
             }
+            std::process::exit(shell.shutdown());
+        }
+
+        // CASE B: Benchmark embutido
+        if args[1] == "bench" {
+            std::process::exit(run_bench_command(&shell.config));
+        }
+
+        // CASE B2: aliases e abreviações para o bash
+        if args[1] == "export-compat" {
+            print!("{}", shell.export_compat());
             return Ok(());
         }
 
-        // CASE B: Rhai Script (.rhai)
+        // CASE C: Rhai Script (.rhai)
         if args[1].ends_with(".rhai") {
-            println!("--- Executando Script Rhai ---");
+            if rhai_debug {
+                if let Err(e) = debug_script(script_engine(), &args[1], &args[2..]) {
+                    eprintln!("{}", t!("cli.rhai_error", e));
+                    std::process::exit(1);
+                }
+                return Ok(());
+            }
+            println!("{}", t!("cli.running_rhai"));
             if let Err(e) = run_rhai_script(&args[1]) {
-                eprintln!("Erro no script Rhai: {}", e);
+                eprintln!("{}", t!("cli.rhai_error", format_trace(&e, None, Some(&args[1]))));
                 std::process::exit(1);
             }
             return Ok(());
         }
 
-        // CASE C: Shell Script
+        // CASE D: Shell Script
         let script_path = Path::new(&args[1]);
         if let Ok(file) = File::open(script_path) {
             let reader = BufReader::new(file);
-            for line in reader.lines() {
-                if let Ok(l) = line
-                    && !l.trim().is_empty() && !l.starts_with('#') {
-                        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                            shell.process_input_line(&l);
-                        }));
+            // `case` e `select` ocupam várias linhas; o que sobrar no fim vira erro de sintaxe
+            let mut statements = StatementBuffer::default();
+            let lines = reader.lines().map_while(Result::ok).map(Some).chain([None]);
+            for line in lines {
+                let statement = match line {
+                    Some(l) if l.trim().is_empty() || l.starts_with('#') => None,
+                    Some(l) => statements.push(&l),
+                    None => statements.finish(),
+                };
+                if let Some(l) = statement {
+                    if let Err(report) = run_guarded(&l, || shell.process_input_line(&l)) {
+                        report_crash(&report);
+                    }
+
+                    // Ctrl+C interrompe o script inteiro, não só a linha atual
+                    if shell.last_exit_code == EXIT_INTERRUPTED {
+                        shell.exit_requested = Some(EXIT_INTERRUPTED);
                     }
+
+                    if shell.exit_requested.is_some() {
+                        break;
+                    }
+                }
             }
-            return Ok(());
+            std::process::exit(shell.shutdown());
         } else {
-            eprintln!("Erro: Arquivo não encontrado '{}'", args[1]);
+            eprintln!("{}", t!("cli.file_not_found", args[1]));
             std::process::exit(1);
         }
     }
@@ -115,11 +270,20 @@ fn main() -> rustyline::Result<()> {
         (".clios_history", 1000)
     };
 
+    // Modo de edição (emacs ou vi)
+    let vi_mode = shell
+        .config
+        .behavior
+        .as_ref()
+        .and_then(|b| b.edit_mode.as_deref())
+        .is_some_and(|m| m.eq_ignore_ascii_case("vi"))
+        .then(ViModeState::default);
+
     // Configure Rustyline
     let config = rustyline::Config::builder()
         .auto_add_history(false)
-        .max_history_size(max_entries)
-        .unwrap()
+        .max_history_size(max_entries)?
+        .edit_mode(if vi_mode.is_some() { EditMode::Vi } else { EditMode::Emacs })
         .build();
 
     // Get syntax highlighting colors
@@ -133,11 +297,59 @@ fn main() -> rustyline::Result<()> {
     };
 
     // Create the helper
-    let h = CliosHelper::new(get_color_ansi(valid_str), get_color_ansi(invalid_str));
+    let mut h = CliosHelper::new(get_color_ansi(valid_str), get_color_ansi(invalid_str));
+    h.abbreviations = shell.abbreviations.clone();
+    h.plugins = shell.plugins.clone();
+
+    // Fechamento automático de aspas/parênteses
+    let auto_pair = shell
+        .config
+        .behavior
+        .as_ref()
+        .and_then(|b| b.auto_pair)
+        .unwrap_or(false);
+    h.auto_pair = auto_pair;
+    h.vi_mode = vi_mode.clone();
+    (h.recent_files, h.argument_history) = completion_sources(&shell);
+    let edits = EditHistory::default();
+    h.edits = edits.clone();
 
     // Initialize the Editor
     let mut rl: Editor<CliosHelper, DefaultHistory> = Editor::with_config(config)?;
     rl.set_helper(Some(h));
+    // O Editor troca o handler de SIGINT pelo dele até ser destruído; sem o
+    // nosso de volta, builtins longos (`watch`, `ping-lite -t`) ignoram o Ctrl+C
+    install_signal_handlers();
+    // Comandos em foreground com process group próprio (Ctrl+Z, `fg`)
+    enable_job_control();
+
+    // Atalhos da shell, ligados a partir do mesmo registro que o `keys` lista:
+    // espaço expande abreviações, Alt+H abre man/tldr, Alt+O insere a última
+    // saída, desfazer/refazer do [keys] e os fechamentos do auto_pair
+    for spec in invalid_keys(&shell.config) {
+        eprintln!("{}", t!("tag.config_warning", t!("keys.invalid", spec)));
+    }
+    for binding in active_bindings(&shell.config) {
+        for key in &binding.keys {
+            let handler: Box<dyn ConditionalEventHandler> = match binding.action {
+                KeyAction::Editor(..) => continue,
+                KeyAction::Undo => Box::new(UndoKey { history: edits.clone() }),
+                KeyAction::Redo => Box::new(RedoKey { history: edits.clone() }),
+                KeyAction::ExpandAbbr => Box::new(AbbrExpander { abbrs: shell.abbreviations.clone() }),
+                KeyAction::Docs => Box::new(DocsLookup),
+                KeyAction::LastOutput => Box::new(InsertLastOutput),
+                KeyAction::SkipCloser => Box::new(SkipCloser),
+            };
+            rl.bind_sequence(*key, EventHandler::Conditional(handler));
+        }
+    }
+    // Modo vi: acompanha o modo a cada tecla para o indicador do prompt
+    if let Some(state) = &vi_mode {
+        rl.bind_sequence(
+            Event::Any,
+            EventHandler::Conditional(Box::new(ViModeTracker { state: state.clone() })),
+        );
+    }
 
     // History path
     let history_path = env::var("HOME")
@@ -146,10 +358,29 @@ fn main() -> rustyline::Result<()> {
 
     // Load history
     if rl.load_history(&history_path).is_err() {
-        println!("Bem-vindo ao Clios Shell v1.0 (Final Release) ");
-        println!("Digite 'create' para iniciar um projeto ou 'rhai' para scripts.");
+        println!("{}", t!("cli.welcome"));
+        println!("{}", t!("cli.welcome_hint"));
     }
 
+    // Metadados para `history stats`
+    let history_meta_path = meta_path(hist_file);
+
+    // Credenciais escondidas no histórico e nos logs de erro interno
+    let redactor = match &shell.config.history {
+        Some(h) if h.redact == Some(false) => Redactor::disabled(),
+        Some(h) => {
+            let (redactor, errors) = Redactor::new(h.redact_patterns.as_deref().unwrap_or_default());
+            for error in errors {
+                eprintln!("{}", t!("tag.warning", error));
+            }
+            redactor
+        }
+        None => Redactor::default(),
+    };
+    // Os comandos desta sessão que foram gravados escondidos, pela linha
+    // gravada: `!!` e companhia expandem o comando de verdade
+    let mut unredacted: HashMap<String, String> = HashMap::new();
+
     // Theme control
     let mut current_theme = shell
         .config
@@ -157,16 +388,73 @@ fn main() -> rustyline::Result<()> {
         .clone()
         .unwrap_or_else(|| "powerline".to_string());
 
+    // Confirmação de saída com jobs pendentes
+    let mut confirm_exit = shell
+        .config
+        .behavior
+        .as_ref()
+        .and_then(|b| b.confirm_exit)
+        .unwrap_or(true);
+    let mut eof_warned = false;
+
+    // Explicação de códigos de saída comuns
+    let mut explain_errors = shell
+        .config
+        .behavior
+        .as_ref()
+        .and_then(|b| b.explain_errors)
+        .unwrap_or(false);
+
+    if level >= DEEP_LEVEL {
+        eprintln!("{}", t!("tag.warning", t!("nesting.deep", level)));
+    }
+
+    // Limite de jobs acima do `ulimit -u`: o sistema barraria antes da shell
+    if let Some(warning) = ulimit_warning(limits(), nproc_limit()) {
+        eprintln!("{}", t!("tag.warning", warning));
+    }
+
+    // Comando sugerido pelo `??`, colocado na próxima leitura
+    let mut pending_suggestion: Option<String> = None;
+
+    // Último prompt renderizado, reaproveitado enquanto nada muda
+    let mut prompt_cache = PromptCache::default();
+
     // --- MAIN LOOP (REPL) ---
     loop {
-        let final_prompt = if current_theme == "powerline" {
-            // Powerline mode
-            let segments = get_powerline_segments(&shell.config);
-            let prompt_bar = build_powerline_prompt(segments);
-            format!("{} \x1b[1;32m❯\x1b[0m ", prompt_bar)
+        // Hooks de diretório (`[toolchain]`, `on_cd`) quando o último comando mudou o cwd
+        shell.check_directory_change();
+
+        // Saída capturada de jobs que terminaram enquanto o usuário digitava
+        report_finished_jobs(&shell.jobs);
+
+        // Mensagens de `notify`/`queue_output` dos plugins
+        flush_queued_output();
+
+        let render = || {
+            if screen_reader() {
+                // Leitor de tela: texto simples, sem glifos nem cores
+                build_accessible_prompt(&shell.config, shell.last_exit_code, &shell.jobs)
+            } else if current_theme == "powerline" {
+                // Powerline mode
+                let segments = get_powerline_segments(&shell.config, &shell.jobs);
+                let prompt_bar = build_powerline_prompt(segments);
+                format!("{} \x1b[1;32m❯\x1b[0m ", prompt_bar)
+            } else {
+                // Classic mode
+                build_classic_prompt(&shell.config, shell.last_exit_code, &shell.jobs)
+            }
+        };
+        let final_prompt = if shell.demo.is_some() {
+            // Modo de apresentação: uma linha de respiro e o prompt mínimo
+            println!();
+            demo_prompt(shell.last_exit_code)
+        } else if prompt_cache_enabled(&shell) {
+            // Mesmo diretório, HEAD, configuração...: o prompt anterior serve
+            let key = PromptKey::current(&shell.config, &current_theme, screen_reader(), shell.last_exit_code, &shell.jobs);
+            prompt_cache.get_or_render(key, render)
         } else {
-            // Classic mode
-            build_classic_prompt(&shell)
+            render()
         };
 
         // Inject prompt into Rustyline
@@ -174,8 +462,50 @@ fn main() -> rustyline::Result<()> {
             helper.colored_prompt = final_prompt.clone();
         }
 
-        match rl.readline(&final_prompt) {
+        // Toda leitura começa em modo de inserção; o indicador ocupa a mesma
+        // largura em qualquer modo
+        let final_prompt = match &vi_mode {
+            Some(state) => {
+                state.set(ViMode::Insert);
+                format!("{}{}", ViMode::Insert.indicator(), final_prompt)
+            }
+            None => final_prompt,
+        };
+
+        // Sugestão do `??` já no editor, para revisar antes do Enter
+        let read = match pending_suggestion.take() {
+            Some(command) => rl.readline_with_initial(&final_prompt, (&command, "")),
+            None => rl.readline(&final_prompt),
+        };
+        match read {
             Ok(line) => {
+                eof_warned = false;
+                // `!!`, `!$`...: a linha expandida aparece antes de rodar e é
+                // ela que vai para o histórico (no `rhai`, `!` é o "não")
+                let line = if line.contains('!') && !line.trim_start().starts_with("rhai") {
+                    let history: Vec<String> =
+                        rl.history().iter().map(|entry| unredacted.get(entry).unwrap_or(entry).clone()).collect();
+                    match expand_history(&line, &history) {
+                        Ok(Some(expanded)) => {
+                            println!("{}", expanded);
+                            expanded
+                        }
+                        Ok(None) => line,
+                        Err(e) => {
+                            eprintln!("{}", e);
+                            continue;
+                        }
+                    }
+                } else {
+                    line
+                };
+                let mut line = match shell.abbreviations.read() {
+                    Ok(abbrs) => expand_line(&line, &abbrs),
+                    Err(_) => line,
+                };
+                if auto_pair {
+                    line.push_str(&missing_closers(&line));
+                }
                 let input = line.trim();
                 if input.is_empty() {
                     continue;
@@ -191,23 +521,80 @@ fn main() -> rustyline::Result<()> {
                     continue;
                 }
 
-                // Save to history
-                let _ = rl.add_history_entry(input);
-                let _ = rl.append_history(&history_path);
+                // Save to history (pausado no modo de apresentação)
+                let recorded = redactor.redact(input);
+                let record = shell.demo.is_none();
+                if record {
+                    if recorded != input {
+                        unredacted.insert(recorded.to_string(), input.to_string());
+                    }
+                    let _ = rl.add_history_entry(recorded.as_ref());
+                    let _ = rl.append_history(&history_path);
+                }
+
+                // `?? pergunta`: nada roda, a resposta volta para o editor
+                if let Some(query) = parse_query(input) {
+                    match shell.suggest(query) {
+                        Ok(command) => pending_suggestion = Some(command),
+                        Err(e) => eprintln!("??: {}", e),
+                    }
+                    continue;
+                }
 
                 // Execute
-                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                    shell.process_input_line(input);
-                }));
-                if result.is_err() {
-                    eprintln!("\n(!) Panic recuperado.");
+                let cwd = env::current_dir().map(|p| p.display().to_string()).unwrap_or_default();
+                if let Err(report) = run_guarded(&recorded, || shell.process_input_line(input)) {
+                    report_crash(&report);
+                }
+                prompt_cache.invalidate();
+                if explain_errors
+                    && shell.exit_requested.is_none()
+                    && let Some(text) = explain_exit_code(shell.last_exit_code)
+                {
+                    eprintln!("{}", text);
+                }
+
+                if record {
+                    append_entry(
+                        &history_meta_path,
+                        &HistoryEntry {
+                            cmd: recorded.into_owned(),
+                            ts: chrono::Local::now().timestamp(),
+                            cwd,
+                            exit: shell.last_exit_code,
+                        },
+                    );
+                }
+
+                // `profile use`: tema e comportamento seguem o novo perfil
+                if shell.take_profile_change() {
+                    current_theme = shell.config.theme.clone().unwrap_or_else(|| "powerline".to_string());
+                    let behavior = shell.config.behavior.as_ref();
+                    confirm_exit = behavior.and_then(|b| b.confirm_exit).unwrap_or(true);
+                    explain_errors = behavior.and_then(|b| b.explain_errors).unwrap_or(false);
+                    if let Some(helper) = rl.helper_mut() {
+                        (helper.recent_files, helper.argument_history) = completion_sources(&shell);
+                    }
+                }
+
+                if shell.exit_requested.is_some() {
+                    break;
                 }
             }
             Err(ReadlineError::Interrupted) => {
+                clear_interrupt();
                 println!("CTRL-C");
                 continue;
             }
             Err(ReadlineError::Eof) => {
+                if confirm_exit && !eof_warned {
+                    let pending = count_active_jobs(&shell.jobs);
+                    if pending > 0 {
+                        println!("{}", t!("cli.pending_jobs", pending));
+                        eof_warned = true;
+                        continue;
+                    }
+                }
                 break;
             }
             Err(_) => {
@@ -216,54 +603,49 @@ fn main() -> rustyline::Result<()> {
         }
     }
 
+    // Shutdown: traps, hook on_exit e jobs restantes
+    let exit_code = shell.shutdown();
+
     // Save history on exit
     rl.save_history(&history_path)?;
-    Ok(())
+    std::process::exit(exit_code);
 }
 
-// -----------------------------------------------------------------------------
-// HELPER FUNCTIONS
-// -----------------------------------------------------------------------------
+/// `cache` de `[prompt]` (padrão: ligado).
+fn prompt_cache_enabled(shell: &CliosShell) -> bool {
+    shell.config.prompt.as_ref().and_then(|p| p.cache).unwrap_or(true)
+}
 
-/// Builds the classic (customizable) prompt.
-fn build_classic_prompt(shell: &CliosShell) -> String {
-    let current_dir = env::current_dir().unwrap_or_default();
-    let dir_display = current_dir.display();
-
-    let (symbol, default_color, path_color_cfg, symbol_color_cfg, show_git) =
-        if let Some(p) = &shell.config.prompt {
-            (
-                p.symbol.as_deref().unwrap_or(">"),
-                p.color.as_deref().unwrap_or("blue"),
-                p.path_color.as_deref(),
-                p.symbol_color.as_deref(),
-                p.show_git.unwrap_or(true),
-            )
-        } else {
-            (">", "blue", None, None, true)
-        };
+/// `recent_files` e `argument_history` de `[completion]` (padrão: ligados).
+fn completion_sources(shell: &CliosShell) -> (bool, bool) {
+    let completion = shell.config.completion.as_ref();
+    (
+        completion.and_then(|c| c.recent_files).unwrap_or(true),
+        completion.and_then(|c| c.argument_history).unwrap_or(true),
+    )
+}
 
-    let path_ansi = get_color_ansi(path_color_cfg.unwrap_or(default_color));
-    let arrow_ansi = get_color_ansi(symbol_color_cfg.unwrap_or(default_color));
+// -----------------------------------------------------------------------------
+// ARGUMENT HELPERS
+// -----------------------------------------------------------------------------
 
-    let git_color = if show_git {
-        if let Some(branch) = get_git_branch() {
-            format!(" (\x1b[1;35m{}\x1b[0m)", branch)
-        } else {
-            String::new()
-        }
-    } else {
-        String::new()
+/// Remove `flag` dos argumentos; `true` se ele estava lá.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let Some(pos) = args.iter().position(|a| a == flag) else {
+        return false;
     };
+    args.remove(pos);
+    true
+}
 
-    let arrow_colored = if shell.last_exit_code == 0 {
-        format!("{}{}\x1b[0m ", arrow_ansi, symbol)
-    } else {
-        format!("\x1b[1;31m[{}]>\x1b[0m ", shell.last_exit_code)
+/// Remove `flag <valor>` dos argumentos e devolve o valor.
+/// Encerra com erro se a flag vier sem valor.
+fn take_flag_value(args: &mut Vec<String>, flag: &str) -> Option<String> {
+    let pos = args.iter().position(|a| a == flag)?;
+    let Some(value) = args.get(pos + 1).cloned() else {
+        eprintln!("{}", t!("cli.flag_requires_value", flag));
+        std::process::exit(1);
     };
-
-    format!(
-        "{}{}:{}{}\x1b[0m{}",
-        path_ansi, "clios", dir_display, git_color, arrow_colored
-    )
+    args.drain(pos..=pos + 1);
+    Some(value)
 }
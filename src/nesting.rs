@@ -0,0 +1,54 @@
+//! # Nesting Module
+//!
+//! Nível de aninhamento da Clios (como o `$SHLVL` do bash).
+//!
+//! Cada Clios iniciada lê `CLIOS_LEVEL` do ambiente, soma 1 e exporta o novo
+//! valor, então uma Clios aberta dentro de outra (ou por um script) sabe a
+//! profundidade em que está:
+//!
+//! - **Prompt:** a partir do nível 2 ganha um indicador `⤷2`.
+//! - **Aviso:** a partir de [`DEEP_LEVEL`] a shell avisa ao iniciar (um
+//!   `.cliosrc` ou alias que abre a Clios de novo costuma ser o motivo).
+//! - **Proteção:** acima de [`MAX_LEVEL`] a shell se recusa a iniciar, o que
+//!   interrompe uma recursão infinita.
+
+use std::env;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Variável exportada com o nível atual.
+pub const LEVEL_VAR: &str = "CLIOS_LEVEL";
+
+/// Nível a partir do qual a shell avisa ao iniciar.
+pub const DEEP_LEVEL: u32 = 4;
+
+/// Nível máximo; acima dele a shell não inicia.
+pub const MAX_LEVEL: u32 = 32;
+
+static LEVEL: AtomicU32 = AtomicU32::new(1);
+
+/// Nível de uma shell filha de uma com `CLIOS_LEVEL = parent` (1 sem valor válido).
+pub fn next_level(parent: Option<&str>) -> u32 {
+    parent
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .map_or(1, |level| level.saturating_add(1))
+}
+
+/// Calcula e exporta o nível desta shell.
+pub fn init_level() -> u32 {
+    let level = next_level(env::var(LEVEL_VAR).ok().as_deref());
+    LEVEL.store(level, Ordering::Relaxed);
+    unsafe {
+        env::set_var(LEVEL_VAR, level.to_string());
+    }
+    level
+}
+
+/// Nível desta shell (1 = não aninhada).
+pub fn current_level() -> u32 {
+    LEVEL.load(Ordering::Relaxed)
+}
+
+/// Texto do indicador do prompt (`⤷2`); `None` no nível 1.
+pub fn level_label(level: u32) -> Option<String> {
+    (level > 1).then(|| format!("⤷{}", level))
+}
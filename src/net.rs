@@ -0,0 +1,407 @@
+//! # Net Module
+//!
+//! Diagnósticos de rede sem depender do net-tools/iproute2/iputils:
+//!
+//! - `ports` - sockets escutando (TCP) ou abertos (UDP), com o processo dono,
+//!   lidos de `/proc/net/*` e `/proc/<pid>/fd`
+//! - `myip` - endereços das interfaces locais e o IP público (consultado por
+//!   HTTP com o `reqwest`, o mesmo cliente do `fetch`)
+//! - `ping-lite host` - tempo de conexão TCP; funciona sem ICMP (que pede
+//!   root ou `CAP_NET_RAW` e costuma ser bloqueado em redes corporativas)
+
+use crate::registry::usage;
+use crate::signals::{is_interrupted, take_interrupt};
+use crate::t;
+use crate::watch::sleep_interruptible;
+use nix::ifaddrs::getifaddrs;
+use nix::net::if_::InterfaceFlags;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+// -----------------------------------------------------------------------------
+// PORTS
+// -----------------------------------------------------------------------------
+
+/// Estado `LISTEN` nas tabelas TCP do kernel.
+const TCP_LISTEN: &str = "0A";
+
+/// Um socket escutando.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListenSocket {
+    /// `tcp`, `tcp6`, `udp` ou `udp6`
+    pub proto: &'static str,
+    pub addr: SocketAddr,
+    pub inode: u64,
+}
+
+/// Endereço no formato do `/proc/net`: hex em palavras de 32 bits na ordem do
+/// host (`0100007F:1F90` é `127.0.0.1:8080` numa máquina little-endian).
+pub fn parse_proc_addr(text: &str) -> Option<SocketAddr> {
+    let (ip, port) = text.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let words: Vec<u32> = (0..ip.len() / 8)
+        .map(|i| u32::from_str_radix(ip.get(i * 8..i * 8 + 8)?, 16).ok())
+        .collect::<Option<_>>()?;
+    let bytes: Vec<u8> = words.iter().flat_map(|w| w.to_ne_bytes()).collect();
+    let ip = match bytes.len() {
+        4 => IpAddr::V4(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3])),
+        16 => IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(bytes).ok()?)),
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+/// Sockets escutando numa tabela do `/proc/net` (`tcp`, `udp6`...). No UDP,
+/// que não tem `LISTEN`, valem os sem endereço remoto.
+pub fn parse_proc_net(content: &str, proto: &'static str) -> Vec<ListenSocket> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (local, remote, state, inode) = (fields.get(1)?, fields.get(2)?, fields.get(3)?, fields.get(9)?);
+            let listening = if proto.starts_with("tcp") {
+                *state == TCP_LISTEN
+            } else {
+                parse_proc_addr(remote).is_some_and(|r| r.port() == 0)
+            };
+            if !listening {
+                return None;
+            }
+            Some(ListenSocket { proto, addr: parse_proc_addr(local)?, inode: inode.parse().ok()? })
+        })
+        .collect()
+}
+
+/// Inode de socket -> (PID, nome) lendo os descritores em `/proc/<pid>/fd`.
+/// Processos de outros usuários só aparecem para o root.
+fn socket_owners() -> HashMap<u64, (u32, String)> {
+    let mut owners = HashMap::new();
+    let Ok(procs) = fs::read_dir("/proc") else {
+        return owners;
+    };
+    for entry in procs.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|n| n.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        let mut name = None;
+        for fd in fds.flatten() {
+            let Ok(target) = fs::read_link(fd.path()) else {
+                continue;
+            };
+            let Some(inode) = target
+                .to_str()
+                .and_then(|t| t.strip_prefix("socket:["))
+                .and_then(|t| t.strip_suffix(']'))
+                .and_then(|t| t.parse::<u64>().ok())
+            else {
+                continue;
+            };
+            let name = name.get_or_insert_with(|| {
+                fs::read_to_string(entry.path().join("comm")).map(|c| c.trim().to_string()).unwrap_or_default()
+            });
+            owners.entry(inode).or_insert_with(|| (pid, name.clone()));
+        }
+    }
+    owners
+}
+
+/// Builtin `ports [-t | -u] [porta]`.
+pub fn handle_ports(tokens: &[String]) -> i32 {
+    let mut tables: Vec<&'static str> = vec!["tcp", "tcp6", "udp", "udp6"];
+    let mut port = None;
+    for arg in &tokens[1..] {
+        match arg.as_str() {
+            "-t" | "--tcp" => tables.retain(|t| t.starts_with("tcp")),
+            "-u" | "--udp" => tables.retain(|t| t.starts_with("udp")),
+            _ if port.is_none() && arg.parse::<u16>().is_ok() => port = arg.parse::<u16>().ok(),
+            _ => {
+                eprintln!("{}", usage("ports"));
+                return 2;
+            }
+        }
+    }
+
+    let mut sockets = Vec::new();
+    let mut found_table = false;
+    for proto in tables {
+        if let Ok(content) = fs::read_to_string(format!("/proc/net/{}", proto)) {
+            found_table = true;
+            sockets.extend(parse_proc_net(&content, proto));
+        }
+    }
+    if !found_table {
+        eprintln!("{}", t!("ports.unsupported"));
+        return 1;
+    }
+    sockets.retain(|s| port.is_none_or(|p| s.addr.port() == p));
+    if sockets.is_empty() {
+        eprintln!("{}", t!("ports.none"));
+        return 1;
+    }
+    sockets.sort_by_key(|s| (s.addr.port(), s.proto, s.addr.ip()));
+    sockets.dedup_by(|a, b| a.proto == b.proto && a.addr == b.addr);
+
+    let owners = socket_owners();
+    let address_width = sockets.iter().map(|s| s.addr.ip().to_string().len()).max().unwrap_or(0).max(8);
+    println!(
+        "\x1b[1m{:<6} {:<aw$} {:>6}  {:>7}  {}\x1b[0m",
+        "PROTO",
+        t!("ports.address"),
+        t!("ports.port"),
+        "PID",
+        t!("ports.process"),
+        aw = address_width
+    );
+    let mut hidden = false;
+    for socket in &sockets {
+        let (pid, name) = match owners.get(&socket.inode) {
+            Some((pid, name)) => (pid.to_string(), name.as_str()),
+            None => {
+                hidden = true;
+                ("-".to_string(), "-")
+            }
+        };
+        println!(
+            "{:<6} {:<aw$} \x1b[1;36m{:>6}\x1b[0m  {:>7}  {}",
+            socket.proto,
+            socket.addr.ip(),
+            socket.addr.port(),
+            pid,
+            name,
+            aw = address_width
+        );
+    }
+    if hidden && !nix::unistd::geteuid().is_root() {
+        eprintln!("\x1b[90m{}\x1b[0m", t!("ports.hidden_owner"));
+    }
+    0
+}
+
+// -----------------------------------------------------------------------------
+// MYIP
+// -----------------------------------------------------------------------------
+
+/// Serviços que devolvem o IP público em texto puro, tentados em ordem.
+const PUBLIC_IP_SERVICES: &[&str] = &["https://api.ipify.org", "https://ifconfig.me/ip", "https://icanhazip.com"];
+
+/// Endereços das interfaces ativas (sem loopback): (interface, IP).
+pub fn local_addresses() -> io::Result<Vec<(String, IpAddr)>> {
+    let mut addresses = Vec::new();
+    for iface in getifaddrs()? {
+        if !iface.flags.contains(InterfaceFlags::IFF_UP) || iface.flags.contains(InterfaceFlags::IFF_LOOPBACK) {
+            continue;
+        }
+        let Some(address) = iface.address else {
+            continue;
+        };
+        let ip = if let Some(v4) = address.as_sockaddr_in() {
+            IpAddr::V4(v4.ip())
+        } else if let Some(v6) = address.as_sockaddr_in6() {
+            IpAddr::V6(v6.ip())
+        } else {
+            continue;
+        };
+        addresses.push((iface.interface_name, ip));
+    }
+    Ok(addresses)
+}
+
+/// IP público, perguntando aos [`PUBLIC_IP_SERVICES`].
+pub fn public_ip() -> Result<IpAddr, String> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut last_error = String::new();
+    for url in PUBLIC_IP_SERVICES {
+        match client.get(*url).send().and_then(|r| r.error_for_status()).and_then(|r| r.text()) {
+            Ok(body) => match body.trim().parse() {
+                Ok(ip) => return Ok(ip),
+                Err(_) => last_error = t!("myip.invalid_response", url),
+            },
+            Err(e) => last_error = e.to_string(),
+        }
+    }
+    Err(last_error)
+}
+
+/// Builtin `myip [-l | -p]`.
+pub fn handle_myip(tokens: &[String]) -> i32 {
+    let (local, public) = match tokens.get(1).map(|s| s.as_str()) {
+        None if tokens.len() == 1 => (true, true),
+        Some("-l" | "--local") if tokens.len() == 2 => (true, false),
+        Some("-p" | "--public") if tokens.len() == 2 => (false, true),
+        _ => {
+            eprintln!("{}", usage("myip"));
+            return 2;
+        }
+    };
+
+    // Só o IP público: uma linha, para `$(myip -p)`
+    if !local {
+        return match public_ip() {
+            Ok(ip) => {
+                println!("{}", ip);
+                0
+            }
+            Err(e) => {
+                eprintln!("{}", t!("myip.public_failed", e));
+                1
+            }
+        };
+    }
+
+    let mut status = 0;
+    match local_addresses() {
+        Ok(addresses) => {
+            let width = addresses.iter().map(|(name, _)| name.len()).max().unwrap_or(0).max(8);
+            for (name, ip) in &addresses {
+                println!("\x1b[1;36m{:<w$}\x1b[0m  {}", name, ip, w = width);
+            }
+            if public {
+                match public_ip() {
+                    Ok(ip) => println!("\x1b[1;35m{:<w$}\x1b[0m  {}", t!("myip.public"), ip, w = width),
+                    Err(e) => {
+                        eprintln!("{}", t!("myip.public_failed", e));
+                        status = 1;
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            eprintln!("myip: {}", e);
+            status = 1;
+        }
+    }
+    status
+}
+
+// -----------------------------------------------------------------------------
+// PING-LITE
+// -----------------------------------------------------------------------------
+
+/// Porta testada sem `-p` (HTTPS costuma passar por qualquer firewall).
+pub const DEFAULT_PING_PORT: u16 = 443;
+
+/// Espera por conexão antes de contar como perda.
+const PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Opções do `ping-lite`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PingOptions {
+    pub host: String,
+    pub port: u16,
+    /// Tentativas (`None`: até o Ctrl+C)
+    pub count: Option<u32>,
+}
+
+/// Interpreta `host`, `host:porta`, `[::1]:22`, `-p porta` e `-c N`.
+pub fn parse_ping_args(args: &[String]) -> Result<PingOptions, String> {
+    let mut host = None;
+    let mut port = None;
+    let mut count = Some(4);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-p" | "--port" => port = Some(args.next().and_then(|p| p.parse().ok()).ok_or(t!("ping.requires_port"))?),
+            "-c" | "--count" => count = Some(args.next().and_then(|c| c.parse().ok()).ok_or_else(|| t!("args.requires_number", "-c"))?),
+            "-t" => count = None,
+            flag if flag.starts_with('-') => return Err(t!("args.unknown_option", flag)),
+            _ if host.is_some() => return Err(t!("args.extra_argument", arg)),
+            _ => host = Some(arg.clone()),
+        }
+    }
+    let host = host.ok_or(t!("ping.missing_host"))?;
+    // `host:porta` (um `:` só; IPv6 com porta vai entre colchetes)
+    let (host, inline_port) = if let Some(rest) = host.strip_prefix('[') {
+        let (ip, tail) = rest.split_once(']').ok_or(t!("ping.unclosed_ipv6"))?;
+        let port = tail.strip_prefix(':').map(|p| p.parse().map_err(|_| t!("ping.invalid_port", p))).transpose()?;
+        (ip.to_string(), port)
+    } else {
+        match host.split_once(':') {
+            Some((name, p)) if !p.contains(':') => (name.to_string(), Some(p.parse().map_err(|_| t!("ping.invalid_port", p))?)),
+            _ => (host, None),
+        }
+    };
+    if count == Some(0) {
+        return Err(t!("ping.zero_count").to_string());
+    }
+    Ok(PingOptions { host, port: port.or(inline_port).unwrap_or(DEFAULT_PING_PORT), count })
+}
+
+/// Resumo: (mínimo, média, máximo) em ms.
+pub fn ping_stats(times: &[f64]) -> Option<(f64, f64, f64)> {
+    if times.is_empty() {
+        return None;
+    }
+    let min = times.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = times.iter().copied().fold(0.0, f64::max);
+    Some((min, times.iter().sum::<f64>() / times.len() as f64, max))
+}
+
+/// Builtin `ping-lite [-p porta] [-c N | -t] host[:porta]`.
+pub fn handle_ping_lite(tokens: &[String]) -> i32 {
+    let opts = match parse_ping_args(&tokens[1..]) {
+        Ok(opts) => opts,
+        Err(e) => {
+            eprintln!("ping-lite: {}", e);
+            eprintln!("{}", usage("ping-lite"));
+            return 2;
+        }
+    };
+    let addr = match (opts.host.as_str(), opts.port).to_socket_addrs().map(|mut a| a.next()) {
+        Ok(Some(addr)) => addr,
+        Ok(None) => {
+            eprintln!("ping-lite: {}: {}", opts.host, t!("ping.no_address"));
+            return 1;
+        }
+        Err(e) => {
+            eprintln!("ping-lite: {}: {}", opts.host, e);
+            return 1;
+        }
+    };
+    println!("{}", t!("ping.header", opts.host, addr.ip(), opts.port));
+
+    let mut times = Vec::new();
+    let mut sent = 0;
+    while opts.count.is_none_or(|c| sent < c) && !is_interrupted() {
+        if sent > 0 && !sleep_interruptible(Duration::from_secs(1)) {
+            break;
+        }
+        sent += 1;
+        let start = Instant::now();
+        let result = TcpStream::connect_timeout(&addr, PING_TIMEOUT);
+        let ms = start.elapsed().as_secs_f64() * 1000.0;
+        match result {
+            Ok(_) => {
+                times.push(ms);
+                println!("{}", t!("ping.reply", addr, sent, format!("{:.1}", ms)));
+            }
+            // RST: o host respondeu, só a porta está fechada
+            Err(e) if e.kind() == io::ErrorKind::ConnectionRefused => {
+                times.push(ms);
+                println!("{}", t!("ping.refused", addr, sent, format!("{:.1}", ms)));
+            }
+            Err(e) if e.kind() == io::ErrorKind::TimedOut => {
+                println!("\x1b[33m{}\x1b[0m", t!("ping.timeout", addr, sent, PING_TIMEOUT.as_secs()));
+            }
+            Err(e) => println!("\x1b[31m{}: seq={} {}\x1b[0m", addr, sent, e),
+        }
+    }
+    // O Ctrl+C só encerra as tentativas; o resumo sai como no `ping`
+    take_interrupt();
+
+    let loss = (sent as usize - times.len()) * 100 / (sent as usize).max(1);
+    println!("\n{}", t!("ping.summary", sent, times.len(), loss));
+    if let Some((min, avg, max)) = ping_stats(&times) {
+        println!("{}", t!("ping.times", format!("{:.1}", min), format!("{:.1}", avg), format!("{:.1}", max)));
+    }
+    if times.is_empty() { 1 } else { 0 }
+}
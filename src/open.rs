@@ -0,0 +1,120 @@
+//! # Open Module
+//!
+//! Builtin `open`: abre arquivos, diretórios e URLs no programa padrão do
+//! sistema, sem lembrar qual comando cada plataforma usa.
+//!
+//! - Linux/BSD: `xdg-open` (ou `gio open`, se só ele existir)
+//! - macOS: `open`
+//! - Windows: `cmd /C start`
+//!
+//! Argumentos que não existem no disco mas parecem um endereço
+//! (`github.com/foo`, `localhost:3000`) viram URLs: `open github.com/foo`
+//! abre `https://github.com/foo`.
+
+use crate::command_hash;
+use crate::registry::usage;
+use crate::t;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Prefixos que já são URLs completas.
+const URL_SCHEMES: &[&str] = &["http://", "https://", "file://", "ftp://", "mailto:", "ssh://"];
+
+/// Comando que abre um item no programa padrão da plataforma.
+pub fn opener() -> Vec<String> {
+    let tokens: &[&str] = if cfg!(target_os = "macos") {
+        &["open"]
+    } else if cfg!(windows) {
+        // O primeiro argumento entre aspas do `start` é o título da janela
+        &["cmd", "/C", "start", ""]
+    } else if command_hash::peek("xdg-open").is_none() && command_hash::peek("gio").is_some() {
+        &["gio", "open"]
+    } else {
+        &["xdg-open"]
+    };
+    tokens.iter().map(|s| s.to_string()).collect()
+}
+
+/// Domínios aceitos sem caminho (`open example.com`); com caminho ou porta
+/// (`github.com/foo`) qualquer domínio vale. Sem a lista, `open notas.md` com
+/// o nome errado abriria um site.
+const BARE_TLDS: &[&str] = &["com", "org", "net", "io", "dev", "app", "br", "edu", "gov", "co", "me", "ai"];
+
+/// `arg` tem cara de endereço web (`github.com/foo`, `localhost:8080/x`)?
+fn looks_like_host(arg: &str) -> bool {
+    let host = arg.split(['/', '?', '#']).next().unwrap_or("");
+    let bare = host == arg && !host.contains(':');
+    let (name, port) = match host.rsplit_once(':') {
+        Some((name, port)) => (name, Some(port)),
+        None => (host, None),
+    };
+    if port.is_some_and(|p| p.is_empty() || !p.chars().all(|c| c.is_ascii_digit())) {
+        return false;
+    }
+    if name == "localhost" {
+        return true;
+    }
+    let labels: Vec<&str> = name.split('.').collect();
+    labels.len() >= 2
+        && labels
+            .iter()
+            .all(|l| !l.is_empty() && l.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+        && labels.last().is_some_and(|tld| {
+            tld.len() >= 2
+                && tld.chars().all(|c| c.is_ascii_alphabetic())
+                && (!bare || labels[0] == "www" || BARE_TLDS.contains(&tld.to_ascii_lowercase().as_str()))
+        })
+}
+
+/// O que entregar ao [`opener`]: URLs como estão, caminhos existentes como
+/// estão e endereços sem esquema com `https://` (`http://` para `localhost`).
+/// `None` quando não é nem um caminho existente nem um endereço.
+pub fn resolve_target(arg: &str, exists: bool) -> Option<String> {
+    if URL_SCHEMES.iter().any(|scheme| arg.starts_with(scheme)) || exists {
+        return Some(arg.to_string());
+    }
+    if !looks_like_host(arg) {
+        return None;
+    }
+    let scheme = if arg.starts_with("localhost") { "http" } else { "https" };
+    Some(format!("{}://{}", scheme, arg))
+}
+
+/// Builtin `open <caminho|url...>`.
+pub fn handle_open(tokens: &[String]) -> i32 {
+    let args = &tokens[1..];
+    if args.is_empty() || args.iter().any(|a| a.starts_with('-')) {
+        eprintln!("{}", usage("open"));
+        return 2;
+    }
+
+    let opener = opener();
+    let mut status = 0;
+    for arg in args {
+        let Some(target) = resolve_target(arg, Path::new(arg).exists()) else {
+            eprintln!("{}", t!("open.not_found", arg));
+            status = 1;
+            continue;
+        };
+        // O programa aberto não deve escrever no terminal da shell
+        let result = Command::new(&opener[0])
+            .args(&opener[1..])
+            .arg(&target)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        match result {
+            Ok(s) if s.success() => {}
+            Ok(_) => {
+                eprintln!("{}", t!("open.failed", target, opener.join(" ")));
+                status = 1;
+            }
+            Err(e) => {
+                eprintln!("{}", t!("open.failed", target, e));
+                status = 1;
+            }
+        }
+    }
+    status
+}
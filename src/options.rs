@@ -0,0 +1,136 @@
+//! # Options Module
+//!
+//! Opções da shell, ligadas e desligadas com o `set`, como no bash:
+//!
+//! ```bash
+//! set -o pipefail       # liga
+//! set +o pipefail       # desliga
+//! set -x                # o mesmo que set -o xtrace
+//! set -o                # as opções e o estado de cada uma
+//! set +o                # o mesmo, como comandos para um ~/.cliosrc
+//! ```
+//!
+//! - `dryrun`: os comandos são mostrados, já expandidos, e não rodam (só o
+//!   `set`, para a opção poder ser desligada). Cada um conta como sucesso.
+//! - `pipefail`: o código de uma pipeline é o do primeiro comando que falhou,
+//!   e não só o do último comando: `curl -s url | tar x` falha junto com o
+//!   `curl`, e o `&&` seguinte não roda.
+//! - `xtrace` (`-x`): cada comando aparece na saída de erro (`+ cmd`), já
+//!   expandido, antes de rodar.
+//!
+//! Nos dois casos a linha mostrada tem as aspas de volta ([`join_command`]):
+//! colada no prompt, roda o mesmo comando.
+//!
+//! Um subshell (`( ... )`, `$( ... )`, um job com `&`) herda as opções.
+
+use crate::batch::trace_line;
+use crate::quote::join_command;
+use crate::registry::usage;
+use crate::t;
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Opção `dryrun`.
+static DRYRUN: AtomicBool = AtomicBool::new(false);
+
+/// Opção `pipefail`.
+static PIPEFAIL: AtomicBool = AtomicBool::new(false);
+
+/// Opção `xtrace`.
+static XTRACE: AtomicBool = AtomicBool::new(false);
+
+/// As opções, pelo nome, na ordem do `set -o`.
+static OPTIONS: &[(&str, &AtomicBool)] = &[("dryrun", &DRYRUN), ("pipefail", &PIPEFAIL), ("xtrace", &XTRACE)];
+
+/// As letras do `set -x` / `set +x`, como no bash.
+static SHORT_OPTIONS: &[(&str, &str)] = &[("x", "xtrace")];
+
+/// A opção `pipefail` está ligada?
+pub fn pipefail() -> bool {
+    PIPEFAIL.load(Ordering::Relaxed)
+}
+
+/// O estado da opção `name`; `None` se ela não existe.
+pub fn option_enabled(name: &str) -> Option<bool> {
+    OPTIONS.iter().find(|(option, _)| *option == name).map(|(_, flag)| flag.load(Ordering::Relaxed))
+}
+
+/// Liga ou desliga a opção `name`. Devolve `false` se ela não existe.
+pub fn set_option(name: &str, enabled: bool) -> bool {
+    match OPTIONS.iter().find(|(option, _)| *option == name) {
+        Some((_, flag)) => {
+            flag.store(enabled, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Mostra um comando (ou os comandos de uma pipeline) já expandido: na saída
+/// de erro com `xtrace`, na saída padrão com `dryrun`. Devolve `true` se ele
+/// não deve rodar.
+pub fn announce_command(commands: &[Vec<String>]) -> bool {
+    let is_set = matches!(commands, [command] if command.first().is_some_and(|name| name == "set"));
+    let dry_run = DRYRUN.load(Ordering::Relaxed) && !is_set;
+    if !dry_run && !XTRACE.load(Ordering::Relaxed) {
+        return false;
+    }
+    let line = commands.iter().map(|command| join_command(command)).collect::<Vec<_>>().join(" | ");
+    if dry_run {
+        println!("{}", line);
+    } else {
+        eprintln!("{}", trace_line(&line, io::stderr().is_terminal()));
+    }
+    dry_run
+}
+
+/// `-x` / `+x`: o nome da opção e se ela liga.
+fn short_option(flag: &str) -> Option<(&'static str, bool)> {
+    let (letter, enabled) = match flag.split_at_checked(1)? {
+        ("-", letter) => (letter, true),
+        ("+", letter) => (letter, false),
+        _ => return None,
+    };
+    SHORT_OPTIONS.iter().find(|(short, _)| *short == letter).map(|(_, name)| (*name, enabled))
+}
+
+/// Builtin `set [-o|+o] [opção...]`.
+pub fn handle_set(tokens: &[String]) -> i32 {
+    let args: Vec<&str> = tokens[1..].iter().map(String::as_str).collect();
+    if let [flag] = args[..]
+        && let Some((name, enabled)) = short_option(flag)
+    {
+        set_option(name, enabled);
+        return 0;
+    }
+    match args[..] {
+        [] | ["-o"] => {
+            for (name, flag) in OPTIONS {
+                let state = if flag.load(Ordering::Relaxed) { t!("set.on") } else { t!("set.off") };
+                println!("{:<15} {}", name, state);
+            }
+            0
+        }
+        ["+o"] => {
+            for (name, flag) in OPTIONS {
+                println!("set {}o {}", if flag.load(Ordering::Relaxed) { '-' } else { '+' }, name);
+            }
+            0
+        }
+        [flag @ ("-o" | "+o"), ref names @ ..] => {
+            // Nada muda se algum nome está errado
+            if let Some(unknown) = names.iter().find(|name| option_enabled(name).is_none()) {
+                eprintln!("{}", t!("set.unknown_option", unknown));
+                return 2;
+            }
+            for name in names {
+                set_option(name, flag == "-o");
+            }
+            0
+        }
+        _ => {
+            eprintln!("{}", usage("set"));
+            2
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! # Parallel Module
+//!
+//! Builtin `parallel`: executa um comando para cada argumento, com vários
+//! processos ao mesmo tempo.
+//!
+//! ## Formas
+//! - `parallel -j4 -- gzip ::: a.log b.log c.log` - argumentos após `:::`
+//! - `parallel -a lista.txt wget` - um argumento por linha do arquivo
+//! - `parallel echo` - sem `:::` nem `-a`, lê as linhas do STDIN
+//!
+//! `{}` no comando é trocado pelo argumento; sem `{}` o argumento vai no fim.
+//! Cada processo fica registrado na lista de jobs enquanto roda. Com `-k` a
+//! saída de cada comando é bufferizada e impressa na ordem dos argumentos.
+//!
+//! O código de saída é o número de comandos que falharam (máximo 100).
+
+use crate::executor::{self, ProcessGroup};
+use crate::jobs::{add_job, remove_job, JobList};
+use crate::signals::{is_interrupted, take_interrupt, EXIT_INTERRUPTED};
+use crate::t;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Limite do código de saída agregado (como no GNU parallel)
+const MAX_FAILED_EXIT: i32 = 100;
+
+/// Opções do `parallel`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParallelOptions {
+    /// Número de processos simultâneos
+    pub jobs: usize,
+    /// Bufferiza a saída e imprime na ordem dos argumentos
+    pub keep_order: bool,
+    /// Tokens do comando (podem conter `{}`)
+    pub template: Vec<String>,
+    /// Argumentos explícitos (`:::` ou `-a`); `None` = ler do STDIN
+    pub args: Option<Vec<String>>,
+}
+
+/// Resultado de um comando.
+struct JobOutcome {
+    index: usize,
+    code: i32,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+}
+
+fn default_jobs() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+/// Interpreta os argumentos do `parallel` (sem o nome do builtin).
+pub fn parse_parallel_args(args: &[String]) -> Result<ParallelOptions, String> {
+    let mut jobs = default_jobs();
+    let mut keep_order = false;
+    let mut arg_file = None;
+    let mut i = 0;
+
+    while i < args.len() {
+        let arg = args[i].as_str();
+        match arg {
+            "-k" | "--keep-order" => {
+                keep_order = true;
+                i += 1;
+            }
+            "-j" | "--jobs" | "-a" | "--arg-file" => {
+                let value = args.get(i + 1).ok_or_else(|| format!("{} requer um valor", arg))?;
+                if arg.starts_with("-a") || arg == "--arg-file" {
+                    arg_file = Some(value.clone());
+                } else {
+                    jobs = parse_jobs(value)?;
+                }
+                i += 2;
+            }
+            "--" => {
+                i += 1;
+                break;
+            }
+            _ if arg.starts_with("-j") && arg.len() > 2 => {
+                jobs = parse_jobs(&arg[2..])?;
+                i += 1;
+            }
+            _ => break,
+        }
+    }
+
+    let rest = &args[i..];
+    let (template, explicit) = match rest.iter().position(|a| a == ":::") {
+        Some(sep) => (rest[..sep].to_vec(), Some(rest[sep + 1..].to_vec())),
+        None => (rest.to_vec(), None),
+    };
+    if template.is_empty() {
+        return Err(t!("args.no_command").to_string());
+    }
+
+    let args = match (explicit, arg_file) {
+        (Some(explicit), _) => Some(explicit),
+        (None, Some(path)) => {
+            let content = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path, e))?;
+            Some(content.lines().filter(|l| !l.is_empty()).map(String::from).collect())
+        }
+        (None, None) => None,
+    };
+
+    Ok(ParallelOptions {
+        jobs,
+        keep_order,
+        template,
+        args,
+    })
+}
+
+fn parse_jobs(value: &str) -> Result<usize, String> {
+    value
+        .parse::<usize>()
+        .ok()
+        .filter(|n| *n > 0)
+        .ok_or_else(|| t!("parallel.invalid_jobs", value))
+}
+
+/// Monta os tokens de um comando trocando `{}` pelo argumento.
+pub fn build_command(template: &[String], arg: &str) -> Vec<String> {
+    if template.iter().any(|t| t.contains("{}")) {
+        template.iter().map(|t| t.replace("{}", arg)).collect()
+    } else {
+        let mut tokens = template.to_vec();
+        tokens.push(arg.to_string());
+        tokens
+    }
+}
+
+/// Executa um comando, registrando-o na lista de jobs enquanto roda.
+fn run_one(tokens: &[String], capture: bool, jobs: &JobList) -> (i32, Vec<u8>, Vec<u8>) {
+    let mut command = executor::command(tokens, ProcessGroup::Shell);
+    if capture {
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    }
+
+    let child = match executor::spawn(&mut command) {
+        Ok(child) => child,
+        Err(e) => {
+            let msg = format!("parallel: {}\n", e);
+            if capture {
+                return (e.exit_code(), Vec::new(), msg.into_bytes());
+            }
+            eprint!("{}", msg);
+            return (e.exit_code(), Vec::new(), Vec::new());
+        }
+    };
+
+    let pid = child.id() as i32;
+    add_job(jobs, pid, tokens.join(" "));
+    let result = child.wait_with_output();
+    remove_job(jobs, pid);
+
+    match result {
+        Ok(out) => (executor::exit_code(out.status), out.stdout, out.stderr),
+        Err(_) => (1, Vec::new(), Vec::new()),
+    }
+}
+
+/// Executa todos os comandos. Retorna o código agregado.
+pub fn run_parallel(opts: &ParallelOptions, jobs: &JobList) -> i32 {
+    let args = match &opts.args {
+        Some(args) => args.clone(),
+        None => io::stdin().lock().lines().map_while(Result::ok).filter(|l| !l.is_empty()).collect(),
+    };
+    if args.is_empty() {
+        return 0;
+    }
+
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel::<JobOutcome>();
+    let workers = opts.jobs.min(args.len());
+    let mut failed = 0;
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let tx = tx.clone();
+            let (next, args) = (&next, &args);
+            scope.spawn(move || {
+                loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    if index >= args.len() || is_interrupted() {
+                        break;
+                    }
+                    let tokens = build_command(&opts.template, &args[index]);
+                    let (code, stdout, stderr) = run_one(&tokens, opts.keep_order, jobs);
+                    let _ = tx.send(JobOutcome {
+                        index,
+                        code,
+                        stdout,
+                        stderr,
+                    });
+                }
+            });
+        }
+        drop(tx);
+
+        // Com -k, imprime assim que o próximo índice da sequência fica pronto
+        let mut pending: BTreeMap<usize, JobOutcome> = BTreeMap::new();
+        let mut next_to_print = 0;
+        for outcome in rx {
+            if outcome.code != 0 {
+                failed += 1;
+            }
+            if !opts.keep_order {
+                continue;
+            }
+            pending.insert(outcome.index, outcome);
+            while let Some(ready) = pending.remove(&next_to_print) {
+                let _ = io::stdout().write_all(&ready.stdout);
+                let _ = io::stderr().write_all(&ready.stderr);
+                next_to_print += 1;
+            }
+        }
+        // Interrompido no meio: imprime o que terminou
+        for ready in pending.into_values() {
+            let _ = io::stdout().write_all(&ready.stdout);
+            let _ = io::stderr().write_all(&ready.stderr);
+        }
+        let _ = io::stdout().flush();
+    });
+
+    if take_interrupt() {
+        return EXIT_INTERRUPTED;
+    }
+    failed.min(MAX_FAILED_EXIT)
+}
@@ -0,0 +1,310 @@
+//! # Parser Module
+//!
+//! Divide a linha de comando em **posições de comando** (início da linha e
+//! tudo que vem depois de `|`, `&&` e `||`) respeitando aspas.
+//!
+//! ## Responsabilidades
+//! - Separar estágios de pipeline (`|`)
+//! - Expandir aliases em cada posição de comando **exatamente uma vez**
+//!
+//! A expansão de aliases acontece aqui, antes do parser lógico e do parser de
+//! pipes, para que `ls | count` resolva `count` e para que aliases cujo valor
+//! contém `|` ou `&&` sejam lidos como se o usuário os tivesse digitado.
+//!
+//! Também faz a análise sintática usada pelo editor de linha ([`analyze_line`]):
+//! onde estão os operadores e se a linha está incompleta (aspas abertas,
+//! `|`/`&&`/`||` no final, um `case` sem `esac` ou um `select` sem `done`).
+
+use crate::expansion::expand_alias_checked;
+use crate::statements::open_statement;
+use crate::t;
+use std::collections::HashMap;
+
+// -----------------------------------------------------------------------------
+// COMMAND POSITIONS
+// -----------------------------------------------------------------------------
+
+/// Um trecho da linha entre dois operadores, com o operador que o segue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandSegment {
+    pub text: String,
+    pub next_op: Option<&'static str>,
+}
+
+/// Divide a linha em segmentos separados por `|`, `&&` e `||` (fora de aspas
+/// e de grupos `( ... )`).
+pub fn split_command_positions(input: &str) -> Vec<CommandSegment> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut in_single_quote = false;
+    let mut in_double_quote = false;
+    let mut depth = 0usize;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let quoted = in_single_quote || in_double_quote || depth > 0;
+        let op = match c {
+            '\'' if !in_double_quote => {
+                in_single_quote = !in_single_quote;
+                None
+            }
+            '"' if !in_single_quote => {
+                in_double_quote = !in_double_quote;
+                None
+            }
+            '(' if !in_single_quote && !in_double_quote => {
+                depth += 1;
+                None
+            }
+            ')' if !in_single_quote && !in_double_quote => {
+                depth = depth.saturating_sub(1);
+                None
+            }
+            '|' if !quoted => {
+                if chars.peek() == Some(&'|') {
+                    chars.next();
+                    Some("||")
+                } else {
+                    Some("|")
+                }
+            }
+            '&' if !quoted && chars.peek() == Some(&'&') => {
+                chars.next();
+                Some("&&")
+            }
+            _ => None,
+        };
+
+        match op {
+            Some(op) => {
+                segments.push(CommandSegment {
+                    text: std::mem::take(&mut current),
+                    next_op: Some(op),
+                });
+            }
+            None => current.push(c),
+        }
+    }
+
+    segments.push(CommandSegment {
+        text: current,
+        next_op: None,
+    });
+    segments
+}
+
+/// Divide uma string por pipes (|) respeitando aspas.
+///
+/// Esta função percorre a string caractere por caractere e só divide por |
+/// quando não está dentro de aspas simples ou duplas.
+pub fn split_pipes_respecting_quotes(input: &str) -> Vec<String> {
+    let parts: Vec<String> = split_command_positions(input)
+        .into_iter()
+        .map(|s| s.text)
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    // Se não encontrou nenhum pipe ou se parts está vazio, retorna input
+    if parts.is_empty() {
+        vec![input.to_string()]
+    } else {
+        parts
+    }
+}
+
+/// Reconhece uma pipeline que vai para o background (`a | b &`) e devolve a
+/// linha sem o `&`; `None` para um comando só ou sem o `&` no fim.
+pub fn background_pipeline(input: &str) -> Option<&str> {
+    let line = input.trim().strip_suffix('&')?.trim_end();
+    (!line.ends_with('&') && split_pipes_respecting_quotes(line).len() > 1).then_some(line)
+}
+
+/// Reconhece um bloco que é um único grupo `( ... )`, opcionalmente seguido
+/// de `&`.
+///
+/// Retorna o conteúdo do grupo e se ele vai para o background; `None` se o
+/// bloco não for um grupo (ex: `(a) | b` ou `echo (x)`).
+pub fn subshell_group(input: &str) -> Option<(&str, bool)> {
+    let trimmed = input.trim();
+    let (body, background) = match trimmed.strip_suffix('&') {
+        Some(rest) if !rest.ends_with('&') => (rest.trim_end(), true),
+        _ => (trimmed, false),
+    };
+    if !body.starts_with('(') {
+        return None;
+    }
+
+    let mut in_single_quote = false;
+    let mut in_double_quote = false;
+    let mut depth = 0usize;
+    for (i, c) in body.char_indices() {
+        match c {
+            '\'' if !in_double_quote => in_single_quote = !in_single_quote,
+            '"' if !in_single_quote => in_double_quote = !in_double_quote,
+            '(' if !in_single_quote && !in_double_quote => depth += 1,
+            ')' if !in_single_quote && !in_double_quote => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    // O grupo precisa fechar no fim do bloco
+                    return (i == body.len() - 1).then(|| (&body[1..i], background));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+// -----------------------------------------------------------------------------
+// ALIAS EXPANSION PER COMMAND POSITION
+// -----------------------------------------------------------------------------
+
+/// Expande aliases em todas as posições de comando da linha.
+///
+/// Cada posição é resolvida uma única vez. Quando o valor de um alias traz
+/// novos operadores (ex: `psg='ps aux | grep -i'`), as novas posições também
+/// são resolvidas, mas os aliases já ativos naquela cadeia não são reaplicados.
+pub fn expand_aliases_in_line(input: &str, aliases: &HashMap<String, String>) -> String {
+    if aliases.is_empty() {
+        return input.to_string();
+    }
+    expand_line_with_active(input, aliases, &[])
+}
+
+fn expand_line_with_active(input: &str, aliases: &HashMap<String, String>, active: &[String]) -> String {
+    let mut output = String::with_capacity(input.len());
+
+    for segment in split_command_positions(input) {
+        let first_word = segment.text.split_whitespace().next().unwrap_or("");
+
+        let expanded = if active.iter().any(|a| a == first_word) {
+            segment.text.clone()
+        } else {
+            match expand_alias_checked(&segment.text, aliases) {
+                Ok(expanded) if expanded != segment.text => {
+                    // O valor do alias pode ter criado novas posições de comando
+                    if split_command_positions(&expanded).len() > 1 {
+                        let mut chain = active.to_vec();
+                        chain.push(first_word.to_string());
+                        expand_line_with_active(&expanded, aliases, &chain)
+                    } else {
+                        expanded
+                    }
+                }
+                Ok(unchanged) => unchanged,
+                Err(cycle) => {
+                    eprintln!("{}", t!("tag.warning", t!("exec.alias_cycle", cycle.join(" -> "))));
+                    segment.text.clone()
+                }
+            }
+        };
+
+        output.push_str(&expanded);
+        if let Some(op) = segment.next_op {
+            if !output.ends_with(' ') {
+                output.push(' ');
+            }
+            output.push_str(op);
+            output.push(' ');
+        }
+    }
+    output
+}
+
+// -----------------------------------------------------------------------------
+// LINE ANALYSIS (EDITOR)
+// -----------------------------------------------------------------------------
+
+/// Um operador encontrado na linha (posições em bytes).
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperatorSpan {
+    pub start: usize,
+    pub end: usize,
+    pub op: &'static str,
+}
+
+/// Resultado da análise de uma linha em edição.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LineSyntax {
+    /// Operadores fora de aspas (`|`, `||`, `&&`, `&`)
+    pub operators: Vec<OperatorSpan>,
+    /// Posição da aspa que ficou aberta, se houver
+    pub open_quote: Option<usize>,
+}
+
+/// Por que uma linha não pode ser executada ainda.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Incomplete {
+    /// Aspa (`'` ou `"`) sem fechamento
+    UnclosedQuote(char),
+    /// Linha termina em `|`, `&&` ou `||`
+    DanglingOperator(&'static str),
+    /// `case` ainda sem o `esac` ou `select` sem o `done`
+    UnclosedStatement,
+}
+
+impl LineSyntax {
+    /// Operador no fim da linha que ainda espera um comando.
+    pub fn dangling_operator(&self, input: &str) -> Option<&OperatorSpan> {
+        self.operators
+            .last()
+            .filter(|span| span.op != "&" && input[span.end..].trim().is_empty())
+    }
+}
+
+/// Localiza operadores e aspas abertas, com as mesmas regras de aspas do
+/// [`split_command_positions`] (mais `\` escapando o próximo caractere).
+pub fn analyze_line(input: &str) -> LineSyntax {
+    let mut syntax = LineSyntax::default();
+    let mut quote: Option<(char, usize)> = None;
+    let mut chars = input.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (Some((q, _)), _) if c == q => quote = None,
+            (Some(('"', _)), '\\') => {
+                chars.next();
+            }
+            (Some(_), _) => {}
+            (None, '\\') => {
+                chars.next();
+            }
+            (None, '\'' | '"') => quote = Some((c, i)),
+            (None, '|' | '&') => {
+                let doubled = chars.peek().map(|&(_, next)| next) == Some(c);
+                if doubled {
+                    chars.next();
+                }
+                let op = match (c, doubled) {
+                    ('|', true) => "||",
+                    ('|', false) => "|",
+                    ('&', true) => "&&",
+                    _ => "&",
+                };
+                syntax.operators.push(OperatorSpan {
+                    start: i,
+                    end: i + op.len(),
+                    op,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    syntax.open_quote = quote.map(|(_, i)| i);
+    syntax
+}
+
+/// Verifica se a linha está sintaticamente incompleta.
+pub fn incomplete_line(input: &str) -> Option<Incomplete> {
+    let syntax = analyze_line(input);
+    if let Some(pos) = syntax.open_quote {
+        return input[pos..].chars().next().map(Incomplete::UnclosedQuote);
+    }
+    if open_statement(input) {
+        return Some(Incomplete::UnclosedStatement);
+    }
+    syntax
+        .dangling_operator(input)
+        .map(|span| Incomplete::DanglingOperator(span.op))
+}
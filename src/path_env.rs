@@ -0,0 +1,260 @@
+//! # Path Env Module
+//!
+//! Builtin `path`: edita o `$PATH` sem montar a string à mão.
+//!
+//! - `path` - uma entrada por linha, marcando as que não existem e as repetidas
+//! - `path add [-p] dir...` - acrescenta no fim (ou no começo, com `-p`); uma
+//!   entrada que já está lá não é repetida (`-p` só a move para a frente)
+//! - `path rm dir...` - remove todas as ocorrências
+//! - `path dedupe` - remove as repetidas, mantendo a primeira
+//!
+//! Com `--save`, a mudança também vai para o `~/.cliosrc` (as linhas `path`
+//! antigas do mesmo diretório são substituídas), e vale nas próximas sessões.
+
+use crate::quote::join_words;
+use crate::registry::usage;
+use crate::t;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Mudança pedida ao `path`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathAction {
+    List,
+    Add { dirs: Vec<String>, prepend: bool },
+    Remove { dirs: Vec<String> },
+    Dedupe,
+}
+
+/// Interpreta os argumentos; devolve a ação e se deve salvar (`--save`).
+pub fn parse_path_args(args: &[String]) -> Result<(PathAction, bool), String> {
+    let mut save = false;
+    let mut prepend = false;
+    let mut words = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--save" | "-s" => save = true,
+            "--prepend" | "-p" => prepend = true,
+            flag if flag.starts_with('-') => return Err(t!("args.unknown_option", flag)),
+            _ => words.push(arg.clone()),
+        }
+    }
+    let (action, dirs) = match words.split_first() {
+        None => return Ok((PathAction::List, false)),
+        Some((action, dirs)) => (action.as_str(), dirs.to_vec()),
+    };
+    let action = match action {
+        "list" | "ls" if dirs.is_empty() => PathAction::List,
+        "add" if !dirs.is_empty() => PathAction::Add { dirs, prepend },
+        "rm" | "remove" if !dirs.is_empty() => PathAction::Remove { dirs },
+        "dedupe" if dirs.is_empty() => PathAction::Dedupe,
+        "add" | "rm" | "remove" => return Err(t!("path.directory_required", action)),
+        _ => return Err(t!("path.invalid_action", action)),
+    };
+    if prepend && !matches!(action, PathAction::Add { .. }) {
+        return Err(t!("path.prepend_add_only").to_string());
+    }
+    Ok((action, save))
+}
+
+// -----------------------------------------------------------------------------
+// ENTRIES
+// -----------------------------------------------------------------------------
+
+/// Forma usada para comparar entradas: `/usr/bin/` e `/usr/bin` são a mesma.
+fn normalize(entry: &str) -> &str {
+    match entry.trim_end_matches('/') {
+        "" if entry.starts_with('/') => "/",
+        trimmed => trimmed,
+    }
+}
+
+/// Entradas do `PATH` (sem as vazias).
+pub fn split_path(path: &str) -> Vec<String> {
+    path.split(':').filter(|e| !e.is_empty()).map(String::from).collect()
+}
+
+/// Acrescenta `dirs` (no começo, com `prepend`). Entradas existentes não se
+/// repetem; com `prepend` elas vão para a frente.
+pub fn add_entries(entries: &[String], dirs: &[String], prepend: bool) -> Vec<String> {
+    let mut result: Vec<String> = entries.to_vec();
+    if prepend {
+        result.retain(|e| !dirs.iter().any(|d| normalize(d) == normalize(e)));
+        let mut front: Vec<String> = Vec::new();
+        for dir in dirs {
+            if !front.iter().any(|f| normalize(f) == normalize(dir)) {
+                front.push(dir.clone());
+            }
+        }
+        front.extend(result);
+        return front;
+    }
+    for dir in dirs {
+        if !result.iter().any(|e| normalize(e) == normalize(dir)) {
+            result.push(dir.clone());
+        }
+    }
+    result
+}
+
+/// Remove todas as ocorrências de `dirs`.
+pub fn remove_entries(entries: &[String], dirs: &[String]) -> Vec<String> {
+    entries
+        .iter()
+        .filter(|e| !dirs.iter().any(|d| normalize(d) == normalize(e)))
+        .cloned()
+        .collect()
+}
+
+/// Mantém só a primeira ocorrência de cada entrada.
+pub fn dedupe_entries(entries: &[String]) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    for entry in entries {
+        if !result.iter().any(|e| normalize(e) == normalize(entry)) {
+            result.push(entry.clone());
+        }
+    }
+    result
+}
+
+/// Diretório relativo vira absoluto (o `PATH` não deve depender do cwd).
+fn absolute(dir: &str) -> String {
+    let path = Path::new(dir);
+    if path.is_absolute() {
+        return dir.to_string();
+    }
+    env::current_dir()
+        .map(|cwd| cwd.join(path).to_string_lossy().into_owned())
+        .unwrap_or_else(|_| dir.to_string())
+}
+
+// -----------------------------------------------------------------------------
+// RC FILE
+// -----------------------------------------------------------------------------
+
+/// Arquivo onde o `--save` grava (`~/.cliosrc`).
+pub fn rc_path() -> PathBuf {
+    env::var("HOME")
+        .map(|home| Path::new(&home).join(".cliosrc"))
+        .unwrap_or_else(|_| PathBuf::from(".cliosrc"))
+}
+
+/// Linha do rc com `path add`/`path rm` sem os diretórios de `dirs`; `None`
+/// quando não sobra nenhum. Outras linhas ficam como estão.
+fn strip_dirs(line: &str, dirs: &[String]) -> Option<String> {
+    let Some(tokens) = shlex::split(line.trim()) else {
+        return Some(line.to_string());
+    };
+    if tokens.first().map(|s| s.as_str()) != Some("path")
+        || !matches!(tokens.get(1).map(|s| s.as_str()), Some("add" | "rm" | "remove"))
+    {
+        return Some(line.to_string());
+    }
+    let kept: Vec<&String> = tokens
+        .iter()
+        .filter(|t| t.starts_with('-') || !dirs.iter().any(|d| normalize(d) == normalize(t)))
+        .collect();
+    if kept.len() == tokens.len() {
+        return Some(line.to_string());
+    }
+    kept.iter().skip(2).any(|t| !t.starts_with('-')).then(|| join_line(kept.iter().map(|s| s.as_str())))
+}
+
+fn join_line<'a>(words: impl IntoIterator<Item = &'a str>) -> String {
+    join_words(&words.into_iter().collect::<Vec<_>>())
+}
+
+/// Conteúdo do rc depois de salvar `action`: as linhas `path` antigas dos
+/// mesmos diretórios saem e a nova linha vai para o fim.
+pub fn update_rc(content: &str, action: &PathAction) -> String {
+    let (dirs, line): (&[String], String) = match action {
+        PathAction::List => return content.to_string(),
+        PathAction::Dedupe => {
+            if content.lines().any(|l| l.trim() == "path dedupe") {
+                return content.to_string();
+            }
+            (&[], "path dedupe".to_string())
+        }
+        PathAction::Add { dirs, prepend } => {
+            let mut words = vec!["path", "add"];
+            if *prepend {
+                words.push("--prepend");
+            }
+            words.extend(dirs.iter().map(|d| d.as_str()));
+            (dirs, join_line(words))
+        }
+        PathAction::Remove { dirs } => {
+            let words = ["path", "rm"].into_iter().chain(dirs.iter().map(|d| d.as_str()));
+            (dirs, join_line(words))
+        }
+    };
+    let mut lines: Vec<String> = content.lines().filter_map(|l| strip_dirs(l, dirs)).collect();
+    lines.push(line);
+    let mut result = lines.join("\n");
+    result.push('\n');
+    result
+}
+
+// -----------------------------------------------------------------------------
+// BUILTIN
+// -----------------------------------------------------------------------------
+
+fn print_entries(entries: &[String]) {
+    for (i, entry) in entries.iter().enumerate() {
+        let repeated = entries[..i].iter().any(|e| normalize(e) == normalize(entry));
+        let note = if repeated {
+            format!("  \x1b[33m({})\x1b[0m", t!("path.duplicate"))
+        } else if !Path::new(entry).is_dir() {
+            format!("  \x1b[31m({})\x1b[0m", t!("path.missing"))
+        } else {
+            String::new()
+        };
+        println!("{:>3}  {}{}", i + 1, entry, note);
+    }
+}
+
+/// Builtin `path [list | add [-p] dir... | rm dir... | dedupe] [--save]`.
+pub fn handle_path(tokens: &[String]) -> i32 {
+    let (action, save) = match parse_path_args(&tokens[1..]) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("path: {}", e);
+            eprintln!("{}", usage("path"));
+            return 2;
+        }
+    };
+    let entries = split_path(&env::var("PATH").unwrap_or_default());
+    let action = match action {
+        PathAction::List => {
+            print_entries(&entries);
+            return 0;
+        }
+        PathAction::Add { dirs, prepend } => PathAction::Add { dirs: dirs.iter().map(|d| absolute(d)).collect(), prepend },
+        PathAction::Remove { dirs } => PathAction::Remove { dirs: dirs.iter().map(|d| absolute(d)).collect() },
+        PathAction::Dedupe => PathAction::Dedupe,
+    };
+
+    let updated = match &action {
+        PathAction::Add { dirs, prepend } => add_entries(&entries, dirs, *prepend),
+        PathAction::Remove { dirs } => remove_entries(&entries, dirs),
+        _ => dedupe_entries(&entries),
+    };
+    if updated != entries {
+        // A tabela de comandos percebe a mudança e se esvazia sozinha
+        unsafe {
+            env::set_var("PATH", updated.join(":"));
+        }
+    }
+
+    if save {
+        let rc = rc_path();
+        let content = fs::read_to_string(&rc).unwrap_or_default();
+        if let Err(e) = fs::write(&rc, update_rc(&content, &action)) {
+            eprintln!("{}", t!("path.save_failed", e));
+            return 1;
+        }
+        println!("{}", t!("path.saved", rc.display()));
+    }
+    0
+}
@@ -6,19 +6,25 @@
 //! - Execução de pipelines de comandos (`cmd1 | cmd2 | cmd3`)
 //! - Redirecionamento de I/O (`>`, `>>`, `2>`, `2>>`)
 //! - Gerenciamento de processos filhos
+//! - O código de cada comando da pipeline em `$PIPESTATUS` (`${PIPESTATUS[@]}`)
+//!   e, com `set -o pipefail`, o do primeiro que falhou como código da pipeline
 
+use crate::executor::{self, ProcessGroup};
+use crate::jobs::{job_control_enabled, wait_foreground_statuses, BackgroundJob, JobStatus};
+use crate::last_output;
+use crate::options::pipefail;
+use crate::quote::join_words;
+use crate::range::range_input;
+use crate::t;
+use crate::terminal::restore_terminal;
+use nix::sys::signal::{self, Signal};
+use nix::sys::wait;
+use nix::unistd;
 use std::fs::{File, OpenOptions};
-use std::process::{Child, Command, Stdio};
-
-// -----------------------------------------------------------------------------
-// CONSTANTES
-// -----------------------------------------------------------------------------
-
-/// Código de saída padrão POSIX para "comando não encontrado"
-const EXIT_COMMAND_NOT_FOUND: i32 = 127;
-
-/// Código de saída para erros genéricos
-const EXIT_ERROR: i32 = 1;
+use std::io::{self, Write};
+use std::process::{Child, Stdio};
+use std::sync::Mutex;
+use std::time::Instant;
 
 // -----------------------------------------------------------------------------
 // I/O REDIRECTION PARSING
@@ -59,11 +65,11 @@ pub fn parse_redirection(tokens: &[String]) -> (Vec<String>, Option<File>, Optio
                     match File::open(f) {
                         Ok(o) => stdin_file = Some(o),
                         Err(e) => {
-                            eprintln!("\x1b[1;31m[ERRO REDIRECIONAMENTO]\x1b[0m Falha ao abrir '{}': {}", f, e);
+                            eprintln!("{}", t!("pipe.redirect_open", f, e));
                         }
                     }
                 } else {
-                    eprintln!("\x1b[1;31m[ERRO SINTAXE]\x1b[0m Operador '<' requer um arquivo");
+                    eprintln!("{}", t!("tag.syntax", t!("pipe.redirect_missing", "<")));
                 }
             }
             // Saída Padrão (Overwrite)
@@ -77,11 +83,11 @@ pub fn parse_redirection(tokens: &[String]) -> (Vec<String>, Option<File>, Optio
                     {
                         Ok(o) => stdout_file = Some(o),
                         Err(e) => {
-                            eprintln!("\x1b[1;31m[ERRO REDIRECIONAMENTO]\x1b[0m Falha ao abrir '{}': {}", f, e);
+                            eprintln!("{}", t!("pipe.redirect_open", f, e));
                         }
                     }
                 } else {
-                    eprintln!("\x1b[1;31m[ERRO SINTAXE]\x1b[0m Operador '>' requer um arquivo");
+                    eprintln!("{}", t!("tag.syntax", t!("pipe.redirect_missing", ">")));
                 }
             }
             // Saída Padrão (Append)
@@ -95,11 +101,11 @@ pub fn parse_redirection(tokens: &[String]) -> (Vec<String>, Option<File>, Optio
                     {
                         Ok(o) => stdout_file = Some(o),
                         Err(e) => {
-                            eprintln!("\x1b[1;31m[ERRO REDIRECIONAMENTO]\x1b[0m Falha ao abrir '{}': {}", f, e);
+                            eprintln!("{}", t!("pipe.redirect_open", f, e));
                         }
                     }
                 } else {
-                    eprintln!("\x1b[1;31m[ERRO SINTAXE]\x1b[0m Operador '>>' requer um arquivo");
+                    eprintln!("{}", t!("tag.syntax", t!("pipe.redirect_missing", ">>")));
                 }
             }
             // Saída de Erro (Overwrite)
@@ -113,11 +119,11 @@ pub fn parse_redirection(tokens: &[String]) -> (Vec<String>, Option<File>, Optio
                     {
                         Ok(o) => stderr_file = Some(o),
                         Err(e) => {
-                            eprintln!("\x1b[1;31m[ERRO REDIRECIONAMENTO]\x1b[0m Falha ao abrir '{}': {}", f, e);
+                            eprintln!("{}", t!("pipe.redirect_open", f, e));
                         }
                     }
                 } else {
-                    eprintln!("\x1b[1;31m[ERRO SINTAXE]\x1b[0m Operador '2>' requer um arquivo");
+                    eprintln!("{}", t!("tag.syntax", t!("pipe.redirect_missing", "2>")));
                 }
             }
             // Saída de Erro (Append)
@@ -131,11 +137,11 @@ pub fn parse_redirection(tokens: &[String]) -> (Vec<String>, Option<File>, Optio
                     {
                         Ok(o) => stderr_file = Some(o),
                         Err(e) => {
-                            eprintln!("\x1b[1;31m[ERRO REDIRECIONAMENTO]\x1b[0m Falha ao abrir '{}': {}", f, e);
+                            eprintln!("{}", t!("pipe.redirect_open", f, e));
                         }
                     }
                 } else {
-                    eprintln!("\x1b[1;31m[ERRO SINTAXE]\x1b[0m Operador '2>>' requer um arquivo");
+                    eprintln!("{}", t!("tag.syntax", t!("pipe.redirect_missing", "2>>")));
                 }
             }
             // Token normal
@@ -145,6 +151,65 @@ pub fn parse_redirection(tokens: &[String]) -> (Vec<String>, Option<File>, Optio
     (clean, stdin_file, stdout_file, stderr_file)
 }
 
+/// Se os tokens redirecionam STDOUT ou STDERR para um arquivo.
+pub fn has_output_redirection(tokens: &[String]) -> bool {
+    tokens.iter().any(|t| matches!(t.as_str(), ">" | ">>" | "2>" | "2>>"))
+}
+
+/// Roda `run` na própria shell com STDOUT/STDERR apontando para os arquivos
+/// e devolve os descritores originais no fim. É o redirecionamento dos
+/// builtins, que não têm um processo filho onde ligar o arquivo.
+pub fn with_redirected_output(stdout_file: Option<File>, stderr_file: Option<File>, run: impl FnOnce() -> i32) -> i32 {
+    let _ = io::stdout().flush();
+    let _ = io::stderr().flush();
+    let saved_stdout = stdout_file.as_ref().and_then(|_| unistd::dup(io::stdout()).ok());
+    let saved_stderr = stderr_file.as_ref().and_then(|_| unistd::dup(io::stderr()).ok());
+    if let Some(file) = &stdout_file {
+        let _ = unistd::dup2_stdout(file);
+    }
+    if let Some(file) = &stderr_file {
+        let _ = unistd::dup2_stderr(file);
+    }
+
+    let code = run();
+
+    let _ = io::stdout().flush();
+    let _ = io::stderr().flush();
+    if let Some(fd) = saved_stdout {
+        let _ = unistd::dup2_stdout(fd);
+    }
+    if let Some(fd) = saved_stderr {
+        let _ = unistd::dup2_stderr(fd);
+    }
+    code
+}
+
+// -----------------------------------------------------------------------------
+// PIPESTATUS
+// -----------------------------------------------------------------------------
+
+/// O código de cada comando da última pipeline, até a shell o guardar em
+/// `$PIPESTATUS`.
+static PIPE_STATUS: Mutex<Option<Vec<i32>>> = Mutex::new(None);
+
+/// Os códigos da última pipeline desde a chamada anterior (esvazia).
+pub fn take_pipe_status() -> Option<Vec<i32>> {
+    PIPE_STATUS.lock().ok().and_then(|mut status| status.take())
+}
+
+fn set_pipe_status(statuses: Vec<i32>) {
+    if let Ok(mut status) = PIPE_STATUS.lock() {
+        *status = Some(statuses);
+    }
+}
+
+/// O código de uma pipeline: o do último comando ou, com `pipefail`, o do
+/// primeiro que falhou (0 se todos deram certo).
+pub fn pipeline_code(statuses: &[i32], pipefail: bool) -> i32 {
+    let code = if pipefail { statuses.iter().find(|&&code| code != 0) } else { statuses.last() };
+    code.copied().unwrap_or(0)
+}
+
 // -----------------------------------------------------------------------------
 // PIPELINE EXECUTION
 // -----------------------------------------------------------------------------
@@ -161,42 +226,142 @@ pub fn parse_redirection(tokens: &[String]) -> (Vec<String>, Option<File>, Optio
 /// * **A**: Stdin = Teclado, Stdout = Pipe(A->B)
 /// * **B**: Stdin = Pipe(A->B), Stdout = Pipe(B->C)
 /// * **C**: Stdin = Pipe(B->C), Stdout = Tela
+///
+/// A shell espera todos os comandos: o código de cada um vai para o
+/// `$PIPESTATUS` (ver [`take_pipe_status`]) e o da pipeline vem do
+/// [`pipeline_code`].
 pub fn execute_pipeline(commands: Vec<Vec<String>>) -> i32 {
+    // `capture_output`: a saída passa pela shell e fica guardada (`$__`, `out`)
+    let capture = last_output::should_capture(&commands);
+    // Job control: a pipeline ganha um process group e o terminal (Ctrl+Z).
+    // Com a saída capturada, a shell lê o pipe até o fim e não teria como parar
+    let job_control = !capture && job_control_enabled();
+    let line: Vec<String> = commands.iter().map(|tokens| join_words(tokens)).collect();
+
+    let mut stages = match spawn_pipeline(&commands, capture, job_control) {
+        Ok(stages) => stages,
+        Err(code) => {
+            restore_terminal();
+            set_pipe_status(vec![code]);
+            return code;
+        }
+    };
+    // Um pipe que ninguém lê (`a | b < arquivo`) prenderia o comando que escreve nele
+    if let Some((_, earlier)) = stages.split_last_mut() {
+        for child in earlier.iter_mut().flatten() {
+            drop(child.stdout.take());
+        }
+    }
+
+    // 6. Espera Final
+    let statuses: Vec<i32> = match stages.last_mut() {
+        Some(Some(final_child)) => {
+            if capture {
+                last_output::tee_child(final_child, line.join(" | "));
+            }
+            let last = unistd::Pid::from_raw(final_child.id() as i32);
+            if job_control && let Ok(pgid) = unistd::getpgid(Some(last)) {
+                let job = BackgroundJob {
+                    pid: pgid.as_raw(),
+                    last: last.as_raw(),
+                    command: line.join(" | "),
+                    started: Instant::now(),
+                    status: JobStatus::Running,
+                    output: None,
+                    suspended: None,
+                };
+                // Um comando que não terminou (Ctrl+Z) fica com o código do job
+                let (code, exits) = wait_foreground_statuses(job);
+                stages
+                    .iter()
+                    .map(|stage| stage.as_ref().map_or(0, |child| exits.get(&(child.id() as i32)).copied().unwrap_or(code)))
+                    .collect()
+            } else {
+                stages
+                    .iter_mut()
+                    .map(|stage| stage.as_mut().map_or(0, |child| child.wait().map_or(1, executor::exit_code)))
+                    .collect()
+            }
+        }
+        _ => Vec::new(),
+    };
+
+    // Um filho que morreu em modo raw não pode deixar o terminal quebrado
+    restore_terminal();
+
+    let code = pipeline_code(&statuses, pipefail());
+    if !statuses.is_empty() {
+        set_pipe_status(statuses);
+    }
+    code
+}
+
+/// Inicia a pipeline com o STDOUT do último comando ligado a um pipe para a shell.
+///
+/// Usado por builtins que consomem a saída de uma pipeline (ex: `find . | each ...`).
+/// Quem chama é responsável por ler o STDOUT e esperar o processo.
+pub fn spawn_pipeline_capture(commands: &[Vec<String>]) -> Result<Option<Child>, i32> {
+    spawn_pipeline(commands, true, false).map(|mut stages| stages.pop().flatten())
+}
+
+/// Mata e recolhe o process group de uma pipeline que não chegou a rodar
+/// inteira.
+fn abort_group(pgid: i32) {
+    let _ = signal::killpg(unistd::Pid::from_raw(pgid), Signal::SIGKILL);
+    while wait::waitpid(unistd::Pid::from_raw(-pgid), None).is_ok() {}
+}
+
+/// Cria os processos da pipeline e devolve todos, em ordem (sem esperar).
+/// Um comando que roda dentro da shell (o `range` no começo) é `None`.
+///
+/// Com `job_control`, todos entram no process group do primeiro, que fica
+/// com o terminal.
+fn spawn_pipeline(commands: &[Vec<String>], capture_last: bool, job_control: bool) -> Result<Vec<Option<Child>>, i32> {
     // Validação: pipeline vazio
     if commands.is_empty() {
-        return 0;
+        return Ok(Vec::new());
     }
 
     // Validação: todos os comandos estão vazios
     if commands.iter().all(|cmd| cmd.is_empty()) {
-        eprintln!("\x1b[1;33m[AVISO]\x1b[0m Pipeline vazio detectado");
-        return 0;
+        eprintln!("{}", t!("tag.warning", t!("pipe.empty")));
+        return Ok(Vec::new());
     }
 
-    let mut prev_cmd: Option<Child> = None;
-    let mut final_exit_code = 0;
+    let mut stages: Vec<Option<Child>> = Vec::new();
+
+    // `range ... | cmd`: os números vêm de uma thread da shell
+    let (commands, mut input) = match commands {
+        [first, rest @ ..] if !rest.is_empty() && first.first().is_some_and(|c| c == "range") => {
+            stages.push(None);
+            (rest, Some(range_input(first)?))
+        }
+        _ => (commands, None),
+    };
+
+    let mut pgid: Option<i32> = None;
 
     for (i, tokens) in commands.iter().enumerate() {
         if tokens.is_empty() {
-            eprintln!("\x1b[1;33m[AVISO]\x1b[0m Comando vazio no pipeline (posição {})", i + 1);
+            eprintln!("{}", t!("tag.warning", t!("pipe.empty_command", i + 1)));
             continue;
         }
 
         // 1. Separa o comando dos redirecionamentos de arquivo
-        let (mut args, infile, outfile, errfile) = parse_redirection(tokens);
+        let (args, infile, outfile, errfile) = parse_redirection(tokens);
 
         if args.is_empty() {
             continue;
         }
 
-        let cmd = args.remove(0);
-
         // 2. Configuração do STDIN
         let stdin = if let Some(f) = infile {
             // Redirecionamento de entrada tem prioridade
             Stdio::from(f)
-        } else if let Some(mut child) = prev_cmd {
-            Stdio::from(child.stdout.take().unwrap())
+        } else if let Some(stdout) = stages.last_mut().and_then(|stage| stage.as_mut()?.stdout.take()) {
+            Stdio::from(stdout)
+        } else if let Some(reader) = input.take() {
+            Stdio::from(reader)
         } else {
             Stdio::inherit()
         };
@@ -204,7 +369,7 @@ pub fn execute_pipeline(commands: Vec<Vec<String>>) -> i32 {
         // 3. Configuração do STDOUT
         let stdout = if let Some(f) = outfile {
             Stdio::from(f)
-        } else if i < commands.len() - 1 {
+        } else if i < commands.len() - 1 || capture_last {
             Stdio::piped()
         } else {
             Stdio::inherit()
@@ -218,34 +383,38 @@ pub fn execute_pipeline(commands: Vec<Vec<String>>) -> i32 {
         };
 
         // 5. Executa (Spawn)
-        match Command::new(&cmd)
-            .args(&args)
-            .stdin(stdin)
-            .stdout(stdout)
-            .stderr(stderr)
-            .spawn()
-        {
-            Ok(child) => prev_cmd = Some(child),
+        let group = if job_control { ProcessGroup::Foreground(pgid.unwrap_or(0)) } else { ProcessGroup::Shell };
+        let mut command = executor::command(&args, group);
+        command.stdin(stdin).stdout(stdout).stderr(stderr);
+        match executor::spawn(&mut command) {
+            Ok(child) => {
+                if job_control && pgid.is_none() {
+                    let leader = child.id() as i32;
+                    pgid = Some(leader);
+                    // O filho já tentou; com o STDIN redirecionado, só a shell consegue
+                    let _ = unistd::tcsetpgrp(io::stdin(), unistd::Pid::from_raw(leader));
+                }
+                stages.push(Some(child));
+            }
             Err(e) => {
-                // Mensagem de erro mais descritiva baseada no tipo de erro
-                let error_msg = if e.kind() == std::io::ErrorKind::NotFound {
-                    format!("comando não encontrado: '{}'", cmd)
-                } else if e.kind() == std::io::ErrorKind::PermissionDenied {
-                    format!("permissão negada: '{}'", cmd)
-                } else {
-                    format!("erro ao executar '{}': {}", cmd, e)
-                };
-                eprintln!("\x1b[1;31m[ERRO]\x1b[0m {}", error_msg);
-                return EXIT_COMMAND_NOT_FOUND;
+                eprintln!("{}", t!("tag.error", e));
+                // Os comandos que já rodam estão com o terminal e não têm para
+                // onde escrever: um `cat | inexistente` prenderia a shell
+                // Sem job control não há grupo: cada um é morto e recolhido,
+                // para não ficar zumbi
+                match pgid {
+                    Some(leader) => abort_group(leader),
+                    None => {
+                        for child in stages.iter_mut().flatten() {
+                            let _ = child.kill();
+                            let _ = child.wait();
+                        }
+                    }
+                }
+                return Err(e.exit_code());
             }
         }
     }
 
-    // 6. Espera Final
-    if let Some(mut final_child) = prev_cmd
-        && let Ok(status) = final_child.wait() {
-            final_exit_code = status.code().unwrap_or(EXIT_ERROR);
-        }
-
-    final_exit_code
+    Ok(stages)
 }
@@ -0,0 +1,460 @@
+//! # Plugin Registry Module
+//!
+//! Comandos declarados por plugins Rhai com `register_command`:
+//!
+//! ```rhai
+//! register_command("weather", |args| {
+//!     print(http_get(`https://wttr.in/${args[0]}?format=3`));
+//! }, #{
+//!     description: "Previsão do tempo",
+//!     usage: "weather <cidade>",
+//!     completions: ["recife", "lisboa", "tokyo"],
+//! });
+//! ```
+//!
+//! Diferente das funções soltas de um plugin (que também viram comandos, mas
+//! sem nenhuma descrição), um comando registrado aparece no `help`, no `type`,
+//! no `plugins` e no autocomplete, que sugere as `completions` como argumentos.
+//!
+//! Os plugins de `~/.clios_plugins` são compilados só quando um dos comandos
+//! ou funções deles é usado pela primeira vez: na inicialização, cada arquivo
+//! é apenas lido e indexado ([`PluginManifest::scan`]), e os nomes ficam no
+//! registro como pendentes.
+//!
+//! Cada comando lembra o plugin que o registrou. Se dois plugins registram o
+//! mesmo nome, fica o de maior prioridade ([`PluginRegistry::rank`]) e a shell
+//! avisa do conflito.
+//!
+//! O registro guarda o callback (`FnPtr`), que não é `Send`; por isso ele é
+//! compartilhado por `Rc` entre a shell, o motor Rhai e o editor de linha, todos
+//! na thread principal.
+
+use crate::rhai_debug::format_trace;
+use crate::rhai_integration::create_rhai_engine;
+use crate::t;
+use rhai::{Array, Dynamic, Engine, FnPtr, Map, AST};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+/// Registro compartilhado entre a shell, o motor Rhai e o editor de linha.
+pub type SharedPluginRegistry = Rc<RefCell<PluginRegistry>>;
+
+/// Um comando registrado por plugin.
+#[derive(Debug, Clone)]
+pub struct PluginCommand {
+    pub name: String,
+    pub description: String,
+    /// Forma de uso (`weather <cidade>`); o nome quando o plugin não informa
+    pub usage: String,
+    /// Sugestões de argumento para o autocomplete
+    pub completions: Vec<String>,
+    pub callback: FnPtr,
+    /// Arquivo do plugin que registrou o comando (`None` pelo `rhai`)
+    pub plugin: Option<String>,
+}
+
+impl PluginCommand {
+    /// A descrição, ou um texto genérico quando o plugin não deu nenhuma.
+    pub fn summary(&self) -> &str {
+        if self.description.is_empty() { t!("plugin.no_description") } else { &self.description }
+    }
+}
+
+/// Um provedor de autocomplete (`register_completion`) para os argumentos de
+/// um comando qualquer, do plugin ou não (`kubectl`, `make`).
+#[derive(Debug, Clone)]
+pub struct PluginCompletion {
+    pub command: String,
+    /// Recebe as palavras até o cursor (a última é a sendo digitada) e devolve
+    /// os candidatos
+    pub callback: FnPtr,
+    /// Arquivo do plugin que registrou o provedor (`None` pelo `rhai`)
+    pub plugin: Option<String>,
+}
+
+/// Comandos registrados, em ordem alfabética.
+#[derive(Debug, Clone, Default)]
+pub struct PluginRegistry {
+    commands: BTreeMap<String, PluginCommand>,
+    completions: BTreeMap<String, PluginCompletion>,
+    /// As funções de cada plugin, que os provedores de autocomplete podem chamar
+    functions: BTreeMap<String, AST>,
+    /// Plugins ainda não compilados, pelo nome de cada comando ou função
+    /// que eles fornecem (com `true` para os comandos registrados)
+    pending: BTreeMap<String, (String, bool)>,
+    /// Plugins de `~/.clios_plugins`, em ordem de prioridade
+    order: Vec<String>,
+    /// O plugin cujo código de topo está rodando agora
+    loading: Option<String>,
+}
+
+impl PluginRegistry {
+    /// Registra (ou substitui, ao recarregar o plugin) um comando, em nome do
+    /// plugin sendo carregado. Se outro plugin já registrou o nome, fica o de
+    /// maior prioridade ([`Self::rank`]) e o aviso do conflito volta como erro.
+    pub fn register(&mut self, mut command: PluginCommand) -> Result<(), String> {
+        command.plugin = self.loading.clone();
+        let existing = self.commands.get(&command.name).and_then(|c| c.plugin.as_deref());
+        let conflict = self.conflict(&command.name, existing, command.plugin.as_deref());
+        if conflict.as_ref().is_none_or(|(new_wins, _)| *new_wins) {
+            self.commands.insert(command.name.clone(), command);
+        }
+        conflict.map_or(Ok(()), |(_, warning)| Err(warning))
+    }
+
+    /// Registra o provedor de autocomplete de um comando, com as mesmas regras
+    /// de conflito de [`Self::register`].
+    pub fn register_completion(&mut self, mut completion: PluginCompletion) -> Result<(), String> {
+        completion.plugin = self.loading.clone();
+        let existing = self.completions.get(&completion.command).and_then(|c| c.plugin.as_deref());
+        let conflict = self.conflict(&completion.command, existing, completion.plugin.as_deref());
+        if conflict.as_ref().is_none_or(|(new_wins, _)| *new_wins) {
+            self.completions.insert(completion.command.clone(), completion);
+        }
+        conflict.map_or(Ok(()), |(_, warning)| Err(warning))
+    }
+
+    /// Dois plugins diferentes com o mesmo nome: se o novo vence e o aviso.
+    fn conflict(&self, name: &str, old: Option<&str>, new: Option<&str>) -> Option<(bool, String)> {
+        let (old, new) = (old?, new?);
+        if old == new {
+            return None;
+        }
+        let new_wins = self.rank(new) < self.rank(old);
+        let (winner, loser) = if new_wins { (new, old) } else { (old, new) };
+        Some((new_wins, conflict_warning(name, winner, loser)))
+    }
+
+    /// O provedor de autocomplete de `command`.
+    pub fn completion(&self, command: &str) -> Option<&PluginCompletion> {
+        self.completions.get(command)
+    }
+
+    /// Guarda as funções do plugin `path` (ao carregar ou recarregar).
+    pub fn set_functions(&mut self, path: &str, functions: AST) {
+        self.functions.insert(path.to_string(), functions);
+    }
+
+    /// As funções do plugin `path`.
+    pub fn functions(&self, path: &str) -> Option<&AST> {
+        self.functions.get(path)
+    }
+
+    /// Define a ordem de prioridade dos plugins da inicialização.
+    pub fn set_order(&mut self, paths: Vec<String>) {
+        self.order = paths;
+    }
+
+    /// Prioridade de um plugin (menor vence): os de `~/.clios_plugins` em
+    /// ordem alfabética, depois os carregados com `source`, na ordem de carga.
+    pub fn rank(&self, path: &str) -> usize {
+        self.order.iter().position(|p| p == path).unwrap_or(usize::MAX)
+    }
+
+    /// Marca (ou, com `None`, desmarca) o plugin cujo código de topo vai rodar.
+    pub fn set_loading(&mut self, path: Option<&str>) {
+        self.loading = path.map(String::from);
+    }
+
+    /// Procura um comando pelo nome.
+    pub fn get(&self, name: &str) -> Option<&PluginCommand> {
+        self.commands.get(name)
+    }
+
+    /// Todos os comandos, por nome.
+    pub fn commands(&self) -> impl Iterator<Item = &PluginCommand> {
+        self.commands.values()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Guarda o plugin em `path` para ser compilado quando um dos nomes do
+    /// `manifest` for usado. Um nome que outro plugin já fornece fica com
+    /// o primeiro, que tem prioridade.
+    pub fn defer(&mut self, path: &str, manifest: &PluginManifest) {
+        for name in &manifest.functions {
+            self.pending.entry(name.clone()).or_insert((path.to_string(), false));
+        }
+        for name in &manifest.commands {
+            self.pending.entry(name.clone()).or_insert((path.to_string(), true));
+        }
+    }
+
+    /// Algum plugin ainda não compilado registra o comando `name`?
+    pub fn is_pending_command(&self, name: &str) -> bool {
+        self.pending.get(name).is_some_and(|(_, command)| *command)
+    }
+
+    /// Os comandos registrados pelos plugins ainda não compilados.
+    pub fn pending_commands(&self) -> impl Iterator<Item = &str> {
+        self.pending.iter().filter(|(_, (_, command))| *command).map(|(name, _)| name.as_str())
+    }
+
+    /// O plugin que fornece `name`, se ainda não foi compilado; todos os
+    /// nomes dele deixam de ser pendentes.
+    pub fn take_pending(&mut self, name: &str) -> Option<String> {
+        let (path, _) = self.pending.get(name)?.clone();
+        self.pending.retain(|_, (p, _)| *p != path);
+        Some(path)
+    }
+
+    /// Todos os plugins ainda não compilados (para quem precisa de todas as
+    /// funções, como o `rhai` e o `help`).
+    pub fn take_all_pending(&mut self) -> Vec<String> {
+        let mut paths: Vec<String> = std::mem::take(&mut self.pending).into_values().map(|(path, _)| path).collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+}
+
+// -----------------------------------------------------------------------------
+// LAZY LOADING
+// -----------------------------------------------------------------------------
+
+/// Funções que a shell chama sozinha (ao mudar de diretório, ao sair): um
+/// plugin que define alguma é compilado já na inicialização.
+pub const PLUGIN_HOOKS: [&str; 2] = ["on_cd", "on_exit"];
+
+/// Os plugins de `~/.clios_plugins`, em ordem alfabética (que é a ordem de
+/// prioridade quando dois usam o mesmo nome).
+pub fn plugin_dir_files() -> Vec<String> {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    let Ok(entries) = std::fs::read_dir(std::path::Path::new(&home).join(".clios_plugins")) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("rhai"))
+        .filter_map(|path| path.to_str().map(String::from))
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Os nomes que um plugin fornece, lidos do código sem compilar.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PluginManifest {
+    /// `fn nome(...)` no começo de uma linha (as `private fn` ficam de fora)
+    pub functions: Vec<String>,
+    /// `register_command("nome", ...)`
+    pub commands: Vec<String>,
+    /// `register_completion("nome", ...)`
+    pub completions: Vec<String>,
+}
+
+impl PluginManifest {
+    /// Indexa o código de um plugin linha a linha. Um nome a mais (dentro de
+    /// um comentário, por exemplo) só faz o plugin ser compilado mais cedo.
+    pub fn scan(source: &str) -> Self {
+        let identifier = |text: &str| -> String {
+            text.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect()
+        };
+        let mut manifest = PluginManifest::default();
+        for line in source.lines() {
+            if let Some(rest) = line.trim_start().strip_prefix("fn ") {
+                let name = identifier(rest.trim_start());
+                if !name.is_empty() && !manifest.functions.contains(&name) {
+                    manifest.functions.push(name);
+                }
+            }
+            for (call, names) in [("register_command(", &mut manifest.commands), ("register_completion(", &mut manifest.completions)] {
+                for (pos, _) in line.match_indices(call) {
+                    let rest = line[pos + call.len()..].trim_start();
+                    if let Some((name, _)) = rest.strip_prefix('"').and_then(|r| r.split_once('"'))
+                        && is_valid_command_name(name)
+                        && !names.iter().any(|c| c == name)
+                    {
+                        names.push(name.to_string());
+                    }
+                }
+            }
+        }
+        manifest
+    }
+
+    /// Funções e comandos, na ordem em que aparecem.
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        self.functions.iter().chain(&self.commands)
+    }
+
+    /// O plugin precisa ser compilado já: tem ganchos da shell, provedores de
+    /// autocomplete (o TAB não carrega plugins) ou nenhum nome que o
+    /// carregaria depois (só código de topo).
+    pub fn needs_eager_load(&self) -> bool {
+        self.names().next().is_none()
+            || !self.completions.is_empty()
+            || self.functions.iter().any(|f| PLUGIN_HOOKS.contains(&f.as_str()))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// RHAI API
+// -----------------------------------------------------------------------------
+
+/// Aviso de um nome fornecido por dois plugins, dos quais vale `winner`.
+pub fn conflict_warning(name: &str, winner: &str, loser: &str) -> String {
+    let file = |path: &str| {
+        std::path::Path::new(path).file_name().map_or(path.to_string(), |f| f.to_string_lossy().into_owned())
+    };
+    t!("tag.warning", t!("plugin.conflict", name, file(winner), file(loser), file(winner)))
+}
+
+/// Nome aceito para um comando: não vazio, sem espaços nem operadores da shell.
+pub fn is_valid_command_name(name: &str) -> bool {
+    !name.is_empty() && !name.starts_with('-') && !name.chars().any(|c| c.is_whitespace() || "|&;<>()$`'\"\\".contains(c))
+}
+
+/// Monta o comando a partir das opções do terceiro argumento: uma string é a
+/// descrição; um mapa aceita `description`, `usage` e `completions`.
+pub fn build_command(name: &str, callback: FnPtr, options: &Dynamic) -> Result<PluginCommand, String> {
+    if !is_valid_command_name(name) {
+        return Err(t!("plugin.invalid_command_name", name));
+    }
+    let mut command = PluginCommand {
+        name: name.to_string(),
+        description: String::new(),
+        usage: name.to_string(),
+        completions: Vec::new(),
+        callback,
+        plugin: None,
+    };
+    if options.is_unit() {
+        return Ok(command);
+    }
+    if let Ok(description) = options.clone().into_string() {
+        command.description = description;
+        return Ok(command);
+    }
+    let Some(map) = options.clone().try_cast::<Map>() else {
+        return Err(t!("plugin.invalid_command_options", name, options.type_name()));
+    };
+    for (key, value) in map {
+        match key.as_str() {
+            "description" => command.description = value.to_string(),
+            "usage" => command.usage = value.to_string(),
+            "completions" => match value.try_cast::<Array>() {
+                Some(items) => command.completions = items.iter().map(|c| c.to_string()).collect(),
+                None => return Err(t!("plugin.invalid_command_options", name, "completions")),
+            },
+            other => return Err(t!("plugin.invalid_command_options", name, other)),
+        }
+    }
+    Ok(command)
+}
+
+/// Registra `register_command` e `register_completion` no motor, gravando em
+/// `registry`.
+pub fn register_plugin_api(engine: &mut Engine, registry: &SharedPluginRegistry) {
+    let plugins = registry.clone();
+    engine.register_fn("register_command", move |name: &str, callback: FnPtr| -> Result<(), Box<rhai::EvalAltResult>> {
+        let command = build_command(name, callback, &Dynamic::UNIT)?;
+        if let Err(warning) = plugins.borrow_mut().register(command) {
+            eprintln!("{}", warning);
+        }
+        Ok(())
+    });
+    let plugins = registry.clone();
+    engine.register_fn(
+        "register_command",
+        move |name: &str, callback: FnPtr, options: Dynamic| -> Result<(), Box<rhai::EvalAltResult>> {
+            let command = build_command(name, callback, &options)?;
+            if let Err(warning) = plugins.borrow_mut().register(command) {
+                eprintln!("{}", warning);
+            }
+            Ok(())
+        },
+    );
+    let plugins = registry.clone();
+    engine.register_fn("register_completion", move |name: &str, callback: FnPtr| -> Result<(), Box<rhai::EvalAltResult>> {
+        if !is_valid_command_name(name) {
+            return Err(t!("plugin.invalid_completion_name", name).into());
+        }
+        let completion = PluginCompletion { command: name.to_string(), callback, plugin: None };
+        if let Err(warning) = plugins.borrow_mut().register_completion(completion) {
+            eprintln!("{}", warning);
+        }
+        Ok(())
+    });
+}
+
+// -----------------------------------------------------------------------------
+// EXECUTION
+// -----------------------------------------------------------------------------
+
+/// Executa o callback com os argumentos como um array de strings. `ast` são
+/// as funções que o callback pode chamar: as do plugin que o registrou e as
+/// exportadas pelos outros ([`crate::rhai_integration::PluginSet::context`]).
+///
+/// O retorno vira o código de saída: um inteiro é usado como está, `false`
+/// vale 1 e qualquer outro valor, 0. Erros do script são impressos e valem 1.
+pub fn run_plugin_command(engine: &Engine, ast: Option<&AST>, command: &PluginCommand, args: &[String]) -> i32 {
+    let args: Array = args.iter().cloned().map(Dynamic::from).collect();
+    let empty = AST::empty();
+    match command.callback.call::<Dynamic>(engine, ast.unwrap_or(&empty), (args,)) {
+        Ok(value) => exit_code_of(&value),
+        Err(e) => {
+            let trace = format_trace(&e, None, ast.and_then(AST::source));
+            eprintln!("{}", t!("tag.plugin_error", t!("plugin.command_failed", command.name, trace)));
+            1
+        }
+    }
+}
+
+/// Os candidatos do provedor de autocomplete de `words[0]` para a última
+/// palavra de `words` (a que está sendo digitada), ou `None` sem provedor.
+///
+/// O provedor roda num motor à parte, com as funções do próprio plugin: o
+/// TAB acontece dentro do editor de linha, longe do motor da sessão. Um erro
+/// no provedor não aparece (atrapalharia a linha sendo editada) e vale como
+/// nenhum candidato.
+pub fn plugin_completions(registry: &SharedPluginRegistry, words: &[String]) -> Option<Vec<String>> {
+    let (callback, functions) = {
+        let plugins = registry.borrow();
+        let completion = plugins.completion(words.first()?)?;
+        let functions = completion.plugin.as_deref().and_then(|path| plugins.functions(path)).cloned();
+        (completion.callback.clone(), functions.unwrap_or_else(AST::empty))
+    };
+    let engine = create_rhai_engine(&SharedPluginRegistry::default());
+    let args: Array = words.iter().cloned().map(Dynamic::from).collect();
+    let word = words.last().map_or("", String::as_str);
+    let candidates = match callback.call::<Dynamic>(&engine, &functions, (args,)) {
+        Ok(value) => value.try_cast::<Array>().unwrap_or_default(),
+        Err(_) => Array::new(),
+    };
+    Some(candidates.iter().map(|c| c.to_string()).filter(|c| c.starts_with(word)).collect())
+}
+
+/// Código de saída do valor devolvido por um comando de plugin.
+pub fn exit_code_of(value: &Dynamic) -> i32 {
+    if let Ok(code) = value.as_int() {
+        code as i32
+    } else if let Ok(ok) = value.as_bool() {
+        if ok { 0 } else { 1 }
+    } else {
+        0
+    }
+}
+
+// -----------------------------------------------------------------------------
+// HELP
+// -----------------------------------------------------------------------------
+
+/// Página do `help <comando>` de um comando de plugin.
+pub fn format_plugin_help(command: &PluginCommand) -> String {
+    let mut page = format!(
+        "\x1b[1;33m{}\x1b[0m - {} \x1b[90m(plugin)\x1b[0m\n\n\x1b[1;32m{}\x1b[0m\n  {}\n",
+        command.name,
+        command.summary(),
+        t!("help.usage"),
+        command.usage
+    );
+    if !command.completions.is_empty() {
+        page.push_str(&format!("\n\x1b[1;32m{}\x1b[0m\n  {}\n", t!("help.arguments"), command.completions.join(", ")));
+    }
+    page
+}
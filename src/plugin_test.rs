@@ -0,0 +1,230 @@
+//! # Plugin Test Module
+//!
+//! Testes de plugins Rhai escritos no próprio plugin: toda função `test_*`
+//! sem parâmetros é um teste.
+//!
+//! ```rhai
+//! fn soma(a, b) { a + b }
+//!
+//! fn test_soma() {
+//!     assert_eq(soma(2, 3), 5);
+//!     assert(soma(-1, 1) == 0, "soma com negativo");
+//! }
+//! ```
+//!
+//! ```bash
+//! plugin test              # todos os plugins (~/.clios_plugins e os carregados)
+//! plugin test utils        # só utils.rhai (pelo nome, pelo módulo ou pelo caminho)
+//! ```
+//!
+//! Cada teste roda num motor à parte, com um registro de comandos próprio e
+//! o código de topo do plugin executado de novo num Scope vazio: um teste não
+//! vê o que outro deixou nem mexe na sessão. Além das funções da shell, o
+//! motor tem `assert`, `assert_eq` e `assert_ne`. O código de saída é 1 se
+//! algum teste falhou.
+
+use crate::plugin_registry::{plugin_dir_files, PluginRegistry};
+use crate::registry::usage;
+use crate::rhai_debug::format_trace;
+use crate::rhai_integration::create_rhai_engine;
+use crate::t;
+use rhai::{Dynamic, Engine, EvalAltResult, NativeCallContext, Scope, AST};
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+// -----------------------------------------------------------------------------
+// ASSERTIONS
+// -----------------------------------------------------------------------------
+
+/// Falha do teste com a mensagem, e a posição vem da chamada.
+fn failure(message: String) -> Result<(), Box<EvalAltResult>> {
+    Err(EvalAltResult::ErrorRuntime(message.into(), rhai::Position::NONE).into())
+}
+
+/// `a == b` pelas regras do Rhai (tipos diferentes são diferentes).
+fn equals(ctx: &NativeCallContext, a: &Dynamic, b: &Dynamic) -> Result<bool, Box<EvalAltResult>> {
+    if a.type_name() != b.type_name() {
+        return Ok(false);
+    }
+    ctx.call_native_fn("==", (a.clone(), b.clone()))
+}
+
+/// Registra `assert`, `assert_eq` e `assert_ne`, com e sem a mensagem.
+pub fn register_assertions(engine: &mut Engine) {
+    engine.register_fn("assert", |condition: bool| {
+        if condition { Ok(()) } else { failure(t!("plugin_test.assert").to_string()) }
+    });
+    engine.register_fn("assert", |condition: bool, message: &str| {
+        if condition { Ok(()) } else { failure(message.to_string()) }
+    });
+
+    let assert_eq = |ctx: NativeCallContext, a: Dynamic, b: Dynamic, message: &str| {
+        if equals(&ctx, &a, &b)? {
+            return Ok(());
+        }
+        let detail = t!("plugin_test.assert_eq", format!("{:?}", a), format!("{:?}", b));
+        failure(if message.is_empty() { detail } else { format!("{}\n    {}", message, detail) })
+    };
+    engine.register_fn("assert_eq", move |ctx: NativeCallContext, a: Dynamic, b: Dynamic| assert_eq(ctx, a, b, ""));
+    engine.register_fn("assert_eq", assert_eq);
+
+    let assert_ne = |ctx: NativeCallContext, a: Dynamic, b: Dynamic, message: &str| {
+        if !equals(&ctx, &a, &b)? {
+            return Ok(());
+        }
+        let detail = t!("plugin_test.assert_ne", format!("{:?}", a));
+        failure(if message.is_empty() { detail } else { format!("{}\n    {}", message, detail) })
+    };
+    engine.register_fn("assert_ne", move |ctx: NativeCallContext, a: Dynamic, b: Dynamic| assert_ne(ctx, a, b, ""));
+    engine.register_fn("assert_ne", assert_ne);
+}
+
+// -----------------------------------------------------------------------------
+// DISCOVERY
+// -----------------------------------------------------------------------------
+
+/// As funções `test_*` sem parâmetros de `ast`, na ordem do arquivo.
+pub fn test_functions(ast: &AST) -> Vec<String> {
+    let mut tests: Vec<_> = ast
+        .iter_fn_def()
+        .filter(|f| f.name.starts_with("test_") && f.params.is_empty())
+        .map(|f| (f.body.position(), f.name.to_string()))
+        .collect();
+    tests.sort_by_key(|(pos, _)| (pos.line(), pos.position()));
+    tests.into_iter().map(|(_, name)| name).collect()
+}
+
+/// O plugin `path` é o pedido em `name`? Vale o caminho, o nome do arquivo,
+/// com ou sem `.rhai`, e o nome do módulo (`git_helpers` para `git-helpers`).
+pub fn plugin_matches(path: &str, name: &str) -> bool {
+    let path = Path::new(path);
+    let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let module: String = stem.chars().map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' }).collect();
+    path == Path::new(name) || path.file_name().is_some_and(|f| f == name) || stem == name || module == name
+}
+
+/// Os plugins a testar: os de `~/.clios_plugins` e os `loaded` (sem repetir),
+/// filtrados por `name`. Um `name` que é um arquivo é testado mesmo sem estar
+/// carregado.
+pub fn select_plugins(name: Option<&str>, loaded: &[String]) -> Vec<String> {
+    if let Some(name) = name
+        && name.ends_with(".rhai")
+        && Path::new(name).is_file()
+    {
+        return vec![name.to_string()];
+    }
+    let mut paths = plugin_dir_files();
+    for path in loaded {
+        if !paths.contains(path) {
+            paths.push(path.clone());
+        }
+    }
+    paths.retain(|path| name.is_none_or(|name| plugin_matches(path, name)));
+    paths
+}
+
+// -----------------------------------------------------------------------------
+// EXECUTION
+// -----------------------------------------------------------------------------
+
+/// O resultado de um teste.
+#[derive(Debug, Clone)]
+pub struct TestOutcome {
+    pub name: String,
+    /// A falha, já com o rastro
+    pub result: Result<(), String>,
+    pub duration: Duration,
+}
+
+/// Motor dos testes: o da shell, com um registro de comandos descartável e
+/// as asserções.
+pub fn test_engine() -> Engine {
+    let mut engine = create_rhai_engine(&Rc::new(RefCell::new(PluginRegistry::default())));
+    register_assertions(&mut engine);
+    engine
+}
+
+/// Roda os testes do plugin `path`; um erro de compilação volta como `Err`.
+pub fn run_plugin_tests(path: &str) -> Result<Vec<TestOutcome>, String> {
+    let probe = test_engine();
+    let mut ast = probe.compile_file(path.into()).map_err(|e| t!("plugin.compile_failed", path, e))?;
+    ast.set_source(path);
+    let outcomes = test_functions(&ast)
+        .into_iter()
+        .map(|name| {
+            let engine = test_engine();
+            let started = Instant::now();
+            let result = engine.call_fn::<Dynamic>(&mut Scope::new(), &ast, &name, ()).map(|_| ()).map_err(|e| {
+                let trace = format_trace(&e, Some(&name), ast.source());
+                trace.strip_prefix("Runtime error: ").map(String::from).unwrap_or(trace)
+            });
+            TestOutcome { name, result, duration: started.elapsed() }
+        })
+        .collect();
+    Ok(outcomes)
+}
+
+/// Builtin `plugin test [nome]`; `loaded` são os plugins carregados na sessão.
+pub fn handle_plugin(tokens: &[String], loaded: &[String]) -> i32 {
+    if tokens.get(1).map(String::as_str) != Some("test") || tokens.len() > 3 {
+        eprintln!("{}", usage("plugin"));
+        return 2;
+    }
+    let name = tokens.get(2).map(String::as_str);
+    let plugins = select_plugins(name, loaded);
+    if plugins.is_empty() {
+        eprintln!("{}", t!("plugin_test.no_plugins", name.unwrap_or("~/.clios_plugins")));
+        return 1;
+    }
+
+    let (mut passed, mut failed) = (0, 0);
+    for path in &plugins {
+        let file = Path::new(path).file_name().map_or(path.into(), |f| f.to_string_lossy());
+        match run_plugin_tests(path) {
+            Ok(outcomes) if outcomes.is_empty() => {
+                if name.is_some() {
+                    println!("\x1b[1m{}\x1b[0m \x1b[90m{}\x1b[0m", file, t!("plugin_test.no_tests"));
+                }
+            }
+            Ok(outcomes) => {
+                println!("\x1b[1m{}\x1b[0m", file);
+                for outcome in outcomes {
+                    let ms = format!("{:.1}ms", outcome.duration.as_secs_f64() * 1000.0);
+                    match outcome.result {
+                        Ok(()) => {
+                            passed += 1;
+                            println!("  \x1b[32m{:<5}\x1b[0m {} \x1b[90m{}\x1b[0m", t!("plugin_test.ok"), outcome.name, ms);
+                        }
+                        Err(e) => {
+                            failed += 1;
+                            println!("  \x1b[31m{:<5}\x1b[0m {} \x1b[90m{}\x1b[0m", t!("plugin_test.fail"), outcome.name, ms);
+                            for line in e.lines() {
+                                println!("        {}", line);
+                            }
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                println!("\x1b[1m{}\x1b[0m", file);
+                println!("  \x1b[31m{:<5}\x1b[0m {}", t!("plugin_test.fail"), e);
+            }
+        }
+    }
+
+    if passed + failed == 0 {
+        println!("{}", t!("plugin_test.none_found"));
+        return 0;
+    }
+    let summary = t!("plugin_test.summary", passed + failed, passed, failed);
+    if failed > 0 {
+        println!("\n\x1b[31m{}\x1b[0m", summary);
+        1
+    } else {
+        println!("\n\x1b[32m{}\x1b[0m", summary);
+        0
+    }
+}
@@ -0,0 +1,274 @@
+//! # Procs Module
+//!
+//! Builtin `procs`: lista de processos lida pelo crate `sysinfo`, sem
+//! interpretar a saída do `ps` (que muda entre GNU, BSD e o do busybox).
+//!
+//! - `procs` / `procs node` - PID, usuário, CPU, memória e comando, filtrados
+//!   pelo nome ou pela linha de comando (um número também casa com o PID)
+//! - `procs -s mem -n 10` - ordena por `cpu` (padrão), `mem`, `pid` ou `name`
+//! - `procs --kill node` - escolhe na lista (espaço marca, Enter confirma)
+//!   quais processos recebem SIGTERM (`-9`: SIGKILL)
+
+use crate::archive::human_size;
+use crate::registry::usage;
+use crate::t;
+use crate::terminal::terminal_width;
+use inquire::MultiSelect;
+use nix::sys::signal::{kill, Signal};
+use nix::unistd::Pid;
+use std::cmp::Ordering;
+use std::io::{self, IsTerminal};
+use std::thread;
+use sysinfo::{ProcessRefreshKind, ProcessStatus, ProcessesToUpdate, System, UpdateKind, Users, MINIMUM_CPU_UPDATE_INTERVAL};
+
+/// Critério de ordenação.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProcSort {
+    Cpu,
+    Mem,
+    Pid,
+    Name,
+}
+
+/// Opções do `procs`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcsOptions {
+    pub pattern: Option<String>,
+    pub sort: ProcSort,
+    pub reverse: bool,
+    pub limit: Option<usize>,
+    pub kill: bool,
+    /// Com `--kill`: SIGKILL em vez de SIGTERM
+    pub force: bool,
+}
+
+/// Interpreta os argumentos do `procs`.
+pub fn parse_procs_args(args: &[String]) -> Result<ProcsOptions, String> {
+    let mut opts = ProcsOptions { pattern: None, sort: ProcSort::Cpu, reverse: false, limit: None, kill: false, force: false };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-s" | "--sort" => {
+                opts.sort = match args.next().map(|s| s.as_str()) {
+                    Some("cpu") => ProcSort::Cpu,
+                    Some("mem") => ProcSort::Mem,
+                    Some("pid") => ProcSort::Pid,
+                    Some("name") => ProcSort::Name,
+                    _ => return Err(t!("procs.invalid_sort").to_string()),
+                }
+            }
+            "-n" => {
+                let n = args.next().and_then(|n| n.parse().ok()).ok_or_else(|| t!("args.requires_number", "-n"))?;
+                opts.limit = Some(n);
+            }
+            "-r" | "--reverse" => opts.reverse = true,
+            "-k" | "--kill" => opts.kill = true,
+            "-9" => opts.force = true,
+            flag if flag.starts_with('-') => return Err(t!("args.unknown_option", flag)),
+            _ if opts.pattern.is_some() => return Err(t!("args.extra_argument", arg)),
+            _ => opts.pattern = Some(arg.clone()),
+        }
+    }
+    if opts.force && !opts.kill {
+        return Err(t!("procs.force_needs_kill").to_string());
+    }
+    Ok(opts)
+}
+
+// -----------------------------------------------------------------------------
+// ROWS
+// -----------------------------------------------------------------------------
+
+/// Um processo da lista.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcRow {
+    pub pid: u32,
+    pub user: String,
+    /// Percentual de um núcleo (passa de 100 com várias threads)
+    pub cpu: f32,
+    /// Memória residente em bytes
+    pub memory: u64,
+    pub name: String,
+    /// Linha de comando (o nome, para threads do kernel)
+    pub command: String,
+}
+
+/// `pattern` aparece no nome ou na linha de comando (sem diferenciar
+/// maiúsculas), ou é o PID.
+pub fn matches(row: &ProcRow, pattern: &str) -> bool {
+    if pattern.parse() == Ok(row.pid) {
+        return true;
+    }
+    let pattern = pattern.to_lowercase();
+    row.name.to_lowercase().contains(&pattern) || row.command.to_lowercase().contains(&pattern)
+}
+
+/// Ordena: CPU e memória do maior para o menor, PID e nome em ordem crescente
+/// (`reverse` inverte).
+pub fn sort_rows(rows: &mut [ProcRow], sort: ProcSort, reverse: bool) {
+    rows.sort_by(|a, b| {
+        let order = match sort {
+            ProcSort::Cpu => b.cpu.partial_cmp(&a.cpu).unwrap_or(Ordering::Equal),
+            ProcSort::Mem => b.memory.cmp(&a.memory),
+            ProcSort::Pid => a.pid.cmp(&b.pid),
+            ProcSort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        };
+        order.then(a.pid.cmp(&b.pid))
+    });
+    if reverse {
+        rows.reverse();
+    }
+}
+
+/// Corta `text` em `width` caracteres, terminando com `…`.
+pub fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+/// Processos da máquina (sem as threads e os zumbis), com o uso de CPU medido entre duas
+/// leituras.
+fn collect_rows() -> Vec<ProcRow> {
+    let kind = ProcessRefreshKind::nothing()
+        .with_cpu()
+        .with_memory()
+        .with_cmd(UpdateKind::OnlyIfNotSet)
+        .with_user(UpdateKind::OnlyIfNotSet);
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(ProcessesToUpdate::All, true, kind);
+    thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+    sys.refresh_processes_specifics(ProcessesToUpdate::All, true, kind);
+    let users = Users::new_with_refreshed_list();
+
+    sys.processes()
+        .values()
+        // Zumbis já terminaram: só esperam o pai
+        .filter(|p| p.thread_kind().is_none() && p.status() != ProcessStatus::Zombie)
+        .map(|p| {
+            let name = p.name().to_string_lossy().into_owned();
+            // Argumentos com quebra de linha (`python -c '...'`) ficam numa linha só
+            let command = p
+                .cmd()
+                .iter()
+                .map(|a| a.to_string_lossy().replace(char::is_control, " "))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let user = p
+                .user_id()
+                .map(|uid| users.get_user_by_id(uid).map(|u| u.name().to_string()).unwrap_or_else(|| uid.to_string()))
+                .unwrap_or_default();
+            ProcRow {
+                pid: p.pid().as_u32(),
+                user,
+                cpu: p.cpu_usage(),
+                memory: p.memory(),
+                command: if command.is_empty() { format!("[{}]", name) } else { command },
+                name,
+            }
+        })
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
+// OUTPUT
+// -----------------------------------------------------------------------------
+
+fn print_rows(rows: &[ProcRow]) {
+    let header = t!("procs.user");
+    let user_width = rows.iter().map(|r| r.user.chars().count()).max().unwrap_or(0).clamp(header.chars().count(), 12);
+    // PID, usuário, CPU e memória ocupam a parte fixa da linha
+    let fixed = 7 + 2 + user_width + 2 + 6 + 2 + 9 + 2;
+    let command_width = terminal_width().map(|w| w.saturating_sub(fixed).max(20));
+    println!(
+        "\x1b[1m{:>7}  {:<uw$}  {:>6}  {:>9}  {}\x1b[0m",
+        "PID",
+        header,
+        "CPU%",
+        "MEM",
+        t!("procs.command"),
+        uw = user_width
+    );
+    for row in rows {
+        let cpu_color = if row.cpu >= 50.0 { "\x1b[31m" } else if row.cpu >= 10.0 { "\x1b[33m" } else { "" };
+        let command = command_width.map_or_else(|| row.command.clone(), |w| truncate(&row.command, w));
+        println!(
+            "\x1b[36m{:>7}\x1b[0m  {:<uw$}  {}{:>6.1}\x1b[0m  {:>9}  {}",
+            row.pid,
+            truncate(&row.user, user_width),
+            cpu_color,
+            row.cpu,
+            human_size(row.memory),
+            command,
+            uw = user_width
+        );
+    }
+}
+
+/// `--kill`: escolhe na lista e manda o sinal para os marcados.
+fn kill_interactive(rows: &[ProcRow], signal: Signal) -> i32 {
+    if !io::stdin().is_terminal() {
+        eprintln!("{}", t!("procs.kill_needs_terminal"));
+        return 1;
+    }
+    let width = terminal_width().unwrap_or(80).saturating_sub(8);
+    let options: Vec<String> = rows
+        .iter()
+        .map(|r| truncate(&format!("{:>7}  {:<10} {}", r.pid, truncate(&r.user, 10), r.command), width))
+        .collect();
+    let selected = match MultiSelect::new(t!("procs.kill_prompt"), options).with_page_size(15).raw_prompt() {
+        Ok(selected) => selected,
+        // Esc / Ctrl+C: nada é encerrado
+        Err(_) => return 1,
+    };
+
+    let mut status = 0;
+    for option in selected {
+        let row = &rows[option.index];
+        match kill(Pid::from_raw(row.pid as i32), signal) {
+            Ok(()) => println!("{}", t!("procs.killed", signal.as_str(), row.pid, row.name)),
+            Err(e) => {
+                eprintln!("procs: {} ({}): {}", row.pid, row.name, e.desc());
+                status = 1;
+            }
+        }
+    }
+    status
+}
+
+/// Builtin `procs [-s cpu|mem|pid|name] [-r] [-n N] [--kill [-9]] [padrão]`.
+pub fn handle_procs(tokens: &[String]) -> i32 {
+    let opts = match parse_procs_args(&tokens[1..]) {
+        Ok(opts) => opts,
+        Err(e) => {
+            eprintln!("procs: {}", e);
+            eprintln!("{}", usage("procs"));
+            return 2;
+        }
+    };
+
+    let own_pid = std::process::id();
+    let mut rows: Vec<ProcRow> = collect_rows()
+        .into_iter()
+        // A própria shell não aparece no `--kill`
+        .filter(|r| !(opts.kill && r.pid == own_pid))
+        .filter(|r| opts.pattern.as_deref().is_none_or(|p| matches(r, p)))
+        .collect();
+    sort_rows(&mut rows, opts.sort, opts.reverse);
+    if let Some(limit) = opts.limit {
+        rows.truncate(limit);
+    }
+
+    if rows.is_empty() {
+        eprintln!("{}", t!("procs.none"));
+        return 1;
+    }
+    if opts.kill {
+        return kill_interactive(&rows, if opts.force { Signal::SIGKILL } else { Signal::SIGTERM });
+    }
+    print_rows(&rows);
+    0
+}
@@ -2,10 +2,21 @@
 //!
 //! Handles prompt building, including the Powerline theme and Git branch detection.
 
-use crate::config::{CargoToml, CliosConfig, PackageJson, PyProjectToml};
+use crate::config::{get_color_ansi, CargoToml, CliosConfig, PackageJson, PyProjectToml};
+use crate::container::{container_label, default_container};
+use crate::disk_usage::current_low_disk_label;
+use crate::git::current_repo;
+use crate::jobs::{count_active_jobs, count_stopped_jobs, JobList};
+use crate::nesting::{current_level, level_label};
+use crate::remote::{default_remote, remote_label};
+use crate::system_info::current_load_label;
+use crate::t;
+use crate::terminal::terminal_width;
+use crate::toolchain::{detect_toolchains, toolchain_label, Toolchain};
 use chrono::Local;
 use std::fs;
 use std::process::{Command, Stdio};
+use unicode_width::UnicodeWidthChar;
 
 // -----------------------------------------------------------------------------
 // POWERLINE SEGMENT
@@ -16,6 +27,9 @@ pub struct PowerlineSegment {
     pub text: String,
     pub bg: String, // Código de cor ANSI do fundo (ex: "218")
     pub fg: String, // Código de cor ANSI do texto (ex: "0" para preto)
+    /// Ordem de descarte em terminal estreito (ver [`fit_prompt`]): 0 é o
+    /// caminho, que é encurtado em vez de sumir
+    pub priority: u8,
 }
 
 // -----------------------------------------------------------------------------
@@ -24,10 +38,10 @@ pub struct PowerlineSegment {
 
 /// Detecta a Branch do Git para o Prompt (Nível 7).
 ///
-/// Executa `git branch --show-current` em um processo separado.
-/// O `stderr` é descartado para evitar mensagens de erro caso a pasta
-/// atual não seja um repositório git.
+/// Executa `git branch --show-current` em um processo separado, só dentro de
+/// um repositório ([`current_repo`], com cache). O `stderr` é descartado.
 pub fn get_git_branch() -> Option<String> {
+    current_repo()?;
     let output = Command::new("git")
         .arg("branch")
         .arg("--show-current")
@@ -44,6 +58,25 @@ pub fn get_git_branch() -> Option<String> {
     None
 }
 
+// -----------------------------------------------------------------------------
+// JOB COUNT
+// -----------------------------------------------------------------------------
+
+/// Texto do indicador de jobs em background (`[1 job]`, `[2 jobs]`).
+/// Retorna `None` quando não há jobs, para o segmento sumir do prompt.
+pub fn jobs_label(active: usize) -> Option<String> {
+    match active {
+        0 => None,
+        1 => Some("[1 job]".to_string()),
+        n => Some(format!("[{} jobs]", n)),
+    }
+}
+
+/// Lê a tabela de jobs: (ativos, parados).
+fn job_counts(jobs: &JobList) -> (usize, usize) {
+    (count_active_jobs(jobs), count_stopped_jobs(jobs))
+}
+
 // -----------------------------------------------------------------------------
 // VERSION READING
 // -----------------------------------------------------------------------------
@@ -128,9 +161,18 @@ pub fn build_powerline_prompt(segments: Vec<PowerlineSegment>) -> String {
 /// 1. Ícone do SO + Usuário
 /// 2. Diretório Atual
 /// 3. Git Branch
-/// 4. Contexto de Linguagem
-/// 5. Relógio
-pub fn get_powerline_segments(_config: &CliosConfig) -> Vec<PowerlineSegment> {
+/// 4. Contexto de Linguagem, e a versão pedida pelo projeto (`show_toolchain`)
+/// 5. Jobs em background (só se houver)
+/// 6. Nível de aninhamento (só dentro de outra Clios)
+/// 7. Host remoto padrão (só se definido)
+/// 8. Container padrão (só se definido)
+/// 9. Carga alta (só com `show_load` e a máquina sobrecarregada)
+/// 10. Disco quase cheio (só com `show_disk`)
+/// 11. Relógio
+///
+/// Se não couberem no terminal, o caminho é encurtado e os de menor
+/// prioridade somem (ver [`fit_prompt`]).
+pub fn get_powerline_segments(config: &CliosConfig, jobs: &JobList) -> Vec<PowerlineSegment> {
     let mut segments = Vec::new();
 
     // 1. Ícone do SO + Usuário (Rosa - Cor 218)
@@ -139,19 +181,16 @@ pub fn get_powerline_segments(_config: &CliosConfig) -> Vec<PowerlineSegment> {
         text: format!("🐧 {}", user),
         bg: "218".to_string(), // Rosa pastel
         fg: "0".to_string(),   // Preto
+        priority: 6,
     });
 
     // 2. Diretório Atual (Laranja - Cor 215)
-    if let Ok(path) = std::env::current_dir() {
-        let path_str = path.display().to_string();
-        // Truque para encurtar o home
-        let home = std::env::var("HOME").unwrap_or_default();
-        let short_path = path_str.replace(&home, "~");
-
+    if let Some(path) = current_path_display(config) {
         segments.push(PowerlineSegment {
-            text: short_path,
+            text: path,
             bg: "215".to_string(), // Laranja
             fg: "0".to_string(),
+            priority: 0,
         });
     }
 
@@ -161,6 +200,7 @@ pub fn get_powerline_segments(_config: &CliosConfig) -> Vec<PowerlineSegment> {
             text: format!(" {}", branch), // Ícone de branch
             bg: "229".to_string(),         // Amarelo claro
             fg: "0".to_string(),
+            priority: 1,
         });
     }
 
@@ -202,6 +242,7 @@ pub fn get_powerline_segments(_config: &CliosConfig) -> Vec<PowerlineSegment> {
                 text: format!("{} {}", lang.icon, version).trim().to_string(),
                 bg: lang.color.clone(),
                 fg: "0".to_string(),
+                priority: 5,
             });
             found_lang = true;
             break;
@@ -217,16 +258,514 @@ pub fn get_powerline_segments(_config: &CliosConfig) -> Vec<PowerlineSegment> {
             text: "🐍 Py".to_string(),
             bg: "220".to_string(),
             fg: "0".to_string(),
+            priority: 5,
+        });
+    }
+
+    // Versão pedida pelo projeto (Verde-água - Cor 152)
+    if let Some(label) = current_toolchain_label(config) {
+        segments.push(PowerlineSegment {
+            text: label,
+            bg: "152".to_string(),
+            fg: "0".to_string(),
+            priority: 4,
+        });
+    }
+
+    // 5. Jobs em background (Lilás - Cor 183; Vermelho - Cor 210 se algum parado)
+    let (active, stopped) = job_counts(jobs);
+    if let Some(label) = jobs_label(active) {
+        segments.push(PowerlineSegment {
+            text: format!("⚙ {}", label),
+            bg: if stopped > 0 { "210" } else { "183" }.to_string(),
+            fg: "0".to_string(),
+            priority: 1,
+        });
+    }
+
+    // 6. Nível de aninhamento (Cinza - Cor 250)
+    if let Some(label) = level_label(current_level()) {
+        segments.push(PowerlineSegment {
+            text: label,
+            bg: "250".to_string(),
+            fg: "0".to_string(),
+            priority: 2,
+        });
+    }
+
+    // 7. Host remoto padrão (Laranja - Cor 216)
+    if let Some(label) = remote_label(default_remote().as_deref()) {
+        segments.push(PowerlineSegment {
+            text: label,
+            bg: "216".to_string(),
+            fg: "0".to_string(),
+            priority: 2,
         });
     }
 
-    // 5. Relógio (Azul - Cor 117)
+    // 8. Container padrão (Azul-petróleo - Cor 116)
+    if let Some(label) = container_label(default_container().as_deref()) {
+        segments.push(PowerlineSegment {
+            text: label,
+            bg: "116".to_string(),
+            fg: "0".to_string(),
+            priority: 2,
+        });
+    }
+
+    // 9. Carga alta (Vermelho - Cor 210)
+    if show_load(config)
+        && let Some(label) = current_load_label()
+    {
+        segments.push(PowerlineSegment {
+            text: format!("⚠ {}", label),
+            bg: "210".to_string(),
+            fg: "0".to_string(),
+            priority: 3,
+        });
+    }
+
+    // 10. Disco quase cheio (Vermelho - Cor 210)
+    if show_disk(config)
+        && let Some(label) = current_low_disk_label()
+    {
+        segments.push(PowerlineSegment {
+            text: format!("⚠ {}", label),
+            bg: "210".to_string(),
+            fg: "0".to_string(),
+            priority: 3,
+        });
+    }
+
+    // 11. Relógio (Azul - Cor 117)
     let time = Local::now().format("%H:%M").to_string();
     segments.push(PowerlineSegment {
         text: format!("🕑 {}", time),
         bg: "117".to_string(),
         fg: "0".to_string(),
+        priority: 7,
     });
 
+    match prompt_budget() {
+        Some(max) => fit_powerline_segments(segments, max, path_components(config)),
+        None => segments,
+    }
+}
+
+/// Ajusta os segmentos do Powerline a `max` colunas.
+pub fn fit_powerline_segments(segments: Vec<PowerlineSegment>, max: usize, components: usize) -> Vec<PowerlineSegment> {
+    let Some(path_index) = segments.iter().position(|s| s.priority == 0) else {
+        return segments;
+    };
+    // Cada bloco: espaço + texto + espaço + separador; mais a borda inicial e o ` ❯ `
+    let parts: Vec<(u8, usize)> = segments
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != path_index)
+        .map(|(_, s)| (s.priority, display_width(&s.text) + 3))
+        .collect();
+    let fit = fit_prompt(&segments[path_index].text, &parts, 1 + 3 + 3, max, components);
+
+    let mut keep = fit.keep.into_iter();
     segments
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, mut segment)| {
+            if i == path_index {
+                segment.text = fit.path.clone();
+                return Some(segment);
+            }
+            keep.next().unwrap_or(true).then_some(segment)
+        })
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
+// PATH DISPLAY
+// -----------------------------------------------------------------------------
+
+/// Forma do caminho no prompt (`[prompt] path_style`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathStyle {
+    /// `~/projetos/cliente/src`
+    #[default]
+    Full,
+    /// Só o diretório atual: `src`
+    Short,
+    /// Diretórios do meio pela inicial, como no fish: `~/p/c/src`
+    Fish,
+}
+
+impl PathStyle {
+    /// O estilo configurado; valores desconhecidos ficam no completo.
+    pub fn from_config(config: &CliosConfig) -> Self {
+        match config.prompt.as_ref().and_then(|p| p.path_style.as_deref()) {
+            Some("short") => PathStyle::Short,
+            Some("fish") => PathStyle::Fish,
+            _ => PathStyle::Full,
+        }
+    }
+}
+
+/// `path` com o HOME no começo trocado por `~` (`/home/anaa` não vira `~a`).
+pub fn tilde_path(path: &str, home: &str) -> String {
+    let home = home.trim_end_matches('/');
+    match path.strip_prefix(home) {
+        Some(rest) if !home.is_empty() && (rest.is_empty() || rest.starts_with('/')) => format!("~{}", rest),
+        _ => path.to_string(),
+    }
+}
+
+/// Inicial de um diretório no estilo `fish` (`.config` vira `.c`).
+fn fish_initial(component: &str) -> String {
+    let keep = if component.starts_with('.') { 2 } else { 1 };
+    component.chars().take(keep).collect()
+}
+
+/// O caminho como o prompt mostra, no estilo pedido.
+pub fn format_path(path: &str, home: &str, style: PathStyle) -> String {
+    let path = tilde_path(path, home);
+    match style {
+        PathStyle::Full => path,
+        PathStyle::Short => match path.rsplit('/').find(|c| !c.is_empty()) {
+            Some(last) => last.to_string(),
+            None => path,
+        },
+        PathStyle::Fish => {
+            let components: Vec<&str> = path.split('/').collect();
+            let last = components.len() - 1;
+            components
+                .iter()
+                .enumerate()
+                .map(|(i, component)| {
+                    if i == last || component.is_empty() || *component == "~" {
+                        component.to_string()
+                    } else {
+                        fish_initial(component)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("/")
+        }
+    }
+}
+
+/// O diretório atual no estilo do `[prompt]`.
+fn current_path_display(config: &CliosConfig) -> Option<String> {
+    let cwd = std::env::current_dir().ok()?;
+    let home = std::env::var("HOME").unwrap_or_default();
+    Some(format_path(&cwd.display().to_string(), &home, PathStyle::from_config(config)))
+}
+
+// -----------------------------------------------------------------------------
+// WIDTH FITTING
+// -----------------------------------------------------------------------------
+
+/// Colunas deixadas para o comando à direita do prompt.
+pub const MIN_INPUT_COLUMNS: usize = 20;
+
+/// Segmentos com prioridade a partir desta somem antes de o caminho ser
+/// encurtado (usuário, relógio, linguagem).
+const OPTIONAL_PRIORITY: u8 = 5;
+
+/// Largura em colunas do texto no terminal, ignorando as sequências ANSI
+/// (emojis e CJK ocupam duas).
+pub fn display_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            if chars.peek() == Some(&'[') {
+                chars.next();
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        width += UnicodeWidthChar::width(c).unwrap_or(0);
+    }
+    width
+}
+
+/// O caminho com só os `keep` últimos diretórios: `~/projetos/api/src/bin`
+/// com 2 vira `~/…/src/bin`.
+pub fn truncate_path(path: &str, keep: usize) -> String {
+    let keep = keep.max(1);
+    let (root, rest) = match path.strip_prefix("~/") {
+        Some(rest) => ("~/", rest),
+        None => match path.strip_prefix('/') {
+            Some(rest) => ("/", rest),
+            None => ("", path),
+        },
+    };
+    let parts: Vec<&str> = rest.split('/').filter(|p| !p.is_empty()).collect();
+    if parts.len() <= keep {
+        return path.to_string();
+    }
+    format!("{}…/{}", root, parts[parts.len() - keep..].join("/"))
+}
+
+/// O final do texto que cabe em `max` colunas, com `…` no começo.
+fn truncate_start(text: &str, max: usize) -> String {
+    if display_width(text) <= max {
+        return text.to_string();
+    }
+    let mut tail: Vec<char> = Vec::new();
+    let mut width = 1;
+    for c in text.chars().rev() {
+        let w = UnicodeWidthChar::width(c).unwrap_or(0);
+        if width + w > max {
+            break;
+        }
+        width += w;
+        tail.push(c);
+    }
+    std::iter::once('…').chain(tail.into_iter().rev()).collect()
+}
+
+/// Resultado de [`fit_prompt`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptFit {
+    /// O caminho, encurtado se preciso
+    pub path: String,
+    /// Se cada parte continua no prompt (mesma ordem da entrada)
+    pub keep: Vec<bool>,
+}
+
+/// Encaixa o prompt em `max` colunas. `parts` são as outras partes como
+/// (prioridade, largura) e `fixed` as colunas que não mudam.
+///
+/// Reduções, até caber: somem as partes opcionais (prioridade 5 ou mais), o
+/// caminho fica com `components` diretórios, somem as demais partes (a de
+/// maior prioridade e, no empate, a mais à direita primeiro), o caminho fica
+/// só com o último diretório e, por fim, é cortado com `…`.
+pub fn fit_prompt(path: &str, parts: &[(u8, usize)], fixed: usize, max: usize, components: usize) -> PromptFit {
+    let mut fit = PromptFit { path: path.to_string(), keep: vec![true; parts.len()] };
+    let total = |fit: &PromptFit| {
+        let kept: usize = parts.iter().zip(&fit.keep).filter(|(_, keep)| **keep).map(|((_, w), _)| w).sum();
+        fixed + display_width(&fit.path) + kept
+    };
+    let drop_one = |fit: &mut PromptFit, min: u8| {
+        let least = (0..parts.len()).filter(|&i| fit.keep[i] && parts[i].0 >= min).max_by_key(|&i| (parts[i].0, i));
+        least.map(|i| fit.keep[i] = false).is_some()
+    };
+
+    while total(&fit) > max && drop_one(&mut fit, OPTIONAL_PRIORITY) {}
+    if total(&fit) > max {
+        fit.path = truncate_path(&fit.path, components);
+    }
+    while total(&fit) > max && drop_one(&mut fit, 1) {}
+    if total(&fit) > max {
+        fit.path = truncate_path(&fit.path, 1);
+    }
+    let over = total(&fit).saturating_sub(max);
+    if over > 0 {
+        let width = display_width(&fit.path).saturating_sub(over).max(2);
+        fit.path = truncate_start(&fit.path, width);
+    }
+    fit
+}
+
+/// Colunas disponíveis para o prompt; `None` fora de um terminal.
+fn prompt_budget() -> Option<usize> {
+    terminal_width().map(|width| width.saturating_sub(MIN_INPUT_COLUMNS))
+}
+
+/// `[prompt] path_components` (3 por padrão).
+fn path_components(config: &CliosConfig) -> usize {
+    config.prompt.as_ref().and_then(|p| p.path_components).unwrap_or(3)
+}
+
+/// `[prompt] show_load` (ligado por padrão).
+fn show_load(config: &CliosConfig) -> bool {
+    config.prompt.as_ref().and_then(|p| p.show_load).unwrap_or(true)
+}
+
+/// `[prompt] show_disk` (ligado por padrão).
+fn show_disk(config: &CliosConfig) -> bool {
+    config.prompt.as_ref().and_then(|p| p.show_disk).unwrap_or(true)
+}
+
+/// Versões do `.nvmrc`/`rust-toolchain.toml`/`.python-version` do diretório
+/// atual, com `[prompt] show_toolchain` (ligado por padrão).
+fn current_toolchain_label(config: &CliosConfig) -> Option<String> {
+    toolchain_label(&current_toolchains(config))
+}
+
+/// As versões pedidas no diretório atual, se o prompt deve mostrá-las.
+fn current_toolchains(config: &CliosConfig) -> Vec<Toolchain> {
+    if !config.prompt.as_ref().and_then(|p| p.show_toolchain).unwrap_or(true) {
+        return Vec::new();
+    }
+    std::env::current_dir().map(|dir| detect_toolchains(&dir)).unwrap_or_default()
+}
+
+// -----------------------------------------------------------------------------
+// CLASSIC PROMPT BUILDING
+// -----------------------------------------------------------------------------
+
+/// Builds the classic (customizable) prompt.
+pub fn build_classic_prompt(config: &CliosConfig, last_exit_code: i32, jobs: &JobList) -> String {
+    let dir_display = current_path_display(config).unwrap_or_default();
+
+    let (symbol, default_color, path_color_cfg, symbol_color_cfg, show_git) =
+        if let Some(p) = &config.prompt {
+            (
+                p.symbol.as_deref().unwrap_or(">"),
+                p.color.as_deref().unwrap_or("blue"),
+                p.path_color.as_deref(),
+                p.symbol_color.as_deref(),
+                p.show_git.unwrap_or(true),
+            )
+        } else {
+            (">", "blue", None, None, true)
+        };
+
+    let path_ansi = get_color_ansi(path_color_cfg.unwrap_or(default_color));
+    let arrow_ansi = get_color_ansi(symbol_color_cfg.unwrap_or(default_color));
+
+    let git_color = if show_git {
+        if let Some(branch) = get_git_branch() {
+            format!(" (\x1b[1;35m{}\x1b[0m)", branch)
+        } else {
+            String::new()
+        }
+    } else {
+        String::new()
+    };
+
+    // Versão pedida pelo projeto
+    let toolchain_segment = match current_toolchain_label(config) {
+        Some(label) => format!(" \x1b[32m{}\x1b[0m", label),
+        None => String::new(),
+    };
+
+    // Jobs em background: amarelo se algum estiver parado (Ctrl+Z)
+    let (active, stopped) = job_counts(jobs);
+    let jobs_segment = match jobs_label(active) {
+        Some(label) if stopped > 0 => format!(" \x1b[1;33m{}\x1b[0m", label),
+        Some(label) => format!(" \x1b[2m{}\x1b[0m", label),
+        None => String::new(),
+    };
+
+    // Nível de aninhamento (Clios dentro de Clios)
+    let level_segment = match level_label(current_level()) {
+        Some(label) => format!(" \x1b[1;36m{}\x1b[0m", label),
+        None => String::new(),
+    };
+
+    // Host remoto padrão (`remote default`)
+    let remote_segment = match remote_label(default_remote().as_deref()) {
+        Some(label) => format!(" \x1b[1;33m{}\x1b[0m", label),
+        None => String::new(),
+    };
+
+    // Container padrão do `inside`
+    let container_segment = match container_label(default_container().as_deref()) {
+        Some(label) => format!(" \x1b[1;34m{}\x1b[0m", label),
+        None => String::new(),
+    };
+
+    // Carga alta
+    let load_segment = match current_load_label().filter(|_| show_load(config)) {
+        Some(label) => format!(" \x1b[1;31m{}\x1b[0m", label),
+        None => String::new(),
+    };
+
+    // Disco quase cheio
+    let disk_segment = match current_low_disk_label().filter(|_| show_disk(config)) {
+        Some(label) => format!(" \x1b[1;31m{}\x1b[0m", label),
+        None => String::new(),
+    };
+
+    let arrow_colored = if last_exit_code == 0 {
+        format!("{}{}\x1b[0m ", arrow_ansi, symbol)
+    } else {
+        format!("\x1b[1;31m[{}]>\x1b[0m ", last_exit_code)
+    };
+
+    // Em terminal estreito: caminho encurtado e segmentos de menor prioridade fora
+    let mut dir = dir_display;
+    let mut segments = [
+        (1, git_color),
+        (4, toolchain_segment),
+        (1, jobs_segment),
+        (2, level_segment),
+        (2, remote_segment),
+        (2, container_segment),
+        (3, load_segment),
+        (3, disk_segment),
+    ];
+    if let Some(max) = prompt_budget() {
+        let parts: Vec<(u8, usize)> = segments.iter().map(|(priority, text)| (*priority, display_width(text))).collect();
+        let fixed = display_width("clios:") + display_width(&arrow_colored);
+        let fit = fit_prompt(&dir, &parts, fixed, max, path_components(config));
+        dir = fit.path;
+        for ((_, text), keep) in segments.iter_mut().zip(fit.keep) {
+            if !keep {
+                text.clear();
+            }
+        }
+    }
+    let extras: String = segments[1..].iter().map(|(_, text)| text.as_str()).collect();
+
+    format!(
+        "{}{}:{}{}\x1b[0m{}{}",
+        path_ansi,
+        "clios",
+        dir,
+        segments[0].1,
+        extras,
+        arrow_colored
+    )
+}
+
+// -----------------------------------------------------------------------------
+// ACCESSIBLE PROMPT BUILDING
+// -----------------------------------------------------------------------------
+
+/// Prompt para leitores de tela (`[accessibility] screen_reader`): as mesmas
+/// informações do prompt clássico em palavras separadas por vírgula, sem
+/// glifos e sem cores.
+///
+/// Ex: `clios ~/api, git main, node 20, 1 job, código 1 > `
+pub fn build_accessible_prompt(config: &CliosConfig, last_exit_code: i32, jobs: &JobList) -> String {
+    // Sempre completo: abreviações do `fish` seriam soletradas
+    let cwd = std::env::current_dir().map(|p| p.display().to_string()).unwrap_or_default();
+    let dir = tilde_path(&cwd, &std::env::var("HOME").unwrap_or_default());
+
+    let mut parts = vec![format!("clios {}", dir)];
+    if config.prompt.as_ref().and_then(|p| p.show_git).unwrap_or(true)
+        && let Some(branch) = get_git_branch()
+    {
+        parts.push(format!("git {}", branch));
+    }
+    for toolchain in current_toolchains(config) {
+        parts.push(format!("{} {}", toolchain.kind.name(), toolchain.version));
+    }
+    if let Some(label) = jobs_label(count_active_jobs(jobs)) {
+        parts.push(label.trim_matches(['[', ']']).to_string());
+    }
+    if current_level() > 1 {
+        parts.push(t!("prompt.level", current_level()));
+    }
+    if let Some(host) = default_remote() {
+        parts.push(t!("prompt.remote", host));
+    }
+    if let Some(container) = default_container() {
+        parts.push(format!("container {}", container));
+    }
+    if let Some(label) = current_load_label().filter(|_| show_load(config)) {
+        parts.push(label);
+    }
+    if let Some(label) = current_low_disk_label().filter(|_| show_disk(config)) {
+        parts.push(label);
+    }
+    if last_exit_code != 0 {
+        parts.push(t!("prompt.exit_code", last_exit_code));
+    }
+    format!("{} > ", parts.join(", "))
 }
@@ -0,0 +1,107 @@
+//! # Prompt Cache Module
+//!
+//! Reaproveita o prompt já renderizado quando nada do que ele mostra mudou:
+//! um Enter numa linha vazia ou um Ctrl+C não rodam `git`, não leem a carga
+//! nem procuram os arquivos de versão de novo.
+//!
+//! A chave ([`PromptKey`]) junta o diretório atual, o `HEAD` do git (lido do
+//! arquivo, sem processo), um hash da configuração e do tema, o código de
+//! saída, os jobs, a largura do terminal e o minuto do relógio. Rodar um
+//! comando invalida o cache ([`PromptCache::invalidate`]), já que ele pode ter
+//! mudado qualquer coisa (variáveis, `remote default`, a branch).
+//!
+//! Desliga com `cache = false` em `[prompt]`.
+
+use crate::config::CliosConfig;
+use crate::git::current_repo;
+use crate::jobs::{count_active_jobs, count_stopped_jobs, JobList};
+use crate::terminal::terminal_width;
+use chrono::Local;
+use std::env;
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Tudo de que o prompt depende; dois prompts com a mesma chave são iguais.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptKey {
+    pub cwd: Option<PathBuf>,
+    /// Conteúdo do `.git/HEAD` (`ref: refs/heads/main`), fora de um repositório `None`
+    pub git_head: Option<String>,
+    /// Hash da configuração, do tema e do modo de acessibilidade
+    pub config_hash: u64,
+    pub last_exit_code: i32,
+    /// Jobs ativos e parados
+    pub jobs: (usize, usize),
+    pub width: Option<usize>,
+    /// `HH:MM` do relógio do Powerline
+    pub minute: String,
+}
+
+impl PromptKey {
+    /// A chave do prompt que seria mostrado agora.
+    pub fn current(config: &CliosConfig, theme: &str, screen_reader: bool, last_exit_code: i32, jobs: &JobList) -> Self {
+        PromptKey {
+            cwd: env::current_dir().ok(),
+            git_head: current_repo().and_then(|root| git_head(&root)),
+            config_hash: config_hash(config, theme, screen_reader),
+            last_exit_code,
+            jobs: (count_active_jobs(jobs), count_stopped_jobs(jobs)),
+            width: terminal_width(),
+            minute: Local::now().format("%H:%M").to_string(),
+        }
+    }
+}
+
+/// O `HEAD` do repositório em `root`, lido direto do arquivo. Em worktrees e
+/// submódulos o `.git` é um arquivo com `gitdir: caminho`.
+pub fn git_head(root: &Path) -> Option<String> {
+    let dot_git = root.join(".git");
+    let git_dir = match fs::read_to_string(&dot_git) {
+        Ok(pointer) => {
+            let dir = PathBuf::from(pointer.strip_prefix("gitdir:")?.trim());
+            if dir.is_absolute() { dir } else { root.join(dir) }
+        }
+        Err(_) => dot_git,
+    };
+    fs::read_to_string(git_dir.join("HEAD")).ok().map(|head| head.trim().to_string())
+}
+
+/// Hash da configuração (pelo `Debug`, que cobre todos os campos), do tema e
+/// do modo para leitores de tela.
+pub fn config_hash(config: &CliosConfig, theme: &str, screen_reader: bool) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    format!("{:?}", config).hash(&mut hasher);
+    theme.hash(&mut hasher);
+    screen_reader.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// O último prompt renderizado e a chave dele.
+#[derive(Debug, Default)]
+pub struct PromptCache {
+    entry: Option<(PromptKey, String)>,
+    /// Prompts reaproveitados desde o início
+    pub hits: u64,
+}
+
+impl PromptCache {
+    /// O prompt para `key`: o guardado, se a chave é a mesma; senão o de
+    /// `render`, que passa a ser o guardado.
+    pub fn get_or_render(&mut self, key: PromptKey, render: impl FnOnce() -> String) -> String {
+        if let Some((cached_key, prompt)) = &self.entry
+            && *cached_key == key
+        {
+            self.hits += 1;
+            return prompt.clone();
+        }
+        let prompt = render();
+        self.entry = Some((key, prompt.clone()));
+        prompt
+    }
+
+    /// Esquece o prompt guardado (depois de rodar um comando).
+    pub fn invalidate(&mut self) {
+        self.entry = None;
+    }
+}
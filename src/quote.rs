@@ -0,0 +1,67 @@
+//! # Quote Module
+//!
+//! O caminho de volta do `shlex::split`: palavras já separadas (e expandidas)
+//! viram uma linha de comando que, lida de novo, dá as mesmas palavras.
+//! Toda linha que a shell remonta passa por aqui: a listagem do `alias` e do
+//! `abbr`, os comandos do `each`, `watch`, `repeat` e `run`, os candidatos do
+//! autocomplete, o `set -x` e o `set -o dryrun` (comandos já expandidos) e o
+//! eco da expansão do histórico (`!$`).
+//!
+//! ```text
+//! ["git", "commit", "-m", "it's done"]  →  git commit -m "it's done"
+//! ["echo", "", "a b", "$HOME"]          →  echo '' 'a b' '$HOME'
+//! ["grep", "-e", "it's $x"]             →  grep -e 'it'\''s $x'
+//! ```
+//!
+//! Diferente do `shlex::try_join`, nunca falha (um byte NUL, que nenhuma
+//! shell aceita, sai como está) e só põe aspas quando precisa: `FOO=bar`,
+//! `%1` e `a,b` ficam como foram escritos.
+
+use std::borrow::Cow;
+
+/// Caracteres que nunca precisam de aspas.
+fn is_plain(c: char) -> bool {
+    c.is_alphanumeric() || "-_./:@%+,=^".contains(c)
+}
+
+/// Caracteres que as aspas duplas não protegem.
+fn breaks_double_quotes(c: char) -> bool {
+    matches!(c, '$' | '`' | '"' | '\\' | '!')
+}
+
+/// Uma palavra pronta para a linha de comando: sem aspas se não precisa,
+/// entre aspas simples, ou entre duplas se ela tem uma aspa simples (`'` no
+/// meio de aspas simples vira `'\''` só quando nem as duplas servem).
+pub fn quote_word(word: &str) -> Cow<'_, str> {
+    // `=` e `~` só são especiais no começo (`=expr` da calculadora, `~/dir`)
+    let plain = !word.is_empty()
+        && !word.starts_with(['=', '~'])
+        && word.chars().all(|c| is_plain(c) || c == '~');
+    if plain {
+        Cow::Borrowed(word)
+    } else if !word.contains('\'') {
+        Cow::Owned(format!("'{}'", word))
+    } else if !word.chars().any(breaks_double_quotes) {
+        Cow::Owned(format!("\"{}\"", word))
+    } else {
+        Cow::Owned(format!("'{}'", word.replace('\'', r"'\''")))
+    }
+}
+
+/// As palavras numa linha só, separadas por espaço.
+pub fn join_words<S: AsRef<str>>(words: &[S]) -> String {
+    words.iter().map(|word| quote_word(word.as_ref())).collect::<Vec<_>>().join(" ")
+}
+
+/// Como [`join_words`], para um comando: os operadores de redirecionamento
+/// (`>`, `2>>`, `<`...) ficam sem aspas, como foram digitados.
+pub fn join_command<S: AsRef<str>>(words: &[S]) -> String {
+    words
+        .iter()
+        .map(|word| match word.as_ref() {
+            op @ ("<" | ">" | ">>" | "2>" | "2>>") => Cow::Borrowed(op),
+            word => quote_word(word),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
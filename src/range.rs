@@ -0,0 +1,172 @@
+//! # Range Module
+//!
+//! Sequências de números sem o `seq` externo: o builtin `range` e a expansão
+//! `{início..fim..passo}` usam o mesmo código.
+//!
+//! ```bash
+//! range 5                      # 1 2 3 4 5, um por linha
+//! range 1 100 5                # 1 6 11 ... 96
+//! range 10 1 3                 # 10 7 4 1 (o sentido vem do início e do fim)
+//! range -w 1 10                # 01 02 ... 10
+//! range 1 20 | each 'curl -s host/page/{}'
+//! echo img{1..3}.png           # img1.png img2.png img3.png
+//! echo {00..20..10}            # 00 10 20
+//! ```
+//!
+//! Como primeiro comando de uma pipeline, os números saem de uma thread da
+//! shell direto para o pipe, sem juntar tudo na memória.
+
+use crate::registry::usage;
+use crate::t;
+use std::io::{self, BufWriter, PipeReader, Write};
+use std::thread;
+
+/// Uma sequência: `start` até `end` (inclusive), de `step` em `step`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RangeSpec {
+    pub start: i64,
+    pub end: i64,
+    /// Sempre positivo; o sentido vem de `start` e `end`
+    pub step: i64,
+    /// Largura mínima, completada com zeros (`-w`, `{01..10}`)
+    pub width: usize,
+}
+
+impl RangeSpec {
+    /// Os números da sequência.
+    pub fn values(&self) -> impl Iterator<Item = i64> + use<> {
+        let RangeSpec { start, end, step, .. } = *self;
+        let delta = if start <= end { step } else { -step };
+        std::iter::successors(Some(start), move |&n| n.checked_add(delta))
+            .take_while(move |&n| if delta > 0 { n <= end } else { n >= end })
+    }
+
+    /// Um número com a largura da sequência (`-5` com largura 3: `-05`).
+    pub fn format(&self, n: i64) -> String {
+        if n < 0 {
+            format!("-{:0>width$}", n.unsigned_abs(), width = self.width.saturating_sub(1))
+        } else {
+            format!("{:0>width$}", n, width = self.width)
+        }
+    }
+}
+
+/// Monta a sequência a partir dos números escritos (`"1"`, `"100"`, `"5"`);
+/// o passo é opcional e o sinal dele não importa.
+fn build_spec(start: &str, end: &str, step: Option<&str>) -> Option<RangeSpec> {
+    let step = match step {
+        Some(step) => step.parse::<i64>().ok()?.checked_abs()?,
+        None => 1,
+    };
+    (step != 0).then_some(RangeSpec { start: start.parse().ok()?, end: end.parse().ok()?, step, width: 0 })
+}
+
+/// Algum dos números começa com zero (`01`, `-007`)? Então a sequência
+/// inteira tem a largura do maior deles, como no bash.
+fn padded_width(start: &str, end: &str) -> usize {
+    let zero_padded = |s: &str| {
+        let digits = s.strip_prefix('-').unwrap_or(s);
+        digits.len() > 1 && digits.starts_with('0')
+    };
+    if zero_padded(start) || zero_padded(end) {
+        start.len().max(end.len())
+    } else {
+        0
+    }
+}
+
+/// Interpreta os argumentos do `range`: `[-w] [início] fim [passo]`.
+pub fn parse_range_args(args: &[String]) -> Result<RangeSpec, String> {
+    let equal_width = args.first().is_some_and(|a| a == "-w");
+    let numbers: Vec<&str> = args[usize::from(equal_width)..].iter().map(String::as_str).collect();
+    let spec = match numbers[..] {
+        [end] => build_spec("1", end, None),
+        [start, end] => build_spec(start, end, None),
+        [start, end, step] => build_spec(start, end, Some(step)),
+        _ => return Err(t!("range.expected_args").to_string()),
+    };
+    let mut spec = spec.ok_or(t!("range.invalid_numbers"))?;
+    if equal_width {
+        spec.width = spec.format(spec.start).len().max(spec.format(spec.end).len());
+    }
+    Ok(spec)
+}
+
+// -----------------------------------------------------------------------------
+// BRACE EXPANSION
+// -----------------------------------------------------------------------------
+
+/// `{início..fim}` ou `{início..fim..passo}` no token vira uma palavra por
+/// número, mantendo o que vem antes e depois (`img{1..3}.png`). `${...}` é
+/// variável e fica de fora; outros tokens devolvem `None`.
+pub fn brace_range(token: &str) -> Option<Vec<String>> {
+    let mut from = 0;
+    while let Some(offset) = token[from..].find('{') {
+        let open = from + offset;
+        from = open + 1;
+        if token[..open].ends_with('$') {
+            continue;
+        }
+        let len = token[open..].find('}')?;
+        let inner = &token[open + 1..open + len];
+        let parts: Vec<&str> = inner.split("..").collect();
+        let spec = match parts[..] {
+            [start, end] => build_spec(start, end, None),
+            [start, end, step] => build_spec(start, end, Some(step)),
+            _ => None,
+        };
+        if let Some(mut spec) = spec {
+            spec.width = padded_width(parts[0], parts[1]);
+            let (prefix, suffix) = (&token[..open], &token[open + len + 1..]);
+            return Some(spec.values().map(|n| format!("{}{}{}", prefix, spec.format(n), suffix)).collect());
+        }
+    }
+    None
+}
+
+// -----------------------------------------------------------------------------
+// BUILTIN
+// -----------------------------------------------------------------------------
+
+/// Escreve a sequência, um número por linha.
+pub fn write_range(spec: &RangeSpec, out: impl Write) -> io::Result<()> {
+    let mut out = BufWriter::new(out);
+    for n in spec.values() {
+        writeln!(out, "{}", spec.format(n))?;
+    }
+    out.flush()
+}
+
+/// Os argumentos de um comando `range ...`; se forem inválidos, mostra o erro
+/// e devolve o código de saída.
+pub fn range_spec(tokens: &[String]) -> Result<RangeSpec, i32> {
+    parse_range_args(&tokens[1..]).map_err(|e| {
+        eprintln!("range: {}", e);
+        eprintln!("{}", usage("range"));
+        2
+    })
+}
+
+/// Builtin `range [-w] [início] fim [passo]`.
+pub fn handle_range(tokens: &[String]) -> i32 {
+    match range_spec(tokens) {
+        // Quem lê pode parar antes do fim (`range 1 1000000 | head`)
+        Ok(spec) => {
+            let _ = write_range(&spec, io::stdout().lock());
+            0
+        }
+        Err(code) => code,
+    }
+}
+
+/// `range ...` no começo de uma pipeline: a entrada do próximo comando, escrita
+/// por uma thread.
+pub fn range_input(tokens: &[String]) -> Result<PipeReader, i32> {
+    let spec = range_spec(tokens)?;
+    let (reader, writer) = io::pipe().map_err(|e| {
+        eprintln!("range: {}", e);
+        1
+    })?;
+    thread::spawn(move || write_range(&spec, writer));
+    Ok(reader)
+}
@@ -0,0 +1,92 @@
+//! # Recent Module
+//!
+//! Arquivos recentes no autocomplete de argumentos: os citados nos últimos
+//! comandos do histórico e os modificados nas últimas horas vêm antes da lista
+//! alfabética (no máximo [`MAX_RECENT`] deles).
+//!
+//! ```text
+//! $ vim <TAB>          # notas.md acabou de ser editado, Cargo.toml foi citado
+//! Cargo.toml  notas.md  build.rs  Cargo.lock  README.md  src
+//! ```
+//!
+//! Liga e desliga com `recent_files` em `[completion]` (padrão: ligado).
+
+use std::time::{Duration, SystemTime};
+
+/// Candidatos recentes colocados na frente.
+pub const MAX_RECENT: usize = 5;
+
+/// Até quando uma modificação conta como recente.
+pub const RECENT_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Comandos do histórico olhados, do mais novo para trás.
+pub const HISTORY_SCAN: usize = 200;
+
+/// Um arquivo que pode completar a palavra.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileCandidate {
+    /// Texto que substitui a palavra (`src/main.rs`)
+    pub path: String,
+    pub modified: Option<SystemTime>,
+}
+
+/// Os argumentos dos comandos do histórico (do mais novo ao mais antigo), sem
+/// repetições e sem o `./` do começo.
+pub fn history_references<'a>(commands: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut refs: Vec<String> = Vec::new();
+    for command in commands.into_iter().take(HISTORY_SCAN) {
+        let words = shlex::split(command).unwrap_or_else(|| command.split_whitespace().map(String::from).collect());
+        for word in words.into_iter().skip(1) {
+            if word.starts_with('-') {
+                continue;
+            }
+            let word = word.strip_prefix("./").unwrap_or(&word).trim_end_matches('/').to_string();
+            if !word.is_empty() && !refs.contains(&word) {
+                refs.push(word);
+            }
+        }
+    }
+    refs
+}
+
+/// A ordem do autocomplete: primeiro os citados no histórico (na ordem das
+/// citações), depois os modificados dentro de [`RECENT_WINDOW`] (o mais novo
+/// primeiro), até [`MAX_RECENT`]; o resto em ordem alfabética.
+pub fn rank_candidates(mut candidates: Vec<FileCandidate>, references: &[String], now: SystemTime) -> Vec<String> {
+    let mut ranked: Vec<String> = Vec::new();
+    let trimmed = |path: &str| path.trim_end_matches('/').to_string();
+
+    for reference in references {
+        if ranked.len() >= MAX_RECENT {
+            break;
+        }
+        if let Some(pos) = candidates.iter().position(|c| trimmed(&c.path) == *reference) {
+            ranked.push(candidates.remove(pos).path);
+        }
+    }
+
+    let mut fresh: Vec<(SystemTime, usize)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, c)| {
+            let modified = c.modified?;
+            (now.duration_since(modified).unwrap_or_default() <= RECENT_WINDOW).then_some((modified, i))
+        })
+        .collect();
+    fresh.sort_by_key(|&(modified, _)| std::cmp::Reverse(modified));
+    let mut taken: Vec<usize> = fresh.into_iter().take(MAX_RECENT - ranked.len()).map(|(_, i)| i).collect();
+    ranked.extend(taken.iter().map(|&i| candidates[i].path.clone()));
+    taken.sort_unstable();
+    for i in taken.into_iter().rev() {
+        candidates.remove(i);
+    }
+
+    ranked.extend(alphabetical(candidates));
+    ranked
+}
+
+/// Os candidatos em ordem alfabética, sem diferenciar maiúsculas.
+pub fn alphabetical(mut candidates: Vec<FileCandidate>) -> Vec<String> {
+    candidates.sort_by_cached_key(|c| c.path.to_lowercase());
+    candidates.into_iter().map(|c| c.path).collect()
+}
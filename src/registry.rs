@@ -0,0 +1,1170 @@
+//! # Registry Module
+//!
+//! Registro estruturado dos builtins: nome, sinopse, descrição, flags e
+//! exemplos de cada comando interno.
+//!
+//! É a fonte única usada por `help <comando>`, pelo `type` e pelo autocomplete,
+//! então um builtin novo só precisa ser registrado aqui para aparecer nos três.
+//! Os comandos criados por plugins ficam à parte, no `plugin_registry`.
+//! O `bench` é oculto e fica de fora de propósito.
+
+use crate::i18n::{current_language, Lang};
+use crate::t;
+
+// -----------------------------------------------------------------------------
+// BUILTIN INFO
+// -----------------------------------------------------------------------------
+
+/// Documentação de um builtin.
+#[derive(Debug, Clone, Copy)]
+pub struct BuiltinInfo {
+    /// Nome do comando
+    pub name: &'static str,
+    /// Forma de uso (`cd [dir]`)
+    pub synopsis: &'static str,
+    /// O que o comando faz, em uma ou duas frases
+    pub description: &'static str,
+    /// Flags aceitas: (flag, explicação)
+    pub flags: &'static [(&'static str, &'static str)],
+    /// Exemplos de uso
+    pub examples: &'static [&'static str],
+}
+
+/// Os textos de um builtin em um idioma (os exemplos não mudam).
+#[derive(Debug, Clone, Copy)]
+pub struct BuiltinText {
+    /// Nome do comando
+    pub name: &'static str,
+    /// Forma de uso
+    pub synopsis: &'static str,
+    /// O que o comando faz
+    pub description: &'static str,
+    /// Flags aceitas: (flag, explicação)
+    pub flags: &'static [(&'static str, &'static str)],
+}
+
+impl BuiltinInfo {
+    /// Sinopse, descrição e flags no idioma `lang`. Um builtin sem tradução
+    /// fica com os textos em português.
+    pub fn text_in(&self, lang: Lang) -> BuiltinText {
+        let own = BuiltinText { name: self.name, synopsis: self.synopsis, description: self.description, flags: self.flags };
+        match lang {
+            Lang::Pt => own,
+            Lang::En => BUILTINS_EN.iter().find(|text| text.name == self.name).copied().unwrap_or(own),
+        }
+    }
+}
+
+/// Todos os builtins visíveis, na ordem do `help`.
+pub const BUILTINS: &[BuiltinInfo] = &[
+    BuiltinInfo {
+        name: "cd",
+        synopsis: "cd [dir | -]",
+        description: "Muda o diretório atual. Sem argumento vai para o HOME; `cd -` volta ao diretório anterior.",
+        flags: &[],
+        examples: &["cd ~/projetos", "cd -", "cd .."],
+    },
+    BuiltinInfo {
+        name: "pwd",
+        synopsis: "pwd",
+        description: "Exibe o diretório atual.",
+        flags: &[],
+        examples: &["pwd"],
+    },
+    BuiltinInfo {
+        name: "alias",
+        synopsis: "alias [nome='comando' | --export-bash]",
+        description: "Sem argumentos lista os aliases. Com `nome='comando'` cria um alias; `{1}`, `{2}`... no valor recebem os argumentos.",
+        flags: &[("--export-bash", "Imprime os aliases em sintaxe do bash (com placeholders viram funções)")],
+        examples: &["alias", "alias ll='ls -lah'", "alias gco='git checkout {1}'", "alias --export-bash > aliases.sh"],
+    },
+    BuiltinInfo {
+        name: "unalias",
+        synopsis: "unalias <nome>",
+        description: "Remove um alias.",
+        flags: &[],
+        examples: &["unalias ll"],
+    },
+    BuiltinInfo {
+        name: "abbr",
+        synopsis: "abbr [nome 'expansão' | -e nome]",
+        description: "Abreviações expandidas na própria linha ao digitar espaço (salvas em ~/.clios_abbr.json). Sem argumentos lista as abreviações.",
+        flags: &[("-e, --erase <nome>", "Remove a abreviação")],
+        examples: &["abbr gco 'git checkout'", "abbr -e gco"],
+    },
+    BuiltinInfo {
+        name: "export-compat",
+        synopsis: "export-compat",
+        description: "Imprime os aliases e as abreviações em sintaxe do bash, para manter o bash e a Clios em sincronia. Comandos Rhai são só listados. Também roda como `clios export-compat`.",
+        flags: &[],
+        examples: &["export-compat > ~/.clios_compat.sh", "clios export-compat >> ~/.bashrc"],
+    },
+    BuiltinInfo {
+        name: "export",
+        synopsis: "export VAR=VALOR",
+        description: "Define uma variável de ambiente (herdada pelos comandos executados).",
+        flags: &[],
+        examples: &["export EDITOR=vim", "export PATH=$PATH:~/bin"],
+    },
+    BuiltinInfo {
+        name: "unset",
+        synopsis: "unset <VARIAVEL...>",
+        description: "Remove variáveis de ambiente e arrays da shell.",
+        flags: &[],
+        examples: &["unset EDITOR", "unset files"],
+    },
+    BuiltinInfo {
+        name: "set",
+        synopsis: "set [-o|+o] [opção...] | set -x|+x",
+        description: "Liga (`-o`) ou desliga (`+o`) opções da shell. Sem nome, lista as opções; com `+o`, como comandos `set` para o ~/.cliosrc. Opções: `dryrun` (mostra os comandos expandidos sem rodar), `pipefail` (o código de uma pipeline é o do primeiro comando que falhou) e `xtrace` (mostra cada comando expandido antes de rodar).",
+        flags: &[("-o <opção>", "Liga a opção"), ("+o <opção>", "Desliga a opção"), ("-x / +x", "Liga / desliga o xtrace")],
+        examples: &["set -o pipefail", "set +o pipefail", "set -x", "set -o dryrun", "set -o"],
+    },
+    BuiltinInfo {
+        name: "env",
+        synopsis: "env [--json] [padrão]",
+        description: "Lista o ambiente em ordem alfabética, filtrando os nomes pelo padrão (trecho ou glob, sem diferenciar maiúsculas). `env VAR=x cmd` e `env programa` vão para o env do sistema.",
+        flags: &[("--json", "Saída como objeto JSON")],
+        examples: &["env", "env git", "env 'LC_*'", "env --json PATH"],
+    },
+    BuiltinInfo {
+        name: "history",
+        synopsis: "history [stats]",
+        description: "Exibe o histórico de comandos. `history stats` mostra estatísticas de uso.",
+        flags: &[],
+        examples: &["history", "history stats"],
+    },
+    BuiltinInfo {
+        name: "source",
+        synopsis: "source <arquivo.rhai>",
+        description: "Carrega um plugin Rhai; as funções dele passam a funcionar como comandos.",
+        flags: &[],
+        examples: &["source ~/.clios_plugins/utils.rhai"],
+    },
+    BuiltinInfo {
+        name: "load",
+        synopsis: "load <arquivo.rhai>",
+        description: "Sinônimo de `source`.",
+        flags: &[],
+        examples: &["load meu_plugin.rhai"],
+    },
+    BuiltinInfo {
+        name: "include",
+        synopsis: "include <arquivo...>",
+        description: "Roda arquivos no formato do ~/.cliosrc (aliases, export, abbr...), para dividir a configuração em módulos. Dentro de um arquivo rc, um caminho relativo parte do diretório dele. Um arquivo que já está sendo lido (ciclo) é pulado com um erro.",
+        flags: &[],
+        examples: &["include aliases.clios", "include ~/.clios.d/*.clios"],
+    },
+    BuiltinInfo {
+        name: "plugins",
+        synopsis: "plugins",
+        description: "Lista os comandos (register_command) e as funções dos plugins Rhai carregados.",
+        flags: &[],
+        examples: &["plugins"],
+    },
+    BuiltinInfo {
+        name: "plugin",
+        synopsis: "plugin test [nome]",
+        description: "Roda as funções `test_*` dos plugins Rhai (de `~/.clios_plugins` e os carregados), cada uma num motor isolado com `assert`, `assert_eq` e `assert_ne`. Sai com 1 se algum teste falhar.",
+        flags: &[],
+        examples: &["plugin test", "plugin test utils", "plugin test ./meu_plugin.rhai"],
+    },
+    BuiltinInfo {
+        name: "rhai",
+        synopsis: "rhai [código] | rhai debug <arquivo> [linha|função...]",
+        description: "Executa código Rhai inline. Sem argumentos entra no modo REPL. `rhai debug` roda um arquivo no debugger, com breakpoints e passo a passo (`help` no prompt `dbg>`).",
+        flags: &[],
+        examples: &["rhai 40 + 2", "rhai", "rhai debug deploy.rhai", "rhai debug deploy.rhai 12 check_host"],
+    },
+    BuiltinInfo {
+        name: "fg",
+        synopsis: "fg [%N|%+|%-|PID]",
+        description: "Traz um job parado ou em background para o foreground. Sem argumentos (ou com `%+`), traz o job atual: o último parado com Ctrl+Z ou, sem nenhum parado, o último mandado para o background. `%-` é o anterior a ele.",
+        flags: &[],
+        examples: &["fg", "fg %2", "fg %-", "fg 12345"],
+    },
+    BuiltinInfo {
+        name: "bg",
+        synopsis: "bg [%N|%+|%-|PID...]",
+        description: "Continua em background um job parado com Ctrl+Z, sem tomar o terminal. Sem argumentos, continua o último job parado.",
+        flags: &[],
+        examples: &["bg", "bg %2", "bg %1 %3"],
+    },
+    BuiltinInfo {
+        name: "wait",
+        synopsis: "wait [%N|PID...]",
+        description: "Espera jobs em background terminarem. Sem argumentos, espera todos os que estão rodando e sai com 0; com argumentos, sai com o código do último (127 se o job não existe).",
+        flags: &[],
+        examples: &["wait", "wait %1", "wait %1 %2"],
+    },
+    BuiltinInfo {
+        name: "disown",
+        synopsis: "disown [-a] [%N|%+|%-|PID...]",
+        description: "Tira jobs da lista: a shell não avisa quando terminam, não espera por eles e não manda SIGHUP ao sair, então eles continuam rodando depois dela. Sem argumentos, tira o job atual; um job parado é continuado.",
+        flags: &[("-a", "Todos os jobs")],
+        examples: &["disown", "disown %1", "disown -a"],
+    },
+    BuiltinInfo {
+        name: "jobs",
+        synopsis: "jobs [--output <%N|PID> | --watch]",
+        description: "Lista os jobs em background. Com `--output`, mostra a saída capturada do job (requer `capture_job_output`). Com `--watch`, mostra a tabela com CPU e memória de cada job, atualizada a cada segundo até uma tecla ser pressionada.",
+        flags: &[
+            ("-o, --output <%N|PID>", "Saída capturada do job"),
+            ("-w, --watch", "Monitor ao vivo de CPU/memória (qualquer tecla sai)"),
+        ],
+        examples: &["jobs", "jobs --output %1", "jobs --watch"],
+    },
+    BuiltinInfo {
+        name: "fix-terminal",
+        synopsis: "fix-terminal",
+        description: "Restaura o estado do terminal depois que um programa sai em modo raw.",
+        flags: &[],
+        examples: &["fix-terminal"],
+    },
+    BuiltinInfo {
+        name: "trap",
+        synopsis: "trap ['comando' EXIT | - EXIT]",
+        description: "Registra um comando para rodar quando a shell sair. Sem argumentos lista os traps.",
+        flags: &[],
+        examples: &["trap 'echo tchau' EXIT", "trap - EXIT"],
+    },
+    BuiltinInfo {
+        name: "exit",
+        synopsis: "exit [código]",
+        description: "Sai da shell com o código informado (padrão: o do último comando). Com jobs parados, o primeiro `exit` apenas avisa.",
+        flags: &[],
+        examples: &["exit", "exit 1"],
+    },
+    BuiltinInfo {
+        name: "session",
+        synopsis: "session [list | save <nome> | restore <nome> | delete <nome>]",
+        description: "Salva e restaura o contexto da shell (diretório, variáveis alteradas e aliases) em ~/.clios_sessions.",
+        flags: &[],
+        examples: &["session save api", "session restore api", "clios-shell --session api"],
+    },
+    BuiltinInfo {
+        name: "profile",
+        synopsis: "profile [list | use <nome> | off]",
+        description: "Troca o perfil da sessão ([profile.<nome>] do .clios.toml): prompt, aliases, env e plugins do perfil passam a valer. `off` volta à configuração base.",
+        flags: &[],
+        examples: &["profile use work", "profile off", "clios-shell --profile minimal"],
+    },
+    BuiltinInfo {
+        name: "demo",
+        synopsis: "demo [on [--offline] | off]",
+        description: "Modo de apresentação para gravações e pareamento: prompt mínimo sem usuário e máquina, linha em branco entre comandos e histórico pausado. `--offline` (ou block_network em [demo]) recusa comandos de rede; allow em [demo] limita os comandos aceitos.",
+        flags: &[("--offline", "Bloqueia curl, ssh, git push e outros comandos de rede")],
+        examples: &["demo on", "demo on --offline", "demo off", "clios-shell --demo"],
+    },
+    BuiltinInfo {
+        name: "trash",
+        synopsis: "trash <arquivos...>",
+        description: "Move arquivos e diretórios para a lixeira XDG (a mesma do ambiente gráfico).",
+        flags: &[],
+        examples: &["trash build.log", "trash target/"],
+    },
+    BuiltinInfo {
+        name: "restore",
+        synopsis: "restore [nome...]",
+        description: "Sem argumentos lista a lixeira; com nomes, devolve os itens ao caminho original.",
+        flags: &[],
+        examples: &["restore", "restore build.log"],
+    },
+    BuiltinInfo {
+        name: "watch",
+        synopsis: "watch [-n segundos] [--on-change <glob>] comando...",
+        description: "Reexecuta um comando a cada intervalo, redesenhando a tela. Ctrl+C encerra.",
+        flags: &[
+            ("-n, --interval <s>", "Intervalo entre execuções (padrão: 2s)"),
+            ("--on-change <glob>", "Executa só quando algum arquivo do glob muda"),
+        ],
+        examples: &["watch -n 1 date", "watch --on-change 'src/*.rs' cargo check"],
+    },
+    BuiltinInfo {
+        name: "repeat",
+        synopsis: "repeat <n> [--delay s] comando...",
+        description: "Executa o comando n vezes. O código de saída é o da última execução.",
+        flags: &[
+            ("-d, --delay <s>", "Espera entre execuções"),
+            ("--until-success", "Para na primeira execução bem-sucedida"),
+            ("--until-failure", "Para na primeira falha"),
+        ],
+        examples: &["repeat 5 cargo test", "repeat 3 --delay 1 curl -I localhost"],
+    },
+    BuiltinInfo {
+        name: "loop",
+        synopsis: "loop [--until-success | --until-failure] [--delay s] [--max n] comando...",
+        description: "Repete o comando até a condição de parada (ou para sempre, até Ctrl+C).",
+        flags: &[
+            ("--until-success", "Para quando o comando der certo"),
+            ("--until-failure", "Para quando o comando falhar"),
+            ("-d, --delay <s>", "Espera entre execuções"),
+            ("-m, --max <n>", "Número máximo de tentativas"),
+        ],
+        examples: &["loop --until-success --delay 2 ./flaky.sh", "loop --until-failure --max 100 ./stress.sh"],
+    },
+    BuiltinInfo {
+        name: "run",
+        synopsis: "run <tarefa> [args...] | run --list",
+        description: "Executa uma tarefa da seção [tasks] do clios.toml do projeto (procurado do diretório atual para cima), na raiz do projeto. Sem argumentos lista as tarefas.",
+        flags: &[("-l, --list", "Lista as tarefas do projeto")],
+        examples: &["run build", "run test -- --nocapture", "run --list"],
+    },
+    BuiltinInfo {
+        name: "parallel",
+        synopsis: "parallel [-j n] [-k] [-a arquivo] [--] comando [{}] [::: args...]",
+        description: "Executa o comando uma vez por argumento, com vários processos ao mesmo tempo. `{}` recebe o argumento.",
+        flags: &[
+            ("-j, --jobs <n>", "Processos simultâneos (padrão: nº de CPUs)"),
+            ("-k, --keep-order", "Imprime a saída na ordem dos argumentos"),
+            ("-a, --arg-file <arq>", "Um argumento por linha do arquivo"),
+        ],
+        examples: &["parallel -j4 -- gzip ::: a.log b.log", "parallel -a urls.txt wget"],
+    },
+    BuiltinInfo {
+        name: "each",
+        synopsis: "comando | each [-P n] 'cmd {}'",
+        description: "Executa um comando por linha da entrada; `{}` recebe a linha já com aspas.",
+        flags: &[("-P <n>", "Executa n comandos em paralelo")],
+        examples: &["find . -name '*.log' | each 'gzip {}'", "cat hosts.txt | each -P 4 'ping -c1 {}'"],
+    },
+    BuiltinInfo {
+        name: "range",
+        synopsis: "range [-w] [início] fim [passo]",
+        description: "Números de `início` (padrão: 1) até `fim`, de `passo` em `passo`, um por linha, sem o `seq` externo. Conta para trás quando `início` é maior que `fim`. Pode começar uma pipeline (`range 1 10 | each ...`); `{1..10..2}` numa palavra usa o mesmo código.",
+        flags: &[("-w", "Completa com zeros até a largura do maior número")],
+        examples: &["range 5", "range 1 100 5 | each 'curl -s host/p/{}'", "range -w 0 100 10", "echo img{1..3}.png"],
+    },
+    BuiltinInfo {
+        name: "clean-env",
+        synopsis: "clean-env [-k VAR]... comando...",
+        description: "Executa o comando com um ambiente mínimo (como `env -i`): só as variáveis de `[clean_env] keep` (padrão: PATH, HOME, USER, LOGNAME, SHELL, TERM, LANG).",
+        flags: &[("-k, --keep <VAR>", "Mantém mais uma variável")],
+        examples: &["clean-env cargo build", "clean-env -k RUST_LOG ./app"],
+    },
+    BuiltinInfo {
+        name: "out",
+        synopsis: "out [-l] [n] [comando...]",
+        description: "Saída guardada dos últimos comandos (com `capture_output` em [behavior]). Com um comando, usa a saída como entrada dele. A última saída também está em `$__` e o Alt+O insere na linha.",
+        flags: &[("-l", "Lista as saídas guardadas"), ("<n>", "Saída do n-ésimo comando anterior (1 = último)")],
+        examples: &["out", "out grep error", "out 2 wc -l"],
+    },
+    BuiltinInfo {
+        name: "mkfifo",
+        synopsis: "mkfifo [-m modo] <caminho...>",
+        description: "Cria named pipes (FIFOs). Para trocar dados entre sessões sem escolher um caminho, use `pipe`.",
+        flags: &[("-m <modo>", "Permissões em octal (padrão: 666 menos a umask)")],
+        examples: &["mkfifo /tmp/fila", "mkfifo -m 600 /tmp/privada"],
+    },
+    BuiltinInfo {
+        name: "pipe",
+        synopsis: "pipe [list | send <nome> [cmd...] | recv <nome> [cmd...] | path <nome> | rm <nome...>]",
+        description: "Named pipes guardadas em ~/.local/share/clios/pipes, para duas sessões trocarem dados. `send` escreve a entrada (ou a saída do comando) na pipe; `recv` lê (ou entrega ao comando). Cada lado espera o outro conectar.",
+        flags: &[],
+        examples: &["make 2>&1 | pipe send build", "pipe recv build", "pipe recv build grep error", "pipe send logs tail -f app.log"],
+    },
+    BuiltinInfo {
+        name: "remote",
+        synopsis: "remote [list | add <nome> <user@host> | rm <nome> | default [nome | -] | run [hosts] [cmd...] | copy <orig...> <dest>]",
+        description: "Execução remota pelo ssh/scp do sistema, com favoritos em ~/.clios_remotes.json. `hosts` é um nome, uma lista (`web1,web2`) ou `all`; com vários hosts os comandos rodam ao mesmo tempo, com a saída prefixada pelo nome. Sem hosts, `run` usa o padrão (mostrado no prompt). No `copy`, `nome:caminho` aponta para o host e `:caminho` para o padrão.",
+        flags: &[],
+        examples: &["remote add prod deploy@10.0.0.5", "remote run prod 'systemctl status app'", "remote run all uptime", "remote copy -r dist/ web1,web2:/srv/app/", "remote default prod"],
+    },
+    BuiltinInfo {
+        name: "inside",
+        synopsis: "inside [--default nome|-] [container] [comando...]",
+        description: "Executa um comando dentro de um container (`docker exec -it`, ou `podman`); sem comando abre um `sh`. Com um container padrão (`[container] default` ou `--default`) o nome pode ser omitido, e o prompt mostra o container. O TAB completa os nomes do `docker ps`.",
+        flags: &[("--default <nome>", "Container padrão desta sessão (`-` remove; sem nome, mostra)")],
+        examples: &["inside web", "inside web rails console", "inside --default db", "inside psql -U postgres"],
+    },
+    BuiltinInfo {
+        name: "gstatus",
+        synopsis: "gstatus [--json]",
+        description: "Estado do repositório git em uma tabela: branch, upstream, commits à frente/atrás e um arquivo por linha (no índice em verde, na árvore de trabalho em vermelho).",
+        flags: &[("--json", "Saída em JSON")],
+        examples: &["gstatus", "gstatus --json"],
+    },
+    BuiltinInfo {
+        name: "gbranches",
+        synopsis: "gbranches [--json]",
+        description: "Branches locais, da mais recente para a mais antiga, com upstream, commits à frente/atrás, data e assunto do último commit.",
+        flags: &[("--json", "Saída em JSON")],
+        examples: &["gbranches", "gbranches --json"],
+    },
+    BuiltinInfo {
+        name: "glog",
+        synopsis: "glog [-n N] [--json]",
+        description: "Últimos commits em uma tabela alinhada: hash, data, autor, refs e assunto.",
+        flags: &[("-n <N>", "Quantidade de commits (padrão: 15)"), ("--json", "Saída em JSON")],
+        examples: &["glog", "glog -n 50", "glog --json"],
+    },
+    BuiltinInfo {
+        name: "open",
+        synopsis: "open <caminho|url...>",
+        description: "Abre arquivos, diretórios e URLs no programa padrão (`xdg-open` no Linux, `open` no macOS, `start` no Windows). Endereços sem `https://` também funcionam.",
+        flags: &[],
+        examples: &["open relatorio.pdf", "open .", "open github.com/Pedr0bert/clios", "open localhost:3000"],
+    },
+    BuiltinInfo {
+        name: "calc",
+        synopsis: "calc [-x | -b | -o] <expressão>",
+        description: "Calculadora: `+ - * / % ^`, parênteses, `0x`/`0b`/`0o`, funções (`sqrt`, `round`, `min`, `max`...) e variáveis de ambiente. Inteiros ficam inteiros enquanto a conta for exata. `N%` é N/100, e somar ou subtrair uma porcentagem é relativo ao valor (`80 + 10%` = 88). Não precisa de aspas: `*` não vira glob.",
+        flags: &[("-x, --hex", "Resultado em hexadecimal"), ("-b, --bin", "Resultado em binário"), ("-o, --oct", "Resultado em octal")],
+        examples: &["= 2*(3+4)/5", "= 0xff + 1", "calc -b 10", "= 1500 * 12%", "echo $(= 2^10)"],
+    },
+    BuiltinInfo {
+        name: "=",
+        synopsis: "= <expressão>",
+        description: "Sinônimo de `calc`.",
+        flags: &[],
+        examples: &["= 2*(3+4)/5", "= -x 255"],
+    },
+    BuiltinInfo {
+        name: "when",
+        synopsis: "when [--epoch | --iso | -f formato] [instante] [+-deslocamento...] | when --diff <de> [até]",
+        description: "Datas e horas iguais em qualquer sistema (sem as diferenças do `date` GNU/BSD). O instante pode ser `now`, `today`, `tomorrow`, um epoch (segundos ou ms), `2024-05-01 [14:30]` ou `09:00`; deslocamentos usam s, m, h, d, w, mo e y. Mostra local, UTC, epoch e a distância para agora.",
+        flags: &[
+            ("-e, --epoch", "Só o epoch em segundos"),
+            ("-i, --iso", "Só a data em RFC 3339"),
+            ("-f, --format <fmt>", "Formato do strftime (`%d/%m/%Y`)"),
+            ("--diff <de> [até]", "Duração entre dois instantes (sem `até`, até agora)"),
+        ],
+        examples: &["when now +3d", "when 1700000000", "when --diff 09:00 17:30", "when 2024-12-25 -1w", "when --epoch tomorrow"],
+    },
+    BuiltinInfo {
+        name: "path",
+        synopsis: "path [list | add [-p] <dir...> | rm <dir...> | dedupe] [--save]",
+        description: "Mostra o $PATH uma entrada por linha (marcando as repetidas e as que não existem) e edita sem montar a string à mão. `add` não repete uma entrada que já existe; `rm` remove todas as ocorrências; `dedupe` mantém a primeira de cada.",
+        flags: &[
+            ("-p, --prepend", "No `add`, coloca no começo (move a entrada se já existir)"),
+            ("-s, --save", "Grava a mudança no ~/.cliosrc, para as próximas sessões"),
+        ],
+        examples: &["path", "path add ~/.cargo/bin", "path add -p ./node_modules/.bin", "path rm /opt/old/bin --save", "path dedupe"],
+    },
+    BuiltinInfo {
+        name: "extract",
+        synopsis: "extract <arquivo...> [-C dir]",
+        description: "Extrai .tar, .tar.gz/.tgz, .tar.xz, .tar.zst, .zip e arquivos únicos .gz/.xz/.zst, detectando o formato pelo conteúdo. Não depende de `tar`/`unzip` instalados; mostra o progresso no terminal.",
+        flags: &[("-C, --dir <dir>", "Diretório de destino (padrão: o atual)")],
+        examples: &["extract release.tar.gz", "extract dados.zip -C /tmp/dados", "extract dump.sql.zst"],
+    },
+    BuiltinInfo {
+        name: "compress",
+        synopsis: "compress [-f] <origem...> <saída>",
+        description: "Compacta arquivos e diretórios; o formato vem da extensão da saída (.tar, .tar.gz, .tar.xz, .tar.zst, .zip, ou .gz/.xz/.zst para um único arquivo). Symlinks são guardados como links.",
+        flags: &[("-f, --force", "Sobrescreve a saída se ela existir")],
+        examples: &["compress src/ docs/ projeto.tar.zst", "compress dist/ site.zip", "compress app.log app.log.gz"],
+    },
+    BuiltinInfo {
+        name: "hashsum",
+        synopsis: "hashsum [-a sha256|sha512|md5|blake3] [--tag] [arquivos...] | hashsum -c <lista>",
+        description: "Checksums sem depender das variantes do coreutils. A saída segue o formato do `sha256sum` (`hash  nome`); `-c` confere listas nesse formato ou no BSD (`SHA256 (nome) = hash`), detectando o algoritmo pelo tamanho do hash.",
+        flags: &[
+            ("-a, --algorithm <alg>", "sha256 (padrão), sha512, md5 ou blake3"),
+            ("-c, --check <lista>", "Confere os arquivos da lista"),
+            ("-q, --quiet", "No -c, mostra só os que falharam"),
+            ("--tag", "Saída no formato BSD"),
+        ],
+        examples: &["hashsum ubuntu.iso", "hashsum -a blake3 *.tar.gz", "hashsum -c SHA256SUMS"],
+    },
+    BuiltinInfo {
+        name: "fetch",
+        synopsis: "fetch [-X método] [-H 'Nome: valor']... [-d dados|@arquivo] [-o arquivo] [-i] <url>",
+        description: "Requisição HTTP rápida. JSON sai indentado e colorido, texto sai como veio e respostas binárias são gravadas no diretório atual com linha de progresso. URLs sem esquema ganham `https://` (`http://` para localhost). Status fora de 2xx retorna 1.",
+        flags: &[
+            ("-X, --request <método>", "Método HTTP (padrão GET, ou POST com -d)"),
+            ("-H, --header <'Nome: valor'>", "Cabeçalho extra (pode repetir)"),
+            ("-d, --data <dados|@arquivo>", "Corpo da requisição (@- lê o STDIN)"),
+            ("-o, --output <arquivo>", "Grava o corpo no arquivo"),
+            ("-i, --include", "Mostra status e cabeçalhos da resposta"),
+        ],
+        examples: &[
+            "fetch api.github.com/repos/rust-lang/rust",
+            "fetch -X POST -d @body.json -H \"Authorization: Bearer $TOKEN\" api.exemplo.com/itens",
+            "fetch localhost:8080/health -i",
+            "fetch https://exemplo.com/imagem.iso",
+        ],
+    },
+    BuiltinInfo {
+        name: "sysinfo",
+        synopsis: "sysinfo",
+        description: "Painel com sistema, kernel, uptime, uso de CPU, memória e swap, o disco do diretório atual e o load average. Com `show_load` em `[prompt]` (padrão), o prompt mostra a carga quando ela passa do número de núcleos.",
+        flags: &[],
+        examples: &["sysinfo"],
+    },
+    BuiltinInfo {
+        name: "procs",
+        synopsis: "procs [-s cpu|mem|pid|name] [-r] [-n N] [--kill [-9]] [padrão]",
+        description: "Lista processos (PID, usuário, CPU, memória e comando) sem depender do `ps`. O padrão filtra pelo nome ou pela linha de comando (um número casa com o PID). Com `--kill`, os processos da lista são escolhidos num menu (espaço marca, Enter confirma) e recebem SIGTERM.",
+        flags: &[
+            ("-s, --sort <campo>", "cpu (padrão), mem, pid ou name"),
+            ("-r, --reverse", "Inverte a ordem"),
+            ("-n <N>", "Mostra só os N primeiros"),
+            ("-k, --kill", "Escolhe processos para encerrar"),
+            ("-9", "Com --kill, usa SIGKILL"),
+        ],
+        examples: &["procs", "procs node", "procs -s mem -n 10", "procs --kill chrome", "procs --kill -9 1234"],
+    },
+    BuiltinInfo {
+        name: "list",
+        synopsis: "list [-a] [-n N] [dir]",
+        description: "Conteúdo do diretório (padrão: o atual) em colunas, sem o `ls` externo: diretórios primeiro, em azul e com `/`; executáveis com `*` e links com `@`. Fora de um terminal, um nome por linha. É o que o `auto_ls` de `[behavior]` mostra depois de um `cd`.",
+        flags: &[
+            ("-a, --all", "Inclui as entradas ocultas"),
+            ("-n <N>", "Mostra só as N primeiras"),
+        ],
+        examples: &["list", "list -a ~", "list -n 10 /etc"],
+    },
+    BuiltinInfo {
+        name: "usage",
+        synopsis: "usage [-n N | -a] [dir]",
+        description: "Espaço em disco de cada entrada do diretório (padrão: o atual), calculado em paralelo e mostrado do maior para o menor, com barras proporcionais. Conta os blocos ocupados, como o `du`, sem sair do sistema de arquivos nem seguir symlinks.",
+        flags: &[
+            ("-n <N>", "Mostra as N maiores (padrão 20 no terminal)"),
+            ("-a, --all", "Mostra todas as entradas"),
+        ],
+        examples: &["usage", "usage ~/Downloads", "usage -n 5 /var"],
+    },
+    BuiltinInfo {
+        name: "ports",
+        synopsis: "ports [-t | -u] [porta]",
+        description: "Sockets escutando (TCP) ou abertos (UDP) com o processo dono, lidos de `/proc/net` (sem `netstat`/`ss`). Processos de outros usuários só aparecem para o root.",
+        flags: &[
+            ("-t, --tcp", "Só TCP"),
+            ("-u, --udp", "Só UDP"),
+        ],
+        examples: &["ports", "ports 8080", "ports -t"],
+    },
+    BuiltinInfo {
+        name: "myip",
+        synopsis: "myip [-l | -p]",
+        description: "Endereços das interfaces locais (sem loopback) e o IP público, consultado por HTTP.",
+        flags: &[
+            ("-l, --local", "Só os endereços locais"),
+            ("-p, --public", "Só o IP público, numa linha (para `$(myip -p)`)"),
+        ],
+        examples: &["myip", "myip -l", "echo $(myip -p)"],
+    },
+    BuiltinInfo {
+        name: "ping-lite",
+        synopsis: "ping-lite [-p porta] [-c N | -t] host[:porta]",
+        description: "Mede o tempo de conexão TCP com o host (porta 443 por padrão). Não usa ICMP, então funciona sem root e onde o ping é bloqueado; uma porta fechada ainda conta como resposta.",
+        flags: &[
+            ("-p, --port <porta>", "Porta testada (padrão 443)"),
+            ("-c, --count <N>", "Número de tentativas (padrão 4)"),
+            ("-t", "Tenta até o Ctrl+C"),
+        ],
+        examples: &["ping-lite github.com", "ping-lite -p 22 servidor", "ping-lite localhost:5432 -c 1"],
+    },
+    BuiltinInfo {
+        name: "secret",
+        synopsis: "secret set|get|rm <nome> | secret list | secret lock",
+        description: "Cofre de tokens e chaves de API cifrado com age (senha + scrypt) em ~/.clios_secrets.age. A senha é pedida uma vez por sessão; `$(secret get NOME)` usa o valor sem deixá-lo no histórico nem no .cliosrc.",
+        flags: &[
+            ("set <nome>", "Guarda um valor (digitado sem eco, ou lido do STDIN)"),
+            ("get <nome>", "Imprime o valor"),
+            ("rm <nome>", "Remove um segredo"),
+            ("list", "Nomes guardados"),
+            ("lock", "Esquece a senha da sessão"),
+        ],
+        examples: &["secret set GITHUB_TOKEN", "export GITHUB_TOKEN=$(secret get GITHUB_TOKEN)", "secret list"],
+    },
+    BuiltinInfo {
+        name: "keys",
+        synopsis: "keys [tecla | texto]",
+        description: "Lista os atalhos ativos do editor de linha, com a ação e a origem: padrão do rustyline (emacs ou vi), da shell ou da seção [keys] do .clios.toml. Com um argumento, só os atalhos daquela tecla ou cuja descrição contém o texto.",
+        flags: &[],
+        examples: &["keys", "keys ctrl-z", "keys colar"],
+    },
+    BuiltinInfo {
+        name: "??",
+        synopsis: "?? <pergunta>",
+        description: "Pede um comando ao sugestor da seção [suggest] do .clios.toml (um programa externo ou uma função Rhai) e o coloca no editor para revisão; nada é executado. Fora do modo interativo, a sugestão vai para o STDOUT.",
+        flags: &[],
+        examples: &["?? arquivos grandes modificados hoje", "?? desfazer o último commit sem perder as mudanças"],
+    },
+    BuiltinInfo {
+        name: "tldr",
+        synopsis: "tldr [-u] <comando>",
+        description: "Exemplos práticos de um comando (páginas do tldr-pages, guardadas em ~/.clios_tldr). No editor, Alt+H abre o man/tldr do comando sendo digitado.",
+        flags: &[("-u, --update", "Baixa a página de novo, ignorando o cache")],
+        examples: &["tldr tar", "tldr -u git-rebase"],
+    },
+    BuiltinInfo {
+        name: "type",
+        synopsis: "type <comando>",
+        description: "Mostra se o comando é um alias, um builtin ou um executável do PATH.",
+        flags: &[],
+        examples: &["type ll", "type cd", "type git"],
+    },
+    BuiltinInfo {
+        name: "hash",
+        synopsis: "hash [-r] [comando...]",
+        description: "Tabela de localização dos comandos do PATH, preenchida na primeira execução. Sem argumentos lista os caminhos guardados e as estatísticas; com nomes, procura e guarda.",
+        flags: &[("-r", "Esvazia a tabela")],
+        examples: &["hash", "hash -r", "hash git cargo"],
+    },
+    BuiltinInfo {
+        name: "help",
+        synopsis: "help [comando | keys]",
+        description: "Sem argumentos mostra o resumo dos builtins; com um nome, a página de ajuda do builtin; `help keys` é o mesmo que `keys`.",
+        flags: &[],
+        examples: &["help", "help cd", "help keys"],
+    },
+    BuiltinInfo {
+        name: "version",
+        synopsis: "version",
+        description: "Exibe a versão da shell.",
+        flags: &[],
+        examples: &["version"],
+    },
+];
+
+/// Os textos em inglês dos builtins, na ordem de [`BUILTINS`]. Os exemplos
+/// são os mesmos nos dois idiomas.
+pub const BUILTINS_EN: &[BuiltinText] = &[
+    BuiltinText {
+        name: "cd",
+        synopsis: "cd [dir | -]",
+        description: "Changes the current directory. Without an argument goes to HOME; `cd -` goes back to the previous directory.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "pwd",
+        synopsis: "pwd",
+        description: "Prints the current directory.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "alias",
+        synopsis: "alias [name='command' | --export-bash]",
+        description: "Without arguments lists the aliases. With `name='command'` creates an alias; `{1}`, `{2}`... in the value receive the arguments.",
+        flags: &[("--export-bash", "Prints the aliases in bash syntax (those with placeholders become functions)")],
+    },
+    BuiltinText {
+        name: "unalias",
+        synopsis: "unalias <name>",
+        description: "Removes an alias.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "abbr",
+        synopsis: "abbr [name 'expansion' | -e name]",
+        description: "Abbreviations expanded in the line itself when typing space (saved in ~/.clios_abbr.json). Without arguments lists the abbreviations.",
+        flags: &[("-e, --erase <name>", "Removes the abbreviation")],
+    },
+    BuiltinText {
+        name: "export-compat",
+        synopsis: "export-compat",
+        description: "Prints the aliases and abbreviations in bash syntax, to keep bash and Clios in sync. Rhai commands are only listed. Also runs as `clios export-compat`.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "export",
+        synopsis: "export VAR=VALUE",
+        description: "Sets an environment variable (inherited by the commands that run).",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "unset",
+        synopsis: "unset <VARIABLE...>",
+        description: "Removes environment variables and shell arrays.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "set",
+        synopsis: "set [-o|+o] [option...] | set -x|+x",
+        description: "Turns shell options on (`-o`) or off (`+o`). Without a name, lists the options; with `+o`, as `set` commands for ~/.cliosrc. Options: `dryrun` (prints the expanded commands without running them), `pipefail` (a pipeline's status is the one of the first command that failed) and `xtrace` (prints each expanded command before running it).",
+        flags: &[("-o <option>", "Turns the option on"), ("+o <option>", "Turns the option off"), ("-x / +x", "Turns xtrace on / off")],
+    },
+    BuiltinText {
+        name: "env",
+        synopsis: "env [--json] [pattern]",
+        description: "Lists the environment in alphabetical order, filtering the names by the pattern (substring or glob, case-insensitive). `env VAR=x cmd` and `env program` go to the system env.",
+        flags: &[("--json", "Output as a JSON object")],
+    },
+    BuiltinText {
+        name: "history",
+        synopsis: "history [stats]",
+        description: "Shows the command history. `history stats` shows usage statistics.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "source",
+        synopsis: "source <file.rhai>",
+        description: "Loads a Rhai plugin; its functions work as commands.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "load",
+        synopsis: "load <file.rhai>",
+        description: "Same as `source`.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "include",
+        synopsis: "include <file...>",
+        description: "Runs files in the ~/.cliosrc format (aliases, export, abbr...), to split the configuration into modules. Inside an rc file, a relative path starts from its directory. A file that is already being read (a cycle) is skipped with an error.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "plugins",
+        synopsis: "plugins",
+        description: "Lists the commands (register_command) and functions of the loaded Rhai plugins.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "plugin",
+        synopsis: "plugin test [name]",
+        description: "Runs the `test_*` functions of the Rhai plugins (from `~/.clios_plugins` and the loaded ones), each in an isolated engine with `assert`, `assert_eq` and `assert_ne`. Exits with 1 if any test fails.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "rhai",
+        synopsis: "rhai [code] | rhai debug <file> [line|function...]",
+        description: "Runs inline Rhai code. Without arguments enters the REPL. `rhai debug` runs a file in the debugger, with breakpoints and stepping (`help` at the `dbg>` prompt).",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "fg",
+        synopsis: "fg [%N|%+|%-|PID]",
+        description: "Brings a stopped or background job to the foreground. Without arguments (or with `%+`), brings the current job: the last one stopped with Ctrl+Z or, if none is stopped, the last one sent to the background. `%-` is the one before it.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "bg",
+        synopsis: "bg [%N|%+|%-|PID...]",
+        description: "Continues a job stopped with Ctrl+Z in the background, without taking the terminal. Without arguments, continues the last stopped job.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "wait",
+        synopsis: "wait [%N|PID...]",
+        description: "Waits for background jobs to finish. Without arguments, waits for all running jobs and exits with 0; with arguments, exits with the status of the last one (127 if the job does not exist).",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "disown",
+        synopsis: "disown [-a] [%N|%+|%-|PID...]",
+        description: "Removes jobs from the list: the shell does not report when they finish, does not wait for them and does not send SIGHUP on exit, so they keep running after it. Without arguments, removes the current job; a stopped job is continued.",
+        flags: &[("-a", "All jobs")],
+    },
+    BuiltinText {
+        name: "jobs",
+        synopsis: "jobs [--output <%N|PID> | --watch]",
+        description: "Lists the background jobs. With `--output`, shows the job's captured output (requires `capture_job_output`). With `--watch`, shows a table with the CPU and memory of each job, updated every second until a key is pressed.",
+        flags: &[
+            ("-o, --output <%N|PID>", "Captured output of the job"),
+            ("-w, --watch", "Live CPU/memory monitor (any key quits)"),
+        ],
+    },
+    BuiltinText {
+        name: "fix-terminal",
+        synopsis: "fix-terminal",
+        description: "Restores the terminal state after a program exits in raw mode.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "trap",
+        synopsis: "trap ['command' EXIT | - EXIT]",
+        description: "Registers a command to run when the shell exits. Without arguments lists the traps.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "exit",
+        synopsis: "exit [code]",
+        description: "Exits the shell with the given code (default: the last command's). With stopped jobs, the first `exit` only warns.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "session",
+        synopsis: "session [list | save <name> | restore <name> | delete <name>]",
+        description: "Saves and restores the shell context (directory, changed variables and aliases) in ~/.clios_sessions.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "profile",
+        synopsis: "profile [list | use <name> | off]",
+        description: "Switches the session profile ([profile.<name>] in .clios.toml): the profile's prompt, aliases, env and plugins take effect. `off` goes back to the base configuration.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "demo",
+        synopsis: "demo [on [--offline] | off]",
+        description: "Presentation mode for recordings and pairing: minimal prompt without user and host, a blank line between commands and history paused. `--offline` (or block_network in [demo]) refuses network commands; allow in [demo] limits the accepted commands.",
+        flags: &[("--offline", "Blocks curl, ssh, git push and other network commands")],
+    },
+    BuiltinText {
+        name: "trash",
+        synopsis: "trash <files...>",
+        description: "Moves files and directories to the XDG trash (the same one the desktop uses).",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "restore",
+        synopsis: "restore [name...]",
+        description: "Without arguments lists the trash; with names, puts the items back in their original path.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "watch",
+        synopsis: "watch [-n seconds] [--on-change <glob>] command...",
+        description: "Re-runs a command at every interval, redrawing the screen. Ctrl+C stops.",
+        flags: &[
+            ("-n, --interval <s>", "Interval between runs (default: 2s)"),
+            ("--on-change <glob>", "Runs only when a file matching the glob changes"),
+        ],
+    },
+    BuiltinText {
+        name: "repeat",
+        synopsis: "repeat <n> [--delay s] command...",
+        description: "Runs the command n times. The exit status is the one of the last run.",
+        flags: &[
+            ("-d, --delay <s>", "Wait between runs"),
+            ("--until-success", "Stops at the first successful run"),
+            ("--until-failure", "Stops at the first failure"),
+        ],
+    },
+    BuiltinText {
+        name: "loop",
+        synopsis: "loop [--until-success | --until-failure] [--delay s] [--max n] command...",
+        description: "Repeats the command until the stop condition (or forever, until Ctrl+C).",
+        flags: &[
+            ("--until-success", "Stops when the command succeeds"),
+            ("--until-failure", "Stops when the command fails"),
+            ("-d, --delay <s>", "Wait between runs"),
+            ("-m, --max <n>", "Maximum number of attempts"),
+        ],
+    },
+    BuiltinText {
+        name: "run",
+        synopsis: "run <task> [args...] | run --list",
+        description: "Runs a task from the [tasks] section of the project's clios.toml (searched from the current directory upwards), at the project root. Without arguments lists the tasks.",
+        flags: &[("-l, --list", "Lists the project's tasks")],
+    },
+    BuiltinText {
+        name: "parallel",
+        synopsis: "parallel [-j n] [-k] [-a file] [--] command [{}] [::: args...]",
+        description: "Runs the command once per argument, with several processes at the same time. `{}` receives the argument.",
+        flags: &[
+            ("-j, --jobs <n>", "Simultaneous processes (default: number of CPUs)"),
+            ("-k, --keep-order", "Prints the output in argument order"),
+            ("-a, --arg-file <file>", "One argument per line of the file"),
+        ],
+    },
+    BuiltinText {
+        name: "each",
+        synopsis: "command | each [-P n] 'cmd {}'",
+        description: "Runs a command per input line; `{}` receives the line, already quoted.",
+        flags: &[("-P <n>", "Runs n commands in parallel")],
+    },
+    BuiltinText {
+        name: "range",
+        synopsis: "range [-w] [start] end [step]",
+        description: "Numbers from `start` (default: 1) to `end`, in steps of `step`, one per line, without the external `seq`. Counts down when `start` is greater than `end`. Can start a pipeline (`range 1 10 | each ...`); `{1..10..2}` in a word uses the same code.",
+        flags: &[("-w", "Pads with zeros to the width of the largest number")],
+    },
+    BuiltinText {
+        name: "clean-env",
+        synopsis: "clean-env [-k VAR]... command...",
+        description: "Runs the command with a minimal environment (like `env -i`): only the variables in `[clean_env] keep` (default: PATH, HOME, USER, LOGNAME, SHELL, TERM, LANG).",
+        flags: &[("-k, --keep <VAR>", "Keeps one more variable")],
+    },
+    BuiltinText {
+        name: "out",
+        synopsis: "out [-l] [n] [command...]",
+        description: "Saved output of the last commands (with `capture_output` in [behavior]). With a command, uses the output as its input. The last output is also in `$__` and Alt+O inserts it in the line.",
+        flags: &[("-l", "Lists the saved outputs"), ("<n>", "Output of the n-th previous command (1 = last)")],
+    },
+    BuiltinText {
+        name: "mkfifo",
+        synopsis: "mkfifo [-m mode] <path...>",
+        description: "Creates named pipes (FIFOs). To exchange data between sessions without choosing a path, use `pipe`.",
+        flags: &[("-m <mode>", "Octal permissions (default: 666 minus the umask)")],
+    },
+    BuiltinText {
+        name: "pipe",
+        synopsis: "pipe [list | send <name> [cmd...] | recv <name> [cmd...] | path <name> | rm <name...>]",
+        description: "Named pipes kept in ~/.local/share/clios/pipes, for two sessions to exchange data. `send` writes the input (or the command's output) to the pipe; `recv` reads it (or hands it to the command). Each side waits for the other to connect.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "remote",
+        synopsis: "remote [list | add <name> <user@host> | rm <name> | default [name | -] | run [hosts] [cmd...] | copy <src...> <dest>]",
+        description: "Remote execution through the system's ssh/scp, with favorites in ~/.clios_remotes.json. `hosts` is a name, a list (`web1,web2`) or `all`; with several hosts the commands run at the same time, with the output prefixed by the name. Without hosts, `run` uses the default (shown in the prompt). In `copy`, `name:path` points to the host and `:path` to the default.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "inside",
+        synopsis: "inside [--default name|-] [container] [command...]",
+        description: "Runs a command inside a container (`docker exec -it`, or `podman`); without a command opens an `sh`. With a default container (`[container] default` or `--default`) the name can be omitted, and the prompt shows the container. TAB completes the names from `docker ps`.",
+        flags: &[("--default <name>", "Default container for this session (`-` removes it; without a name, shows it)")],
+    },
+    BuiltinText {
+        name: "gstatus",
+        synopsis: "gstatus [--json]",
+        description: "Git repository status as a table: branch, upstream, commits ahead/behind and one file per line (staged in green, in the working tree in red).",
+        flags: &[("--json", "JSON output")],
+    },
+    BuiltinText {
+        name: "gbranches",
+        synopsis: "gbranches [--json]",
+        description: "Local branches, from the most recent to the oldest, with upstream, commits ahead/behind, date and subject of the last commit.",
+        flags: &[("--json", "JSON output")],
+    },
+    BuiltinText {
+        name: "glog",
+        synopsis: "glog [-n N] [--json]",
+        description: "Latest commits in an aligned table: hash, date, author, refs and subject.",
+        flags: &[("-n <N>", "Number of commits (default: 15)"), ("--json", "JSON output")],
+    },
+    BuiltinText {
+        name: "open",
+        synopsis: "open <path|url...>",
+        description: "Opens files, directories and URLs in the default program (`xdg-open` on Linux, `open` on macOS, `start` on Windows). Addresses without `https://` work too.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "calc",
+        synopsis: "calc [-x | -b | -o] <expression>",
+        description: "Calculator: `+ - * / % ^`, parentheses, `0x`/`0b`/`0o`, functions (`sqrt`, `round`, `min`, `max`...) and environment variables. Integers stay integers while the result is exact. `N%` is N/100, and adding or subtracting a percentage is relative to the value (`80 + 10%` = 88). No quotes needed: `*` does not become a glob.",
+        flags: &[("-x, --hex", "Result in hexadecimal"), ("-b, --bin", "Result in binary"), ("-o, --oct", "Result in octal")],
+    },
+    BuiltinText {
+        name: "=",
+        synopsis: "= <expression>",
+        description: "Same as `calc`.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "when",
+        synopsis: "when [--epoch | --iso | -f format] [time] [+-offset...] | when --diff <from> [to]",
+        description: "Dates and times that are the same on any system (without the GNU/BSD `date` differences). The time can be `now`, `today`, `tomorrow`, an epoch (seconds or ms), `2024-05-01 [14:30]` or `09:00`; offsets use s, m, h, d, w, mo and y. Shows local time, UTC, epoch and the distance from now.",
+        flags: &[
+            ("-e, --epoch", "Only the epoch in seconds"),
+            ("-i, --iso", "Only the date in RFC 3339"),
+            ("-f, --format <fmt>", "strftime format (`%d/%m/%Y`)"),
+            ("--diff <from> [to]", "Duration between two times (without `to`, until now)"),
+        ],
+    },
+    BuiltinText {
+        name: "path",
+        synopsis: "path [list | add [-p] <dir...> | rm <dir...> | dedupe] [--save]",
+        description: "Shows $PATH one entry per line (marking duplicates and missing ones) and edits it without building the string by hand. `add` does not repeat an existing entry; `rm` removes every occurrence; `dedupe` keeps the first of each.",
+        flags: &[
+            ("-p, --prepend", "With `add`, puts it at the start (moves the entry if it exists)"),
+            ("-s, --save", "Writes the change to ~/.cliosrc, for the next sessions"),
+        ],
+    },
+    BuiltinText {
+        name: "extract",
+        synopsis: "extract <file...> [-C dir]",
+        description: "Extracts .tar, .tar.gz/.tgz, .tar.xz, .tar.zst, .zip and single .gz/.xz/.zst files, detecting the format from the content. Does not need `tar`/`unzip` installed; shows progress in the terminal.",
+        flags: &[("-C, --dir <dir>", "Target directory (default: the current one)")],
+    },
+    BuiltinText {
+        name: "compress",
+        synopsis: "compress [-f] <source...> <output>",
+        description: "Compresses files and directories; the format comes from the output's extension (.tar, .tar.gz, .tar.xz, .tar.zst, .zip, or .gz/.xz/.zst for a single file). Symlinks are stored as links.",
+        flags: &[("-f, --force", "Overwrites the output if it exists")],
+    },
+    BuiltinText {
+        name: "hashsum",
+        synopsis: "hashsum [-a sha256|sha512|md5|blake3] [--tag] [files...] | hashsum -c <list>",
+        description: "Checksums without depending on coreutils variants. The output follows the `sha256sum` format (`hash  name`); `-c` checks lists in that format or the BSD one (`SHA256 (name) = hash`), detecting the algorithm from the hash length.",
+        flags: &[
+            ("-a, --algorithm <alg>", "sha256 (default), sha512, md5 or blake3"),
+            ("-c, --check <list>", "Checks the files in the list"),
+            ("-q, --quiet", "With -c, shows only the failures"),
+            ("--tag", "BSD-style output"),
+        ],
+    },
+    BuiltinText {
+        name: "fetch",
+        synopsis: "fetch [-X method] [-H 'Name: value']... [-d data|@file] [-o file] [-i] <url>",
+        description: "Quick HTTP request. JSON comes out indented and colored, text as it came, and binary responses are saved in the current directory with a progress line. URLs without a scheme get `https://` (`http://` for localhost). A status outside 2xx returns 1.",
+        flags: &[
+            ("-X, --request <method>", "HTTP method (default GET, or POST with -d)"),
+            ("-H, --header <'Name: value'>", "Extra header (can repeat)"),
+            ("-d, --data <data|@file>", "Request body (@- reads STDIN)"),
+            ("-o, --output <file>", "Writes the body to the file"),
+            ("-i, --include", "Shows the response status and headers"),
+        ],
+    },
+    BuiltinText {
+        name: "sysinfo",
+        synopsis: "sysinfo",
+        description: "Panel with system, kernel, uptime, CPU, memory and swap usage, the current directory's disk and the load average. With `show_load` in `[prompt]` (default), the prompt shows the load when it goes above the number of cores.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "procs",
+        synopsis: "procs [-s cpu|mem|pid|name] [-r] [-n N] [--kill [-9]] [pattern]",
+        description: "Lists processes (PID, user, CPU, memory and command) without depending on `ps`. The pattern filters by name or command line (a number matches the PID). With `--kill`, the processes are picked in a menu (space marks, Enter confirms) and receive SIGTERM.",
+        flags: &[
+            ("-s, --sort <field>", "cpu (default), mem, pid or name"),
+            ("-r, --reverse", "Reverses the order"),
+            ("-n <N>", "Shows only the first N"),
+            ("-k, --kill", "Picks processes to terminate"),
+            ("-9", "With --kill, uses SIGKILL"),
+        ],
+    },
+    BuiltinText {
+        name: "list",
+        synopsis: "list [-a] [-n N] [dir]",
+        description: "Directory contents (default: the current one) in columns, without the external `ls`: directories first, in blue and with `/`; executables with `*` and links with `@`. Outside a terminal, one name per line. It is what `auto_ls` in `[behavior]` shows after a `cd`.",
+        flags: &[
+            ("-a, --all", "Includes hidden entries"),
+            ("-n <N>", "Shows only the first N"),
+        ],
+    },
+    BuiltinText {
+        name: "usage",
+        synopsis: "usage [-n N | -a] [dir]",
+        description: "Disk usage of each entry in the directory (default: the current one), computed in parallel and shown from largest to smallest, with proportional bars. Counts the allocated blocks, like `du`, without leaving the file system or following symlinks.",
+        flags: &[
+            ("-n <N>", "Shows the N largest (default 20 in a terminal)"),
+            ("-a, --all", "Shows every entry"),
+        ],
+    },
+    BuiltinText {
+        name: "ports",
+        synopsis: "ports [-t | -u] [port]",
+        description: "Listening (TCP) or open (UDP) sockets with the owner process, read from `/proc/net` (without `netstat`/`ss`). Other users' processes only show up for root.",
+        flags: &[
+            ("-t, --tcp", "TCP only"),
+            ("-u, --udp", "UDP only"),
+        ],
+    },
+    BuiltinText {
+        name: "myip",
+        synopsis: "myip [-l | -p]",
+        description: "Addresses of the local interfaces (without loopback) and the public IP, looked up over HTTP.",
+        flags: &[
+            ("-l, --local", "Only the local addresses"),
+            ("-p, --public", "Only the public IP, on one line (for `$(myip -p)`)"),
+        ],
+    },
+    BuiltinText {
+        name: "ping-lite",
+        synopsis: "ping-lite [-p port] [-c N | -t] host[:port]",
+        description: "Measures the TCP connection time to the host (port 443 by default). Does not use ICMP, so it works without root and where ping is blocked; a closed port still counts as an answer.",
+        flags: &[
+            ("-p, --port <port>", "Port to test (default 443)"),
+            ("-c, --count <N>", "Number of attempts (default 4)"),
+            ("-t", "Keeps trying until Ctrl+C"),
+        ],
+    },
+    BuiltinText {
+        name: "secret",
+        synopsis: "secret set|get|rm <name> | secret list | secret lock",
+        description: "Vault for tokens and API keys encrypted with age (passphrase + scrypt) in ~/.clios_secrets.age. The passphrase is asked once per session; `$(secret get NAME)` uses the value without leaving it in the history or in .cliosrc.",
+        flags: &[
+            ("set <name>", "Stores a value (typed without echo, or read from STDIN)"),
+            ("get <name>", "Prints the value"),
+            ("rm <name>", "Removes a secret"),
+            ("list", "Stored names"),
+            ("lock", "Forgets the session passphrase"),
+        ],
+    },
+    BuiltinText {
+        name: "keys",
+        synopsis: "keys [key | text]",
+        description: "Lists the active line editor bindings, with the action and where they come from: rustyline default (emacs or vi), the shell or the [keys] section of .clios.toml. With an argument, only the bindings of that key or whose description contains the text.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "??",
+        synopsis: "?? <question>",
+        description: "Asks the suggester in the [suggest] section of .clios.toml (an external program or a Rhai function) for a command and puts it in the editor for review; nothing runs. Outside interactive mode, the suggestion goes to STDOUT.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "tldr",
+        synopsis: "tldr [-u] <command>",
+        description: "Practical examples of a command (tldr-pages pages, kept in ~/.clios_tldr). In the editor, Alt+H opens the man/tldr of the command being typed.",
+        flags: &[("-u, --update", "Downloads the page again, ignoring the cache")],
+    },
+    BuiltinText {
+        name: "type",
+        synopsis: "type <command>",
+        description: "Shows whether the command is an alias, a builtin or an executable in PATH.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "hash",
+        synopsis: "hash [-r] [command...]",
+        description: "Table with the location of the PATH commands, filled on the first run. Without arguments lists the stored paths and the statistics; with names, looks them up and stores them.",
+        flags: &[("-r", "Empties the table")],
+    },
+    BuiltinText {
+        name: "help",
+        synopsis: "help [command | keys]",
+        description: "Without arguments shows the builtins summary; with a name, the builtin's help page; `help keys` is the same as `keys`.",
+        flags: &[],
+    },
+    BuiltinText {
+        name: "version",
+        synopsis: "version",
+        description: "Prints the shell version.",
+        flags: &[],
+    },
+];
+
+// -----------------------------------------------------------------------------
+// LOOKUP
+// -----------------------------------------------------------------------------
+
+/// Procura um builtin pelo nome.
+pub fn find_builtin(name: &str) -> Option<&'static BuiltinInfo> {
+    BUILTINS.iter().find(|b| b.name == name)
+}
+
+/// Retorna `true` se o nome é um builtin visível.
+pub fn is_builtin(name: &str) -> bool {
+    find_builtin(name).is_some()
+}
+
+/// Nomes de todos os builtins visíveis.
+pub fn builtin_names() -> impl Iterator<Item = &'static str> {
+    BUILTINS.iter().map(|b| b.name)
+}
+
+// -----------------------------------------------------------------------------
+// HELP PAGE
+// -----------------------------------------------------------------------------
+
+/// Linha de uso de um builtin (`Uso: cd [dir | -]`), para mensagens de erro.
+pub fn usage(name: &str) -> String {
+    match find_builtin(name) {
+        Some(info) => t!("usage", info.text_in(current_language()).synopsis),
+        None => t!("usage", name),
+    }
+}
+
+/// Monta a página de ajuda de um builtin (`help <comando>`).
+pub fn format_help_page(info: &BuiltinInfo) -> String {
+    let text = info.text_in(current_language());
+    let mut page = format!(
+        "\x1b[1;33m{}\x1b[0m - {}\n\n\x1b[1;32m{}\x1b[0m\n  {}\n",
+        info.name,
+        text.description,
+        t!("help.usage"),
+        text.synopsis
+    );
+
+    if !text.flags.is_empty() {
+        page.push_str(&format!("\n\x1b[1;32m{}\x1b[0m\n", t!("help.flags")));
+        for (flag, text) in text.flags {
+            page.push_str(&format!("  {:<24} {}\n", flag, text));
+        }
+    }
+
+    if !info.examples.is_empty() {
+        page.push_str(&format!("\n\x1b[1;32m{}\x1b[0m\n", t!("help.examples")));
+        for example in info.examples {
+            page.push_str(&format!("  {}\n", example));
+        }
+    }
+    page
+}
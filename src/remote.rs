@@ -0,0 +1,378 @@
+//! # Remote Module
+//!
+//! Builtin `remote`: execução remota com os binários `ssh`/`scp` do sistema,
+//! com favoritos de hosts.
+//!
+//! - `remote add prod deploy@10.0.0.5` - guarda um host com um nome
+//! - `remote run prod 'systemctl status app'` - executa no host (sem comando,
+//!   abre uma sessão interativa)
+//! - `remote run web1,web2 uptime` / `remote run all uptime` - executa em
+//!   vários hosts ao mesmo tempo, com a saída de cada um prefixada pelo nome
+//! - `remote copy app.tar prod:/tmp/` - `scp` com nomes no lugar dos hosts
+//! - `remote default prod` - host padrão: `remote run` sem nome usa ele,
+//!   `:caminho` no `copy` aponta para ele e o prompt mostra `⇄ prod`
+//!
+//! Os favoritos ficam em `~/.clios_remotes.json`.
+
+use crate::executor::{self, ProcessGroup};
+use crate::jobs::{add_job, remove_job, JobList};
+use crate::pipeline::execute_pipeline;
+use crate::registry::usage;
+use crate::signals::{take_interrupt, EXIT_INTERRUPTED};
+use crate::t;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::RwLock;
+use std::thread;
+
+/// Limite do código de saída agregado de uma execução em vários hosts.
+const MAX_FAILED_EXIT: i32 = 100;
+
+/// Nome reservado que seleciona todos os hosts.
+pub const ALL: &str = "all";
+
+/// Host padrão, espelhado aqui para o prompt não ler o arquivo a cada linha.
+static DEFAULT_REMOTE: RwLock<Option<String>> = RwLock::new(None);
+
+/// Favoritos gravados em disco.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RemoteBook {
+    /// Nome → destino do ssh (`user@host`, `host`, alias do `~/.ssh/config`)
+    #[serde(default)]
+    pub remotes: BTreeMap<String, String>,
+    /// Host padrão
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+/// Caminho do arquivo de favoritos.
+pub fn remotes_path() -> PathBuf {
+    env::var("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join(".clios_remotes.json")
+}
+
+/// Carrega os favoritos (arquivo ausente ou inválido = vazio).
+pub fn load_remotes() -> RemoteBook {
+    fs::read_to_string(remotes_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Grava os favoritos em disco.
+pub fn save_remotes(book: &RemoteBook) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(book).map_err(|e| e.to_string())?;
+    fs::write(remotes_path(), json).map_err(|e| e.to_string())
+}
+
+/// Lê o host padrão salvo (chamado ao iniciar a shell).
+pub fn init_default_remote() {
+    set_default_remote(load_remotes().default);
+}
+
+fn set_default_remote(name: Option<String>) {
+    if let Ok(mut current) = DEFAULT_REMOTE.write() {
+        *current = name;
+    }
+}
+
+/// Host padrão atual.
+pub fn default_remote() -> Option<String> {
+    DEFAULT_REMOTE.read().ok().and_then(|d| d.clone())
+}
+
+/// Texto do indicador do prompt (`⇄ prod`).
+pub fn remote_label(name: Option<&str>) -> Option<String> {
+    name.map(|name| format!("⇄ {}", name))
+}
+
+/// Valida o nome de um favorito (`,` e `:` separam nomes e caminhos).
+pub fn valid_remote_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != ALL
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+// -----------------------------------------------------------------------------
+// TARGETS
+// -----------------------------------------------------------------------------
+
+/// Um host selecionado: nome do favorito e destino do ssh.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Remote {
+    pub name: String,
+    pub target: String,
+}
+
+/// Resolve `web1,web2` ou `all` em hosts. `None` se algum nome não é favorito.
+pub fn resolve_spec(book: &RemoteBook, spec: &str) -> Option<Vec<Remote>> {
+    let remote = |name: &str| {
+        book.remotes.get(name).map(|target| Remote {
+            name: name.to_string(),
+            target: target.clone(),
+        })
+    };
+    if spec == ALL {
+        return (!book.remotes.is_empty()).then(|| book.remotes.keys().filter_map(|n| remote(n)).collect());
+    }
+    spec.split(',').map(remote).collect()
+}
+
+/// Hosts e comando de um `remote run`: o primeiro argumento é o host (ou
+/// lista) se for um favorito; senão tudo é comando e vale o host padrão.
+pub fn run_targets(book: &RemoteBook, args: &[String]) -> Result<(Vec<Remote>, Vec<String>), String> {
+    if let Some(spec) = args.first()
+        && let Some(remotes) = resolve_spec(book, spec)
+    {
+        return Ok((remotes, args[1..].to_vec()));
+    }
+    let default = book.default.as_deref().ok_or_else(|| match args.first() {
+        Some(name) => t!("remote.not_found", name),
+        None => t!("remote.no_default").to_string(),
+    })?;
+    let remotes = resolve_spec(book, default).ok_or_else(|| t!("remote.not_found", default))?;
+    Ok((remotes, args.to_vec()))
+}
+
+/// Traduz um argumento do `scp`: `nome:caminho` vira `user@host:caminho` (um
+/// por host, se `nome` for uma lista) e `:caminho` usa o host padrão. Outros
+/// argumentos ficam como estão.
+pub fn copy_endpoints(book: &RemoteBook, arg: &str) -> Vec<(Option<String>, String)> {
+    let Some((spec, path)) = arg.split_once(':') else {
+        return vec![(None, arg.to_string())];
+    };
+    let spec = match (spec, book.default.as_deref()) {
+        ("", Some(default)) => default,
+        _ => spec,
+    };
+    match resolve_spec(book, spec) {
+        Some(remotes) => remotes
+            .into_iter()
+            .map(|r| (Some(r.name), format!("{}:{}", r.target, path)))
+            .collect(),
+        None => vec![(None, arg.to_string())],
+    }
+}
+
+// -----------------------------------------------------------------------------
+// EXECUTION
+// -----------------------------------------------------------------------------
+
+/// Tokens do `ssh` para um host. `interactive` pede um TTY remoto (`-t`);
+/// sem ele a entrada vem de `/dev/null` e senhas não são perguntadas.
+pub fn ssh_command(remote: &Remote, command: &[String], interactive: bool) -> Vec<String> {
+    let mut tokens = vec!["ssh".to_string()];
+    if interactive {
+        if !command.is_empty() {
+            tokens.push("-t".to_string());
+        }
+    } else {
+        tokens.extend(["-n", "-o", "BatchMode=yes"].map(String::from));
+    }
+    tokens.push(remote.target.clone());
+    tokens.extend(command.iter().cloned());
+    tokens
+}
+
+/// Executa um comando por host ao mesmo tempo; a saída de cada host sai de uma
+/// vez, com as linhas prefixadas por `[nome]`. Retorna quantos falharam.
+fn fan_out(runs: Vec<(String, Vec<String>)>, jobs: &JobList) -> i32 {
+    let width = runs.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    let mut failed = 0;
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = runs
+            .iter()
+            .map(|(name, tokens)| (name, scope.spawn(move || run_captured(tokens, jobs))))
+            .collect();
+        for (name, handle) in handles {
+            let (code, stdout, stderr) = handle.join().unwrap_or((1, Vec::new(), Vec::new()));
+            if code != 0 {
+                failed += 1;
+            }
+            let prefix = format!("\x1b[1;36m[{:<width$}]\x1b[0m ", name, width = width);
+            for line in String::from_utf8_lossy(&stdout).lines() {
+                println!("{}{}", prefix, line);
+            }
+            for line in String::from_utf8_lossy(&stderr).lines() {
+                eprintln!("{}\x1b[31m{}\x1b[0m", prefix, line);
+            }
+            if code != 0 {
+                eprintln!("{}{}", prefix, t!("remote.failed", code));
+            }
+        }
+        let _ = io::stdout().flush();
+    });
+
+    if take_interrupt() {
+        return EXIT_INTERRUPTED;
+    }
+    failed.min(MAX_FAILED_EXIT)
+}
+
+/// Executa `tokens` com a saída capturada, registrado na lista de jobs.
+fn run_captured(tokens: &[String], jobs: &JobList) -> (i32, Vec<u8>, Vec<u8>) {
+    let mut command = executor::command(tokens, ProcessGroup::Shell);
+    command.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped());
+    let child = match executor::spawn(&mut command) {
+        Ok(child) => child,
+        Err(e) => return (e.exit_code(), Vec::new(), format!("{}\n", e).into_bytes()),
+    };
+    let pid = child.id() as i32;
+    add_job(jobs, pid, tokens.join(" "));
+    let result = child.wait_with_output();
+    remove_job(jobs, pid);
+    match result {
+        Ok(out) => (executor::exit_code(out.status), out.stdout, out.stderr),
+        Err(_) => (1, Vec::new(), Vec::new()),
+    }
+}
+
+fn run_remote_command(book: &RemoteBook, args: &[String], jobs: &JobList) -> i32 {
+    let (remotes, command) = match run_targets(book, args) {
+        Ok(selection) => selection,
+        Err(e) => {
+            eprintln!("remote: {}", e);
+            return 1;
+        }
+    };
+    if let [remote] = remotes.as_slice() {
+        let interactive = io::stdin().is_terminal() && io::stdout().is_terminal();
+        return execute_pipeline(vec![ssh_command(remote, &command, interactive)]);
+    }
+    if command.is_empty() {
+        eprintln!("{}", t!("remote.needs_command"));
+        return 2;
+    }
+    let runs = remotes
+        .iter()
+        .map(|remote| (remote.name.clone(), ssh_command(remote, &command, false)))
+        .collect();
+    fan_out(runs, jobs)
+}
+
+fn run_copy(book: &RemoteBook, args: &[String], jobs: &JobList) -> i32 {
+    let (flags, paths): (Vec<_>, Vec<_>) = args.iter().partition(|a| a.starts_with('-'));
+    let Some((dest, sources)) = paths.split_last() else {
+        eprintln!("{}", usage("remote"));
+        return 2;
+    };
+    if sources.is_empty() {
+        eprintln!("{}", usage("remote"));
+        return 2;
+    }
+
+    let mut base: Vec<String> = vec!["scp".to_string()];
+    base.extend(flags.into_iter().cloned());
+    for source in sources {
+        match copy_endpoints(book, source).as_slice() {
+            [(_, endpoint)] => base.push(endpoint.clone()),
+            _ => {
+                eprintln!("{}", t!("remote.copy_many_sources", source));
+                return 2;
+            }
+        }
+    }
+
+    let dests = copy_endpoints(book, dest);
+    if let [(_, endpoint)] = dests.as_slice() {
+        let mut tokens = base;
+        tokens.push(endpoint.clone());
+        return execute_pipeline(vec![tokens]);
+    }
+    let runs = dests
+        .into_iter()
+        .map(|(name, endpoint)| {
+            let mut tokens = base.clone();
+            tokens.push(endpoint);
+            (name.unwrap_or_default(), tokens)
+        })
+        .collect();
+    fan_out(runs, jobs)
+}
+
+// -----------------------------------------------------------------------------
+// BUILTIN
+// -----------------------------------------------------------------------------
+
+/// Builtin `remote [list | add | rm | default | run | copy]`.
+pub fn handle_remote(tokens: &[String], jobs: &JobList) -> i32 {
+    let mut book = load_remotes();
+    let args = &tokens[1..];
+    let action = args.first().map(|s| s.as_str()).unwrap_or("list");
+
+    match (action, &args[args.len().min(1)..]) {
+        ("list" | "ls", []) => {
+            if book.remotes.is_empty() {
+                println!("{}", t!("remote.none"));
+            }
+            let width = book.remotes.keys().map(|n| n.len()).max().unwrap_or(0);
+            for (name, target) in &book.remotes {
+                let marker = if book.default.as_deref() == Some(name.as_str()) { "*" } else { " " };
+                println!("{} {:<width$}  {}", marker, name, target, width = width);
+            }
+            0
+        }
+        ("run", rest) => run_remote_command(&book, rest, jobs),
+        ("copy" | "cp", rest) => run_copy(&book, rest, jobs),
+        ("add", [name, target]) => {
+            if !valid_remote_name(name) {
+                eprintln!("{}", t!("remote.invalid_name", name));
+                return 1;
+            }
+            book.remotes.insert(name.clone(), target.clone());
+            store(&book)
+        }
+        ("rm", [name]) => {
+            if book.remotes.remove(name).is_none() {
+                eprintln!("remote: {}", t!("remote.not_found", name));
+                return 1;
+            }
+            if book.default.as_deref() == Some(name.as_str()) {
+                book.default = None;
+            }
+            store(&book)
+        }
+        ("default", []) => {
+            match &book.default {
+                Some(name) => println!("{}", name),
+                None => println!("{}", t!("remote.no_default")),
+            }
+            0
+        }
+        ("default", [name]) => {
+            if name == "-" {
+                book.default = None;
+            } else if book.remotes.contains_key(name) {
+                book.default = Some(name.clone());
+            } else {
+                eprintln!("remote: {}", t!("remote.not_found", name));
+                return 1;
+            }
+            store(&book)
+        }
+        _ => {
+            eprintln!("{}", usage("remote"));
+            2
+        }
+    }
+}
+
+/// Grava os favoritos e atualiza o host padrão do prompt.
+fn store(book: &RemoteBook) -> i32 {
+    if let Err(e) = save_remotes(book) {
+        eprintln!("{}", t!("remote.save_failed", e));
+        return 1;
+    }
+    set_default_remote(book.default.clone());
+    0
+}
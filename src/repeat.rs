@@ -0,0 +1,143 @@
+//! # Repeat Module
+//!
+//! Builtins `repeat` e `loop`: laços simples sem precisar da sintaxe de laço
+//! do bash.
+//!
+//! ## Formas
+//! - `repeat 5 cargo test` - executa 5 vezes
+//! - `loop --until-success ./flaky.sh` - repete até o comando dar certo
+//! - `loop --until-failure ./stress.sh` - repete até o comando falhar
+//!
+//! Flags comuns: `--delay <segundos>` entre execuções e `--max <n>` tentativas.
+//! O código de saída final é o da última execução (130 se interrompido por Ctrl+C).
+
+use crate::quote::join_words;
+use crate::signals::{take_interrupt, EXIT_INTERRUPTED};
+use crate::t;
+use crate::watch::sleep_interruptible;
+use std::time::Duration;
+
+/// Condição de parada do `loop`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Until {
+    /// Para na primeira execução com código 0
+    Success,
+    /// Para na primeira execução com código diferente de 0
+    Failure,
+}
+
+/// Opções de `repeat`/`loop`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepeatOptions {
+    /// Máximo de execuções (`None` = sem limite)
+    pub max_runs: Option<usize>,
+    pub until: Option<Until>,
+    pub delay: Duration,
+    /// Linha de comando a executar (já re-quotada)
+    pub command: String,
+}
+
+fn parse_count(flag: &str, value: Option<&String>) -> Result<usize, String> {
+    let value = value.ok_or_else(|| t!("args.requires_number", flag))?;
+    value
+        .parse()
+        .map_err(|_| t!("args.invalid_number", flag, value))
+}
+
+/// Interpreta as flags comuns e o comando.
+fn parse_common(args: &[String], mut opts: RepeatOptions) -> Result<RepeatOptions, String> {
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--delay" | "-d" => {
+                let value = args.get(i + 1).ok_or_else(|| t!("args.requires_seconds", "--delay"))?;
+                let secs: f64 = value
+                    .parse()
+                    .ok()
+                    .filter(|s: &f64| s.is_finite() && *s >= 0.0)
+                    .ok_or_else(|| t!("repeat.invalid_delay", value))?;
+                opts.delay = Duration::from_secs_f64(secs);
+                i += 2;
+            }
+            "--max" | "-m" => {
+                opts.max_runs = Some(parse_count("--max", args.get(i + 1))?);
+                i += 2;
+            }
+            "--until-success" => {
+                opts.until = Some(Until::Success);
+                i += 1;
+            }
+            "--until-failure" => {
+                opts.until = Some(Until::Failure);
+                i += 1;
+            }
+            "--" => {
+                i += 1;
+                break;
+            }
+            _ => break,
+        }
+    }
+
+    let rest = &args[i..];
+    if rest.is_empty() {
+        return Err(t!("args.no_command").to_string());
+    }
+    opts.command = join_words(rest);
+    Ok(opts)
+}
+
+/// `repeat <n> [flags] cmd...`
+pub fn parse_repeat_args(args: &[String]) -> Result<RepeatOptions, String> {
+    let count = parse_count("repeat", args.first())?;
+    parse_common(
+        &args[1..],
+        RepeatOptions {
+            max_runs: Some(count),
+            until: None,
+            delay: Duration::ZERO,
+            command: String::new(),
+        },
+    )
+}
+
+/// `loop [flags] cmd...`
+pub fn parse_loop_args(args: &[String]) -> Result<RepeatOptions, String> {
+    parse_common(
+        args,
+        RepeatOptions {
+            max_runs: None,
+            until: None,
+            delay: Duration::ZERO,
+            command: String::new(),
+        },
+    )
+}
+
+/// Executa o laço. `run` executa uma linha de comando e devolve o código de saída.
+pub fn run_repeat(opts: &RepeatOptions, run: &mut dyn FnMut(&str) -> i32) -> i32 {
+    let mut last_code = 0;
+    let mut runs = 0;
+
+    while opts.max_runs.is_none_or(|max| runs < max) {
+        if runs > 0 && !opts.delay.is_zero() && !sleep_interruptible(opts.delay) {
+            take_interrupt();
+            return EXIT_INTERRUPTED;
+        }
+
+        last_code = run(&opts.command);
+        runs += 1;
+
+        if last_code == EXIT_INTERRUPTED || take_interrupt() {
+            return EXIT_INTERRUPTED;
+        }
+        match opts.until {
+            Some(Until::Success) if last_code == 0 => return 0,
+            Some(Until::Failure) if last_code != 0 => return last_code,
+            _ => {}
+        }
+    }
+
+    // Limite de tentativas atingido sem a condição de parada
+    last_code
+}
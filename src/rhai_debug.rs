@@ -0,0 +1,353 @@
+//! # Rhai Debug Module
+//!
+//! Rastro dos erros de plugins e scripts Rhai e o debugger interativo.
+//!
+//! Um erro dentro de funções aninhadas mostra uma linha por chamada, da mais
+//! interna para fora, com o arquivo e a linha de cada uma:
+//!
+//! ```text
+//! [ERRO PLUGIN] Comando 'deploy': Variable not found: host
+//!   em check_host (deploy.rhai:12:9)
+//!   em deploy (deploy.rhai:30:5)
+//! ```
+//!
+//! Com `--rhai-debug`, o rastro traz o caminho completo e o código de cada
+//! linha.
+//!
+//! `rhai debug <arquivo> [linha|função...]` (ou `clios --rhai-debug
+//! script.rhai`) roda o script no debugger do Rhai: pausa no começo ou, com
+//! breakpoints, só neles; o prompt `dbg>` aceita `step`, `next`, `continue`,
+//! `break`, `print`, `bt` e o resto listado por `help`.
+
+use crate::t;
+use rhai::debugger::{BreakPoint, DebuggerCommand, DebuggerEvent};
+use rhai::{Dynamic, Engine, EvalAltResult, EvalContext, OptimizationLevel, Position, Scope};
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// `--rhai-debug`: rastros com caminho completo e o código das linhas.
+static RHAI_DEBUG: AtomicBool = AtomicBool::new(false);
+
+/// Liga ou desliga o modo `--rhai-debug`.
+pub fn set_rhai_debug(enabled: bool) {
+    RHAI_DEBUG.store(enabled, Ordering::Relaxed);
+}
+
+pub fn rhai_debug() -> bool {
+    RHAI_DEBUG.load(Ordering::Relaxed)
+}
+
+// -----------------------------------------------------------------------------
+// STACK TRACES
+// -----------------------------------------------------------------------------
+
+/// Uma chamada no rastro de um erro.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceFrame {
+    /// A função (`None` no código de topo)
+    pub function: Option<String>,
+    /// O arquivo, quando se sabe
+    pub source: Option<String>,
+    pub position: Position,
+}
+
+/// A mensagem do erro mais interno e as chamadas até ele, da mais interna
+/// para fora.
+///
+/// `entry` é a função que a shell chamou (um `call_fn` não aparece no erro) e
+/// `source`, o arquivo do código de fora. Os repasses dos plugins
+/// (`fn status() { git_helpers::status() }`) não entram: a mesma função,
+/// vinda de outro arquivo, substitui a chamada do repasse.
+pub fn error_trace(err: &EvalAltResult, entry: Option<&str>, source: Option<&str>) -> (String, Vec<TraceFrame>) {
+    let mut frames = Vec::new();
+    let mut function = entry.map(String::from);
+    let mut frame_source = source.map(String::from);
+    let mut position = Position::NONE;
+    let mut current = err;
+    while let EvalAltResult::ErrorInFunctionCall(name, src, inner, pos) = current {
+        let src = (!src.is_empty()).then(|| src.clone());
+        let forwarder = function.as_deref() == Some(name.as_str()) && src.is_some() && src != frame_source;
+        if !forwarder {
+            frames.push(TraceFrame { function: function.take(), source: frame_source.clone(), position: *pos });
+        }
+        function = Some(name.clone());
+        frame_source = src.or(frame_source);
+        position = *pos;
+        current = inner;
+    }
+    // A posição vai para o rastro, não para a mensagem
+    let inner_position = current.position();
+    let message = current.to_string();
+    let message = match message.strip_suffix(&format!(" ({})", inner_position)) {
+        Some(stripped) if !inner_position.is_none() => stripped.to_string(),
+        _ => message,
+    };
+    frames.push(TraceFrame {
+        function,
+        source: frame_source,
+        position: if inner_position.is_none() { position } else { inner_position },
+    });
+    frames.retain(|frame| frame.function.is_some() || !frame.position.is_none());
+    frames.reverse();
+    (message, frames)
+}
+
+/// `arquivo:linha:coluna` (só o nome do arquivo, fora do `--rhai-debug`).
+fn location(source: Option<&str>, position: Position) -> Option<String> {
+    let line = position.line()?;
+    let column = position.position().map(|c| format!(":{}", c)).unwrap_or_default();
+    let file = match source {
+        Some(path) if rhai_debug() => format!("{}:", path),
+        Some(path) => format!("{}:", Path::new(path).file_name().map_or(path.into(), |f| f.to_string_lossy())),
+        None => t!("rhai_debug.line").to_string(),
+    };
+    Some(format!("{}{}{}", file, line, column))
+}
+
+/// A linha `position` do arquivo `source`, sem os espaços das pontas.
+fn source_line(source: Option<&str>, position: Position) -> Option<String> {
+    let text = fs::read_to_string(source?).ok()?;
+    text.lines().nth(position.line()?.checked_sub(1)?).map(|line| line.trim().to_string())
+}
+
+/// O erro com o rastro das chamadas, uma por linha (ver [`error_trace`]).
+pub fn format_trace(err: &EvalAltResult, entry: Option<&str>, source: Option<&str>) -> String {
+    let (message, frames) = error_trace(err, entry, source);
+    let mut out = message;
+    for frame in frames {
+        let function = frame.function.map(|f| if f.starts_with("anon$") { "<closure>".to_string() } else { f });
+        let place = location(frame.source.as_deref(), frame.position);
+        let at = match (function, place) {
+            (Some(function), Some(place)) => format!("{} ({})", function, place),
+            (Some(function), None) => function,
+            (None, Some(place)) => place,
+            (None, None) => continue,
+        };
+        out.push_str(&format!("\n{}", t!("rhai_debug.at", at)));
+        if rhai_debug()
+            && let Some(line) = source_line(frame.source.as_deref(), frame.position)
+        {
+            out.push_str(&format!("\n      \x1b[2m{}\x1b[0m", line));
+        }
+    }
+    out
+}
+
+// -----------------------------------------------------------------------------
+// DEBUGGER
+// -----------------------------------------------------------------------------
+
+/// Breakpoint a partir de um argumento: um número é uma linha de `path`
+/// (`lines`); outra palavra, as chamadas a uma função.
+///
+/// O breakpoint de linha fica no primeiro caractere do código dela (ou da
+/// próxima linha com código): as expressões dentro do comando, na mesma
+/// linha, não param de novo.
+pub fn parse_breakpoint(spec: &str, path: &str, lines: &[String]) -> Option<BreakPoint> {
+    match spec.parse::<usize>() {
+        Ok(line) if line >= 1 => {
+            let (index, text) = lines.iter().enumerate().skip(line - 1).find(|(_, text)| {
+                let code = text.trim_start();
+                !code.is_empty() && !code.starts_with("//")
+            })?;
+            let column = text.len() - text.trim_start().len() + 1;
+            let pos = Position::new(u16::try_from(index + 1).ok()?, u16::try_from(column).ok()?);
+            Some(BreakPoint::AtPosition { source: Some(path.into()), pos, enabled: true })
+        }
+        Ok(_) => None,
+        Err(_) if crate::arrays::is_valid_name(spec) => Some(BreakPoint::AtFunctionName { name: spec.into(), enabled: true }),
+        Err(_) => None,
+    }
+}
+
+/// Mostra as linhas de `lines` em volta de `line`, marcando a atual.
+fn print_listing(lines: &[String], line: usize, before: usize, after: usize) {
+    if line == 0 || line > lines.len() {
+        return;
+    }
+    let first = line.saturating_sub(before).max(1);
+    let last = (line + after).min(lines.len());
+    let width = last.to_string().len();
+    for n in first..=last {
+        if n == line {
+            println!("\x1b[33m> {:>width$}│ {}\x1b[0m", n, lines[n - 1], width = width);
+        } else {
+            println!("  {:>width$}│ {}", n, lines[n - 1], width = width);
+        }
+    }
+}
+
+/// Um comando digitado no `dbg>`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DebugCommand {
+    /// Volta a rodar do jeito pedido
+    Resume(DebuggerCommand),
+    Break(Option<String>),
+    Delete(Option<usize>),
+    Print(Option<String>),
+    Backtrace,
+    List(Option<usize>),
+    Help,
+    Quit,
+}
+
+/// Interpreta uma linha do `dbg>`; vazia é `step`.
+pub fn parse_debug_command(line: &str) -> Result<DebugCommand, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let command = match words[..] {
+        [] | ["s" | "step"] => DebugCommand::Resume(DebuggerCommand::StepInto),
+        ["n" | "next"] => DebugCommand::Resume(DebuggerCommand::Next),
+        ["o" | "over"] => DebugCommand::Resume(DebuggerCommand::StepOver),
+        ["f" | "finish"] => DebugCommand::Resume(DebuggerCommand::FunctionExit),
+        ["c" | "continue"] => DebugCommand::Resume(DebuggerCommand::Continue),
+        ["b" | "break"] => DebugCommand::Break(None),
+        ["b" | "break", spec] => DebugCommand::Break(Some(spec.to_string())),
+        ["d" | "delete"] => DebugCommand::Delete(None),
+        ["d" | "delete", n] => DebugCommand::Delete(Some(n.parse().map_err(|_| t!("rhai_debug.invalid_number", n))?)),
+        ["p" | "print"] => DebugCommand::Print(None),
+        ["p" | "print", name] => DebugCommand::Print(Some(name.to_string())),
+        ["bt" | "backtrace"] => DebugCommand::Backtrace,
+        ["l" | "list"] => DebugCommand::List(None),
+        ["l" | "list", n] => DebugCommand::List(Some(n.parse().map_err(|_| t!("rhai_debug.invalid_number", n))?)),
+        ["h" | "help"] => DebugCommand::Help,
+        ["q" | "quit"] => DebugCommand::Quit,
+        _ => return Err(t!("rhai_debug.unknown_command", line.trim())),
+    };
+    Ok(command)
+}
+
+/// O que o debugger faz a cada pausa: mostra onde o script está e lê comandos
+/// até um deles mandar seguir.
+fn debugger_prompt(
+    mut context: EvalContext,
+    event: DebuggerEvent,
+    source: Option<&str>,
+    pos: Position,
+    path: &str,
+    lines: &[String],
+) -> Result<DebuggerCommand, Box<EvalAltResult>> {
+    match event {
+        DebuggerEvent::Start => println!("{}", t!("rhai_debug.start", path)),
+        DebuggerEvent::End => {
+            println!("{}", t!("rhai_debug.end"));
+            return Ok(DebuggerCommand::Continue);
+        }
+        DebuggerEvent::BreakPoint(n) => {
+            let breakpoint = context.global_runtime_state().debugger().break_points()[n].clone();
+            println!("{}", t!("rhai_debug.hit", n + 1, breakpoint));
+        }
+        DebuggerEvent::FunctionExitWithValue(value) => println!("{}", t!("rhai_debug.returned", value)),
+        DebuggerEvent::FunctionExitWithError(err) => println!("{}", t!("rhai_debug.failed", err)),
+        _ => {}
+    }
+    // Código de outro arquivo (um `import`) não tem as linhas à mão
+    let here = source.is_none_or(|s| s == path);
+    if here && let Some(line) = pos.line() {
+        print_listing(lines, line, 0, 0);
+    } else if let Some(place) = location(source, pos) {
+        println!("  {}", place);
+    }
+
+    loop {
+        print!("dbg> ");
+        let _ = io::stdout().flush();
+        let mut input = String::new();
+        // Sem entrada (EOF), o script segue até o fim
+        if io::stdin().read_line(&mut input).unwrap_or(0) == 0 {
+            println!();
+            return Ok(DebuggerCommand::Continue);
+        }
+        let command = match parse_debug_command(&input) {
+            Ok(command) => command,
+            Err(e) => {
+                eprintln!("{}", e);
+                continue;
+            }
+        };
+        let debugger = context.global_runtime_state_mut().debugger_mut();
+        match command {
+            DebugCommand::Resume(command) => return Ok(command),
+            DebugCommand::Quit => return Err(EvalAltResult::ErrorTerminated(Dynamic::UNIT, pos).into()),
+            DebugCommand::Break(None) => {
+                for (i, breakpoint) in debugger.break_points().iter().enumerate() {
+                    println!("  [{}] {}", i + 1, breakpoint);
+                }
+            }
+            DebugCommand::Break(Some(spec)) => match parse_breakpoint(&spec, path, lines) {
+                Some(breakpoint) => {
+                    println!("{}", t!("rhai_debug.added", debugger.break_points().len() + 1, breakpoint));
+                    debugger.break_points_mut().push(breakpoint);
+                }
+                None => eprintln!("{}", t!("rhai_debug.invalid_breakpoint", spec)),
+            },
+            DebugCommand::Delete(None) => debugger.break_points_mut().clear(),
+            DebugCommand::Delete(Some(n)) => {
+                if n >= 1 && n <= debugger.break_points().len() {
+                    debugger.break_points_mut().remove(n - 1);
+                } else {
+                    eprintln!("{}", t!("rhai_debug.invalid_breakpoint", n));
+                }
+            }
+            DebugCommand::Print(None) => {
+                for (name, constant, value) in context.scope().iter_raw() {
+                    let kind = if constant { "const" } else { "let" };
+                    println!("  {} {} = {:?}", kind, name, value);
+                }
+            }
+            DebugCommand::Print(Some(name)) => match context.scope().get_value::<Dynamic>(&name) {
+                Some(value) => println!("{} = {:?}", name, value),
+                None => eprintln!("{}", t!("rhai_debug.no_variable", name)),
+            },
+            DebugCommand::Backtrace => {
+                let stack = debugger.call_stack();
+                if stack.is_empty() {
+                    println!("  {}", t!("rhai_debug.top_level"));
+                }
+                for frame in stack.iter().rev() {
+                    println!("  {}", frame);
+                }
+            }
+            DebugCommand::List(line) => print_listing(lines, line.or(pos.line()).unwrap_or(1), 3, 6),
+            DebugCommand::Help => println!("{}", t!("rhai_debug.help")),
+        }
+    }
+}
+
+/// Roda o script `path` no debugger, com os breakpoints de `breakpoints`
+/// (linhas ou funções). Sem breakpoints, pausa antes do primeiro comando.
+/// `quit` no `dbg>` encerra sem erro.
+pub fn debug_script(mut engine: Engine, path: &str, breakpoints: &[String]) -> Result<(), String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let lines: Vec<String> = text.lines().map(String::from).collect();
+    let mut initial = Vec::new();
+    for spec in breakpoints {
+        initial.push(parse_breakpoint(spec, path, &lines).ok_or_else(|| t!("rhai_debug.invalid_breakpoint", spec))?);
+    }
+    let pause_at_start = initial.is_empty();
+
+    // Sem otimização, cada comando do arquivo continua sendo um passo
+    engine.set_optimization_level(OptimizationLevel::None);
+    let ast = engine.compile_file(path.into()).map_err(|e| e.to_string())?;
+    let script = path.to_string();
+    #[allow(deprecated)]
+    engine.register_debugger(
+        move |_, mut debugger| {
+            debugger.break_points_mut().extend(initial.clone());
+            debugger
+        },
+        move |context, event, _node, source, pos| {
+            if matches!(event, DebuggerEvent::Start) && !pause_at_start {
+                return Ok(DebuggerCommand::Continue);
+            }
+            debugger_prompt(context, event, source, pos, &script, &lines)
+        },
+    );
+
+    match engine.run_ast_with_scope(&mut Scope::new(), &ast) {
+        Ok(()) => Ok(()),
+        Err(e) if matches!(*e, EvalAltResult::ErrorTerminated(..)) => Ok(()),
+        Err(e) => Err(format_trace(&e, None, Some(path))),
+    }
+}
@@ -3,46 +3,94 @@
 //! Handles the Rhai scripting engine setup, including all registered functions
 //! and script execution.
 
-use rhai::{Engine, EvalAltResult, Scope, AST};
+use crate::executor;
+use crate::jobs::{job_snapshot, kill_job, resolve_job_spec, wait_job, JobList, JobStatus};
+use crate::rhai_debug::format_trace;
+use crate::plugin_registry::{conflict_warning, register_plugin_api, SharedPluginRegistry, PLUGIN_HOOKS};
+use crate::signals::is_interrupted;
+use crate::t;
+use nix::sys::signal::Signal;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, FnAccess, Map, Module, Scope, ScriptFnMetadata, AST};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 // -----------------------------------------------------------------------------
-// ENGINE CREATION
+// SHELL_EXEC
 // -----------------------------------------------------------------------------
 
-/// Creates and configures a new Rhai engine with all shell functions registered.
-pub fn create_rhai_engine() -> Engine {
-    let mut engine = Engine::new();
+/// `shell_exec("cmd args")` do Rhai: executa o comando e devolve
+/// `#{ success, output }` com STDOUT e STDERR juntos.
+fn shell_exec(cmd_str: &str) -> rhai::Map {
+    let parts: Vec<String> = cmd_str.split_whitespace().map(String::from).collect();
+    let mut map = rhai::Map::new();
 
-    // --- shell_exec function ---
-    engine.register_fn("shell_exec", |cmd_str: &str| -> rhai::Map {
-        let parts: Vec<&str> = cmd_str.split_whitespace().collect();
-        let mut map = rhai::Map::new();
+    if parts.is_empty() {
+        map.insert("success".into(), false.into());
+        return map;
+    }
+
+    match executor::capture(&parts) {
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            let combined = format!("{}{}", stdout, stderr).trim().to_string();
 
-        if parts.is_empty() {
+            map.insert("success".into(), output.status.success().into());
+            map.insert("output".into(), combined.into());
+            map
+        }
+        Err(e) => {
             map.insert("success".into(), false.into());
-            return map;
+            map.insert("output".into(), e.error.to_string().into());
+            map
         }
+    }
+}
 
-        match std::process::Command::new(parts[0])
-            .args(&parts[1..])
-            .output()
-        {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-                let combined = format!("{}{}", stdout, stderr).trim().to_string();
-
-                map.insert("success".into(), output.status.success().into());
-                map.insert("output".into(), combined.into());
-                map
-            }
-            Err(e) => {
-                map.insert("success".into(), false.into());
-                map.insert("output".into(), e.to_string().into());
-                map
-            }
-        }
-    });
+// -----------------------------------------------------------------------------
+// DEFERRED OUTPUT
+// -----------------------------------------------------------------------------
+
+/// Mensagens de `notify` e `queue_output` esperando o próximo prompt. Um hook
+/// ou uma tarefa que escreve enquanto o usuário digita embaralha a linha;
+/// na fila, a mensagem sai antes do prompt ser desenhado.
+static QUEUED_OUTPUT: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn queue(message: String) {
+    if let Ok(mut queued) = QUEUED_OUTPUT.lock() {
+        queued.push(message);
+    }
+}
+
+/// Esvazia a fila, devolvendo as mensagens na ordem em que chegaram.
+pub fn take_queued_output() -> Vec<String> {
+    QUEUED_OUTPUT.lock().map(|mut queued| std::mem::take(&mut *queued)).unwrap_or_default()
+}
+
+/// Mostra as mensagens da fila (antes do prompt, no fim de cada comando do
+/// modo em lote e ao sair).
+pub fn flush_queued_output() {
+    for message in take_queued_output() {
+        println!("{}", message);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ENGINE CREATION
+// -----------------------------------------------------------------------------
+
+/// Creates and configures a new Rhai engine with all shell functions registered.
+///
+/// `plugins` recebe os comandos criados por `register_command`.
+pub fn create_rhai_engine(plugins: &SharedPluginRegistry) -> Engine {
+    let mut engine = Engine::new();
+
+    // Ctrl+C interrompe loops longos de scripts
+    engine.on_progress(|_| is_interrupted().then_some(rhai::Dynamic::UNIT));
+
+    // --- shell_exec function ---
+    engine.register_fn("shell_exec", shell_exec);
 
     // --- confirm function (UI Widget) ---
     engine.register_fn("confirm", |prompt: &str| -> bool {
@@ -97,9 +145,86 @@ pub fn create_rhai_engine() -> Engine {
         std::fs::write(path, content).is_ok()
     });
 
+    // --- notify / queue_output (saída adiada até o próximo prompt) ---
+    engine.register_fn("notify", |message: Dynamic| queue(format!("\x1b[1;36m▸ {}\x1b[0m", message)));
+    engine.register_fn("queue_output", |message: Dynamic| queue(message.to_string()));
+
+    // --- register_command (comandos de plugin com help e autocomplete) ---
+    register_plugin_api(&mut engine, plugins);
+
     engine
 }
 
+// -----------------------------------------------------------------------------
+// JOB CONTROL
+// -----------------------------------------------------------------------------
+
+/// O PID de um job dado pelo plugin: um número é o PID; um texto, qualquer
+/// especificação do `fg` (`"%1"`, `"%+"`, `"12345"`).
+fn job_pid(jobs: &JobList, spec: &Dynamic) -> Option<i32> {
+    match spec.as_int() {
+        Ok(pid) => resolve_job_spec(jobs, &pid.to_string()),
+        Err(_) => resolve_job_spec(jobs, spec.clone().into_string().ok()?.trim()),
+    }
+}
+
+/// Um sinal pelo nome (`"KILL"`, `"SIGKILL"`, `"kill"`) ou pelo número.
+pub fn parse_signal(name: &str) -> Option<Signal> {
+    if let Ok(number) = name.parse::<i32>() {
+        return Signal::try_from(number).ok();
+    }
+    let upper = name.trim().to_ascii_uppercase();
+    let full = if upper.starts_with("SIG") { upper } else { format!("SIG{}", upper) };
+    full.parse().ok()
+}
+
+/// `list_jobs()`, `kill_job(job [, sinal])` e `wait_job(job [, segundos])`:
+/// os jobs da sessão para os plugins, pela mesma lista do `jobs` (um
+/// `Arc<Mutex<...>>`, então o plugin nunca vê a lista pela metade).
+pub fn register_job_api(engine: &mut Engine, jobs: &JobList) {
+    let list = jobs.clone();
+    engine.register_fn("list_jobs", move || -> Array {
+        job_snapshot(&list)
+            .into_iter()
+            .map(|job| {
+                let mut map = Map::new();
+                map.insert("id".into(), (job.number as i64).into());
+                map.insert("pid".into(), (job.pid as i64).into());
+                map.insert("command".into(), job.command.into());
+                let status = if job.status == JobStatus::Stopped { "stopped" } else { "running" };
+                map.insert("status".into(), status.into());
+                map.insert("elapsed".into(), job.elapsed.as_secs_f64().into());
+                map.insert("cpu".into(), job.cpu_seconds.into());
+                map.insert("memory".into(), (job.memory as i64).into());
+                map.into()
+            })
+            .collect()
+    });
+
+    let list = jobs.clone();
+    let kill = move |spec: Dynamic, sig: &str| -> Result<bool, Box<EvalAltResult>> {
+        let sig = parse_signal(sig).ok_or_else(|| t!("rhai.invalid_signal", sig))?;
+        Ok(job_pid(&list, &spec).is_some_and(|pid| kill_job(&list, pid, sig)))
+    };
+    let kill_default = kill.clone();
+    engine.register_fn("kill_job", move |spec: Dynamic| kill_default(spec, "TERM"));
+    engine.register_fn("kill_job", move |spec: Dynamic, sig: Dynamic| kill(spec, &sig.to_string()));
+
+    // O código de saída, ou `()` se o job não existe ou o tempo acabou
+    let list = jobs.clone();
+    let wait = move |spec: Dynamic, timeout: Option<Duration>| -> Dynamic {
+        job_pid(&list, &spec)
+            .and_then(|pid| wait_job(&list, pid, timeout))
+            .map_or(Dynamic::UNIT, |code| (code as i64).into())
+    };
+    let wait_forever = wait.clone();
+    engine.register_fn("wait_job", move |spec: Dynamic| wait_forever(spec, None));
+    engine.register_fn("wait_job", move |spec: Dynamic, seconds: Dynamic| {
+        let seconds = seconds.as_float().or_else(|_| seconds.as_int().map(|s| s as f64)).unwrap_or(0.0);
+        wait(spec, Some(Duration::from_secs_f64(seconds.max(0.0))))
+    });
+}
+
 // -----------------------------------------------------------------------------
 // SCRIPT EXECUTION
 // -----------------------------------------------------------------------------
@@ -109,37 +234,16 @@ pub fn create_rhai_engine() -> Engine {
 /// Diferente do modo interativo, esta função cria um motor "limpo" e novo.
 /// Isso garante que scripts rodem em um ambiente isolado.
 pub fn run_rhai_script(path: &str) -> Result<(), Box<EvalAltResult>> {
-    let mut engine = Engine::new();
-
-    engine.register_fn("shell_exec", |cmd_str: &str| -> rhai::Map {
-        let parts: Vec<&str> = cmd_str.split_whitespace().collect();
-        let mut map = rhai::Map::new();
+    script_engine().run_file(path.into())
+}
 
-        if parts.is_empty() {
-            map.insert("success".into(), false.into());
-            return map;
-        }
+/// O motor dos scripts externos: só `shell_exec` e `input`, sem o resto da
+/// sessão (também usado por `clios --rhai-debug script.rhai`).
+pub fn script_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.on_progress(|_| is_interrupted().then_some(rhai::Dynamic::UNIT));
 
-        match std::process::Command::new(parts[0])
-            .args(&parts[1..])
-            .output()
-        {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-                let combined = format!("{}{}", stdout, stderr).trim().to_string();
-
-                map.insert("success".into(), output.status.success().into());
-                map.insert("output".into(), combined.into());
-                map
-            }
-            Err(e) => {
-                map.insert("success".into(), false.into());
-                map.insert("output".into(), e.to_string().into());
-                map
-            }
-        }
-    });
+    engine.register_fn("shell_exec", shell_exec);
 
     engine.register_fn("input", |prompt: &str| -> String {
         use std::io::{self, Write};
@@ -151,15 +255,240 @@ pub fn run_rhai_script(path: &str) -> Result<(), Box<EvalAltResult>> {
         buffer.trim().to_string()
     });
 
-    engine.run_file(path.into())?;
+    engine
+}
 
-    Ok(())
+/// Avalia código Rhai no Engine/Scope da sessão capturando a saída.
+///
+/// Usado pelo subshell `$(rhai ...)`: as chamadas a `print` são redirecionadas
+/// para um buffer (em vez do STDOUT) e o valor final, se não for `()`, é
+/// acrescentado ao resultado. O callback padrão de `print` é restaurado no fim.
+pub fn eval_rhai_capture(
+    engine: &mut Engine,
+    scope: &mut Scope,
+    plugins: &PluginSet,
+    code: &str,
+) -> Result<String, String> {
+    let buffer = Arc::new(Mutex::new(Vec::<String>::new()));
+    let sink = buffer.clone();
+    engine.on_print(move |s| {
+        if let Ok(mut lines) = sink.lock() {
+            lines.push(s.to_string());
+        }
+    });
+
+    let result = match engine.compile(code) {
+        Ok(user_ast) => {
+            let combined = match plugins.public_ast() {
+                Some(ast) => ast.clone().merge(&user_ast),
+                None => user_ast,
+            };
+            engine.eval_ast_with_scope::<rhai::Dynamic>(scope, &combined)
+        }
+        Err(e) => Err(e.into()),
+    };
+
+    engine.on_print(|s| println!("{}", s));
+
+    let mut lines = buffer.lock().map(|l| l.clone()).unwrap_or_default();
+    match result {
+        Ok(value) => {
+            if !value.is_unit() {
+                lines.push(value.to_string());
+            }
+            Ok(lines.join("\n").trim().to_string())
+        }
+        Err(e) => Err(e.to_string()),
+    }
 }
 
 // -----------------------------------------------------------------------------
 // PLUGIN MANAGEMENT
 // -----------------------------------------------------------------------------
 
+/// Um plugin carregado. As funções dele ficam num AST só dele, onde as
+/// chamadas entre elas se resolvem: dois plugins com um `helper()` cada não
+/// se atrapalham.
+#[derive(Debug, Clone)]
+pub struct LoadedPlugin {
+    pub path: String,
+    /// Módulo do plugin no motor (`git_helpers::status()` no `rhai`)
+    pub namespace: String,
+    /// Só as funções (o código de topo roda uma vez, na carga)
+    pub ast: AST,
+    /// Prioridade nos conflitos de nome (menor vence)
+    pub rank: usize,
+}
+
+impl LoadedPlugin {
+    /// As funções que o plugin exporta: todas menos as `private fn` e as
+    /// closures (`anon$...`, callbacks do `register_command`).
+    pub fn exports(&self) -> impl Iterator<Item = ScriptFnMetadata<'_>> {
+        self.ast
+            .iter_functions()
+            .filter(|f| f.access == FnAccess::Public && !f.name.starts_with("anon$"))
+    }
+
+    /// O plugin exporta uma função `name`?
+    pub fn exports_name(&self, name: &str) -> bool {
+        self.exports().any(|f| f.name == name)
+    }
+}
+
+/// Os plugins carregados, do de maior prioridade para o de menor.
+///
+/// Cada nome exportado por mais de um plugin pertence ao primeiro deles; as
+/// funções públicas de todos ficam acessíveis sem prefixo por um AST de
+/// repasses (`fn status() { git_helpers::status() }`), que o `rhai` junta a
+/// cada avaliação.
+#[derive(Debug, Clone, Default)]
+pub struct PluginSet {
+    plugins: Vec<LoadedPlugin>,
+    public: Option<AST>,
+}
+
+impl PluginSet {
+    pub fn is_empty(&self) -> bool {
+        self.plugins.is_empty()
+    }
+
+    /// Os plugins, em ordem de prioridade.
+    pub fn iter(&self) -> impl Iterator<Item = &LoadedPlugin> {
+        self.plugins.iter()
+    }
+
+    /// As funções exportadas sem prefixo (os repasses), se há plugins.
+    pub fn public_ast(&self) -> Option<&AST> {
+        self.public.as_ref()
+    }
+
+    /// O plugin dono da função `name`.
+    pub fn owner(&self, name: &str) -> Option<&LoadedPlugin> {
+        self.plugins.iter().find(|p| p.exports_name(name))
+    }
+
+    /// As funções que um código do plugin em `path` enxerga: as dele e, por
+    /// baixo, as exportadas pelos outros. Sem `path`, só as exportadas.
+    pub fn context(&self, path: Option<&str>) -> AST {
+        let public = self.public.clone().unwrap_or_else(AST::empty);
+        match path.and_then(|path| self.plugins.iter().find(|p| p.path == path)) {
+            Some(plugin) => {
+                // O arquivo aparece no rastro dos erros
+                let mut ast = public.merge(&plugin.ast);
+                ast.set_source(plugin.path.as_str());
+                ast
+            }
+            None => public,
+        }
+    }
+
+    /// Nome de módulo para o arquivo em `path`: o nome do arquivo, só com
+    /// letras, números e `_`, e sem repetir o de outro plugin.
+    pub fn namespace_for(&self, path: &str) -> String {
+        let stem = Path::new(path).file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+        let mut base: String = stem.chars().map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' }).collect();
+        if base.is_empty() || base.starts_with(|c: char| c.is_ascii_digit()) {
+            base.insert(0, '_');
+        }
+        let taken = |name: &str| self.plugins.iter().any(|p| p.namespace == name && p.path != path);
+        let mut namespace = base.clone();
+        let mut n = 2;
+        while taken(&namespace) {
+            namespace = format!("{}_{}", base, n);
+            n += 1;
+        }
+        namespace
+    }
+
+    /// Adiciona (ou, ao recarregar, substitui) um plugin. Devolve os avisos
+    /// dos nomes que ele divide com outros plugins; os ganchos da shell
+    /// (`on_cd`, `on_exit`) rodam em todos e não contam como conflito.
+    pub fn insert(&mut self, plugin: LoadedPlugin) -> Vec<String> {
+        // Recarregado, o plugin continua no mesmo lugar
+        let position = match self.plugins.iter().position(|p| p.path == plugin.path) {
+            Some(position) => {
+                self.plugins[position] = plugin;
+                position
+            }
+            None => {
+                let position = self.plugins.partition_point(|p| p.rank <= plugin.rank);
+                self.plugins.insert(position, plugin);
+                position
+            }
+        };
+
+        let plugin = &self.plugins[position];
+        let mut names: Vec<&str> = plugin.exports().map(|f| f.name).collect();
+        names.sort_unstable();
+        names.dedup();
+        let mut warnings = Vec::new();
+        for name in names.into_iter().filter(|name| !PLUGIN_HOOKS.contains(name)) {
+            for (index, other) in self.plugins.iter().enumerate() {
+                if index != position && other.exports_name(name) {
+                    let (winner, loser) = if index < position { (other, plugin) } else { (plugin, other) };
+                    warnings.push(conflict_warning(name, &winner.path, &loser.path));
+                }
+            }
+        }
+        warnings
+    }
+
+    /// Código dos repasses: cada nome exportado vai para o módulo do dono.
+    fn forwarders(&self) -> String {
+        let mut source = String::new();
+        for plugin in &self.plugins {
+            for f in plugin.exports() {
+                if self.owner(f.name).is_some_and(|owner| owner.path == plugin.path) {
+                    let params = f.params.join(", ");
+                    source.push_str(&format!("fn {0}({1}) {{ {2}::{0}({1}) }}\n", f.name, params, plugin.namespace));
+                }
+            }
+        }
+        source
+    }
+}
+
+/// Instala um plugin já compilado na sessão.
+///
+/// O código de topo do arquivo roda uma única vez, aqui (é onde ficam os
+/// `register_command`), num Scope só dele. As funções viram o módulo
+/// `namespace` do motor e entram no [`PluginSet`], que refaz os repasses.
+pub fn install_plugin(
+    engine: &mut Engine,
+    plugins: &mut PluginSet,
+    registry: &SharedPluginRegistry,
+    new_ast: AST,
+    path: &str,
+) -> Result<(), String> {
+    let init_failed = |e: &EvalAltResult| t!("tag.plugin_error", t!("plugin.init_failed", path, format_trace(e, None, Some(path))));
+
+    // As funções exportadas pelos plugins anteriores ficam visíveis para o
+    // código de topo; as do próprio plugin têm precedência
+    let runnable = plugins.context(None).merge(&new_ast);
+    registry.borrow_mut().set_loading(Some(path));
+    let result = engine.run_ast_with_scope(&mut Scope::new(), &runnable);
+    registry.borrow_mut().set_loading(None);
+    result.map_err(|e| init_failed(&e))?;
+
+    let functions = new_ast.clone_functions_only();
+    let module = Module::eval_ast_as_new(Scope::new(), &functions, engine).map_err(|e| init_failed(&e))?;
+    let namespace = plugins.namespace_for(path);
+    engine.register_static_module(namespace.as_str(), module.into());
+
+    let rank = {
+        let mut registry = registry.borrow_mut();
+        registry.set_functions(path, functions.clone());
+        registry.rank(path)
+    };
+    let warnings = plugins.insert(LoadedPlugin { path: path.to_string(), namespace, ast: functions, rank });
+    for warning in warnings {
+        eprintln!("{}", warning);
+    }
+    let forwarders = engine.compile(plugins.forwarders()).map_err(|e| t!("tag.plugin_error", t!("plugin.init_failed", path, e)))?;
+    plugins.public = Some(forwarders);
+    Ok(())
+}
+
 /// Tenta executar uma função do Plugin carregado.
 /// Retorna `true` se a função existia e foi executada.
 pub fn try_execute_plugin_function(
@@ -179,7 +508,7 @@ pub fn try_execute_plugin_function(
 
         match result {
             Ok(_) => return true,
-            Err(e) => println!("Erro no Plugin (Função {}): {}", cmd, e),
+            Err(e) => println!("{}", t!("plugin.function_failed", cmd, format_trace(&e, Some(cmd), ast.source()))),
         }
         return true;
     }
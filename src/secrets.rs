@@ -0,0 +1,314 @@
+//! # Secrets Module
+//!
+//! Builtin `secret`: um cofre de tokens e chaves de API cifrado com `age`
+//! (senha + scrypt) em `~/.clios_secrets.age`, para que eles não fiquem em
+//! texto puro no histórico nem no `.cliosrc`.
+//!
+//! - `secret set NOME` - pede o valor sem eco (fora do terminal, lê do STDIN)
+//! - `secret get NOME` - imprime o valor; `$(secret get NOME)` é resolvido na
+//!   própria shell, sem subshell, e aproveita a senha já digitada
+//! - `secret list` / `secret rm NOME` - nomes guardados / remover um
+//! - `secret lock` - esquece a senha da sessão
+//!
+//! A senha é pedida uma vez por sessão e fica só na memória. O arquivo é um
+//! age comum: `age -d ~/.clios_secrets.age` também o abre (o conteúdo é JSON).
+
+use crate::registry::usage;
+use crate::t;
+use age::secrecy::SecretString;
+use age::DecryptError;
+use inquire::Password;
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, IsTerminal, Read, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Tentativas de senha antes de desistir.
+const PASSPHRASE_ATTEMPTS: usize = 3;
+
+/// Caminho do cofre.
+pub fn secrets_path() -> PathBuf {
+    env::var("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join(".clios_secrets.age")
+}
+
+/// Nome aceito: letras, números, `_`, `-` e `.` (como variáveis e arquivos).
+pub fn is_valid_secret_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || "_-.".contains(c))
+}
+
+// -----------------------------------------------------------------------------
+// ERRORS
+// -----------------------------------------------------------------------------
+
+/// Falha ao abrir, ler ou gravar o cofre.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SecretError {
+    /// A senha não abre o arquivo
+    WrongPassphrase,
+    /// O arquivo não é um cofre válido
+    Corrupt(String),
+    /// Senha pedida fora de um terminal
+    NeedsTerminal,
+    /// Esc / Ctrl+C no prompt
+    Cancelled,
+    NotFound(String),
+    Io(String),
+}
+
+impl fmt::Display for SecretError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            SecretError::WrongPassphrase => t!("secret.wrong_passphrase").to_string(),
+            SecretError::Corrupt(e) => t!("secret.corrupt", secrets_path().display(), e),
+            SecretError::NeedsTerminal => t!("secret.needs_terminal").to_string(),
+            SecretError::Cancelled => t!("secret.cancelled").to_string(),
+            SecretError::NotFound(name) => t!("secret.not_found", name),
+            SecretError::Io(e) => e.clone(),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl std::error::Error for SecretError {}
+
+// -----------------------------------------------------------------------------
+// ENCRYPTION
+// -----------------------------------------------------------------------------
+
+/// Cifra os segredos (JSON) com a senha.
+pub fn encrypt_secrets(secrets: &BTreeMap<String, String>, passphrase: &SecretString) -> Result<Vec<u8>, SecretError> {
+    let json = serde_json::to_vec(secrets).map_err(|e| SecretError::Io(e.to_string()))?;
+    let recipient = age::scrypt::Recipient::new(passphrase.clone());
+    age::encrypt(&recipient, &json).map_err(|e| SecretError::Io(e.to_string()))
+}
+
+/// Decifra um cofre gravado por [`encrypt_secrets`].
+pub fn decrypt_secrets(data: &[u8], passphrase: &SecretString) -> Result<BTreeMap<String, String>, SecretError> {
+    let identity = age::scrypt::Identity::new(passphrase.clone());
+    let json = age::decrypt(&identity, data).map_err(|e| match e {
+        DecryptError::DecryptionFailed | DecryptError::KeyDecryptionFailed | DecryptError::NoMatchingKeys => {
+            SecretError::WrongPassphrase
+        }
+        other => SecretError::Corrupt(other.to_string()),
+    })?;
+    serde_json::from_slice(&json).map_err(|e| SecretError::Corrupt(e.to_string()))
+}
+
+// -----------------------------------------------------------------------------
+// SESSION VAULT
+// -----------------------------------------------------------------------------
+
+/// Cofre aberto nesta sessão.
+struct Vault {
+    passphrase: SecretString,
+    secrets: BTreeMap<String, String>,
+    /// Data de modificação do arquivo lido: outra sessão pode ter gravado depois
+    modified: Option<SystemTime>,
+}
+
+/// Senha e segredos decifrados, só na memória (o scrypt leva ~1s por abertura).
+static VAULT: Mutex<Option<Vault>> = Mutex::new(None);
+
+fn file_modified() -> Option<SystemTime> {
+    fs::metadata(secrets_path()).and_then(|m| m.modified()).ok()
+}
+
+fn ask_passphrase(confirm: bool) -> Result<SecretString, SecretError> {
+    if !io::stdin().is_terminal() {
+        return Err(SecretError::NeedsTerminal);
+    }
+    let prompt = if confirm { t!("secret.new_passphrase") } else { t!("secret.passphrase") };
+    let mut password = Password::new(prompt);
+    password = if confirm {
+        password.with_custom_confirmation_message(t!("secret.confirm_passphrase"))
+    } else {
+        password.without_confirmation()
+    };
+    match password.prompt() {
+        Ok(text) if text.is_empty() => Err(SecretError::Cancelled),
+        Ok(text) => Ok(SecretString::from(text)),
+        Err(_) => Err(SecretError::Cancelled),
+    }
+}
+
+/// Roda `action` com o cofre aberto, pedindo a senha se a sessão ainda não a
+/// tem (ou se ela não abre mais o arquivo). Sem arquivo, `create` cria um cofre
+/// novo (pedindo a senha duas vezes); sem `create`, `action` vê um cofre vazio.
+fn with_vault<T>(
+    create: bool,
+    action: impl FnOnce(&mut Vault) -> Result<T, SecretError>,
+) -> Result<T, SecretError> {
+    let mut guard = VAULT.lock().map_err(|e| SecretError::Io(e.to_string()))?;
+    let path = secrets_path();
+    let modified = file_modified();
+
+    let stale = guard.as_ref().is_none_or(|v| v.modified != modified);
+    if stale {
+        if modified.is_none() {
+            if !create {
+                let mut empty = Vault { passphrase: SecretString::from(String::new()), secrets: BTreeMap::new(), modified };
+                return action(&mut empty);
+            }
+            // Cofre novo: a senha da sessão (se houver) é reaproveitada
+            let passphrase = match guard.take() {
+                Some(vault) => vault.passphrase,
+                None => ask_passphrase(true)?,
+            };
+            *guard = Some(Vault { passphrase, secrets: BTreeMap::new(), modified: None });
+        } else {
+            let data = fs::read(&path).map_err(|e| SecretError::Io(format!("{}: {}", path.display(), e)))?;
+            let mut cached = guard.take().map(|v| v.passphrase);
+            let mut attempts = 0;
+            let vault = loop {
+                let passphrase = match cached.take() {
+                    Some(passphrase) => passphrase,
+                    None => ask_passphrase(false)?,
+                };
+                match decrypt_secrets(&data, &passphrase) {
+                    Ok(secrets) => break Vault { passphrase, secrets, modified },
+                    Err(SecretError::WrongPassphrase) if attempts + 1 < PASSPHRASE_ATTEMPTS => {
+                        eprintln!("{}", SecretError::WrongPassphrase);
+                        attempts += 1;
+                    }
+                    Err(e) => return Err(e),
+                }
+            };
+            *guard = Some(vault);
+        }
+    }
+    let vault = guard.as_mut().ok_or(SecretError::Cancelled)?;
+    action(vault)
+}
+
+/// Grava o cofre (permissão 0600, substituição atômica).
+fn save(vault: &mut Vault) -> Result<(), SecretError> {
+    let data = encrypt_secrets(&vault.secrets, &vault.passphrase)?;
+    let path = secrets_path();
+    let tmp = path.with_extension("age.tmp");
+    let io_error = |e: io::Error| SecretError::Io(format!("{}: {}", path.display(), e));
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(&tmp)
+        .map_err(io_error)?;
+    file.write_all(&data).and_then(|_| file.sync_all()).map_err(io_error)?;
+    fs::rename(&tmp, &path).map_err(io_error)?;
+    vault.modified = file_modified();
+    Ok(())
+}
+
+/// Valor de um segredo (abrindo o cofre se preciso).
+pub fn get_secret(name: &str) -> Result<String, SecretError> {
+    with_vault(false, |vault| vault.secrets.get(name).cloned().ok_or_else(|| SecretError::NotFound(name.to_string())))
+}
+
+/// Esquece a senha e os segredos da sessão.
+pub fn lock_vault() {
+    if let Ok(mut guard) = VAULT.lock() {
+        *guard = None;
+    }
+}
+
+/// `$(secret get NOME)`: o valor, resolvido na shell em vez de num subshell
+/// (o filho do `fork` não devolveria a senha digitada à sessão). `None` se a
+/// linha não é um `secret get`.
+pub fn capture_secret(line: &str) -> Option<String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let ["secret", "get", name] = words.as_slice() else {
+        return None;
+    };
+    Some(get_secret(name).unwrap_or_else(|e| {
+        eprintln!("{}", t!("tag.warning", format!("secret: {}", e)));
+        String::new()
+    }))
+}
+
+// -----------------------------------------------------------------------------
+// BUILTIN
+// -----------------------------------------------------------------------------
+
+/// Valor do `secret set`: prompt sem eco no terminal, STDIN fora dele.
+fn read_value(name: &str) -> Result<String, SecretError> {
+    let value = if io::stdin().is_terminal() {
+        Password::new(&t!("secret.value_prompt", name))
+            .without_confirmation()
+            .prompt()
+            .map_err(|_| SecretError::Cancelled)?
+    } else {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text).map_err(|e| SecretError::Io(e.to_string()))?;
+        text.trim_end_matches(['\n', '\r']).to_string()
+    };
+    if value.is_empty() {
+        return Err(SecretError::Cancelled);
+    }
+    Ok(value)
+}
+
+/// Builtin `secret set|get|rm NOME`, `secret list`, `secret lock`.
+pub fn handle_secret(tokens: &[String]) -> i32 {
+    let args: Vec<&str> = tokens[1..].iter().map(|s| s.as_str()).collect();
+    let name = match args.as_slice() {
+        ["set" | "get" | "rm", name] => *name,
+        ["list"] | ["lock"] => "",
+        _ => {
+            eprintln!("{}", usage("secret"));
+            return 2;
+        }
+    };
+    if !name.is_empty() && !is_valid_secret_name(name) {
+        eprintln!("secret: {}", t!("secret.invalid_name", name));
+        return 2;
+    }
+
+    let result = match args[0] {
+        "set" => with_vault(true, |vault| {
+            let value = read_value(name)?;
+            let replaced = vault.secrets.insert(name.to_string(), value).is_some();
+            save(vault)?;
+            println!("{}", if replaced { t!("secret.updated", name) } else { t!("secret.saved", name) });
+            Ok(())
+        }),
+        "get" => get_secret(name).map(|value| println!("{}", value)),
+        "rm" => with_vault(false, |vault| {
+            if vault.secrets.remove(name).is_none() {
+                return Err(SecretError::NotFound(name.to_string()));
+            }
+            save(vault)?;
+            println!("{}", t!("secret.removed", name));
+            Ok(())
+        }),
+        "list" => with_vault(false, |vault| {
+            if vault.secrets.is_empty() {
+                println!("{}", t!("secret.empty"));
+            }
+            for name in vault.secrets.keys() {
+                println!("{}", name);
+            }
+            Ok(())
+        }),
+        _ => {
+            lock_vault();
+            println!("{}", t!("secret.locked"));
+            Ok(())
+        }
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("secret: {}", e);
+            1
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! # Session Module
+//!
+//! Salva e restaura o contexto de trabalho da shell (`session save/restore`).
+//!
+//! ## O que uma sessão guarda
+//! - Diretório atual e diretório anterior (`cd -`)
+//! - Diferenças de ambiente em relação ao início da shell (variáveis
+//!   definidas/alteradas e removidas)
+//! - Aliases
+//!
+//! As sessões ficam em `~/.clios_sessions/<nome>.json`.
+
+use crate::t;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Contexto serializável de uma sessão.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Session {
+    pub cwd: String,
+    pub previous_dir: Option<String>,
+    /// Variáveis novas ou alteradas desde o início da shell
+    pub env_set: BTreeMap<String, String>,
+    /// Variáveis que existiam no início e foram removidas
+    pub env_unset: Vec<String>,
+    pub aliases: BTreeMap<String, String>,
+}
+
+/// Diretório onde as sessões são gravadas.
+pub fn sessions_dir() -> PathBuf {
+    env::var("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join(".clios_sessions")
+}
+
+/// Valida o nome (vira nome de arquivo, então nada de `/` ou `..`).
+pub fn valid_session_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.')
+        && !name.starts_with('.')
+}
+
+fn session_path(name: &str) -> PathBuf {
+    sessions_dir().join(format!("{}.json", name))
+}
+
+/// Calcula as diferenças entre o ambiente inicial e o atual.
+pub fn env_delta(
+    initial: &HashMap<String, String>,
+    current: &HashMap<String, String>,
+) -> (BTreeMap<String, String>, Vec<String>) {
+    let set = current
+        .iter()
+        .filter(|(k, v)| initial.get(*k) != Some(*v))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    let mut unset: Vec<String> = initial
+        .keys()
+        .filter(|k| !current.contains_key(*k))
+        .cloned()
+        .collect();
+    unset.sort();
+    (set, unset)
+}
+
+/// Captura o contexto atual.
+pub fn capture(
+    initial_env: &HashMap<String, String>,
+    aliases: &HashMap<String, String>,
+    previous_dir: &Option<PathBuf>,
+) -> Session {
+    let current_env: HashMap<String, String> = env::vars().collect();
+    let (env_set, env_unset) = env_delta(initial_env, &current_env);
+
+    Session {
+        cwd: env::current_dir()
+            .map(|p| p.display().to_string())
+            .unwrap_or_default(),
+        previous_dir: previous_dir.as_ref().map(|p| p.display().to_string()),
+        env_set,
+        env_unset,
+        aliases: aliases.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+    }
+}
+
+/// Aplica uma sessão: ambiente, aliases e diretórios.
+pub fn apply(
+    session: &Session,
+    aliases: &mut HashMap<String, String>,
+    previous_dir: &mut Option<PathBuf>,
+) -> Result<(), String> {
+    for key in &session.env_unset {
+        unsafe {
+            env::remove_var(key);
+        }
+    }
+    for (key, value) in &session.env_set {
+        unsafe {
+            env::set_var(key, value);
+        }
+    }
+
+    aliases.extend(session.aliases.iter().map(|(k, v)| (k.clone(), v.clone())));
+    *previous_dir = session.previous_dir.as_ref().map(PathBuf::from);
+
+    env::set_current_dir(&session.cwd).map_err(|e| format!("cd {}: {}", session.cwd, e))
+}
+
+/// Grava a sessão em disco.
+pub fn save(name: &str, session: &Session) -> Result<PathBuf, String> {
+    if !valid_session_name(name) {
+        return Err(t!("session.invalid_name", name));
+    }
+    let dir = sessions_dir();
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = session_path(name);
+    let json = serde_json::to_string_pretty(session).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+/// Lê uma sessão do disco.
+pub fn load(name: &str) -> Result<Session, String> {
+    if !valid_session_name(name) {
+        return Err(t!("session.invalid_name", name));
+    }
+    let content = fs::read_to_string(session_path(name))
+        .map_err(|_| t!("session.not_found", name))?;
+    serde_json::from_str(&content).map_err(|e| t!("session.corrupted", name, e))
+}
+
+/// Lista os nomes das sessões salvas (ordenados).
+pub fn list() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(sessions_dir())
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| {
+                    let name = e.file_name().into_string().ok()?;
+                    name.strip_suffix(".json").map(|s| s.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// Remove uma sessão salva.
+pub fn delete(name: &str) -> Result<(), String> {
+    if !valid_session_name(name) {
+        return Err(t!("session.invalid_name", name));
+    }
+    fs::remove_file(session_path(name)).map_err(|_| t!("session.not_found", name))
+}
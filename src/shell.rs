@@ -8,68 +8,68 @@
 //! - Gerenciar aliases e plugins
 //! - Coordenar expansões e execução de comandos
 
+use crate::abbr::{load_abbreviations, save_abbreviations, AbbrMap};
+use crate::accessibility::{screen_reader, set_screen_reader};
+use crate::arrays::{assign_array, parse_array_assignment};
 use crate::builtins::{handle_builtin, BuiltinResult};
-use crate::config::CliosConfig;
+use crate::calc::{calc_expression, handle_calc};
+use crate::clean_env::{minimal_env, parse_clean_env_line, CleanEnvOptions, DEFAULT_KEEP};
+use crate::compat::export_compat;
+use crate::config::{
+    apply_env_overrides, default_config_path, format_profile_list, parse_config, CliosConfig, PROFILE_VAR,
+};
+use crate::container::set_settings as set_container_settings;
+use crate::demo::{blocked_reason, parse_demo_args, DemoAction, DemoSettings};
+use crate::each::{parse_each_args, run_each};
+use crate::executor::{fork_error, set_injected_env, subshell, with_clean_env, SubshellMode};
 use crate::expansion::{
-    expand_alias_string, expand_globs, expand_subshells, expand_tilde, expand_variables_with_state,
-    split_logical_operators, LogicalOp,
+    expand_subshells_with, expand_tilde_token, expand_tokens, expand_variables_token, split_logical_operators,
+    LogicalOp,
+};
+use crate::fifo::{recv_stages, send_pipeline};
+use crate::i18n::init_language;
+use crate::include::IncludeStack;
+use crate::options::announce_command;
+use crate::jobs::{add_job, count_active_jobs, count_stopped_jobs, disable_job_control, execute_job_control, hangup_jobs, set_stop_then_bg, JobList, new_job_list};
+use crate::last_output::set_enabled as set_capture_output;
+use crate::limits::{check_job_slot, set_limits};
+use crate::listing::{auto_list, DEFAULT_AUTO_LS_MAX};
+use crate::parser::{background_pipeline, expand_aliases_in_line, split_pipes_respecting_quotes, subshell_group};
+use crate::pipeline::{
+    execute_pipeline, has_output_redirection, parse_redirection, spawn_pipeline_capture, take_pipe_status,
+    with_redirected_output,
+};
+use crate::plugin_registry::{plugin_dir_files, run_plugin_command, PluginManifest, SharedPluginRegistry};
+use crate::quote::quote_word;
+use crate::range::range_spec;
+use crate::rhai_debug::format_trace;
+use crate::rhai_integration::{create_rhai_engine, eval_rhai_capture, flush_queued_output, install_plugin, register_job_api, try_execute_plugin_function, PluginSet};
+use crate::registry::{is_builtin, usage};
+use crate::remote::init_default_remote;
+use crate::secrets::capture_secret;
+use crate::session;
+use crate::signals::{clear_interrupt, take_interrupt, EXIT_INTERRUPTED};
+use crate::statements::{
+    compound_statement, menu_choice, parse_case, parse_select, pattern_matches, split_statements, Compound,
+    StatementBuffer,
 };
-use crate::jobs::{execute_job_control, JobList, new_job_list};
-use crate::pipeline::execute_pipeline;
-use crate::rhai_integration::{create_rhai_engine, try_execute_plugin_function};
+use crate::suggest::{parse_query, parse_suggestion, run_external, SuggestError, SuggestSource};
+use crate::tasks::{find_tasks_file, format_task_list, load_tasks, TASKS_FILE};
+use crate::terminal::release_terminal;
+use crate::toolchain::{activation_command, detect_toolchains, entered_toolchains};
+use crate::t;
+use crate::repeat::{parse_loop_args, parse_repeat_args, run_repeat};
+use crate::trash::rewrite_rm;
+use crate::watch::{parse_watch_args, run_watch};
 
-use rhai::{Engine, Scope, AST};
+use rhai::{Engine, Scope};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::env;
 use std::fs::{self, File};
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, IsTerminal, Write};
 use std::path::{Path, PathBuf};
-
-// -----------------------------------------------------------------------------
-// HELPER FUNCTIONS
-// -----------------------------------------------------------------------------
-
-/// Divide uma string por pipes (|) respeitando aspas.
-/// 
-/// Esta função percorre a string caractere por caractere e só divide por |
-/// quando não está dentro de aspas simples ou duplas.
-fn split_pipes_respecting_quotes(input: &str) -> Vec<String> {
-    let mut parts = Vec::new();
-    let mut current = String::new();
-    let mut in_single_quote = false;
-    let mut in_double_quote = false;
-    let chars = input.chars().peekable();
-
-    for c in chars {
-        match c {
-            '\'' if !in_double_quote => {
-                in_single_quote = !in_single_quote;
-                current.push(c);
-            }
-            '"' if !in_single_quote => {
-                in_double_quote = !in_double_quote;
-                current.push(c);
-            }
-            '|' if !in_single_quote && !in_double_quote => {
-                parts.push(current.clone());
-                current.clear();
-            }
-            _ => current.push(c),
-        }
-    }
-
-    // Adiciona a última parte
-    if !current.is_empty() {
-        parts.push(current);
-    }
-
-    // Se não encontrou nenhum pipe ou se parts está vazio, retorna input
-    if parts.is_empty() {
-        vec![input.to_string()]
-    } else {
-        parts
-    }
-}
+use std::sync::{Arc, RwLock};
 
 // -----------------------------------------------------------------------------
 // CLIOS SHELL STRUCT
@@ -99,27 +99,674 @@ pub struct CliosShell {
     /// Configurações carregadas do arquivo TOML.
     pub config: CliosConfig,
 
-    /// AST do script de inicialização (se houver).
-    pub plugin_ast: Option<AST>,
+    /// Plugins Rhai carregados, cada um com as próprias funções.
+    pub plugin_set: PluginSet,
+
+    /// Comandos criados por `register_command`, compartilhados com o editor de linha.
+    pub plugins: SharedPluginRegistry,
     
     /// Lista de jobs em background
     pub jobs: JobList,
+
+    /// Comandos registrados via `trap` (ex: "EXIT" -> "echo tchau").
+    pub traps: HashMap<String, String>,
+
+    /// Código pedido por `exit`; quando `Some`, a sessão deve terminar.
+    pub exit_requested: Option<i32>,
+
+    /// Quantos `select` estão rodando agora (onde o `break` vale).
+    select_depth: usize,
+
+    /// `break` pedido no corpo de um `select`.
+    break_requested: bool,
+
+    /// Arquivos rc sendo lidos (`include`), para caminhos relativos e ciclos.
+    includes: IncludeStack,
+
+    /// Abreviações (`abbr`), compartilhadas com o editor de linha.
+    pub abbreviations: AbbrMap,
+
+    /// Ambiente no início da shell (base para o delta salvo por `session save`).
+    pub initial_env: HashMap<String, String>,
+
+    /// Modo de apresentação (`demo on`); `None` fora dele.
+    pub demo: Option<DemoSettings>,
+
+    /// Se o aviso de jobs parados já foi exibido (o segundo `exit` sai mesmo assim).
+    exit_warned: bool,
+
+    /// Diretório do último prompt, para os hooks de mudança de diretório.
+    last_dir: Option<PathBuf>,
+
+    /// Plugins do `plugins` do `.clios.toml` já carregados (a troca de perfil
+    /// só carrega os novos).
+    config_plugins: Vec<String>,
+
+    /// Se `profile use` trocou a configuração desde a última consulta.
+    profile_changed: bool,
+}
+
+/// Aplica as partes da configuração que vivem fora da `CliosShell` (ambiente
+/// dos comandos, limites, captura de saída e containers).
+fn apply_config_settings(config: &CliosConfig) {
+    set_injected_env(&config.env.clone().unwrap_or_default());
+    set_limits(config.limits.as_ref().map(|l| l.to_limits()).unwrap_or_default());
+    set_capture_output(config.behavior.as_ref().and_then(|b| b.capture_output).unwrap_or(false));
+    set_stop_then_bg(config.behavior.as_ref().and_then(|b| b.stop_then_bg).unwrap_or(false));
+    set_container_settings(config.container.as_ref().map(|c| c.to_settings()).unwrap_or_default());
+    set_screen_reader(config.accessibility.as_ref().and_then(|a| a.screen_reader).unwrap_or(false));
+}
+
+/// O menu de um `select`: o índice da opção escolhida, ou `None` com Esc,
+/// Ctrl+C ou fim da entrada. Sem terminal, lê o número do STDIN, como o bash:
+/// as opções numeradas vão para o STDERR com `list` ou depois de uma linha
+/// vazia.
+fn choose_option(prompt: &str, options: &[String], mut list: bool) -> Option<usize> {
+    if io::stdin().is_terminal() {
+        return inquire::Select::new(prompt, options.to_vec()).raw_prompt().ok().map(|choice| choice.index);
+    }
+    let mut stdin = io::stdin().lock();
+    loop {
+        if list {
+            for (i, option) in options.iter().enumerate() {
+                eprintln!("{}) {}", i + 1, option);
+            }
+        }
+        eprint!("{} ", prompt);
+        let mut reply = String::new();
+        if stdin.read_line(&mut reply).ok()? == 0 {
+            eprintln!();
+            return None;
+        }
+        if let Some(index) = menu_choice(&reply, options.len()) {
+            return Some(index);
+        }
+        list = reply.trim().is_empty();
+    }
 }
 
 impl CliosShell {
     /// Construtor: Inicializa a Shell e configura o motor de Script (Rhai).
     pub fn new(config: CliosConfig) -> Self {
-        let engine = create_rhai_engine();
+        let plugins = SharedPluginRegistry::default();
+        let jobs = new_job_list();
+        let mut engine = create_rhai_engine(&plugins);
+        register_job_api(&mut engine, &jobs);
+        apply_config_settings(&config);
+        init_default_remote();
 
         Self {
-            aliases: HashMap::new(),
+            aliases: config.aliases.clone().unwrap_or_default(),
             rhai_engine: engine,
             rhai_scope: Scope::new(),
-            plugin_ast: None,
+            plugin_set: PluginSet::default(),
+            plugins,
             last_exit_code: 0,
             previous_dir: None,
             config,
-            jobs: new_job_list(),
+            jobs,
+            traps: HashMap::new(),
+            exit_requested: None,
+            select_depth: 0,
+            break_requested: false,
+            includes: IncludeStack::default(),
+            abbreviations: Arc::new(RwLock::new(load_abbreviations())),
+            initial_env: env::vars().collect(),
+            demo: None,
+            exit_warned: false,
+            last_dir: None,
+            config_plugins: Vec::new(),
+            profile_changed: false,
+        }
+    }
+
+    /// Restaura uma sessão salva (usado por `session restore` e `--session`).
+    pub fn restore_session(&mut self, name: &str) -> Result<(), String> {
+        let saved = session::load(name)?;
+        session::apply(&saved, &mut self.aliases, &mut self.previous_dir)
+    }
+
+    /// Builtins que executam outras linhas de comando (`watch`, `repeat`, `loop`).
+    ///
+    /// Recebem os tokens sem expansão, já que cada execução expande de novo
+    /// (globs e variáveis podem mudar entre uma execução e outra).
+    fn run_control_builtin(&mut self, tokens: &[String]) -> Option<i32> {
+        let args = &tokens[1..];
+        match tokens.first()?.as_str() {
+            "watch" => Some(match parse_watch_args(args) {
+                Ok(opts) => run_watch(&opts, &mut |cmd| {
+                    self.process_input_line(cmd);
+                    self.last_exit_code
+                }),
+                Err(e) => {
+                    eprintln!("watch: {}", e);
+                    eprintln!("{}", usage("watch"));
+                    2
+                }
+            }),
+            "repeat" | "loop" => {
+                let parsed = if tokens[0] == "repeat" {
+                    parse_repeat_args(args)
+                } else {
+                    parse_loop_args(args)
+                };
+                Some(match parsed {
+                    Ok(opts) => run_repeat(&opts, &mut |cmd| {
+                        self.process_input_line(cmd);
+                        self.last_exit_code
+                    }),
+                    Err(e) => {
+                        eprintln!("{}: {}", tokens[0], e);
+                        eprintln!("{}", usage(&tokens[0]));
+                        2
+                    }
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Builtins que usam o estado da shell (sessão, abreviações, perfis...).
+    /// Os demais ficam no [`handle_builtin`]; a documentação de todos, no
+    /// registro ([`crate::registry`]).
+    fn run_shell_builtin(&mut self, tokens: &[String]) -> Option<i32> {
+        let args = &tokens[1..];
+        let code = match tokens.first()?.as_str() {
+            "session" => self.run_session_command(args),
+            "abbr" => self.run_abbr_command(args),
+            "run" => self.run_task_command(args),
+            "profile" => self.run_profile_command(args),
+            "demo" => self.run_demo_command(args),
+            "export-compat" => {
+                print!("{}", self.export_compat());
+                0
+            }
+            "each" => self.run_each_command(args, None),
+            "include" => self.run_include(args),
+            _ => return None,
+        };
+        Some(code)
+    }
+
+    /// `$(...)`: executa a linha num subshell ([`subshell`]) e devolve o STDOUT.
+    ///
+    /// `rhai <código>` não vira subshell: roda no engine da sessão e devolve a
+    /// saída do script (como o `print`), sem o `=> ` do builtin.
+    fn capture_subshell(&mut self, line: &str) -> String {
+        // `$(secret get NOME)` fica na shell: a senha digitada num filho se perderia
+        if let Some(value) = capture_secret(line) {
+            return value;
+        }
+        // `$(rhai ...)` roda na própria shell, sem `fork`: o script vê e muda o
+        // scope da sessão
+        let rhai_code = line.trim_start().strip_prefix("rhai").filter(|rest| rest.starts_with(char::is_whitespace));
+        if let Some(code) = rhai_code {
+            self.load_pending_plugins();
+            return match eval_rhai_capture(&mut self.rhai_engine, &mut self.rhai_scope, &self.plugin_set, code.trim()) {
+                Ok(out) => out.trim().to_string(),
+                Err(e) => {
+                    eprintln!("{}", t!("tag.warning", t!("subshell.rhai_failed_with", e)));
+                    String::new()
+                }
+            };
+        }
+        let result = subshell(SubshellMode::Capture, || {
+            self.process_input_line(line);
+            self.last_exit_code
+        });
+        match result {
+            Ok(result) => {
+                if result.code != 0 {
+                    let prog = line.split_whitespace().next().unwrap_or(line);
+                    eprintln!("{}", t!("tag.warning", t!("subshell.command_failed", prog)));
+                }
+                result.stdout.trim().to_string()
+            }
+            Err(e) => {
+                eprintln!("{}", t!("tag.error", fork_error(&e)));
+                String::new()
+            }
+        }
+    }
+
+    /// Executa `line` num subshell ([`subshell`]): na frente, devolvendo o
+    /// código de saída dele, ou em background, como um job.
+    fn run_subshell(&mut self, line: &str, background: bool) -> i32 {
+        if background && let Err(e) = check_job_slot(count_active_jobs(&self.jobs)) {
+            eprintln!("{}", t!("tag.error", e));
+            return 1;
+        }
+        let mode = if background { SubshellMode::Background } else { SubshellMode::Foreground };
+        let result = subshell(mode, || {
+            if background {
+                disable_job_control();
+                release_terminal();
+            }
+            self.process_input_line(line);
+            self.last_exit_code
+        });
+        match result {
+            Ok(result) if background => {
+                add_job(&self.jobs, result.pid.as_raw(), line.trim().to_string());
+                println!("{}", t!("jobs.started", result.pid));
+                0
+            }
+            Ok(result) => result.code,
+            Err(e) => {
+                eprintln!("{}", t!("tag.error", fork_error(&e)));
+                1
+            }
+        }
+    }
+
+    /// `case palavra in padrão) comandos ;; esac`: roda o corpo do primeiro
+    /// braço que casa e devolve o código do último comando dele (0 se nenhum
+    /// casa).
+    fn run_case(&mut self, text: &str) -> i32 {
+        let statement = match parse_case(text) {
+            Ok(statement) => statement,
+            Err(e) => {
+                eprintln!("{}", t!("tag.syntax", e));
+                return 2;
+            }
+        };
+        let (word, _) = self.expand_case_word(&statement.word);
+        let arm = statement.arms.iter().find(|arm| {
+            arm.patterns.iter().any(|raw| {
+                let (pattern, quoted) = self.expand_case_word(raw);
+                pattern_matches(&pattern, &word, quoted)
+            })
+        });
+        match arm {
+            Some(arm) => self.run_body(&arm.body),
+            None => 0,
+        }
+    }
+
+    /// Roda os comandos do corpo de um `case` ou `select`, parando no `exit`,
+    /// no Ctrl+C ou num `break` dentro de um `select`.
+    fn run_body(&mut self, body: &str) -> i32 {
+        self.last_exit_code = 0;
+        for command in split_statements(body) {
+            if command == "break" && self.select_depth > 0 {
+                self.break_requested = true;
+                return 0;
+            }
+            self.process_input_line(&command);
+            if self.break_requested || self.exit_requested.is_some() || self.last_exit_code == EXIT_INTERRUPTED {
+                break;
+            }
+        }
+        self.last_exit_code
+    }
+
+    /// `select nome in palavras; do ... done`: menu do `inquire` em volta do
+    /// corpo, até um `break`, Esc ou Ctrl+C.
+    fn run_select(&mut self, text: &str) -> i32 {
+        let statement = match parse_select(text) {
+            Ok(statement) => statement,
+            Err(e) => {
+                eprintln!("{}", t!("tag.syntax", e));
+                return 2;
+            }
+        };
+        let words = expand_subshells_with(&statement.words.join(" "), &mut |line| Some(self.capture_subshell(line)));
+        let Some(words) = shlex::split(&words) else {
+            eprintln!("{}", t!("tag.syntax", t!("exec.parse_failed", words)));
+            return 2;
+        };
+        let options = expand_tokens(words, self.last_exit_code, std::process::id());
+        if options.is_empty() {
+            return 0;
+        }
+        let prompt = env::var("PS3").unwrap_or_else(|_| t!("select.prompt").to_string());
+
+        let mut status = 0;
+        self.select_depth += 1;
+        let mut list = true;
+        while let Some(index) = choose_option(&prompt, &options, std::mem::take(&mut list)) {
+            unsafe {
+                env::set_var(&statement.name, &options[index]);
+                env::set_var("REPLY", (index + 1).to_string());
+            }
+            status = self.run_body(&statement.body);
+            if std::mem::take(&mut self.break_requested) {
+                break;
+            }
+            if self.exit_requested.is_some() || status == EXIT_INTERRUPTED {
+                break;
+            }
+        }
+        self.select_depth -= 1;
+        status
+    }
+
+    /// Expande a palavra ou um padrão do `case` (`$(...)`, variáveis e `~`,
+    /// sem globs) e diz se havia aspas.
+    fn expand_case_word(&mut self, raw: &str) -> (String, bool) {
+        let expanded = expand_subshells_with(raw, &mut |line| Some(self.capture_subshell(line)));
+        let quoted = expanded.contains(['\'', '"']);
+        let unquoted = shlex::split(&expanded).map(|words| words.join(" ")).unwrap_or(expanded);
+        let home = env::var("HOME").unwrap_or_else(|_| "/".to_string());
+        let word = expand_variables_token(&unquoted, self.last_exit_code, std::process::id());
+        (expand_tilde_token(word, &home).into_owned(), quoted)
+    }
+
+    /// Prefixo `clean-env`: executa o resto da linha com o ambiente mínimo.
+    fn run_clean_env(&mut self, opts: CleanEnvOptions) -> i32 {
+        let mut keep = match self.config.clean_env.as_ref().and_then(|c| c.keep.clone()) {
+            Some(keep) => keep,
+            None => DEFAULT_KEEP.iter().map(|s| s.to_string()).collect(),
+        };
+        keep.extend(opts.keep);
+        let vars = minimal_env(&keep, |name| env::var(name).ok());
+        with_clean_env(vars, || self.execute_single_command_block(&opts.command))
+    }
+
+    /// Builtin `each`: lê do STDIN ou da saída de `source` (estágios anteriores
+    /// da pipeline) e executa um comando por linha.
+    fn run_each_command(&mut self, args: &[String], source: Option<Vec<Vec<String>>>) -> i32 {
+        let opts = match parse_each_args(args) {
+            Ok(opts) => opts,
+            Err(e) => {
+                eprintln!("each: {}", e);
+                eprintln!("{}", usage("each"));
+                return 2;
+            }
+        };
+        let jobs = self.jobs.clone();
+        let mut run = |cmd: &str| {
+            self.process_input_line(cmd);
+            self.last_exit_code
+        };
+
+        let Some(source) = source else {
+            let lines = io::stdin().lock().lines().map_while(Result::ok);
+            return run_each(&opts, lines, &mut run, &jobs);
+        };
+
+        // `range ... | each`: os números vão direto, sem pipe
+        if let [only] = &source[..]
+            && only.first().is_some_and(|c| c == "range")
+        {
+            return match range_spec(only) {
+                Ok(spec) => run_each(&opts, spec.values().map(|n| spec.format(n)), &mut run, &jobs),
+                Err(code) => code,
+            };
+        }
+
+        let mut child = match spawn_pipeline_capture(&source) {
+            Ok(Some(child)) => child,
+            Ok(None) => return 0,
+            Err(code) => return code,
+        };
+        let code = match child.stdout.take() {
+            Some(stdout) => {
+                let lines = BufReader::new(stdout).lines().map_while(Result::ok);
+                run_each(&opts, lines, &mut run, &jobs)
+            }
+            None => 0,
+        };
+        let _ = child.wait();
+        code
+    }
+
+    /// Builtin `abbr`: lista, cria (`abbr nome 'expansão'`) ou remove (`abbr -e nome`).
+    fn run_abbr_command(&mut self, args: &[String]) -> i32 {
+        let Ok(mut abbrs) = self.abbreviations.write() else {
+            return 1;
+        };
+
+        match args {
+            [] => {
+                for (name, expansion) in abbrs.iter() {
+                    println!("abbr {} {}", name, quote_word(expansion));
+                }
+                return 0;
+            }
+            [flag, name] if flag == "-e" || flag == "--erase" => {
+                if abbrs.remove(name).is_none() {
+                    eprintln!("{}", t!("abbr.not_found", name));
+                    return 1;
+                }
+            }
+            [name, expansion @ ..] if !expansion.is_empty() && !name.starts_with('-') => {
+                abbrs.insert(name.clone(), expansion.join(" "));
+            }
+            _ => {
+                eprintln!("{}", usage("abbr"));
+                return 1;
+            }
+        }
+
+        if let Err(e) = save_abbreviations(&abbrs) {
+            eprintln!("{}", t!("abbr.save_failed", e));
+            return 1;
+        }
+        0
+    }
+
+    /// Aliases e abreviações em sintaxe do bash (`export-compat`).
+    pub fn export_compat(&self) -> String {
+        let abbreviations = self.abbreviations.read().map(|a| a.clone()).unwrap_or_default();
+        let mut rhai_only: Vec<String> = self.plugins.borrow().commands().map(|c| c.name.clone()).collect();
+        for plugin in self.plugin_set.iter() {
+            rhai_only.extend(plugin.exports().filter(|f| !f.name.starts_with('_')).map(|f| f.name.to_string()));
+        }
+        rhai_only.sort();
+        rhai_only.dedup();
+        export_compat(&self.aliases, &abbreviations, &rhai_only)
+    }
+
+    /// Builtin `run`: executa uma tarefa do `clios.toml` do projeto.
+    fn run_task_command(&mut self, args: &[String]) -> i32 {
+        let cwd = match env::current_dir() {
+            Ok(cwd) => cwd,
+            Err(e) => {
+                eprintln!("run: {}", e);
+                return 1;
+            }
+        };
+        let Some(path) = find_tasks_file(&cwd) else {
+            eprintln!("run: {}", t!("tasks.no_file", TASKS_FILE));
+            return 1;
+        };
+        let tasks = match load_tasks(&path) {
+            Ok(tasks) => tasks,
+            Err(e) => {
+                eprintln!("{}", t!("tag.config_error", e));
+                return 1;
+            }
+        };
+
+        let (name, task_args) = match args {
+            [] => {
+                print!("{}", format_task_list(&path, &tasks));
+                return 0;
+            }
+            [flag] if flag == "--list" || flag == "-l" => {
+                print!("{}", format_task_list(&path, &tasks));
+                return 0;
+            }
+            [name, rest @ ..] if !name.starts_with('-') => (name, rest),
+            _ => {
+                eprintln!("{}", usage("run"));
+                return 2;
+            }
+        };
+        let Some(task) = tasks.iter().find(|task| &task.name == name) else {
+            let names: Vec<&str> = tasks.iter().map(|task| task.name.as_str()).collect();
+            eprintln!("run: {}", t!("tasks.not_found", name, names.join(", ")));
+            return 1;
+        };
+
+        // Os passos rodam na raiz do projeto; a shell volta para onde estava
+        let root = path.parent().unwrap_or(Path::new("."));
+        if let Err(e) = env::set_current_dir(root) {
+            eprintln!("run: {}: {}", root.display(), e);
+            return 1;
+        }
+        let mut code = 0;
+        for command in task.commands(task_args) {
+            eprintln!("\x1b[2m▸ {}\x1b[0m", command);
+            self.process_input_line(&command);
+            code = self.last_exit_code;
+            if code != 0 || self.exit_requested.is_some() {
+                break;
+            }
+        }
+        let _ = env::set_current_dir(&cwd);
+        code
+    }
+
+    /// Builtin `session`: save | restore | list | delete.
+    fn run_session_command(&mut self, args: &[String]) -> i32 {
+        let action = args.first().map(|s| s.as_str()).unwrap_or("list");
+        let name = args.get(1).map(|s| s.as_str());
+
+        match (action, name) {
+            ("list", _) => {
+                let names = session::list();
+                if names.is_empty() {
+                    println!("{}", t!("session.none", session::sessions_dir().display()));
+                }
+                for n in names {
+                    println!("{}", n);
+                }
+                0
+            }
+            ("save", Some(name)) => {
+                let current = session::capture(&self.initial_env, &self.aliases, &self.previous_dir);
+                match session::save(name, &current) {
+                    Ok(path) => {
+                        println!("{}", t!("session.saved", name, path.display()));
+                        0
+                    }
+                    Err(e) => {
+                        eprintln!("session: {}", e);
+                        1
+                    }
+                }
+            }
+            ("restore", Some(name)) => match self.restore_session(name) {
+                Ok(()) => 0,
+                Err(e) => {
+                    eprintln!("session: {}", e);
+                    1
+                }
+            },
+            ("delete", Some(name)) => match session::delete(name) {
+                Ok(()) => 0,
+                Err(e) => {
+                    eprintln!("session: {}", e);
+                    1
+                }
+            },
+            _ => {
+                eprintln!("{}", usage("session"));
+                1
+            }
+        }
+    }
+
+    /// Pede o encerramento da sessão com `code`.
+    ///
+    /// Se houver jobs parados, o primeiro pedido apenas avisa (como no bash);
+    /// um segundo `exit` seguido confirma a saída.
+    pub fn request_exit(&mut self, code: i32) -> bool {
+        if !self.exit_warned && count_stopped_jobs(&self.jobs) > 0 {
+            eprintln!("{}", t!("exit.stopped_jobs"));
+            self.exit_warned = true;
+            return false;
+        }
+        self.exit_requested = Some(code);
+        true
+    }
+
+    /// Caminho único de encerramento da shell.
+    ///
+    /// Executa o `trap ... EXIT`, o hook Rhai `on_exit()` e encerra os jobs
+    /// restantes. Retorna o código de saída final do processo.
+    pub fn shutdown(&mut self) -> i32 {
+        let code = self.exit_requested.take().unwrap_or(self.last_exit_code);
+
+        // Remove antes de executar: um `exit` dentro do trap não pode recursar
+        if let Some(cmd) = self.traps.remove("EXIT") {
+            self.process_input_line(&cmd);
+        }
+        self.exit_requested = Some(code);
+
+        for plugin in self.plugin_set.iter() {
+            if plugin.exports().any(|f| f.name == "on_exit" && f.params.is_empty())
+                && let Err(e) = self.rhai_engine.call_fn::<rhai::Dynamic>(
+                    &mut self.rhai_scope,
+                    &self.plugin_set.context(Some(&plugin.path)),
+                    "on_exit",
+                    (),
+                )
+            {
+                eprintln!("{}", t!("exit.on_exit_failed", format_trace(&e, Some("on_exit"), Some(&plugin.path))));
+            }
+        }
+
+        hangup_jobs(&self.jobs);
+        flush_queued_output();
+        code
+    }
+
+    /// Roda os hooks de diretório se o diretório mudou desde o último prompt
+    /// (na primeira chamada, o diretório inicial conta como uma entrada) e,
+    /// com `auto_ls`, lista o novo diretório.
+    pub fn check_directory_change(&mut self) {
+        let Ok(cwd) = env::current_dir() else {
+            return;
+        };
+        if self.last_dir.as_ref() == Some(&cwd) {
+            return;
+        }
+        let old = self.last_dir.take();
+        // O status do prompt continua sendo o do comando do usuário
+        let code = self.last_exit_code;
+        self.run_cd_hooks(old.as_deref(), &cwd);
+        self.last_exit_code = code;
+        // Um hook que muda de diretório não dispara os hooks de novo
+        self.last_dir = env::current_dir().ok();
+
+        let behavior = self.config.behavior.as_ref();
+        if old.is_some() && behavior.and_then(|b| b.auto_ls).unwrap_or(false) {
+            let max = behavior.and_then(|b| b.auto_ls_max).unwrap_or(DEFAULT_AUTO_LS_MAX);
+            auto_list(self.last_dir.as_deref().unwrap_or(&cwd), max);
+        }
+    }
+
+    /// Hooks de mudança de diretório: a ativação do `[toolchain]` para as
+    /// versões que passaram a valer e a função Rhai `on_cd(antigo, novo)`.
+    fn run_cd_hooks(&mut self, old: Option<&Path>, new: &Path) {
+        if let Some(config) = self.config.toolchain.clone() {
+            let before = old.map(detect_toolchains).unwrap_or_default();
+            let after = detect_toolchains(new);
+            for toolchain in entered_toolchains(&before, &after) {
+                if let Some(template) = config.activation(toolchain.kind) {
+                    let command = activation_command(template, toolchain);
+                    eprintln!("\x1b[2m▸ {}\x1b[0m", command);
+                    self.process_input_line(&command);
+                }
+            }
+        }
+
+        let old = old.map(|p| p.display().to_string()).unwrap_or_default();
+        let new = new.display().to_string();
+        for plugin in self.plugin_set.iter() {
+            if plugin.exports().any(|f| f.name == "on_cd" && f.params.len() == 2)
+                && let Err(e) = self.rhai_engine.call_fn::<rhai::Dynamic>(
+                    &mut self.rhai_scope,
+                    &self.plugin_set.context(Some(&plugin.path)),
+                    "on_cd",
+                    (old.clone(), new.clone()),
+                )
+            {
+                eprintln!("{}", t!("cd.on_cd_failed", format_trace(&e, Some("on_cd"), Some(&plugin.path))));
+            }
         }
     }
 
@@ -128,97 +775,349 @@ impl CliosShell {
     pub fn load_plugin(&mut self, path: &str) -> Result<(), String> {
         // Verificar se o arquivo existe
         if !std::path::Path::new(path).exists() {
-            return Err(format!("\x1b[1;31m[ERRO PLUGIN]\x1b[0m Arquivo não encontrado: {}", path));
+            return Err(t!("tag.plugin_error", t!("plugin.file_not_found", path)));
         }
 
         match self.rhai_engine.compile_file(path.into()) {
-            Ok(new_ast) => {
-                if let Some(ref mut existing_ast) = self.plugin_ast {
-                    *existing_ast += new_ast;
-                } else {
-                    self.plugin_ast = Some(new_ast);
-                }
-                Ok(())
-            }
+            Ok(new_ast) => install_plugin(&mut self.rhai_engine, &mut self.plugin_set, &self.plugins, new_ast, path),
             Err(e) => {
-                Err(format!("\x1b[1;31m[ERRO PLUGIN]\x1b[0m Falha ao compilar '{}'\n  Detalhes: {}", path, e))
+                Err(t!("tag.plugin_error", t!("plugin.compile_failed_details", path, e)))
             }
         }
     }
 
     /// NÍVEL 17: Auto-Loader de Plugins
+    ///
+    /// Com `lazy_plugins` (padrão), cada arquivo só é indexado aqui e compilado
+    /// no primeiro uso de um dos seus nomes ([`Self::load_pending_plugin`]).
     pub fn load_auto_plugins(&mut self) {
-        let home = env::var("HOME").unwrap_or_else(|_| ".".to_string());
-        let plugins_dir = Path::new(&home).join(".clios_plugins");
-
-        if let Ok(entries) = fs::read_dir(plugins_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-
-                if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("rhai")
-                    && let Some(path_str) = path.to_str() {
-                        if let Err(e) = self.load_plugin(path_str) {
-                            eprintln!("{}", e);
-                        }
+        let lazy = self.config.lazy_plugins.unwrap_or(true);
+        let paths = plugin_dir_files();
+        self.plugins.borrow_mut().set_order(paths.clone());
+
+        for path in &paths {
+            if lazy
+                && let Ok(source) = fs::read_to_string(path)
+                && let manifest = PluginManifest::scan(&source)
+                && !manifest.needs_eager_load()
+            {
+                self.plugins.borrow_mut().defer(path, &manifest);
+            } else if let Err(e) = self.load_plugin(path) {
+                eprintln!("{}", e);
+            }
+        }
+    }
+
+    /// Compila o plugin adiado que fornece `name`, se houver.
+    pub fn load_pending_plugin(&mut self, name: &str) {
+        let path = self.plugins.borrow_mut().take_pending(name);
+        if let Some(path) = path
+            && let Err(e) = self.load_plugin(&path)
+        {
+            eprintln!("{}", e);
+        }
+    }
+
+    /// Compila todos os plugins adiados (o `rhai`, o `help` e o `plugins`
+    /// precisam de todas as funções e comandos).
+    pub fn load_pending_plugins(&mut self) {
+        let paths = self.plugins.borrow_mut().take_all_pending();
+        for path in paths {
+            if let Err(e) = self.load_plugin(&path) {
+                eprintln!("{}", e);
+            }
+        }
+    }
+
+    /// Carrega os plugins listados em `plugins` no `.clios.toml` (cada um uma vez).
+    pub fn load_config_plugins(&mut self) {
+        let home = env::var("HOME").unwrap_or_default();
+        for path in self.config.plugins.clone().unwrap_or_default() {
+            let path = expand_tilde_token(Cow::Borrowed(&path), &home).into_owned();
+            if self.config_plugins.contains(&path) {
+                continue;
+            }
+            match self.load_plugin(&path) {
+                Ok(()) => self.config_plugins.push(path),
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+    }
+
+    /// Troca o perfil da sessão (`None` volta à configuração base), relendo o
+    /// arquivo de onde a configuração veio.
+    ///
+    /// Os aliases do perfil anterior saem (exceto os redefinidos na sessão) e
+    /// os plugins do novo são carregados; o editor de linha (modo de edição,
+    /// atalhos, histórico) continua como foi iniciado.
+    pub fn use_profile(&mut self, profile: Option<&str>) -> Result<(), String> {
+        let path = self.config.path.clone().unwrap_or_else(default_config_path);
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(_) if profile.is_none() => String::new(),
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        let mut config = parse_config(&contents, profile).map_err(|e| e.to_string())?;
+        config.path = Some(path);
+        apply_env_overrides(&mut config, |key| env::var(key).ok());
+
+        for (name, value) in self.config.aliases.iter().flatten() {
+            if self.aliases.get(name) == Some(value) {
+                self.aliases.remove(name);
+            }
+        }
+        self.aliases.extend(config.aliases.clone().unwrap_or_default());
+        apply_config_settings(&config);
+        init_language(config.general.as_ref().and_then(|g| g.language.as_deref()));
+        // Shells filhas (`clios`, `sh -c clios`) herdam o perfil
+        unsafe {
+            match profile {
+                Some(name) => env::set_var(PROFILE_VAR, name),
+                None => env::remove_var(PROFILE_VAR),
+            }
+        }
+        self.config = config;
+        self.load_config_plugins();
+        self.profile_changed = true;
+        Ok(())
+    }
+
+    /// Se a configuração mudou por `profile use` desde a última chamada (o
+    /// REPL relê o tema).
+    pub fn take_profile_change(&mut self) -> bool {
+        std::mem::take(&mut self.profile_changed)
+    }
+
+    /// Builtin `profile`: `profile [list]`, `profile use <nome>`, `profile off`.
+    fn run_profile_command(&mut self, args: &[String]) -> i32 {
+        let target = match args {
+            [] => None,
+            [cmd] if cmd == "list" => None,
+            [cmd] if cmd == "off" => Some(None),
+            [cmd, name] if cmd == "use" => Some(Some(name.as_str())),
+            _ => {
+                eprintln!("{}", usage("profile"));
+                return 2;
+            }
+        };
+        let Some(profile) = target else {
+            let path = self.config.path.clone().unwrap_or_else(default_config_path);
+            print!("{}", format_profile_list(&path, &self.config.profiles, self.config.active_profile.as_deref()));
+            return 0;
+        };
+        match self.use_profile(profile) {
+            Ok(()) => {
+                match profile {
+                    Some(name) => println!("{}", t!("profile.switched", name)),
+                    None => println!("{}", t!("profile.cleared")),
+                }
+                0
+            }
+            Err(e) => {
+                eprintln!("profile: {}", e);
+                1
+            }
+        }
+    }
+
+    /// Liga o modo de apresentação com o `[demo]` da configuração.
+    pub fn enable_demo(&mut self, offline: bool) {
+        self.demo = Some(self.config.demo.clone().unwrap_or_default().to_settings(offline));
+    }
+
+    /// Builtin `demo [on [--offline] | off]`.
+    fn run_demo_command(&mut self, args: &[String]) -> i32 {
+        match parse_demo_args(args) {
+            Ok(DemoAction::On { offline }) => {
+                self.enable_demo(offline);
+                let blocked = self.demo.as_ref().is_some_and(|d| d.block_network);
+                println!("{}", if blocked { t!("demo.on_offline") } else { t!("demo.on") });
+            }
+            Ok(DemoAction::Off) => {
+                self.demo = None;
+                println!("{}", t!("demo.off"));
+            }
+            Ok(DemoAction::Status) => match &self.demo {
+                Some(demo) => {
+                    println!("{}", t!("demo.status_on"));
+                    if demo.block_network {
+                        println!("  {}", t!("demo.status_network"));
+                    }
+                    if let Some(allow) = &demo.allow {
+                        println!("  {}", t!("demo.status_allow", allow.join(", ")));
                     }
+                }
+                None => println!("{}", t!("demo.status_off")),
+            },
+            Err(e) => {
+                eprintln!("demo: {}", e);
+                eprintln!("{}", usage("demo"));
+                return 2;
             }
         }
+        0
     }
 
     /// Lê o arquivo de configuração `~/.cliosrc` e executa linha por linha.
     pub fn load_config(&mut self) {
         if let Ok(home) = env::var("HOME") {
             let config_path = Path::new(&home).join(".cliosrc");
-            if config_path.exists()
-                && let Ok(file) = File::open(config_path) {
-                    let reader = BufReader::new(file);
-
-                    for (i, line) in reader.lines().enumerate() {
-                        if let Ok(l) = line {
-                            let l = l.trim();
-
-                            if !l.is_empty() && !l.starts_with('#') {
-                                if shlex::split(l).is_none() {
-                                    eprintln!(
-                                        "\x1b[1;31m[ERRO CONFIG]\x1b[0m .cliosrc Linha {}: Aspas não fechadas.",
-                                        i + 1
-                                    );
-                                    eprintln!("--> Conteúdo: {}", l);
-                                    continue;
-                                }
-
-                                self.process_input_line(l);
-                            }
-                        }
+            if config_path.exists() {
+                self.load_rc_file(&config_path);
+            }
+        }
+    }
+
+    /// Executa um arquivo rc (`~/.cliosrc` ou o de `--rc <arquivo>`) linha por linha.
+    pub fn load_rc_file(&mut self, config_path: &Path) {
+        if let Err(e) = self.run_rc_file(config_path) {
+            eprintln!("{}", t!("tag.config_error", e));
+        }
+    }
+
+    /// Builtin `include <arquivo...>`: roda arquivos no formato do
+    /// `~/.cliosrc`, um depois do outro (ver módulo include).
+    fn run_include(&mut self, args: &[String]) -> i32 {
+        if args.is_empty() {
+            eprintln!("{}", usage("include"));
+            return 2;
+        }
+        let mut code = 0;
+        for name in args {
+            let path = self.includes.resolve(name);
+            if let Err(e) = self.run_rc_file(&path) {
+                eprintln!("include: {}", e);
+                code = 1;
+            }
+        }
+        code
+    }
+
+    /// Roda um arquivo rc; os `include` dentro dele são relativos ao
+    /// diretório dele. Falha se ele não abre ou se já está sendo lido.
+    fn run_rc_file(&mut self, config_path: &Path) -> Result<(), String> {
+        let file = File::open(config_path).map_err(|e| format!("{}: {}", config_path.display(), e))?;
+        self.includes.enter(config_path)?;
+        let reader = BufReader::new(file);
+        // Um `case` ocupa várias linhas
+        let mut statements = StatementBuffer::default();
+
+        for (i, line) in reader.lines().enumerate() {
+            if let Ok(l) = line {
+                let l = l.trim();
+
+                if !l.is_empty() && !l.starts_with('#') {
+                    if shlex::split(l).is_none() {
+                        eprintln!(
+                            "{}",
+                            t!("tag.config_error", t!("config.rc_unclosed_quotes", config_path.display(), i + 1))
+                        );
+                        eprintln!("{}", t!("config.rc_content", l));
+                        continue;
+                    }
+
+                    if let Some(statement) = statements.push(l) {
+                        self.process_input_line(&statement);
                     }
                 }
+            }
+        }
+        if let Some(statement) = statements.finish() {
+            self.process_input_line(&statement);
+        }
+        self.includes.leave();
+        Ok(())
+    }
+
+    /// Pede ao sugestor do `[suggest]` um comando para a pergunta do `??`.
+    pub fn suggest(&mut self, query: &str) -> Result<String, SuggestError> {
+        if query.is_empty() {
+            return Err(SuggestError::EmptyQuery);
         }
+        let source = SuggestSource::from_config(self.config.suggest.as_ref()).ok_or(SuggestError::NotConfigured)?;
+        // Leitor de tela: a mensagem fica numa linha própria, sem ser apagada
+        let status = io::stderr().is_terminal() && !screen_reader();
+        if status {
+            eprint!("\x1b[2m{}\x1b[0m", t!("suggest.waiting", source.label()));
+            let _ = io::stderr().flush();
+        } else if screen_reader() {
+            eprintln!("{}", t!("suggest.waiting", source.label()));
+        }
+        let result = match &source {
+            SuggestSource::Command(command) => run_external(command, query, self.last_exit_code),
+            SuggestSource::Function(name) => {
+                self.load_pending_plugin(name);
+                let owner = self.plugin_set.owner(name).ok_or_else(|| SuggestError::Failed(t!("suggest.function_missing", name)))?;
+                let ast = self.plugin_set.context(Some(&owner.path));
+                self.rhai_engine
+                    .call_fn::<rhai::Dynamic>(&mut self.rhai_scope, &ast, name, (query.to_string(),))
+                    .map_err(|e| SuggestError::Failed(t!("suggest.function_failed", name, format_trace(&e, Some(name), ast.source()))))
+                    .and_then(|value| parse_suggestion(&value.to_string()).ok_or(SuggestError::NoSuggestion))
+            }
+        };
+        if status {
+            eprint!("\r\x1b[K");
+        }
+        result
     }
 
     /// O Cérebro da Execução: Processa uma linha de entrada bruta.
     /// Suporta operadores && (AND) e || (OR) com curto-circuito.
     pub fn process_input_line(&mut self, input: &str) {
-        let input_expanded = expand_subshells(input);
+        // `?? pergunta` fora do modo interativo: a sugestão vai para o STDOUT
+        if let Some(query) = parse_query(input) {
+            self.last_exit_code = match self.suggest(query) {
+                Ok(command) => {
+                    println!("{}", command);
+                    0
+                }
+                Err(e) => {
+                    eprintln!("??: {}", e);
+                    1
+                }
+            };
+            return;
+        }
 
-        let logical_parts = split_logical_operators(&input_expanded);
+        // `case ... esac` e `select ... done`: o corpo é expandido só quando roda
+        match compound_statement(input) {
+            Some(Compound::Case) => {
+                self.last_exit_code = self.run_case(input);
+                return;
+            }
+            Some(Compound::Select) => {
+                self.last_exit_code = self.run_select(input);
+                return;
+            }
+            None => {}
+        }
 
-        for part in logical_parts {
-            let expanded_part = expand_alias_string(&part.command, &self.aliases);
+        let input_expanded = expand_subshells_with(input, &mut |line| Some(self.capture_subshell(line)));
 
-            // Se o alias expandido contém operadores lógicos, processa recursivamente
-            if expanded_part != part.command && (expanded_part.contains("&&") || expanded_part.contains("||")) {
-                self.process_input_line(&expanded_part);
+        // Aliases: cada posição de comando (início, após |, && e ||) uma única vez
+        let input_expanded = expand_aliases_in_line(&input_expanded, &self.aliases);
 
-                // Aplica curto-circuito baseado no operador
-                match part.next_op {
-                    Some(LogicalOp::And) if self.last_exit_code != 0 => break,
-                    Some(LogicalOp::Or) if self.last_exit_code == 0 => break,
-                    _ => continue,
-                }
+        let logical_parts = split_logical_operators(&input_expanded);
+
+        for part in logical_parts {
+            if self.exit_requested.is_some() {
+                break;
             }
 
-            let exit_code = self.execute_single_command_block(&expanded_part);
+            // Modo de apresentação: comandos de rede ou fora do `allow` não rodam
+            let blocked = self.demo.as_ref().and_then(|demo| blocked_reason(&part.command, demo));
+            let exit_code = match blocked {
+                Some(reason) => {
+                    eprintln!("{}", t!("tag.error", reason));
+                    1
+                }
+                None => self.execute_single_command_block(&part.command),
+            };
             self.last_exit_code = exit_code;
+            // `$PIPESTATUS`: o código de cada comando da pipeline; fora de
+            // uma pipeline, só o do comando
+            let statuses = take_pipe_status().unwrap_or_else(|| vec![exit_code]);
+            assign_array("PIPESTATUS", statuses.iter().map(i32::to_string).collect(), false);
 
             // Curto-circuito baseado no operador
             match part.next_op {
@@ -229,6 +1128,102 @@ impl CliosShell {
         }
     }
 
+    /// Executa um comando já expandido: plugin, builtin ou programa externo.
+    /// Com `job_line`, o programa externo vira um job em background, listado
+    /// no `jobs` com essa linha.
+    fn run_command(&mut self, mut tokens: Vec<String>, job_line: Option<&str>) -> i32 {
+        // Modo safe_rm: `rm` vira `trash`
+        let safe_rm = self.config.behavior.as_ref().and_then(|b| b.safe_rm).unwrap_or(false);
+        if safe_rm && tokens[0] == "rm" {
+            tokens = rewrite_rm(tokens);
+        }
+
+        let cmd_name = tokens[0].clone();
+        let args = tokens[1..].to_vec();
+
+        // Plugin ainda não compilado: o primeiro uso compila
+        self.load_pending_plugin(&cmd_name);
+        if matches!(cmd_name.as_str(), "rhai" | "plugins" | "help" | "type" | "source" | "load") {
+            self.load_pending_plugins();
+        }
+
+        // 1. Tenta Plugin: comandos registrados, depois funções soltas
+        // (clonado: o callback pode chamar `register_command` de novo)
+        let registered = self.plugins.borrow().get(&cmd_name).cloned();
+        if let Some(command) = registered {
+            let ast = self.plugin_set.context(command.plugin.as_deref());
+            let code = run_plugin_command(&self.rhai_engine, Some(&ast), &command, &args);
+            return if take_interrupt() { EXIT_INTERRUPTED } else { code };
+        }
+        if let Some(owner) = self.plugin_set.owner(&cmd_name)
+            && try_execute_plugin_function(
+                &self.rhai_engine,
+                &mut self.rhai_scope,
+                &self.plugin_set.context(Some(&owner.path)),
+                &cmd_name,
+                args.clone(),
+            ) {
+                return if take_interrupt() { EXIT_INTERRUPTED } else { 0 };
+            }
+
+        // 2. Tenta Builtin: os que mexem no estado da shell, depois os demais
+        if let Some(code) = self.run_shell_builtin(&tokens) {
+            return code;
+        }
+
+        // Obtém arquivo de histórico da config
+        let history_file = self.config.history
+            .as_ref()
+            .and_then(|h| h.file.as_deref())
+            .unwrap_or(".clios_history");
+        
+        let jobs_ref = self.jobs.clone();
+        let result = handle_builtin(
+            &tokens,
+            &mut self.aliases,
+            &mut self.previous_dir,
+            &mut self.rhai_engine,
+            &mut self.rhai_scope,
+            &mut self.plugin_set,
+            &self.plugins,
+            |engine, plugin_set, plugins, path| -> Result<(), String> {
+                match engine.compile_file(path.into()) {
+                    Ok(new_ast) => install_plugin(engine, plugin_set, plugins, new_ast, path),
+                    Err(e) => Err(t!("tag.plugin_error", t!("plugin.compile_failed", path, e))),
+                }
+            },
+            history_file,
+            &jobs_ref,
+            &self.config,
+            &mut self.traps,
+            self.last_exit_code,
+        );
+
+        match result {
+            BuiltinResult::Handled => {
+                return if take_interrupt() { EXIT_INTERRUPTED } else { 0 };
+            }
+            BuiltinResult::Status(code) => return code,
+            BuiltinResult::Exit(code) => {
+                return if self.request_exit(code) { code } else { 1 };
+            }
+            BuiltinResult::NotBuiltin => {}
+        }
+
+        // 3. Executa como programa externo
+        if let Some(line) = job_line {
+            let capture = self
+                .config
+                .behavior
+                .as_ref()
+                .and_then(|b| b.capture_job_output)
+                .unwrap_or(false);
+            execute_job_control(tokens, line, &jobs_ref, capture)
+        } else {
+            execute_pipeline(vec![tokens])
+        }
+    }
+
     /// Executa um bloco de comando único (sem &&, mas pode ter Pipes |).
     fn execute_single_command_block(&mut self, input: &str) -> i32 {
         // Validação: entrada vazia ou só espaços
@@ -236,6 +1231,30 @@ impl CliosShell {
             return 0;
         }
 
+        // Um Ctrl+C anterior não pode vazar para este comando
+        clear_interrupt();
+
+        // `clean-env` vale para o bloco inteiro (pipeline e `&`)
+        match parse_clean_env_line(input) {
+            Some(Ok(opts)) => return self.run_clean_env(opts),
+            Some(Err(e)) => {
+                eprintln!("clean-env: {}", e);
+                eprintln!("{}", usage("clean-env"));
+                return 2;
+            }
+            None => {}
+        }
+
+        // `( ... )`: subshell com estado isolado
+        if let Some((group, background)) = subshell_group(input) {
+            return self.run_subshell(group, background);
+        }
+
+        // `a | b &`: a pipeline inteira vira um job, num subshell em background
+        if let Some(line) = background_pipeline(input) {
+            return self.run_subshell(line, true);
+        }
+
         let commands_raw = split_pipes_respecting_quotes(input);
 
         if commands_raw.len() == 1 {
@@ -248,13 +1267,26 @@ impl CliosShell {
                 raw_line
             };
 
+            // Calculadora: a expressão é lida crua (o `*` não vira glob)
+            if let Some(expr) = calc_expression(clean_line) {
+                return handle_calc(expr);
+            }
+
+            // `nome=(a b c)`: array da shell
+            if let Some(assignment) = parse_array_assignment(clean_line) {
+                let Some(items) = shlex::split(&assignment.items) else {
+                    eprintln!("{}", t!("tag.syntax", t!("exec.parse_failed", clean_line)));
+                    return 1;
+                };
+                let items = expand_tokens(items, self.last_exit_code, std::process::id());
+                assign_array(&assignment.name, items, assignment.append);
+                return 0;
+            }
+
             let mut tokens = match shlex::split(clean_line) {
                 Some(t) => t,
                 None => {
-                    eprintln!(
-                        "\x1b[1;31m[ERRO SINTAXE]\x1b[0m Falha ao processar: '{}'",
-                        clean_line
-                    );
+                    eprintln!("{}", t!("tag.syntax", t!("exec.parse_failed", clean_line)));
                     return 1;
                 }
             };
@@ -266,84 +1298,49 @@ impl CliosShell {
                     tokens = vec!["rhai".to_string(), code_part.to_string()];
                 }
 
+            // Builtins com `&` rodam num subshell: `cd /tmp &` não muda a shell
+            if background && tokens.first().is_some_and(|name| is_builtin(name)) {
+                return self.run_subshell(clean_line, true);
+            }
+
+            // Builtins que reexecutam a linha: recebem os tokens antes das expansões
+            if let Some(code) = self.run_control_builtin(&tokens) {
+                return code;
+            }
+
             // Expansões finais
             if tokens.first().map(|s| s.as_str()) != Some("rhai") {
-                tokens = expand_variables_with_state(tokens, self.last_exit_code, std::process::id());
-                tokens = expand_tilde(tokens);
-                tokens = expand_globs(tokens);
+                tokens = expand_tokens(tokens, self.last_exit_code, std::process::id());
             }
 
             if tokens.is_empty() {
                 return 0;
             }
 
-            let cmd_name = tokens[0].clone();
-            let args = tokens[1..].to_vec();
+            // `set -x` / `set -o dryrun`: antes do redirecionamento, que já
+            // cria o arquivo
+            if announce_command(std::slice::from_ref(&tokens)) {
+                return 0;
+            }
 
-            // 1. Tenta Plugin
-            if let Some(ast) = &self.plugin_ast
-                && try_execute_plugin_function(
-                    &self.rhai_engine,
-                    &mut self.rhai_scope,
-                    ast,
-                    &cmd_name,
-                    args.clone(),
-                ) {
-                    return 0;
+            // Builtins com `>`/`2>`: a saída vai para o arquivo sem subshell,
+            // então um `cd > log` continua mudando o diretório da shell
+            if is_builtin(&tokens[0]) && has_output_redirection(&tokens) {
+                let (clean, _, stdout_file, stderr_file) = parse_redirection(&tokens);
+                if stdout_file.is_none() && stderr_file.is_none() {
+                    return 1;
                 }
-
-            // 2. Tenta Builtin
-            // Obtém arquivo de histórico da config
-            let history_file = self.config.history
-                .as_ref()
-                .and_then(|h| h.file.as_deref())
-                .unwrap_or(".clios_history");
-            
-            let jobs_ref = self.jobs.clone();
-            let result = handle_builtin(
-                &tokens,
-                &mut self.aliases,
-                &mut self.previous_dir,
-                &mut self.rhai_engine,
-                &mut self.rhai_scope,
-                &mut self.plugin_ast,
-                |engine, ast, path| -> Result<(), String> {
-                    match engine.compile_file(path.into()) {
-                        Ok(new_ast) => {
-                            if let Some(existing_ast) = ast {
-                                *existing_ast += new_ast;
-                            } else {
-                                *ast = Some(new_ast);
-                            }
-                            Ok(())
-                        }
-                        Err(e) => Err(format!("\x1b[1;31m[ERRO PLUGIN]\x1b[0m Falha ao compilar '{}': {}", path, e)),
-                    }
-                },
-                history_file,
-                &jobs_ref,
-            );
-
-            match result {
-                BuiltinResult::Handled => return 0,
-                BuiltinResult::Exit => std::process::exit(0),
-                BuiltinResult::NotBuiltin => {}
+                return with_redirected_output(stdout_file, stderr_file, || self.run_command(clean, None));
             }
 
-            // 3. Executa como programa externo
-            if background {
-                execute_job_control(tokens, true, &jobs_ref);
-                0
-            } else {
-                execute_pipeline(vec![tokens])
-            }
+            // O `jobs` mostra a linha como foi digitada (`sleep $T`, não `sleep 5`)
+            self.run_command(tokens, background.then_some(clean_line))
         } else {
             // Pipeline
             let mut parsed_commands = Vec::new();
 
             for raw_cmd in commands_raw {
-                let expanded_cmd = expand_alias_string(&raw_cmd, &self.aliases);
-                let trimmed = expanded_cmd.trim();
+                let trimmed = raw_cmd.trim();
                 
                 if trimmed.is_empty() {
                     continue;
@@ -365,9 +1362,7 @@ impl CliosShell {
                     continue;
                 }
 
-                let tokens = expand_variables_with_state(tokens, self.last_exit_code, std::process::id());
-                let tokens = expand_tilde(tokens);
-                let tokens = expand_globs(tokens);
+                let tokens = expand_tokens(tokens, self.last_exit_code, std::process::id());
 
                 parsed_commands.push(tokens);
             }
@@ -375,6 +1370,34 @@ impl CliosShell {
             if parsed_commands.is_empty() {
                 return 0;
             }
+            if announce_command(&parsed_commands) {
+                return 0;
+            }
+
+            // `pipe recv nome | ...`: a FIFO vira a entrada da pipeline
+            if let Some(stages) = parsed_commands.first().and_then(|first| recv_stages(first)) {
+                match stages {
+                    Ok(stages) => {
+                        parsed_commands.splice(..1, stages);
+                    }
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        return 2;
+                    }
+                }
+            }
+
+            // `... | pipe send nome`: a saída da pipeline vai para a FIFO
+            if parsed_commands.last().and_then(|c| c.first()).map(|s| s.as_str()) == Some("pipe") {
+                let send_tokens = parsed_commands.pop().unwrap_or_default();
+                return send_pipeline(&send_tokens, &parsed_commands);
+            }
+
+            // `... | each 'cmd {}'`: a shell consome a saída da pipeline
+            if parsed_commands.last().and_then(|c| c.first()).map(|s| s.as_str()) == Some("each") {
+                let each_tokens = parsed_commands.pop().unwrap_or_default();
+                return self.run_each_command(&each_tokens[1..], Some(parsed_commands));
+            }
             
             execute_pipeline(parsed_commands)
         }
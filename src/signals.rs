@@ -0,0 +1,72 @@
+//! # Signals Module
+//!
+//! Estratégia de tratamento de `SIGINT` (Ctrl+C) da shell.
+//!
+//! ## Regras
+//! - **Prompt:** o `rustyline` lê em modo raw, então Ctrl+C chega como tecla.
+//! - **Shell:** nunca morre por `SIGINT`; o handler apenas marca uma flag atômica.
+//! - **Foreground:** os filhos recebem o sinal normalmente (handlers capturados
+//!   voltam ao padrão no `exec`, ao contrário de `SIG_IGN`, que seria herdado).
+//! - **Background:** jobs com `&` vivem em outro process group e não recebem o sinal.
+//! - **Builtins/Rhai:** consultam a flag e devolvem o controle com código 130.
+
+use crate::t;
+use nix::sys::signal::{self, SaFlags, SigAction, SigHandler, SigSet, Signal};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// -----------------------------------------------------------------------------
+// CONSTANTES
+// -----------------------------------------------------------------------------
+
+/// Código de saída POSIX para processo interrompido por `SIGINT` (128 + 2).
+pub const EXIT_INTERRUPTED: i32 = 130;
+
+/// Flag levantada pelo handler de `SIGINT`.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+// -----------------------------------------------------------------------------
+// HANDLER
+// -----------------------------------------------------------------------------
+
+/// Handler assíncrono: só pode tocar em atômicos (async-signal-safe).
+extern "C" fn handle_sigint(_: nix::libc::c_int) {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+/// Instala o handler de `SIGINT` da shell.
+///
+/// Deve ser chamado uma única vez na inicialização, antes de executar comandos.
+pub fn install_signal_handlers() {
+    let action = SigAction::new(
+        SigHandler::Handler(handle_sigint),
+        SaFlags::SA_RESTART,
+        SigSet::empty(),
+    );
+    if let Err(e) = unsafe { signal::sigaction(Signal::SIGINT, &action) } {
+        eprintln!("{}", t!("tag.warning", t!("signals.install_failed", e)));
+    }
+}
+
+// -----------------------------------------------------------------------------
+// FLAG ACCESS
+// -----------------------------------------------------------------------------
+
+/// Retorna `true` se um Ctrl+C chegou desde a última limpeza.
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Consome a flag, retornando se ela estava levantada.
+pub fn take_interrupt() -> bool {
+    INTERRUPTED.swap(false, Ordering::SeqCst)
+}
+
+/// Limpa a flag (chamado antes de cada comando).
+pub fn clear_interrupt() {
+    INTERRUPTED.store(false, Ordering::SeqCst);
+}
+
+/// Levanta a flag manualmente (útil em testes e para propagar interrupções).
+pub fn raise_interrupt() {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
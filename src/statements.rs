@@ -0,0 +1,452 @@
+//! # Statements Module
+//!
+//! Comandos compostos, que podem ocupar várias linhas: o `case` e o `select`.
+//!
+//! ## case
+//!
+//! ```bash
+//! case $1 in
+//!     start|up) docker compose up -d ;;
+//!     stop)     docker compose down ;;
+//!     *.log)    less $1 ;;
+//!     *)        echo "uso: $0 start|stop" ;;
+//! esac
+//! ```
+//!
+//! Os padrões são globs (`*`, `?`, `[abc]`), separados por `|`; um padrão
+//! entre aspas é comparado literalmente. Só o primeiro braço que casa roda,
+//! e o `;;` do último é opcional. Também cabe numa linha só:
+//! `case $x in a) echo A ;; *) echo outro ;; esac`.
+//!
+//! ## select
+//!
+//! ```bash
+//! select branch in $(git branch --format '%(refname:short)'); do
+//!     git checkout $branch
+//!     break
+//! done
+//! ```
+//!
+//! Mostra as opções no menu do `inquire` (setas, digitar filtra, Enter
+//! escolhe), guarda a escolha na variável e o número dela (a partir de 1) em
+//! `$REPLY`, roda o corpo e volta ao menu, até um `break` ou Esc/Ctrl+C. Sem
+//! terminal, lista as opções numeradas e lê o número do STDIN, como o bash. No
+//! corpo, `;` separa comandos.
+//!
+//! Scripts, o `.cliosrc` e o modo em lote juntam as linhas com
+//! [`StatementBuffer`]; no editor, o Enter quebra a linha até o `esac` ou o
+//! `done`.
+
+use crate::t;
+use glob::Pattern;
+
+// -----------------------------------------------------------------------------
+// TOKENS
+// -----------------------------------------------------------------------------
+
+/// Pedaço da linha visto pelo parser de comandos compostos.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TokenKind {
+    Word,
+    Newline,
+    /// `;;`
+    DoubleSemi,
+    /// `;` sozinho
+    Semi,
+    /// `|`, `||`, `&&`, `&`
+    Operator,
+    /// `(` solto
+    Open,
+    /// `)` solto
+    Close,
+}
+
+/// Um token com a posição (em bytes) no texto original.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Token<'a> {
+    kind: TokenKind,
+    text: &'a str,
+    start: usize,
+    /// Primeira palavra de um comando (onde `case` e `esac` são palavras-chave)
+    command_position: bool,
+}
+
+/// Separa o texto em palavras e operadores, respeitando aspas, `\` e `$(...)`.
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let bytes = text.as_bytes();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        let kind = match bytes[i] {
+            b' ' | b'\t' | b'\r' => {
+                i += 1;
+                continue;
+            }
+            b'\n' => {
+                i += 1;
+                TokenKind::Newline
+            }
+            b';' if bytes.get(i + 1) == Some(&b';') => {
+                i += 2;
+                TokenKind::DoubleSemi
+            }
+            b';' => {
+                i += 1;
+                TokenKind::Semi
+            }
+            b'|' | b'&' => {
+                i += if bytes.get(i + 1) == Some(&bytes[i]) { 2 } else { 1 };
+                TokenKind::Operator
+            }
+            b'(' => {
+                i += 1;
+                TokenKind::Open
+            }
+            b')' => {
+                i += 1;
+                TokenKind::Close
+            }
+            _ => {
+                i = word_end(bytes, i);
+                TokenKind::Word
+            }
+        };
+        let command_position = match tokens.last() {
+            None => true,
+            Some(prev) => prev.kind != TokenKind::Word || is_keyword(prev, "do"),
+        };
+        tokens.push(Token { kind, text: &text[start..i], start, command_position });
+    }
+    tokens
+}
+
+/// Fim da palavra que começa em `start`.
+fn word_end(bytes: &[u8], start: usize) -> usize {
+    let mut i = start;
+    let mut quote: Option<u8> = None;
+    // Parênteses abertos por `$(`
+    let mut depth = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(b'"') if c == b'\\' => i += 1,
+            Some(_) => {}
+            None => match c {
+                b'\\' => i += 1,
+                b'\'' | b'"' => quote = Some(c),
+                b'$' if bytes.get(i + 1) == Some(&b'(') => {
+                    depth += 1;
+                    i += 1;
+                }
+                b'(' if depth > 0 => depth += 1,
+                b')' if depth > 0 => depth -= 1,
+                _ if depth > 0 => {}
+                b' ' | b'\t' | b'\r' | b'\n' | b'|' | b'&' | b'(' | b')' | b';' => break,
+                _ => {}
+            },
+        }
+        i += 1;
+    }
+    i.min(bytes.len())
+}
+
+fn is_keyword(token: &Token, keyword: &str) -> bool {
+    token.kind == TokenKind::Word && token.command_position && token.text == keyword
+}
+
+// -----------------------------------------------------------------------------
+// OPEN STATEMENTS
+// -----------------------------------------------------------------------------
+
+/// Comando composto que começa a linha.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compound {
+    /// `case ... esac`
+    Case,
+    /// `select ... do ... done`
+    Select,
+}
+
+/// Quanto o token muda a profundidade: +1 quem abre, -1 quem fecha.
+fn nesting(token: &Token) -> i32 {
+    if is_keyword(token, "case") || is_keyword(token, "select") {
+        1
+    } else if is_keyword(token, "esac") || is_keyword(token, "done") {
+        -1
+    } else {
+        0
+    }
+}
+
+/// O texto tem um `case` sem o `esac` ou um `select` sem o `done`?
+pub fn open_statement(text: &str) -> bool {
+    let mut depth: i32 = 0;
+    for token in tokenize(text) {
+        depth = (depth + nesting(&token)).max(0);
+    }
+    depth > 0
+}
+
+/// O comando composto que começa o texto, se houver.
+pub fn compound_statement(text: &str) -> Option<Compound> {
+    let tokens = tokenize(text);
+    let first = tokens.first()?;
+    if is_keyword(first, "case") {
+        Some(Compound::Case)
+    } else if is_keyword(first, "select") {
+        Some(Compound::Select)
+    } else {
+        None
+    }
+}
+
+/// Junta as linhas de um script até formar um comando completo: um `case`
+/// vai do `case` ao `esac`; as outras linhas passam direto.
+#[derive(Debug, Default)]
+pub struct StatementBuffer {
+    pending: String,
+}
+
+impl StatementBuffer {
+    /// Recebe a próxima linha; devolve o comando quando ele está completo.
+    pub fn push(&mut self, line: &str) -> Option<String> {
+        if !self.pending.is_empty() {
+            self.pending.push('\n');
+        }
+        self.pending.push_str(line);
+        if open_statement(&self.pending) {
+            return None;
+        }
+        Some(std::mem::take(&mut self.pending))
+    }
+
+    /// Há linhas esperando o fim de um `case`?
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// O que sobrou no fim do arquivo (um `case` sem `esac`).
+    pub fn finish(&mut self) -> Option<String> {
+        (!self.pending.is_empty()).then(|| std::mem::take(&mut self.pending))
+    }
+}
+
+/// Os comandos de um corpo (de um braço do `case` ou de um `select`), um por
+/// linha ou separados por `;`.
+pub fn split_statements(text: &str) -> Vec<String> {
+    let mut buffer = StatementBuffer::default();
+    let mut statements: Vec<String> = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter_map(|l| buffer.push(l))
+        .collect();
+    statements.extend(buffer.finish());
+    statements.iter().flat_map(|s| split_semicolons(s)).collect()
+}
+
+/// Separa um comando nos `;` fora de aspas e de comandos compostos internos.
+fn split_semicolons(text: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for token in tokenize(text) {
+        depth = (depth + nesting(&token)).max(0);
+        if token.kind == TokenKind::Semi && depth == 0 {
+            parts.push(&text[start..token.start]);
+            start = token.start + 1;
+        }
+    }
+    parts.push(&text[start..]);
+    parts.into_iter().map(str::trim).filter(|p| !p.is_empty()).map(String::from).collect()
+}
+
+// -----------------------------------------------------------------------------
+// CASE
+// -----------------------------------------------------------------------------
+
+/// Um braço do `case`: `padrão|padrão) comandos ;;`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaseArm {
+    /// Padrões como foram escritos (com aspas, sem expandir)
+    pub patterns: Vec<String>,
+    /// Os comandos, como texto (uma ou mais linhas)
+    pub body: String,
+}
+
+/// `case palavra in ... esac`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaseStatement {
+    /// A palavra testada, como foi escrita (`$1`, `"$acao"`)
+    pub word: String,
+    pub arms: Vec<CaseArm>,
+}
+
+/// Interpreta um `case` completo.
+pub fn parse_case(text: &str) -> Result<CaseStatement, String> {
+    let tokens = tokenize(text);
+    let mut pos = 0;
+    let next_word = |pos: &mut usize| -> Option<&Token> {
+        while tokens.get(*pos)?.kind == TokenKind::Newline {
+            *pos += 1;
+        }
+        let token = tokens.get(*pos).filter(|t| t.kind == TokenKind::Word)?;
+        *pos += 1;
+        Some(token)
+    };
+
+    if next_word(&mut pos).is_none_or(|t| t.text != "case") {
+        return Err(t!("case.expected_case").to_string());
+    }
+    let word = next_word(&mut pos).ok_or(t!("case.missing_word"))?.text.to_string();
+    if next_word(&mut pos).is_none_or(|t| t.text != "in") {
+        return Err(t!("case.expected_in").to_string());
+    }
+
+    let mut arms = Vec::new();
+    loop {
+        while tokens.get(pos).is_some_and(|t| matches!(t.kind, TokenKind::Newline | TokenKind::DoubleSemi)) {
+            pos += 1;
+        }
+        let Some(token) = tokens.get(pos) else {
+            return Err(t!("case.missing_esac").to_string());
+        };
+        if token.kind == TokenKind::Word && token.text == "esac" {
+            pos += 1;
+            break;
+        }
+
+        // Padrões: `(a|b)` ou `a|b)`
+        if token.kind == TokenKind::Open {
+            pos += 1;
+        }
+        let mut patterns = Vec::new();
+        loop {
+            match tokens.get(pos) {
+                Some(t) if t.kind == TokenKind::Word => patterns.push(t.text.to_string()),
+                _ => return Err(t!("case.invalid_pattern").to_string()),
+            }
+            pos += 1;
+            match tokens.get(pos) {
+                Some(t) if t.kind == TokenKind::Operator && t.text == "|" => pos += 1,
+                Some(t) if t.kind == TokenKind::Close => {
+                    pos += 1;
+                    break;
+                }
+                _ => return Err(t!("case.missing_paren", patterns.join("|"))),
+            }
+        }
+
+        // Corpo: até o `;;` ou o `esac` deste `case` (os internos contam à parte)
+        let body_start = tokens.get(pos).map_or(text.len(), |t| t.start);
+        let mut depth = 0;
+        let mut body_end = None;
+        while let Some(t) = tokens.get(pos) {
+            if is_keyword(t, "case") {
+                depth += 1;
+            } else if is_keyword(t, "esac") {
+                if depth == 0 {
+                    body_end = Some(t.start);
+                    break;
+                }
+                depth -= 1;
+            } else if t.kind == TokenKind::DoubleSemi && depth == 0 {
+                body_end = Some(t.start);
+                break;
+            }
+            pos += 1;
+        }
+        let body_end = body_end.ok_or(t!("case.missing_esac"))?;
+        arms.push(CaseArm { patterns, body: text[body_start..body_end].trim().to_string() });
+    }
+
+    if let Some(extra) = tokens[pos..].iter().find(|t| t.kind != TokenKind::Newline) {
+        return Err(t!("case.trailing_text", &text[extra.start..]));
+    }
+    Ok(CaseStatement { word, arms })
+}
+
+/// O padrão (já expandido) casa com a palavra? Com `literal` (padrão entre
+/// aspas), só a igualdade conta.
+pub fn pattern_matches(pattern: &str, word: &str, literal: bool) -> bool {
+    if literal {
+        return pattern == word;
+    }
+    Pattern::new(pattern).map_or(pattern == word, |p| p.matches(word))
+}
+
+// -----------------------------------------------------------------------------
+// SELECT
+// -----------------------------------------------------------------------------
+
+/// `select nome in palavras; do ... done`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectStatement {
+    /// Variável que recebe a opção escolhida
+    pub name: String,
+    /// As opções como foram escritas (`*.txt`, `$(git branch)`), sem expandir
+    pub words: Vec<String>,
+    /// Os comandos entre o `do` e o `done`, como texto
+    pub body: String,
+}
+
+/// Interpreta um `select` completo.
+pub fn parse_select(text: &str) -> Result<SelectStatement, String> {
+    let tokens = tokenize(text);
+    let skip_separators = |pos: &mut usize| {
+        while tokens.get(*pos).is_some_and(|t| matches!(t.kind, TokenKind::Newline | TokenKind::Semi)) {
+            *pos += 1;
+        }
+    };
+
+    if tokens.first().is_none_or(|t| !is_keyword(t, "select")) {
+        return Err(t!("select.expected_select").to_string());
+    }
+    let name = match tokens.get(1) {
+        Some(t) if t.kind == TokenKind::Word && crate::arrays::is_valid_name(t.text) => t.text.to_string(),
+        _ => return Err(t!("select.missing_name").to_string()),
+    };
+    if tokens.get(2).is_none_or(|t| t.kind != TokenKind::Word || t.text != "in") {
+        return Err(t!("select.expected_in").to_string());
+    }
+    let mut pos = 3;
+    let mut words = Vec::new();
+    while let Some(t) = tokens.get(pos).filter(|t| t.kind == TokenKind::Word) {
+        words.push(t.text.to_string());
+        pos += 1;
+    }
+    skip_separators(&mut pos);
+    match tokens.get(pos) {
+        Some(t) if is_keyword(t, "do") => pos += 1,
+        _ => return Err(t!("select.expected_do").to_string()),
+    }
+
+    // Corpo: até o `done` deste `select` (os internos contam à parte)
+    let body_start = tokens.get(pos).map_or(text.len(), |t| t.start);
+    let mut depth = 0;
+    let mut body_end = None;
+    while let Some(t) = tokens.get(pos) {
+        pos += 1;
+        if is_keyword(t, "done") && depth == 0 {
+            body_end = Some(t.start);
+            break;
+        }
+        depth += nesting(t);
+    }
+    let body_end = body_end.ok_or(t!("select.missing_done"))?;
+
+    if let Some(extra) = tokens[pos..].iter().find(|t| t.kind != TokenKind::Newline) {
+        return Err(t!("select.trailing_text", &text[extra.start..]));
+    }
+    Ok(SelectStatement { name, words, body: text[body_start..body_end].trim().to_string() })
+}
+
+/// A resposta digitada no menu sem terminal (`2`) vira o índice da opção.
+pub fn menu_choice(reply: &str, options: usize) -> Option<usize> {
+    let n: usize = reply.trim().parse().ok()?;
+    (1..=options).contains(&n).then(|| n - 1)
+}
@@ -0,0 +1,126 @@
+//! # Suggest Module
+//!
+//! `?? pergunta` pede um comando a um sugestor configurado e o coloca no editor
+//! para revisão, sem executar nada:
+//!
+//! ```text
+//! ❯ ?? arquivos grandes modificados hoje
+//! ❯ find . -type f -mtime -1 -size +100M█
+//! ```
+//!
+//! A shell só faz o encanamento; quem responde é configurado na seção
+//! `[suggest]` do `.clios.toml`:
+//!
+//! - `command` - programa externo: recebe a pergunta no STDIN (e em
+//!   `CLIOS_QUERY`, com `CLIOS_LAST_EXIT` e as variáveis do `[env]`) e
+//!   imprime o comando no STDOUT
+//! - `function` - função Rhai de um plugin, chamada como `f(pergunta)`, que
+//!   devolve o comando como string
+//!
+//! Da resposta vale a primeira linha que não é vazia, comentário (`#`) nem
+//! cerca de código (```` ``` ````), então um modelo que responde em Markdown
+//! também serve.
+
+use crate::config::ConfigSuggest;
+use crate::executor::injected_env;
+use crate::t;
+use std::fmt;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Prefixo da linha que vira pergunta.
+pub const SUGGEST_PREFIX: &str = "??";
+
+/// Quem responde o `??`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SuggestSource {
+    /// Programa externo (linha de comando, dividida como na shell)
+    Command(String),
+    /// Função Rhai dos plugins
+    Function(String),
+}
+
+impl SuggestSource {
+    /// A função Rhai tem prioridade quando as duas estão configuradas.
+    pub fn from_config(config: Option<&ConfigSuggest>) -> Option<Self> {
+        let config = config?;
+        let non_empty = |s: &Option<String>| s.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(String::from);
+        non_empty(&config.function)
+            .map(SuggestSource::Function)
+            .or_else(|| non_empty(&config.command).map(SuggestSource::Command))
+    }
+
+    /// Nome mostrado enquanto o sugestor trabalha.
+    pub fn label(&self) -> &str {
+        match self {
+            SuggestSource::Command(command) => command.split_whitespace().next().unwrap_or(command),
+            SuggestSource::Function(name) => name,
+        }
+    }
+}
+
+/// Falha ao pedir uma sugestão.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SuggestError {
+    /// Sem `[suggest]` no `.clios.toml`
+    NotConfigured,
+    /// `??` sem pergunta
+    EmptyQuery,
+    /// O sugestor falhou (mensagem do erro)
+    Failed(String),
+    /// O sugestor não devolveu nenhum comando
+    NoSuggestion,
+}
+
+impl fmt::Display for SuggestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SuggestError::NotConfigured => f.write_str(t!("suggest.not_configured")),
+            SuggestError::EmptyQuery => f.write_str(t!("suggest.empty_query")),
+            SuggestError::Failed(e) => f.write_str(e),
+            SuggestError::NoSuggestion => f.write_str(t!("suggest.no_suggestion")),
+        }
+    }
+}
+
+impl std::error::Error for SuggestError {}
+
+/// A pergunta de uma linha `?? ...`; `None` se a linha não é uma pergunta.
+pub fn parse_query(line: &str) -> Option<&str> {
+    line.trim_start().strip_prefix(SUGGEST_PREFIX).map(str::trim)
+}
+
+/// O comando dentro da resposta do sugestor.
+pub fn parse_suggestion(output: &str) -> Option<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with("```"))
+        .map(|line| line.strip_prefix("$ ").unwrap_or(line).to_string())
+}
+
+/// Roda o programa externo com a pergunta no STDIN.
+pub fn run_external(command: &str, query: &str, last_exit: i32) -> Result<String, SuggestError> {
+    let argv = shlex::split(command)
+        .filter(|argv| !argv.is_empty())
+        .ok_or_else(|| SuggestError::Failed(t!("suggest.invalid_command", command)))?;
+    let mut child = Command::new(&argv[0])
+        .args(&argv[1..])
+        .envs(injected_env())
+        .env("CLIOS_QUERY", query)
+        .env("CLIOS_LAST_EXIT", last_exit.to_string())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| SuggestError::Failed(format!("{}: {}", argv[0], e)))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // Um programa que não lê o STDIN fecha o pipe: não é erro
+        let _ = writeln!(stdin, "{}", query);
+    }
+    let output = child.wait_with_output().map_err(|e| SuggestError::Failed(format!("{}: {}", argv[0], e)))?;
+    if !output.status.success() {
+        return Err(SuggestError::Failed(t!("suggest.command_failed", argv[0], output.status.code().unwrap_or(1))));
+    }
+    parse_suggestion(&String::from_utf8_lossy(&output.stdout)).ok_or(SuggestError::NoSuggestion)
+}
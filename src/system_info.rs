@@ -0,0 +1,141 @@
+//! # System Info Module
+//!
+//! Builtin `sysinfo`: um painel compacto com sistema, kernel, uptime, uso de
+//! CPU e memória, o disco do diretório atual e o load average, lidos pelo
+//! crate `sysinfo` (sem depender de `uptime`, `free` e `df`).
+//!
+//! Também fornece o segmento de carga do prompt ([`load_label`]): com
+//! `show_load` ligado em `[prompt]` (o padrão), o prompt mostra `load 9.3`
+//! enquanto a média de 1 minuto estiver acima do número de núcleos.
+
+use crate::archive::human_size;
+use crate::registry::usage;
+use crate::t;
+use crate::when::format_duration;
+use std::path::Path;
+use std::thread;
+use sysinfo::{Disks, System, MINIMUM_CPU_UPDATE_INTERVAL};
+
+/// Largura das barras de uso.
+const BAR_WIDTH: usize = 20;
+
+/// Barra `██████░░░░` com `percent` preenchido, colorida pelo nível de uso.
+pub fn usage_bar(percent: f64, width: usize) -> String {
+    let percent = percent.clamp(0.0, 100.0);
+    let filled = ((percent / 100.0) * width as f64).round() as usize;
+    format!(
+        "{}{}\x1b[90m{}\x1b[0m",
+        usage_color(percent),
+        "█".repeat(filled),
+        "░".repeat(width - filled)
+    )
+}
+
+/// Verde até 70%, amarelo até 90%, vermelho acima.
+fn usage_color(percent: f64) -> &'static str {
+    if percent >= 90.0 {
+        "\x1b[31m"
+    } else if percent >= 70.0 {
+        "\x1b[33m"
+    } else {
+        "\x1b[32m"
+    }
+}
+
+fn percent_of(used: u64, total: u64) -> f64 {
+    if total == 0 { 0.0 } else { used as f64 * 100.0 / total as f64 }
+}
+
+/// Ponto de montagem de `path`: o mais longo entre `mounts` que o contém.
+pub fn mount_for<'a>(path: &Path, mounts: &[&'a Path]) -> Option<&'a Path> {
+    mounts.iter().copied().filter(|m| path.starts_with(m)).max_by_key(|m| m.as_os_str().len())
+}
+
+// -----------------------------------------------------------------------------
+// PROMPT
+// -----------------------------------------------------------------------------
+
+/// Texto do segmento de carga (`load 9.3`): só quando a média de 1 minuto
+/// passa do número de núcleos, para o segmento sumir com a máquina tranquila.
+pub fn load_label(load: f64, cores: usize) -> Option<String> {
+    (cores > 0 && load >= cores as f64).then(|| format!("load {:.1}", load))
+}
+
+/// [`load_label`] da máquina atual (lê só o `/proc/loadavg`; barato o bastante
+/// para rodar a cada prompt).
+pub fn current_load_label() -> Option<String> {
+    let cores = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    load_label(System::load_average().one, cores)
+}
+
+// -----------------------------------------------------------------------------
+// BUILTIN
+// -----------------------------------------------------------------------------
+
+fn row(label: &str, value: &str) {
+    println!(" \x1b[1;36m{:<9}\x1b[0m {}", label, value);
+}
+
+/// Linha de uso: barra, `usado / total (N%)` e um complemento opcional.
+fn usage_row(label: &str, used: u64, total: u64, extra: &str) {
+    let percent = percent_of(used, total);
+    row(
+        label,
+        &format!("{} {} / {} ({:.0}%){}", usage_bar(percent, BAR_WIDTH), human_size(used), human_size(total), percent, extra),
+    );
+}
+
+/// Builtin `sysinfo`.
+pub fn handle_sysinfo(tokens: &[String]) -> i32 {
+    if tokens.len() > 1 {
+        eprintln!("{}", usage("sysinfo"));
+        return 2;
+    }
+
+    let mut sys = System::new();
+    // O uso de CPU é a diferença entre duas leituras
+    sys.refresh_cpu_all();
+    thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL);
+    sys.refresh_cpu_usage();
+    sys.refresh_memory();
+
+    let user = std::env::var("USER").unwrap_or_else(|_| "clios".to_string());
+    let host = System::host_name().unwrap_or_else(|| "?".to_string());
+    println!(" \x1b[1;35m{}@{}\x1b[0m", user, host);
+
+    let os = System::long_os_version().or_else(System::name).unwrap_or_else(|| std::env::consts::OS.to_string());
+    row(t!("sysinfo.os"), &os);
+    row(t!("sysinfo.kernel"), &format!("{} ({})", System::kernel_version().unwrap_or_default(), System::cpu_arch()));
+    let uptime = System::uptime() as i64;
+    row(t!("sysinfo.uptime"), &format_duration(uptime - uptime % 60));
+
+    let cpus = sys.cpus();
+    let brand = cpus.first().map(|c| c.brand().trim()).filter(|b| !b.is_empty()).unwrap_or("?");
+    let cpu = sys.global_cpu_usage() as f64;
+    row(
+        t!("sysinfo.cpu"),
+        &format!("{} {:.0}%  {} · {}", usage_bar(cpu, BAR_WIDTH), cpu, t!("sysinfo.cores", cpus.len()), brand),
+    );
+
+    usage_row(t!("sysinfo.memory"), sys.used_memory(), sys.total_memory(), "");
+    if sys.total_swap() > 0 {
+        usage_row(t!("sysinfo.swap"), sys.used_swap(), sys.total_swap(), "");
+    }
+
+    let disks = Disks::new_with_refreshed_list();
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let mounts: Vec<&Path> = disks.iter().map(|d| d.mount_point()).collect();
+    if let Some(mount) = mount_for(&cwd, &mounts)
+        && let Some(disk) = disks.iter().find(|d| d.mount_point() == mount)
+    {
+        let total = disk.total_space();
+        let used = total.saturating_sub(disk.available_space());
+        usage_row(t!("sysinfo.disk"), used, total, &format!("  {}", mount.display()));
+    }
+
+    let load = System::load_average();
+    let cores = cpus.len().max(1) as f64;
+    let color = usage_color(load.one * 100.0 / cores);
+    row(t!("sysinfo.load"), &format!("{}{:.2}\x1b[0m {:.2} {:.2}", color, load.one, load.five, load.fifteen));
+    0
+}
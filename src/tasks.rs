@@ -0,0 +1,147 @@
+//! # Tasks Module
+//!
+//! Builtin `run`: tarefas do projeto declaradas num `clios.toml` (sem ponto,
+//! versionado junto com o código), no estilo de um `make`/`just` simples:
+//!
+//! ```toml
+//! [tasks]
+//! build = "cargo build --release"
+//! check = ["cargo fmt --check", "cargo clippy", "cargo test"]
+//! deploy = { run = "ssh {1} systemctl restart app", desc = "Reinicia o app no servidor" }
+//! ```
+//!
+//! - `run <tarefa> [args...]` - executa os passos em ordem, parando no
+//!   primeiro que falhar; os argumentos preenchem `{1}`, `{2}`... como num
+//!   alias e, sem placeholders, vão para o fim do último passo
+//! - `run` / `run --list` - lista as tarefas
+//!
+//! O arquivo é procurado do diretório atual para cima, e os passos rodam no
+//! diretório dele (o diretório da shell volta ao de antes no fim).
+
+use crate::expansion::fill_alias_template;
+use crate::quote::join_words;
+use crate::t;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Nome do arquivo de tarefas do projeto.
+pub const TASKS_FILE: &str = "clios.toml";
+
+/// Um ou mais comandos.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum Steps {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Steps {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            Steps::One(step) => vec![step],
+            Steps::Many(steps) => steps,
+        }
+    }
+}
+
+/// Formas aceitas para uma tarefa no TOML.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum TaskSpec {
+    Steps(Steps),
+    Full { run: Steps, desc: Option<String> },
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectFile {
+    #[serde(default)]
+    tasks: BTreeMap<String, TaskSpec>,
+}
+
+/// Uma tarefa do projeto.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Task {
+    pub name: String,
+    pub steps: Vec<String>,
+    pub desc: Option<String>,
+}
+
+impl Task {
+    /// Texto do `run --list`: a descrição ou os próprios passos.
+    pub fn summary(&self) -> String {
+        self.desc.clone().unwrap_or_else(|| self.steps.join(" && "))
+    }
+
+    /// Os passos com os argumentos aplicados.
+    pub fn commands(&self, args: &[String]) -> Vec<String> {
+        let rest = if args.is_empty() { String::new() } else { format!(" {}", join_words(args)) };
+        let last = self.steps.len().saturating_sub(1);
+        self.steps
+            .iter()
+            .enumerate()
+            .map(|(i, step)| {
+                if i == last || step.contains('{') {
+                    fill_alias_template(step, &rest)
+                } else {
+                    step.clone()
+                }
+            })
+            .collect()
+    }
+}
+
+/// O `clios.toml` mais próximo, de `start` para cima.
+pub fn find_tasks_file(start: &Path) -> Option<PathBuf> {
+    start.ancestors().map(|dir| dir.join(TASKS_FILE)).find(|path| path.is_file())
+}
+
+/// Tarefas de um conteúdo TOML, em ordem alfabética.
+pub fn parse_tasks(content: &str) -> Result<Vec<Task>, String> {
+    let file: ProjectFile = toml::from_str(content).map_err(|e| e.message().to_string())?;
+    let mut tasks = Vec::new();
+    for (name, spec) in file.tasks {
+        let (steps, desc) = match spec {
+            TaskSpec::Steps(steps) => (steps.into_vec(), None),
+            TaskSpec::Full { run, desc } => (run.into_vec(), desc),
+        };
+        if steps.iter().all(|s| s.trim().is_empty()) {
+            return Err(t!("tasks.empty_task", name));
+        }
+        tasks.push(Task { name, steps, desc });
+    }
+    Ok(tasks)
+}
+
+/// Tarefas do arquivo `path`.
+pub fn load_tasks(path: &Path) -> Result<Vec<Task>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    parse_tasks(&content).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Nomes de tarefa para o autocomplete do `run`, a partir do diretório atual.
+pub fn complete_task(prefix: &str) -> Vec<String> {
+    let Some(path) = std::env::current_dir().ok().and_then(|cwd| find_tasks_file(&cwd)) else {
+        return Vec::new();
+    };
+    load_tasks(&path)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|task| task.name)
+        .filter(|name| name.starts_with(prefix))
+        .collect()
+}
+
+/// A listagem do `run --list`.
+pub fn format_task_list(path: &Path, tasks: &[Task]) -> String {
+    let mut out = format!("{}\n", t!("tasks.list_header", path.display()));
+    if tasks.is_empty() {
+        out.push_str(&format!("  {}\n", t!("tasks.none")));
+    }
+    let width = tasks.iter().map(|task| task.name.chars().count()).max().unwrap_or(0);
+    for task in tasks {
+        out.push_str(&format!("  \x1b[1;33m{:<width$}\x1b[0m  {}\n", task.name, task.summary(), width = width));
+    }
+    out
+}
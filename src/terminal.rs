@@ -0,0 +1,117 @@
+//! # Terminal Module
+//!
+//! Guarda o estado do terminal no início da sessão e o restaura quando algo
+//! sai do controle (panic interno, filho que morreu em modo raw, etc.).
+//!
+//! ## Estado Salvo
+//! - Atributos `termios` do STDIN (modo canônico, echo, ...)
+//! - Process group da shell (dono do terminal)
+
+use nix::sys::signal::{self, SigHandler, Signal};
+use nix::sys::termios::{self, SetArg, Termios};
+use nix::unistd;
+use std::io::IsTerminal;
+use std::sync::Mutex;
+
+/// Atributos do terminal capturados na inicialização.
+static SAVED_TERMIOS: Mutex<Option<Termios>> = Mutex::new(None);
+
+/// Salva os atributos atuais do terminal (chamado uma vez, no início).
+///
+/// Sem efeito quando o STDIN não é um terminal (scripts, pipes, testes).
+pub fn save_terminal_state() {
+    if !std::io::stdin().is_terminal() {
+        return;
+    }
+    if let Ok(attrs) = termios::tcgetattr(std::io::stdin())
+        && let Ok(mut slot) = SAVED_TERMIOS.lock() {
+            *slot = Some(attrs);
+        }
+}
+
+/// Esquece o estado salvo: num subshell em background, o [`restore_terminal`]
+/// não pode tomar o terminal da shell.
+pub fn release_terminal() {
+    if let Ok(mut slot) = SAVED_TERMIOS.lock() {
+        *slot = None;
+    }
+}
+
+/// Restaura os atributos salvos e devolve o terminal ao process group da shell.
+///
+/// Retorna `false` se não havia estado salvo (ex: sessão não interativa).
+pub fn restore_terminal() -> bool {
+    let Some(attrs) = SAVED_TERMIOS.lock().ok().and_then(|slot| slot.clone()) else {
+        return false;
+    };
+
+    // Sem isso o tcsetpgrp suspenderia a shell se ela não for o foreground
+    let _ = unsafe { signal::signal(Signal::SIGTTOU, SigHandler::SigIgn) };
+
+    let _ = termios::tcsetattr(std::io::stdin(), SetArg::TCSADRAIN, &attrs);
+    let _ = unistd::tcsetpgrp(std::io::stdin(), unistd::getpgrp());
+    true
+}
+
+/// Sequências ANSI que desfazem o estado visual deixado por apps TUI:
+/// sai da tela alternativa, mostra o cursor, desliga keypad/mouse e reseta cores.
+pub const VISUAL_RESET: &str = "\x1b[?1049l\x1b[?25h\x1b[?1l\x1b>\x1b[?1000l\x1b[0m";
+
+/// Restauração completa (usada pelo builtin `fix-terminal`).
+pub fn fix_terminal() -> bool {
+    use std::io::Write;
+    print!("{}", VISUAL_RESET);
+    let _ = std::io::stdout().flush();
+    restore_terminal()
+}
+
+/// Largura do terminal ligado ao STDOUT (`None` quando ele não é um terminal).
+pub fn terminal_width() -> Option<usize> {
+    use nix::libc;
+    let mut size = libc::winsize { ws_row: 0, ws_col: 0, ws_xpixel: 0, ws_ypixel: 0 };
+    // SAFETY: TIOCGWINSZ só escreve na struct passada
+    let result = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) };
+    (result == 0 && size.ws_col > 0).then_some(usize::from(size.ws_col))
+}
+
+/// STDIN sem modo canônico nem echo enquanto o valor existir: as teclas chegam
+/// uma a uma, sem esperar o Enter (telas como o `jobs --watch`).
+pub struct KeyReader {
+    saved: Termios,
+}
+
+impl KeyReader {
+    /// `None` quando o STDIN não é um terminal.
+    pub fn new() -> Option<Self> {
+        let stdin = std::io::stdin();
+        if !stdin.is_terminal() {
+            return None;
+        }
+        let saved = termios::tcgetattr(&stdin).ok()?;
+        let mut raw = saved.clone();
+        raw.local_flags.remove(termios::LocalFlags::ICANON | termios::LocalFlags::ECHO);
+        termios::tcsetattr(&stdin, SetArg::TCSANOW, &raw).ok()?;
+        Some(Self { saved })
+    }
+
+    /// Espera uma tecla por até `timeout`; `true` se alguma chegou (ela é
+    /// consumida). Um sinal (Ctrl+C) interrompe a espera com `false`.
+    pub fn key_pressed(&self, timeout: std::time::Duration) -> bool {
+        use nix::libc;
+        let mut fds = libc::pollfd { fd: libc::STDIN_FILENO, events: libc::POLLIN, revents: 0 };
+        let millis = libc::c_int::try_from(timeout.as_millis()).unwrap_or(libc::c_int::MAX);
+        // SAFETY: um único pollfd válido durante a chamada
+        if unsafe { libc::poll(&mut fds, 1, millis) } <= 0 {
+            return false;
+        }
+        let mut byte = [0u8; 16];
+        // SAFETY: lê no máximo o tamanho do buffer
+        unsafe { libc::read(libc::STDIN_FILENO, byte.as_mut_ptr().cast(), byte.len()) > 0 }
+    }
+}
+
+impl Drop for KeyReader {
+    fn drop(&mut self) {
+        let _ = termios::tcsetattr(std::io::stdin(), SetArg::TCSANOW, &self.saved);
+    }
+}
@@ -0,0 +1,176 @@
+//! # Tldr Module
+//!
+//! Consulta rápida de documentação: builtin `tldr <cmd>` e o atalho Alt+H.
+//!
+//! - `tldr tar` - baixa a página do projeto tldr-pages (via `reqwest`), guarda
+//!   em `~/.clios_tldr/<cmd>.md` e exibe formatada. Consultas seguintes usam o
+//!   cache; `tldr -u tar` baixa de novo.
+//! - **Alt+H** no editor - abre o `man` do comando sendo digitado (o da posição
+//!   de comando sob o cursor) ou, se não houver `man`, a página tldr no pager.
+
+use crate::parser::split_command_positions;
+use crate::registry::usage;
+use crate::t;
+use rustyline::{Cmd, ConditionalEventHandler, Event, EventContext, RepeatCount};
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Onde as páginas ficam no repositório do tldr-pages.
+const TLDR_BASE_URL: &str = "https://raw.githubusercontent.com/tldr-pages/tldr/main/pages";
+
+/// Plataformas consultadas, em ordem.
+const PLATFORMS: &[&str] = &["common", "linux"];
+
+// -----------------------------------------------------------------------------
+// CACHE
+// -----------------------------------------------------------------------------
+
+/// Diretório do cache (`~/.clios_tldr`).
+pub fn tldr_cache_dir() -> PathBuf {
+    env::var("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join(".clios_tldr")
+}
+
+/// Nomes de página aceitos (evita montar caminhos/URLs com `/` ou `..`).
+pub fn valid_page_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '+'))
+}
+
+/// Lê a página do cache ou baixa do tldr-pages.
+pub fn fetch_page(name: &str, refresh: bool) -> Result<String, String> {
+    if !valid_page_name(name) {
+        return Err(t!("tldr.invalid_name", name));
+    }
+    let cached = tldr_cache_dir().join(format!("{}.md", name));
+    if !refresh && let Ok(page) = fs::read_to_string(&cached) {
+        return Ok(page);
+    }
+
+    for platform in PLATFORMS {
+        let url = format!("{}/{}/{}.md", TLDR_BASE_URL, platform, name);
+        let response = reqwest::blocking::get(&url).map_err(|e| t!("tldr.connection_failed", e))?;
+        if !response.status().is_success() {
+            continue;
+        }
+        let page = response.text().map_err(|e| e.to_string())?;
+        let _ = fs::create_dir_all(tldr_cache_dir());
+        let _ = fs::write(&cached, &page);
+        return Ok(page);
+    }
+    Err(t!("tldr.not_found", name))
+}
+
+// -----------------------------------------------------------------------------
+// RENDERING
+// -----------------------------------------------------------------------------
+
+/// Formata o markdown do tldr com cores ANSI.
+///
+/// `# título`, `> descrição`, `- explicação` e `` `exemplo` `` com os
+/// `{{parâmetros}}` destacados.
+pub fn render_page(markdown: &str) -> String {
+    let mut out = String::new();
+    for line in markdown.lines() {
+        if let Some(title) = line.strip_prefix("# ") {
+            out.push_str(&format!("\x1b[1;33m{}\x1b[0m\n", title));
+        } else if let Some(desc) = line.strip_prefix("> ") {
+            out.push_str(&format!("{}\n", desc));
+        } else if let Some(item) = line.strip_prefix("- ") {
+            out.push_str(&format!("\n\x1b[1;32m{}\x1b[0m\n", item));
+        } else if let Some(example) = line.strip_prefix('`').and_then(|l| l.strip_suffix('`')) {
+            let example = example.replace("{{", "\x1b[4;36m").replace("}}", "\x1b[0m\x1b[36m");
+            out.push_str(&format!("  \x1b[36m{}\x1b[0m\n", example));
+        } else if line.is_empty() && out.ends_with("\n\n") {
+            continue;
+        } else {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Builtin `tldr [-u] <cmd>`.
+pub fn handle_tldr(tokens: &[String]) {
+    let (refresh, name) = match &tokens[1..] {
+        [flag, name] if flag == "-u" || flag == "--update" => (true, name),
+        [name] => (false, name),
+        _ => {
+            eprintln!("{}", usage("tldr"));
+            return;
+        }
+    };
+    match fetch_page(name, refresh) {
+        Ok(page) => print!("{}", render_page(&page)),
+        Err(e) => eprintln!("tldr: {}", e),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// ALT+H
+// -----------------------------------------------------------------------------
+
+/// Nome do comando na posição de comando sob o cursor.
+///
+/// Em `git log | gr|ep x` (cursor em `|`) retorna `grep`.
+pub fn command_at_cursor(line: &str, pos: usize) -> Option<String> {
+    let segment_len = split_command_positions(&line[..pos]).last()?.text.len();
+    let start = pos - segment_len;
+    let rest = &line[start..];
+    let end = rest.find(['|', '&']).unwrap_or(rest.len());
+    rest[..end].split_whitespace().next().map(String::from)
+}
+
+/// Abre `man <cmd>` ou, se não houver página de manual, o tldr no pager.
+fn open_docs(cmd: &str) {
+    let has_man = Command::new("man")
+        .args(["-w", cmd])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success());
+    if has_man {
+        let _ = Command::new("man").arg(cmd).status();
+        return;
+    }
+
+    match fetch_page(cmd, false) {
+        Ok(page) => {
+            let pager = env::var("PAGER").unwrap_or_else(|_| "less".to_string());
+            let mut parts = pager.split_whitespace();
+            let program = parts.next().unwrap_or("less");
+            let mut args: Vec<&str> = parts.collect();
+            if program == "less" && args.is_empty() {
+                args.push("-R");
+            }
+            if let Ok(mut child) = Command::new(program).args(&args).stdin(Stdio::piped()).spawn() {
+                if let Some(mut stdin) = child.stdin.take() {
+                    let _ = stdin.write_all(render_page(&page).as_bytes());
+                }
+                let _ = child.wait();
+            }
+        }
+        // Terminal em modo raw: precisa de \r
+        Err(e) => eprint!("\r\ntldr: {}\r\n", e),
+    }
+}
+
+/// Handler do Alt+H.
+pub struct DocsLookup;
+
+impl ConditionalEventHandler for DocsLookup {
+    fn handle(&self, _evt: &Event, _n: RepeatCount, _positive: bool, ctx: &EventContext) -> Option<Cmd> {
+        let cmd = command_at_cursor(ctx.line(), ctx.pos())?;
+        open_docs(&cmd);
+        Some(Cmd::Repaint)
+    }
+}
@@ -0,0 +1,138 @@
+//! # Toolchain Module
+//!
+//! Versões de ferramenta pedidas pelo projeto, lidas dos arquivos que o nvm,
+//! o rustup e o pyenv usam:
+//!
+//! - `.nvmrc` / `.node-version` - Node
+//! - `rust-toolchain.toml` / `rust-toolchain` - Rust (o `channel`)
+//! - `.python-version` - Python (a primeira versão)
+//!
+//! Cada arquivo vale para o diretório dele e os de baixo, como nas próprias
+//! ferramentas. A versão aparece no prompt (`show_toolchain` em `[prompt]`)
+//! e, ao entrar num projeto, a seção `[toolchain]` pode rodar um comando de
+//! ativação por ferramenta, com `{version}` e `{root}` preenchidos:
+//!
+//! ```toml
+//! [toolchain]
+//! node = "path add -p ~/.nvm/versions/node/v{version}/bin"
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Ferramenta com versão por projeto.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolKind {
+    Node,
+    Rust,
+    Python,
+}
+
+impl ToolKind {
+    /// Nome usado na seção `[toolchain]`.
+    pub fn name(self) -> &'static str {
+        match self {
+            ToolKind::Node => "node",
+            ToolKind::Rust => "rust",
+            ToolKind::Python => "python",
+        }
+    }
+
+    /// Ícone do prompt (os mesmos do segmento de linguagem).
+    pub fn icon(self) -> &'static str {
+        match self {
+            ToolKind::Node => "⬢",
+            ToolKind::Rust => "",
+            ToolKind::Python => "",
+        }
+    }
+}
+
+/// Arquivos procurados em cada diretório, na ordem de preferência.
+const TOOLCHAIN_FILES: &[(ToolKind, &str)] = &[
+    (ToolKind::Node, ".nvmrc"),
+    (ToolKind::Node, ".node-version"),
+    (ToolKind::Rust, "rust-toolchain.toml"),
+    (ToolKind::Rust, "rust-toolchain"),
+    (ToolKind::Python, ".python-version"),
+];
+
+/// Versão pedida por um arquivo de projeto.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toolchain {
+    pub kind: ToolKind,
+    pub version: String,
+    /// Arquivo de onde a versão veio
+    pub file: PathBuf,
+}
+
+impl Toolchain {
+    /// Diretório do arquivo (a raiz do projeto para a ferramenta).
+    pub fn root(&self) -> &Path {
+        self.file.parent().unwrap_or(Path::new("."))
+    }
+}
+
+/// A versão dentro do conteúdo de um dos arquivos de [`TOOLCHAIN_FILES`].
+pub fn parse_version(kind: ToolKind, content: &str) -> Option<String> {
+    if kind == ToolKind::Rust
+        && let Ok(table) = content.parse::<toml::Table>()
+    {
+        return table
+            .get("toolchain")
+            .and_then(|t| t.get("channel"))
+            .and_then(|c| c.as_str())
+            .map(String::from);
+    }
+    let line = content
+        .lines()
+        .map(|l| l.split('#').next().unwrap_or("").trim())
+        .find(|l| !l.is_empty())?;
+    let version = match kind {
+        // `v20.11.0` e `20.11.0` são a mesma versão
+        ToolKind::Node => line.strip_prefix('v').unwrap_or(line),
+        _ => line,
+    };
+    Some(version.to_string())
+}
+
+/// As versões que valem em `dir`: para cada ferramenta, o arquivo mais próximo
+/// subindo a árvore.
+pub fn detect_toolchains(dir: &Path) -> Vec<Toolchain> {
+    let mut found: Vec<Toolchain> = Vec::new();
+    for ancestor in dir.ancestors() {
+        for &(kind, name) in TOOLCHAIN_FILES {
+            if found.iter().any(|t| t.kind == kind) {
+                continue;
+            }
+            let file = ancestor.join(name);
+            if let Some(version) = fs::read_to_string(&file).ok().and_then(|c| parse_version(kind, &c)) {
+                found.push(Toolchain { kind, version, file });
+            }
+        }
+    }
+    found.sort_by_key(|t| t.kind as u8);
+    found
+}
+
+/// Texto do segmento do prompt (`⬢ 20 ·  1.75`); `None` sem nenhuma versão.
+pub fn toolchain_label(toolchains: &[Toolchain]) -> Option<String> {
+    if toolchains.is_empty() {
+        return None;
+    }
+    let parts: Vec<String> = toolchains.iter().map(|t| format!("{} {}", t.kind.icon(), t.version)).collect();
+    Some(parts.join(" · "))
+}
+
+/// Versões de `new` que não valiam em `old`: ao entrar num projeto (ou num
+/// subprojeto com outra versão), mas não ao andar dentro dele.
+pub fn entered_toolchains<'a>(old: &[Toolchain], new: &'a [Toolchain]) -> Vec<&'a Toolchain> {
+    new.iter().filter(|t| !old.contains(t)).collect()
+}
+
+/// Comando de ativação com `{version}` e `{root}` preenchidos.
+pub fn activation_command(template: &str, toolchain: &Toolchain) -> String {
+    template
+        .replace("{version}", &toolchain.version)
+        .replace("{root}", &toolchain.root().display().to_string())
+}
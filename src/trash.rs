@@ -0,0 +1,309 @@
+//! # Trash Module
+//!
+//! Lixeira para operações destrutivas (`trash` / `restore`).
+//!
+//! Segue a especificação XDG Trash: o arquivo vai para
+//! `$XDG_DATA_HOME/Trash/files/<nome>` e os metadados (caminho original e data)
+//! para `$XDG_DATA_HOME/Trash/info/<nome>.trashinfo`, então a lixeira do
+//! ambiente gráfico enxerga os mesmos itens.
+//!
+//! Com `safe_rm = true` na seção `[behavior]`, um `rm` simples é reescrito para
+//! `trash`; só `rm -f --real ...` apaga de verdade.
+
+use crate::registry::usage;
+use crate::t;
+use chrono::{Local, NaiveDateTime};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Formato de data exigido pelo `.trashinfo`.
+const DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Um item da lixeira.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrashEntry {
+    /// Nome dentro de `Trash/files`
+    pub name: String,
+    /// Caminho original (absoluto)
+    pub original_path: PathBuf,
+    /// Quando foi para a lixeira
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+// -----------------------------------------------------------------------------
+// PATHS
+// -----------------------------------------------------------------------------
+
+/// Diretório raiz da lixeira (`$XDG_DATA_HOME/Trash` ou `~/.local/share/Trash`).
+pub fn trash_dir() -> PathBuf {
+    let data_home = env::var("XDG_DATA_HOME")
+        .ok()
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            env::var("HOME")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from("."))
+                .join(".local/share")
+        });
+    data_home.join("Trash")
+}
+
+fn files_dir() -> PathBuf {
+    trash_dir().join("files")
+}
+
+fn info_dir() -> PathBuf {
+    trash_dir().join("info")
+}
+
+// -----------------------------------------------------------------------------
+// TRASHINFO ENCODING
+// -----------------------------------------------------------------------------
+
+/// Codifica o caminho como URL (a especificação pede percent-encoding).
+pub fn encode_path(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+/// Desfaz o [`encode_path`].
+pub fn decode_path(encoded: &str) -> String {
+    let bytes = encoded.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(hex) = encoded.get(i + 1..i + 3)
+            && let Ok(byte) = u8::from_str_radix(hex, 16)
+        {
+            out.push(byte);
+            i += 3;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Gera o conteúdo de um arquivo `.trashinfo`.
+pub fn format_trashinfo(original: &Path, deleted_at: NaiveDateTime) -> String {
+    format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        encode_path(&original.to_string_lossy()),
+        deleted_at.format(DATE_FORMAT)
+    )
+}
+
+/// Lê um arquivo `.trashinfo`, retornando (caminho original, data).
+pub fn parse_trashinfo(content: &str) -> Option<(PathBuf, Option<NaiveDateTime>)> {
+    let mut path = None;
+    let mut date = None;
+    for line in content.lines() {
+        if let Some(p) = line.strip_prefix("Path=") {
+            path = Some(PathBuf::from(decode_path(p)));
+        } else if let Some(d) = line.strip_prefix("DeletionDate=") {
+            date = NaiveDateTime::parse_from_str(d, DATE_FORMAT).ok();
+        }
+    }
+    path.map(|p| (p, date))
+}
+
+// -----------------------------------------------------------------------------
+// OPERATIONS
+// -----------------------------------------------------------------------------
+
+/// Move `from` para `to`, copiando quando estão em sistemas de arquivos diferentes.
+fn move_path(from: &Path, to: &Path) -> io::Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_recursive(from, to)?;
+    if from.is_dir() {
+        fs::remove_dir_all(from)
+    } else {
+        fs::remove_file(from)
+    }
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> io::Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(from, to).map(|_| ())
+    }
+}
+
+/// Escolhe um nome livre na lixeira (`a.txt`, `a.txt.2`, `a.txt.3`, ...).
+fn unique_name(base: &str) -> String {
+    let taken = |n: &str| {
+        files_dir().join(n).symlink_metadata().is_ok()
+            || info_dir().join(format!("{}.trashinfo", n)).exists()
+    };
+    if !taken(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|i| format!("{}.{}", base, i))
+        .find(|n| !taken(n))
+        .unwrap_or_else(|| base.to_string())
+}
+
+/// Move um arquivo ou diretório para a lixeira. Retorna o nome usado.
+pub fn trash_path(path: &Path) -> Result<String, String> {
+    if path.symlink_metadata().is_err() {
+        return Err(t!("trash.file_not_found", path.display()));
+    }
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        env::current_dir().map_err(|e| e.to_string())?.join(path)
+    };
+    let base = absolute
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .ok_or_else(|| t!("trash.invalid_path", path.display()))?;
+
+    fs::create_dir_all(files_dir()).map_err(|e| e.to_string())?;
+    fs::create_dir_all(info_dir()).map_err(|e| e.to_string())?;
+
+    let name = unique_name(&base);
+    let info_path = info_dir().join(format!("{}.trashinfo", name));
+    fs::write(&info_path, format_trashinfo(&absolute, Local::now().naive_local()))
+        .map_err(|e| e.to_string())?;
+
+    if let Err(e) = move_path(&absolute, &files_dir().join(&name)) {
+        let _ = fs::remove_file(&info_path);
+        return Err(format!("{}: {}", path.display(), e));
+    }
+    Ok(name)
+}
+
+/// Lista os itens da lixeira, do mais recente para o mais antigo.
+pub fn list_entries() -> Vec<TrashEntry> {
+    let Ok(entries) = fs::read_dir(info_dir()) else {
+        return Vec::new();
+    };
+    let mut items: Vec<TrashEntry> = entries
+        .flatten()
+        .filter_map(|e| {
+            let file_name = e.file_name().into_string().ok()?;
+            let name = file_name.strip_suffix(".trashinfo")?.to_string();
+            let content = fs::read_to_string(e.path()).ok()?;
+            let (original_path, deleted_at) = parse_trashinfo(&content)?;
+            Some(TrashEntry {
+                name,
+                original_path,
+                deleted_at,
+            })
+        })
+        .collect();
+    items.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at).then_with(|| a.name.cmp(&b.name)));
+    items
+}
+
+/// Devolve um item ao caminho original (falha se já existir algo lá).
+pub fn restore_entry(name: &str) -> Result<PathBuf, String> {
+    let info_path = info_dir().join(format!("{}.trashinfo", name));
+    let content = fs::read_to_string(&info_path)
+        .map_err(|_| t!("trash.not_in_trash", name))?;
+    let (original, _) =
+        parse_trashinfo(&content).ok_or_else(|| t!("trash.invalid_info", name))?;
+
+    if original.symlink_metadata().is_ok() {
+        return Err(t!("trash.already_exists", original.display()));
+    }
+    if let Some(parent) = original.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    move_path(&files_dir().join(name), &original).map_err(|e| e.to_string())?;
+    let _ = fs::remove_file(info_path);
+    Ok(original)
+}
+
+// -----------------------------------------------------------------------------
+// SAFE RM
+// -----------------------------------------------------------------------------
+
+/// Reescreve um `rm` para `trash` (modo `safe_rm`).
+///
+/// `rm -f --real ...` continua sendo um `rm` de verdade (sem o `--real`).
+/// Nos demais casos as flags do `rm` são descartadas e os arquivos vão para a lixeira.
+pub fn rewrite_rm(tokens: Vec<String>) -> Vec<String> {
+    let has_force = tokens[1..]
+        .iter()
+        .any(|t| t.starts_with('-') && !t.starts_with("--") && t.contains('f'));
+    let has_real = tokens.iter().any(|t| t == "--real");
+
+    if has_force && has_real {
+        return tokens.into_iter().filter(|t| t != "--real").collect();
+    }
+
+    let mut rewritten = vec!["trash".to_string()];
+    let mut only_files = false;
+    for token in tokens.into_iter().skip(1) {
+        if only_files || !token.starts_with('-') {
+            rewritten.push(token);
+        } else if token == "--" {
+            only_files = true;
+        }
+    }
+    rewritten
+}
+
+// -----------------------------------------------------------------------------
+// BUILTINS
+// -----------------------------------------------------------------------------
+
+/// Builtin `trash <arquivos...>`.
+pub fn handle_trash(tokens: &[String]) {
+    if tokens.len() < 2 {
+        eprintln!("{}", usage("trash"));
+        return;
+    }
+    for file in &tokens[1..] {
+        if let Err(e) = trash_path(Path::new(file)) {
+            eprintln!("trash: {}", e);
+        }
+    }
+}
+
+/// Builtin `restore [nome...]`: sem argumentos lista a lixeira.
+pub fn handle_restore(tokens: &[String]) {
+    if tokens.len() < 2 {
+        let entries = list_entries();
+        if entries.is_empty() {
+            println!("{}", t!("trash.empty", trash_dir().display()));
+        }
+        for entry in entries {
+            let date = entry
+                .deleted_at
+                .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "?".to_string());
+            println!("{:<16}  {:<24}  {}", date, entry.name, entry.original_path.display());
+        }
+        return;
+    }
+    for name in &tokens[1..] {
+        match restore_entry(name) {
+            Ok(path) => println!("{}", t!("trash.restored", path.display())),
+            Err(e) => eprintln!("restore: {}", e),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! # Vi Mode Module
+//!
+//! Indicador do modo vi no prompt (opção `edit_mode = "vi"` da seção `[behavior]`).
+//!
+//! O prompt ganha um prefixo `[I]` (inserção), `[N]` (normal) ou `[R]`
+//! (substituição), trocado sempre que o modo muda.
+//!
+//! ## Como o modo é acompanhado
+//! O `rustyline` não avisa quando o modo vi muda, mas entrega o modo atual a
+//! todo handler de tecla. O [`ViModeTracker`] fica ligado a `Event::Any`: a
+//! cada tecla ele lê o modo antes dela e calcula o modo depois (Esc sai da
+//! inserção, `i`/`a`/`A`/`I`/`s`/`S`/`c`/`C` entram nela...). O prompt é
+//! redesenhado pelo próprio `rustyline` junto com a linha.
+
+use rustyline::{Cmd, ConditionalEventHandler, Event, EventContext, InputMode, KeyCode, KeyEvent, Modifiers, RepeatCount};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
+/// Modo de edição vi exibido no prompt.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ViMode {
+    Insert,
+    Normal,
+    Replace,
+}
+
+impl ViMode {
+    /// Indicador colorido (`[I] `, `[N] `, `[R] `), todos com a mesma largura.
+    pub fn indicator(self) -> &'static str {
+        match self {
+            ViMode::Insert => "\x1b[1;32m[I]\x1b[0m ",
+            ViMode::Normal => "\x1b[1;33m[N]\x1b[0m ",
+            ViMode::Replace => "\x1b[1;31m[R]\x1b[0m ",
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// STATE
+// -----------------------------------------------------------------------------
+
+/// Modo atual, compartilhado entre o handler de teclas e o highlighter.
+#[derive(Debug, Clone, Default)]
+pub struct ViModeState(Arc<AtomicU8>);
+
+impl ViModeState {
+    pub fn get(&self) -> ViMode {
+        match self.0.load(Ordering::Relaxed) {
+            1 => ViMode::Normal,
+            2 => ViMode::Replace,
+            _ => ViMode::Insert,
+        }
+    }
+
+    pub fn set(&self, mode: ViMode) {
+        let value = match mode {
+            ViMode::Insert => 0,
+            ViMode::Normal => 1,
+            ViMode::Replace => 2,
+        };
+        self.0.store(value, Ordering::Relaxed);
+    }
+}
+
+/// Modo depois de `key`, dado o modo antes dela.
+pub fn next_mode(current: InputMode, key: &KeyEvent) -> ViMode {
+    match current {
+        InputMode::Insert | InputMode::Replace => match key {
+            // Esc e Alt+tecla (comando rápido) vão para o modo normal
+            KeyEvent(KeyCode::Esc, _) => ViMode::Normal,
+            KeyEvent(KeyCode::Char(_), Modifiers::ALT) => ViMode::Normal,
+            _ if current == InputMode::Replace => ViMode::Replace,
+            _ => ViMode::Insert,
+        },
+        InputMode::Command => match key {
+            KeyEvent(KeyCode::Char('a' | 'A' | 'i' | 'I' | 's' | 'S' | 'c' | 'C'), Modifiers::NONE) => ViMode::Insert,
+            KeyEvent(KeyCode::Char('R'), Modifiers::NONE) => ViMode::Replace,
+            // Busca no histórico (Ctrl+R / Ctrl+S) volta em modo de inserção
+            KeyEvent(KeyCode::Char('R' | 'S'), Modifiers::CTRL) => ViMode::Insert,
+            _ => ViMode::Normal,
+        },
+    }
+}
+
+// -----------------------------------------------------------------------------
+// HANDLER
+// -----------------------------------------------------------------------------
+
+/// Handler ligado a `Event::Any`: atualiza o modo e deixa a tecla seguir.
+pub struct ViModeTracker {
+    pub state: ViModeState,
+}
+
+impl ConditionalEventHandler for ViModeTracker {
+    fn handle(&self, evt: &Event, _n: RepeatCount, _positive: bool, ctx: &EventContext) -> Option<Cmd> {
+        if let Some(key) = evt.get(0) {
+            self.state.set(next_mode(ctx.input_mode(), key));
+        }
+        None
+    }
+}
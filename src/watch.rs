@@ -0,0 +1,162 @@
+//! # Watch Module
+//!
+//! Builtin `watch`: reexecuta um comando periodicamente, redesenhando a saída
+//! no lugar, sem depender do `watch` do procps.
+//!
+//! ## Modos
+//! - `watch [-n segundos] cmd...` - roda a cada intervalo (padrão: 2s)
+//! - `watch --on-change <glob> cmd...` - roda quando algum arquivo do glob muda
+//!   (criado, removido ou com mtime diferente), verificando a cada intervalo
+//!   (padrão: 0,5s)
+//!
+//! Ctrl+C encerra o `watch` e devolve o controle ao prompt.
+
+use crate::quote::join_words;
+use crate::signals::{is_interrupted, take_interrupt, EXIT_INTERRUPTED};
+use crate::t;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// Intervalo padrão entre execuções
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Intervalo de verificação padrão no modo `--on-change`
+const ON_CHANGE_POLL: Duration = Duration::from_millis(500);
+
+/// Granularidade da espera (para responder rápido ao Ctrl+C)
+const SLEEP_STEP: Duration = Duration::from_millis(50);
+
+/// Opções do `watch`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchOptions {
+    pub interval: Duration,
+    pub on_change: Option<String>,
+    /// Linha de comando a executar (já re-quotada)
+    pub command: String,
+}
+
+/// Interpreta os argumentos do `watch` (sem o nome do builtin).
+pub fn parse_watch_args(args: &[String]) -> Result<WatchOptions, String> {
+    let mut interval = None;
+    let mut on_change = None;
+    let mut i = 0;
+
+    while i < args.len() {
+        match args[i].as_str() {
+            "-n" | "--interval" => {
+                let value = args.get(i + 1).ok_or_else(|| t!("args.requires_seconds", "-n"))?;
+                let secs: f64 = value
+                    .parse()
+                    .ok()
+                    .filter(|s: &f64| s.is_finite() && *s > 0.0)
+                    .ok_or_else(|| t!("watch.invalid_interval", value))?;
+                interval = Some(Duration::from_secs_f64(secs));
+                i += 2;
+            }
+            "--on-change" => {
+                let pattern = args.get(i + 1).ok_or(t!("watch.requires_glob"))?;
+                on_change = Some(pattern.clone());
+                i += 2;
+            }
+            "--" => {
+                i += 1;
+                break;
+            }
+            _ => break,
+        }
+    }
+
+    let rest = &args[i..];
+    if rest.is_empty() {
+        return Err(t!("args.no_command").to_string());
+    }
+    let command = join_words(rest);
+
+    let default = if on_change.is_some() { ON_CHANGE_POLL } else { DEFAULT_INTERVAL };
+    Ok(WatchOptions {
+        interval: interval.unwrap_or(default),
+        on_change,
+        command,
+    })
+}
+
+/// Estado dos arquivos de um glob: caminhos e mtimes, ordenados.
+pub fn snapshot(pattern: &str) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut files: Vec<(PathBuf, Option<SystemTime>)> = glob::glob(pattern)
+        .map(|paths| {
+            paths
+                .flatten()
+                .map(|p| {
+                    let mtime = fs::metadata(&p).and_then(|m| m.modified()).ok();
+                    (p, mtime)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// Espera `duration`, retornando `false` se um Ctrl+C chegou no meio.
+pub fn sleep_interruptible(duration: Duration) -> bool {
+    let mut waited = Duration::ZERO;
+    while waited < duration {
+        if is_interrupted() {
+            return false;
+        }
+        let step = SLEEP_STEP.min(duration - waited);
+        thread::sleep(step);
+        waited += step;
+    }
+    !is_interrupted()
+}
+
+fn redraw_header(opts: &WatchOptions) {
+    let trigger = match &opts.on_change {
+        Some(pattern) => format!("Ao mudar {}", pattern),
+        None => format!("A cada {:.1}s", opts.interval.as_secs_f64()),
+    };
+    print!("\x1b[H\x1b[2J");
+    println!(
+        "\x1b[1;36m{}: {}\x1b[0m    {}\n",
+        trigger,
+        opts.command,
+        chrono::Local::now().format("%H:%M:%S")
+    );
+    let _ = std::io::stdout().flush();
+}
+
+/// Executa o laço do `watch`. `run` executa uma linha de comando e devolve o
+/// código de saída. Só termina com Ctrl+C, retornando 130.
+pub fn run_watch(opts: &WatchOptions, run: &mut dyn FnMut(&str) -> i32) -> i32 {
+    let mut last_state = opts.on_change.as_deref().map(snapshot);
+
+    loop {
+        redraw_header(opts);
+        let code = run(&opts.command);
+        if code == EXIT_INTERRUPTED || take_interrupt() {
+            return EXIT_INTERRUPTED;
+        }
+
+        // Aguarda o próximo disparo (intervalo ou mudança nos arquivos)
+        loop {
+            if !sleep_interruptible(opts.interval) {
+                take_interrupt();
+                return EXIT_INTERRUPTED;
+            }
+            match (&opts.on_change, &mut last_state) {
+                (Some(pattern), Some(state)) => {
+                    let current = snapshot(pattern);
+                    if current != *state {
+                        *state = current;
+                        break;
+                    }
+                }
+                _ => break,
+            }
+        }
+    }
+}
@@ -0,0 +1,295 @@
+//! # When Module
+//!
+//! Builtin `when`: datas e horas sem depender das flags do `date` (que mudam
+//! entre GNU e BSD).
+//!
+//! - `when` / `when now +3d` - um instante, com deslocamentos
+//! - `when 1700000000` - converte epoch (segundos ou milissegundos)
+//! - `when 2024-05-01 14:30 -2h` - data e hora locais
+//! - `when --diff 09:00 17:30` - duração entre dois instantes
+//!
+//! A saída mostra o horário local, UTC, epoch e a distância para agora;
+//! `--epoch`, `--iso` e `-f <formato>` imprimem só um valor, para scripts.
+
+use crate::registry::usage;
+use crate::t;
+use chrono::{DateTime, Duration, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+
+/// Formato da linha principal.
+pub const DISPLAY_FORMAT: &str = "%Y-%m-%d %H:%M:%S %:z";
+
+/// Epochs acima disso estão em milissegundos (~ano 5138 em segundos).
+const EPOCH_MILLIS_THRESHOLD: i64 = 100_000_000_000;
+
+/// Como imprimir o instante.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WhenOutput {
+    /// Local, UTC, epoch e distância para agora
+    Full,
+    Epoch,
+    Iso,
+    /// Formato do `strftime` (`-f '%d/%m'`)
+    Format(String),
+}
+
+/// Ação do `when`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WhenAction {
+    Show { args: Vec<String>, output: WhenOutput },
+    Diff { from: Vec<String>, to: Vec<String> },
+}
+
+/// Interpreta as flags; o resto são as partes do instante.
+pub fn parse_when_args(args: &[String]) -> Result<WhenAction, String> {
+    if args.first().map(|s| s.as_str()) == Some("--diff") {
+        let rest = &args[1..];
+        return match rest {
+            [from] => Ok(WhenAction::Diff { from: vec![from.clone()], to: Vec::new() }),
+            [from, to] => Ok(WhenAction::Diff { from: vec![from.clone()], to: vec![to.clone()] }),
+            _ => Err(t!("when.diff_args").to_string()),
+        };
+    }
+
+    let mut output = WhenOutput::Full;
+    let mut rest = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--epoch" | "-e" => output = WhenOutput::Epoch,
+            "--iso" | "-i" => output = WhenOutput::Iso,
+            "--format" | "-f" => {
+                let format = args.get(i + 1).ok_or(t!("when.format_required"))?;
+                output = WhenOutput::Format(format.clone());
+                i += 1;
+            }
+            // `-3d` é deslocamento, não flag
+            flag if flag.starts_with("--") || (flag.starts_with('-') && parse_offset(flag).is_none()) => {
+                return Err(t!("args.unknown_option", flag));
+            }
+            _ => rest.push(args[i].clone()),
+        }
+        i += 1;
+    }
+    Ok(WhenAction::Show { args: rest, output })
+}
+
+// -----------------------------------------------------------------------------
+// PARSING
+// -----------------------------------------------------------------------------
+
+/// Deslocamento: `+3d`, `-2h`, `+1mo`... (s, m, h, d, w, mo, y).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Offset {
+    Exact(Duration),
+    Months(i64),
+}
+
+pub fn parse_offset(text: &str) -> Option<Offset> {
+    let sign = match text.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let body = &text[1..];
+    let split = body.find(|c: char| !c.is_ascii_digit())?;
+    let (digits, unit) = body.split_at(split);
+    let n: i64 = digits.parse().ok()?;
+    let n = n.checked_mul(sign)?;
+    Some(match unit {
+        "s" => Offset::Exact(Duration::try_seconds(n)?),
+        "m" | "min" => Offset::Exact(Duration::try_minutes(n)?),
+        "h" => Offset::Exact(Duration::try_hours(n)?),
+        "d" => Offset::Exact(Duration::try_days(n)?),
+        "w" => Offset::Exact(Duration::try_weeks(n)?),
+        "mo" => Offset::Months(n),
+        "y" => Offset::Months(n.checked_mul(12)?),
+        _ => return None,
+    })
+}
+
+fn apply_offset<Tz: TimeZone>(time: DateTime<Tz>, offset: Offset) -> Option<DateTime<Tz>> {
+    match offset {
+        Offset::Exact(duration) => time.checked_add_signed(duration),
+        Offset::Months(n) => {
+            let months = Months::new(u32::try_from(n.unsigned_abs()).ok()?);
+            if n >= 0 { time.checked_add_months(months) } else { time.checked_sub_months(months) }
+        }
+    }
+}
+
+/// Hora do dia (`9:00`, `17:30:15`).
+fn parse_time_of_day(text: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(text, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(text, "%H:%M"))
+        .ok()
+}
+
+fn local_to<Tz: TimeZone>(tz: &Tz, naive: NaiveDateTime) -> Option<DateTime<Tz>> {
+    // No horário de verão, a hora que se repete fica com a primeira ocorrência
+    tz.from_local_datetime(&naive).earliest()
+}
+
+/// O instante base (sem deslocamentos) de um argumento; `next` é o argumento
+/// seguinte, usado quando uma data vem acompanhada da hora. Devolve também se
+/// `next` foi consumido.
+fn parse_base<Tz: TimeZone>(text: &str, next: Option<&str>, now: &DateTime<Tz>) -> Option<(DateTime<Tz>, bool)> {
+    let tz = now.timezone();
+    let today = now.date_naive();
+    let midnight = |date: NaiveDate| local_to(&tz, date.and_time(NaiveTime::MIN));
+    let base = match text {
+        "now" | "agora" => now.clone(),
+        "today" | "hoje" => midnight(today)?,
+        "tomorrow" | "amanhã" | "amanha" => midnight(today.succ_opt()?)?,
+        "yesterday" | "ontem" => midnight(today.pred_opt()?)?,
+        _ => {
+            let epoch = text.strip_prefix('@').unwrap_or(text);
+            if !epoch.is_empty() && epoch.chars().all(|c| c.is_ascii_digit()) {
+                let n: i64 = epoch.parse().ok()?;
+                let utc = if n >= EPOCH_MILLIS_THRESHOLD {
+                    DateTime::from_timestamp_millis(n)?
+                } else {
+                    DateTime::from_timestamp(n, 0)?
+                };
+                return Some((utc.with_timezone(&tz), false));
+            }
+            if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+                return Some((time.with_timezone(&tz), false));
+            }
+            if let Ok(naive) = NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S")
+                .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M"))
+            {
+                return Some((local_to(&tz, naive)?, false));
+            }
+            if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+                return match next.and_then(parse_time_of_day) {
+                    Some(time) => Some((local_to(&tz, date.and_time(time))?, true)),
+                    None => Some((midnight(date)?, false)),
+                };
+            }
+            let time = parse_time_of_day(text)?;
+            local_to(&tz, today.and_time(time))?
+        }
+    };
+    Some((base, false))
+}
+
+/// Instante descrito por `args` (base opcional seguida de deslocamentos),
+/// relativo a `now`.
+pub fn parse_instant<Tz: TimeZone>(args: &[String], now: &DateTime<Tz>) -> Result<DateTime<Tz>, String> {
+    let mut time = now.clone();
+    let mut i = 0;
+    if let Some(first) = args.first()
+        && parse_offset(first).is_none()
+    {
+        let (base, used_next) = parse_base(first, args.get(1).map(|s| s.as_str()), now)
+            .ok_or_else(|| t!("when.invalid_date", first))?;
+        time = base;
+        i = if used_next { 2 } else { 1 };
+    }
+    for arg in &args[i..] {
+        let offset = parse_offset(arg).ok_or_else(|| t!("when.invalid_offset", arg))?;
+        time = apply_offset(time, offset).ok_or_else(|| t!("when.out_of_range", arg))?;
+    }
+    Ok(time)
+}
+
+// -----------------------------------------------------------------------------
+// OUTPUT
+// -----------------------------------------------------------------------------
+
+/// Duração compacta: `1d 2h 3m 4s` (sem as unidades zeradas).
+pub fn format_duration(seconds: i64) -> String {
+    let mut rest = seconds.unsigned_abs();
+    let mut parts = Vec::new();
+    for (unit, size) in [("d", 86_400), ("h", 3_600), ("m", 60), ("s", 1)] {
+        if rest >= size {
+            parts.push(format!("{}{}", rest / size, unit));
+            rest %= size;
+        }
+    }
+    if parts.is_empty() {
+        return "0s".to_string();
+    }
+    parts.join(" ")
+}
+
+/// Distância de `time` para `now`: `há 2h 5m` / `daqui a 3d`. Só as duas
+/// maiores unidades: os segundos de `3d 4h 12m 9s` não interessam.
+pub fn relative(seconds: i64) -> String {
+    if seconds == 0 {
+        return t!("when.now").to_string();
+    }
+    let text = format_duration(seconds).split(' ').take(2).collect::<Vec<_>>().join(" ");
+    if seconds < 0 { t!("when.ago", text) } else { t!("when.in", text) }
+}
+
+fn print_full(time: &DateTime<Local>, now: &DateTime<Local>) {
+    println!("{}", time.format(DISPLAY_FORMAT));
+    println!("\x1b[90mUTC\x1b[0m      {}", time.with_timezone(&Utc).format("%Y-%m-%d %H:%M:%S"));
+    println!("\x1b[90mepoch\x1b[0m    {}", time.timestamp());
+    println!("\x1b[90m{:<9}\x1b[0m{}", t!("when.relative"), relative(time.timestamp() - now.timestamp()));
+}
+
+/// Builtin `when [--epoch | --iso | -f fmt] [instante] [+-deslocamento...]`
+/// / `when --diff <de> [até]`.
+pub fn handle_when(tokens: &[String]) -> i32 {
+    let action = match parse_when_args(&tokens[1..]) {
+        Ok(action) => action,
+        Err(e) => {
+            eprintln!("when: {}", e);
+            eprintln!("{}", usage("when"));
+            return 2;
+        }
+    };
+    let now = Local::now();
+    match action {
+        WhenAction::Show { args, output } => {
+            let time = match parse_instant(&args, &now) {
+                Ok(time) => time,
+                Err(e) => {
+                    eprintln!("when: {}", e);
+                    return 1;
+                }
+            };
+            match output {
+                WhenOutput::Full => print_full(&time, &now),
+                WhenOutput::Epoch => println!("{}", time.timestamp()),
+                WhenOutput::Iso => println!("{}", time.to_rfc3339()),
+                WhenOutput::Format(format) => {
+                    use std::fmt::Write;
+                    // Especificador inválido faz o `Display` do chrono falhar
+                    let mut text = String::new();
+                    if write!(text, "{}", time.format(&format)).is_err() {
+                        eprintln!("when: {}", t!("when.invalid_format", format));
+                        return 1;
+                    }
+                    println!("{}", text);
+                }
+            }
+            0
+        }
+        WhenAction::Diff { from, to } => match diff_seconds(&from, &to, &now) {
+            Ok(seconds) => {
+                println!("{}  ({}s)", format_duration(seconds), seconds);
+                0
+            }
+            Err(e) => {
+                eprintln!("when: {}", e);
+                1
+            }
+        },
+    }
+}
+
+/// Segundos de `from` até `to` (sem `to`, até agora). Entre duas horas do dia,
+/// um fim antes do início passa da meia-noite: `22:00 06:00` são 8h.
+pub fn diff_seconds<Tz: TimeZone>(from: &[String], to: &[String], now: &DateTime<Tz>) -> Result<i64, String> {
+    let start = parse_instant(from, now)?;
+    let end = parse_instant(to, now)?;
+    let mut seconds = end.timestamp() - start.timestamp();
+    let times_of_day = from.iter().chain(to).all(|arg| parse_time_of_day(arg).is_some()) && !to.is_empty();
+    if times_of_day && seconds < 0 {
+        seconds += 86_400;
+    }
+    Ok(seconds)
+}